@@ -1,6 +1,8 @@
 use crate::error::{AppError, AppResult};
+use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceTrend {
@@ -10,98 +12,401 @@ pub struct ServiceTrend {
     pub message: String,
     pub incident_count_current: i64,
     pub incident_count_previous: i64,
+    /// Baseline (EWMA) incident count expected for the current 7-day window.
+    pub expected_count: f64,
+    /// Probability that the observed count is not explained by the baseline (0..1).
+    pub confidence: f64,
+    /// Standardized distance of the observed count from the baseline.
+    pub significance: f64,
 }
 
-/// Detect trending services by comparing incident counts between
-/// the last 7 days and the previous 7 days.
+/// Tuning for the baseline model used by `detect_service_trends`.
+#[derive(Debug, Clone)]
+pub struct TrendConfig {
+    /// Number of complete 7-day windows before the current one used as baseline.
+    pub lookback_weeks: usize,
+    /// EWMA smoothing factor; higher values weight recent weeks more.
+    pub smoothing: f64,
+    /// Minimum confidence required to flag a service as degrading.
+    pub min_confidence: f64,
+    /// Floor on the expected weekly count so quiet services need more than a
+    /// single incident to be flagged.
+    pub min_expected: f64,
+    /// Incidents in the current window that count as high volume.
+    pub high_volume_threshold: i64,
+}
+
+impl Default for TrendConfig {
+    fn default() -> Self {
+        Self {
+            lookback_weeks: 8,
+            smoothing: 0.3,
+            min_confidence: 0.95,
+            min_expected: 0.5,
+            high_volume_threshold: 3,
+        }
+    }
+}
+
+impl TrendConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(2..=52).contains(&self.lookback_weeks) {
+            return Err(AppError::Validation(
+                "Lookback must be between 2 and 52 weeks".into(),
+            ));
+        }
+        if !(self.smoothing > 0.0 && self.smoothing <= 1.0) {
+            return Err(AppError::Validation(
+                "Smoothing factor must be in (0, 1]".into(),
+            ));
+        }
+        if !(self.min_confidence > 0.5 && self.min_confidence < 1.0) {
+            return Err(AppError::Validation(
+                "Confidence must be between 0.5 and 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Baseline comparison for a single service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendStats {
+    pub observed: i64,
+    pub previous: i64,
+    pub expected: f64,
+    pub confidence: f64,
+    pub significance: f64,
+}
+
+/// Count timestamps into trailing 7-day windows ending at `now`.
+/// Index 0 is the current window, index 1 the week before, and so on.
+/// Whole weeks are compared so every window holds the same mix of weekdays,
+/// which keeps the weekday/weekend cycle from showing up as a trend.
+pub fn weekly_counts(timestamps: &[NaiveDateTime], now: NaiveDateTime, windows: usize) -> Vec<i64> {
+    let mut counts = vec![0i64; windows];
+    for ts in timestamps {
+        let age_minutes = now.signed_duration_since(*ts).num_minutes();
+        if age_minutes < 0 {
+            continue;
+        }
+        let idx = (age_minutes / (7 * 24 * 60)) as usize;
+        if idx < windows {
+            counts[idx] += 1;
+        }
+    }
+    counts
+}
+
+/// Exponentially weighted mean of `history` (ordered oldest first), seeded
+/// with the plain mean so a single early spike does not dominate.
+pub fn ewma(history: &[i64], smoothing: f64) -> f64 {
+    if history.is_empty() {
+        return 0.0;
+    }
+    let mut level = history.iter().sum::<i64>() as f64 / history.len() as f64;
+    for value in history {
+        level = smoothing * *value as f64 + (1.0 - smoothing) * level;
+    }
+    level
+}
+
+/// P(X >= k) for X ~ Poisson(lambda).
+pub fn poisson_upper_tail(k: i64, lambda: f64) -> f64 {
+    if k <= 0 {
+        return 1.0;
+    }
+    if lambda <= 0.0 {
+        return 0.0;
+    }
+    let mut term = (-lambda).exp();
+    let mut cdf = term;
+    for i in 1..k {
+        term *= lambda / i as f64;
+        cdf += term;
+    }
+    (1.0 - cdf).clamp(0.0, 1.0)
+}
+
+/// Compare the current window against the EWMA baseline of the prior windows.
+/// `counts` is ordered newest first, as returned by `weekly_counts`.
+pub fn evaluate_counts(counts: &[i64], config: &TrendConfig) -> TrendStats {
+    let observed = counts.first().copied().unwrap_or(0);
+    let previous = counts.get(1).copied().unwrap_or(0);
+    let mut history: Vec<i64> = counts.iter().skip(1).copied().collect();
+    history.reverse();
+
+    let expected = ewma(&history, config.smoothing).max(config.min_expected);
+    let confidence = if observed as f64 > expected {
+        1.0 - poisson_upper_tail(observed, expected)
+    } else {
+        0.0
+    };
+    let significance = (observed as f64 - expected) / expected.sqrt();
+
+    TrendStats {
+        observed,
+        previous,
+        expected,
+        confidence,
+        significance,
+    }
+}
+
+/// Detect services whose incident volume in the last 7 days is unusual
+/// compared with their own recent history.
+///
+/// Each service's expected weekly count is an EWMA over the previous
+/// `lookback_weeks` windows. The current count is scored against a Poisson
+/// distribution with that mean.
 ///
 /// Flags:
-/// - "degrading": current count > previous count * 1.5 (50%+ increase)
-/// - "high_volume": 3+ incidents in the last 7 days
-pub async fn detect_service_trends(db: &SqlitePool) -> AppResult<Vec<ServiceTrend>> {
+/// - "degrading": the current count exceeds the baseline with at least
+///   `min_confidence` confidence
+/// - "high_volume": `high_volume_threshold`+ incidents in the last 7 days
+///   without being statistically unusual for the service
+pub async fn detect_service_trends(
+    db: &SqlitePool,
+    config: &TrendConfig,
+) -> AppResult<Vec<ServiceTrend>> {
+    config.validate()?;
+
+    let now = Utc::now().naive_utc();
+    let windows = config.lookback_weeks + 1;
+    let since = now - chrono::Duration::days(7 * windows as i64);
+
     let rows = sqlx::query(
         "SELECT
             s.id as service_id,
             s.name as service_name,
-            COALESCE(SUM(CASE
-                WHEN i.created_at >= datetime('now', '-7 days') THEN 1
-                ELSE 0
-            END), 0) as current_count,
-            COALESCE(SUM(CASE
-                WHEN i.created_at >= datetime('now', '-14 days')
-                 AND i.created_at < datetime('now', '-7 days') THEN 1
-                ELSE 0
-            END), 0) as previous_count
+            i.started_at
          FROM services s
-         LEFT JOIN incidents i ON i.service_id = s.id AND i.deleted_at IS NULL
-         WHERE s.deleted_at IS NULL
-         GROUP BY s.id, s.name
-         HAVING current_count > 0 OR previous_count > 0",
+         JOIN incidents i ON i.service_id = s.id AND i.deleted_at IS NULL
+         WHERE s.is_active = 1
+           AND i.started_at >= ?",
     )
+    .bind(since.format("%Y-%m-%dT%H:%M:%SZ").to_string())
     .fetch_all(db)
     .await
     .map_err(|e| AppError::Database(format!("Failed to query service trends: {}", e)))?;
 
-    let mut trends: Vec<ServiceTrend> = Vec::new();
-
+    let mut by_service: HashMap<String, (String, Vec<NaiveDateTime>)> = HashMap::new();
     for row in &rows {
         let service_id: String = row.get("service_id");
         let service_name: String = row.get("service_name");
-        let current: i64 = row.get("current_count");
-        let previous: i64 = row.get("previous_count");
+        let started_at: String = row.get("started_at");
+        let Some(ts) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        by_service
+            .entry(service_id)
+            .or_insert_with(|| (service_name, Vec::new()))
+            .1
+            .push(ts);
+    }
 
-        // Check for degrading trend: current > previous * 1.5
-        if previous > 0 && current as f64 > previous as f64 * 1.5 {
+    let mut trends: Vec<ServiceTrend> = Vec::new();
+
+    for (service_id, (service_name, timestamps)) in by_service {
+        let counts = weekly_counts(&timestamps, now, windows);
+        let stats = evaluate_counts(&counts, config);
+
+        if stats.confidence >= config.min_confidence {
             trends.push(ServiceTrend {
-                service_id: service_id.clone(),
+                service_id,
                 service_name: service_name.clone(),
                 trend_type: "degrading".to_string(),
                 message: format!(
-                    "{} has {} incidents in the last 7 days vs {} in the previous 7 days ({}% increase)",
+                    "{} has {} incidents in the last 7 days vs ~{:.1} expected from the previous {} weeks ({:.0}% confidence)",
                     service_name,
-                    current,
-                    previous,
-                    ((current as f64 - previous as f64) / previous as f64 * 100.0) as i64,
+                    stats.observed,
+                    stats.expected,
+                    config.lookback_weeks,
+                    stats.confidence * 100.0,
                 ),
-                incident_count_current: current,
-                incident_count_previous: previous,
+                incident_count_current: stats.observed,
+                incident_count_previous: stats.previous,
+                expected_count: stats.expected,
+                confidence: stats.confidence,
+                significance: stats.significance,
             });
-        } else if previous == 0 && current > 0 {
-            // New incidents where there were none before — also degrading
+        } else if stats.observed >= config.high_volume_threshold {
             trends.push(ServiceTrend {
-                service_id: service_id.clone(),
+                service_id,
                 service_name: service_name.clone(),
-                trend_type: "degrading".to_string(),
+                trend_type: "high_volume".to_string(),
                 message: format!(
-                    "{} has {} new incidents in the last 7 days with none in the previous period",
-                    service_name, current,
+                    "{} has {} incidents in the last 7 days (~{:.1} expected)",
+                    service_name, stats.observed, stats.expected,
                 ),
-                incident_count_current: current,
-                incident_count_previous: previous,
+                incident_count_current: stats.observed,
+                incident_count_previous: stats.previous,
+                expected_count: stats.expected,
+                confidence: stats.confidence,
+                significance: stats.significance,
             });
         }
+    }
 
-        // Check for high volume: 3+ in the last 7 days
-        if current >= 3 {
-            // Avoid duplicate if already flagged as degrading with the same service
-            let already_flagged = trends.iter().any(|t| {
-                t.service_id == service_id && t.trend_type == "high_volume"
-            });
-            if !already_flagged {
-                trends.push(ServiceTrend {
-                    service_id: service_id.clone(),
-                    service_name: service_name.clone(),
-                    trend_type: "high_volume".to_string(),
-                    message: format!(
-                        "{} has {} incidents in the last 7 days",
-                        service_name, current,
-                    ),
-                    incident_count_current: current,
-                    incident_count_previous: previous,
-                });
-            }
+    // Most anomalous first; name as a stable tie-breaker
+    trends.sort_by(|a, b| {
+        b.significance
+            .partial_cmp(&a.significance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.service_name.cmp(&b.service_name))
+    });
+
+    Ok(trends)
+}
+
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%SZ")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.fZ"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    /// Build a newest-first count series from an oldest-first history plus the current week.
+    fn series(history_oldest_first: &[i64], current: i64) -> Vec<i64> {
+        let mut counts = vec![current];
+        counts.extend(history_oldest_first.iter().rev());
+        counts
+    }
+
+    #[test]
+    fn weekly_counts_buckets_by_trailing_week() {
+        let now = at(29, 12);
+        let timestamps = vec![at(29, 11), at(23, 13), at(22, 11), at(15, 0), at(1, 0)];
+        assert_eq!(weekly_counts(&timestamps, now, 3), vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn weekly_counts_ignores_future_timestamps() {
+        let now = at(10, 0);
+        assert_eq!(weekly_counts(&[at(11, 0)], now, 2), vec![0, 0]);
+    }
+
+    #[test]
+    fn poisson_upper_tail_matches_known_values() {
+        assert_eq!(poisson_upper_tail(0, 2.0), 1.0);
+        // P(X >= 1 | 1) = 1 - e^-1
+        assert!((poisson_upper_tail(1, 1.0) - 0.632_120_6).abs() < 1e-6);
+        // P(X >= 3 | 1) = 1 - e^-1 * (1 + 1 + 0.5)
+        assert!((poisson_upper_tail(3, 1.0) - 0.080_301_4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ewma_of_constant_series_is_constant() {
+        assert!((ewma(&[2, 2, 2, 2], 0.3) - 2.0).abs() < 1e-9);
+        assert_eq!(ewma(&[], 0.3), 0.0);
+    }
+
+    #[test]
+    fn ewma_weights_recent_weeks_more() {
+        let rising = ewma(&[0, 0, 0, 4], 0.5);
+        let falling = ewma(&[4, 0, 0, 0], 0.5);
+        assert!(rising > falling);
+    }
+
+    #[test]
+    fn zero_to_one_incident_is_not_flagged() {
+        let config = TrendConfig::default();
+        let stats = evaluate_counts(&series(&[0, 0, 0, 0, 0, 0, 0, 0], 1), &config);
+        assert_eq!(stats.expected, config.min_expected);
+        assert!(stats.confidence < config.min_confidence);
+    }
+
+    #[test]
+    fn spike_over_stable_baseline_is_flagged() {
+        let config = TrendConfig::default();
+        let stats = evaluate_counts(&series(&[1, 1, 1, 1, 1, 1, 1, 1], 6), &config);
+        assert!((stats.expected - 1.0).abs() < 1e-9);
+        assert!(stats.confidence >= 0.99);
+        assert!(stats.significance > 4.0);
+    }
+
+    #[test]
+    fn normal_variation_on_busy_service_is_not_flagged() {
+        // 1.5x week-over-week, which the old rule flagged, is ordinary noise at this volume
+        let config = TrendConfig::default();
+        let stats = evaluate_counts(&series(&[6, 5, 7, 6, 6, 5, 7, 4], 6), &config);
+        assert_eq!(stats.previous, 4);
+        assert!(stats.confidence < config.min_confidence);
+    }
+
+    #[test]
+    fn count_below_baseline_has_zero_confidence() {
+        let config = TrendConfig::default();
+        let stats = evaluate_counts(&series(&[5, 5, 5, 5], 1), &config);
+        assert_eq!(stats.confidence, 0.0);
+        assert!(stats.significance < 0.0);
+    }
+
+    #[tokio::test]
+    async fn detect_service_trends_flags_spiking_service() {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_url = format!("sqlite:{}?mode=rwc", dir.path().join("trends-tests.db").display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+
+        // One incident a week for eight weeks on Slack, then six this week
+        let now = Utc::now().naive_utc();
+        let mut offsets_hours: Vec<i64> = (1..=8).map(|w| w * 24 * 7 + 12).collect();
+        offsets_hours.extend([2, 10, 20, 30, 40, 50]);
+        for (n, hours) in offsets_hours.iter().enumerate() {
+            let started = (now - chrono::Duration::hours(*hours))
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string();
+            sqlx::query(
+                "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at) VALUES (?, 'Spike', 'svc-slack', 'High', 'High', 'Active', ?, ?)",
+            )
+            .bind(format!("inc-trend-{}", n))
+            .bind(&started)
+            .bind(&started)
+            .execute(&pool)
+            .await
+            .expect("insert incident");
         }
+
+        let trends = detect_service_trends(&pool, &TrendConfig::default())
+            .await
+            .expect("trends");
+        assert_eq!(trends.len(), 1);
+        assert_eq!(trends[0].service_id, "svc-slack");
+        assert_eq!(trends[0].trend_type, "degrading");
+        assert_eq!(trends[0].incident_count_current, 6);
+        assert!(trends[0].confidence >= 0.95);
     }
 
-    Ok(trends)
+    #[test]
+    fn config_validation_rejects_out_of_range_values() {
+        let mut config = TrendConfig::default();
+        assert!(config.validate().is_ok());
+        config.lookback_weeks = 1;
+        assert!(config.validate().is_err());
+        config.lookback_weeks = 8;
+        config.min_confidence = 1.0;
+        assert!(config.validate().is_err());
+    }
 }
@@ -111,6 +111,14 @@ pub async fn check_duplicate_incidents(
 #[tauri::command]
 pub async fn detect_service_trends(
     db: State<'_, SqlitePool>,
+    lookback_weeks: Option<usize>,
+    min_confidence: Option<f64>,
 ) -> Result<Vec<trends::ServiceTrend>, AppError> {
-    ai::trends::detect_service_trends(&*db).await
+    let defaults = trends::TrendConfig::default();
+    let config = trends::TrendConfig {
+        lookback_weeks: lookback_weeks.unwrap_or(defaults.lookback_weeks),
+        min_confidence: min_confidence.unwrap_or(defaults.min_confidence),
+        ..defaults
+    };
+    ai::trends::detect_service_trends(&*db, &config).await
 }
@@ -94,10 +94,14 @@ pub async fn list_attachments(
     db: State<'_, SqlitePool>,
     incident_id: String,
 ) -> Result<Vec<Attachment>, AppError> {
+    list_incident_attachments(&db, &incident_id).await
+}
+
+pub(crate) async fn list_incident_attachments(db: &SqlitePool, incident_id: &str) -> AppResult<Vec<Attachment>> {
     let rows =
         sqlx::query("SELECT * FROM attachments WHERE incident_id = ? ORDER BY created_at ASC")
-            .bind(&incident_id)
-            .fetch_all(&*db)
+            .bind(incident_id)
+            .fetch_all(db)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
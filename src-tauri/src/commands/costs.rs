@@ -0,0 +1,82 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::db::queries::{audit, costs, metrics};
+use crate::error::AppError;
+use crate::models::cost::{summarize_costs, CostModel, CostSummary, IncidentCost, SetCostOverrideRequest};
+use crate::models::metrics::MetricFilters;
+
+#[tauri::command]
+pub async fn get_cost_model(
+    db: State<'_, SqlitePool>,
+) -> Result<CostModel, AppError> {
+    costs::get_cost_model(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_cost_model(
+    db: State<'_, SqlitePool>,
+    model: CostModel,
+) -> Result<CostModel, AppError> {
+    model.validate()?;
+    let result = costs::save_cost_model(&*db, &model).await?;
+    let _ = audit::insert_audit_entry(&*db, "cost_model", "cost_model", "updated", "Updated cost model", "").await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_incident_cost(
+    db: State<'_, SqlitePool>,
+    incident_id: String,
+) -> Result<IncidentCost, AppError> {
+    let model = costs::get_cost_model(&*db).await?;
+    costs::get_incident_cost(&*db, &incident_id, &model).await
+}
+
+#[tauri::command]
+pub async fn set_incident_cost_override(
+    db: State<'_, SqlitePool>,
+    req: SetCostOverrideRequest,
+) -> Result<IncidentCost, AppError> {
+    req.validate()?;
+    costs::set_cost_override(&*db, &req.incident_id, req.amount, &req.note).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "incident",
+        &req.incident_id,
+        "cost_overridden",
+        &format!("Set manual cost override: {:.2}", req.amount),
+        "",
+    )
+    .await;
+    let model = costs::get_cost_model(&*db).await?;
+    costs::get_incident_cost(&*db, &req.incident_id, &model).await
+}
+
+#[tauri::command]
+pub async fn clear_incident_cost_override(
+    db: State<'_, SqlitePool>,
+    incident_id: String,
+) -> Result<IncidentCost, AppError> {
+    costs::clear_cost_override(&*db, &incident_id).await?;
+    let _ = audit::insert_audit_entry(&*db, "incident", &incident_id, "cost_override_cleared", "Cleared manual cost override", "").await;
+    let model = costs::get_cost_model(&*db).await?;
+    costs::get_incident_cost(&*db, &incident_id, &model).await
+}
+
+#[tauri::command]
+pub async fn get_cost_summary(
+    db: State<'_, SqlitePool>,
+    start_date: String,
+    end_date: String,
+    filters: Option<MetricFilters>,
+) -> Result<CostSummary, AppError> {
+    if start_date.is_empty() || end_date.is_empty() {
+        return Err(AppError::Validation("Start and end dates are required".into()));
+    }
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
+    let model = costs::get_cost_model(&*db).await?;
+    let incident_costs =
+        costs::list_incident_costs(&*db, &range, &filters.unwrap_or_default(), &model).await?;
+    Ok(summarize_costs(&incident_costs, &model.currency))
+}
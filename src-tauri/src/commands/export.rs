@@ -1,10 +1,23 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use chrono::{Datelike, Timelike};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
 use sqlx::{Row, SqlitePool};
 use tauri::State;
 
-use crate::db::queries::audit;
-use crate::error::AppError;
-use crate::models::incident::IncidentFilters;
+use crate::commands::pir_review::extract_markdown;
+use crate::db::queries::metrics::{self, DateRange};
+use crate::db::queries::{audit, costs, custom_fields, incidents, postmortems, roles, sla, tags};
+use crate::error::{AppError, AppResult};
+use crate::models::custom_field::CustomFieldDefinition;
+use crate::models::export::{ExportColumn, ExportEncoding, ExportOptions, JsonExportMode};
+use crate::models::incident::{ActionItem, IncidentFilters};
+use crate::models::metrics::{MetricFilters, MetricResult};
+use crate::models::postmortem::{ContributingFactor, Postmortem};
 use crate::models::priority::{Impact, Severity, calculate_priority};
+use crate::models::role::IncidentRole;
+use crate::models::sla::SlaStatus;
 
 /// Sanitize a CSV field value to prevent CSV injection.
 /// Prefixes with a single quote if the value starts with =, +, -, or @.
@@ -23,7 +36,8 @@ fn sanitize_csv_field(value: &str) -> String {
 /// Build a filtered query for incidents based on IncidentFilters.
 fn build_filtered_query(filters: &IncidentFilters) -> (String, Vec<String>) {
     let mut sql = String::from(
-        "SELECT i.*, s.name as service_name FROM incidents i \
+        "SELECT i.*, s.name as service_name \
+         FROM incidents i \
          LEFT JOIN services s ON i.service_id = s.id \
          WHERE i.deleted_at IS NULL",
     );
@@ -59,15 +73,126 @@ fn build_filtered_query(filters: &IncidentFilters) -> (String, Vec<String>) {
     (sql, binds)
 }
 
-#[tauri::command]
-pub async fn export_incidents_csv(
-    db: State<'_, SqlitePool>,
-    filters_json: String,
-) -> Result<String, AppError> {
-    let filters: IncidentFilters =
-        serde_json::from_str(&filters_json).unwrap_or_default();
+/// Standard export columns in their default order, as (key, label).
+const STANDARD_COLUMNS: &[(&str, &str)] = &[
+    ("id", "ID"),
+    ("title", "Title"),
+    ("service_id", "Service ID"),
+    ("service_name", "Service Name"),
+    ("severity", "Severity"),
+    ("impact", "Impact"),
+    ("priority", "Priority"),
+    ("status", "Status"),
+    ("started_at", "Started At"),
+    ("detected_at", "Detected At"),
+    ("acknowledged_at", "Acknowledged At"),
+    ("first_response_at", "First Response At"),
+    ("mitigation_started_at", "Mitigation Started At"),
+    ("responded_at", "Responded At"),
+    ("resolved_at", "Resolved At"),
+    ("reopened_at", "Reopened At"),
+    ("reopen_count", "Reopen Count"),
+    ("duration_minutes", "Duration (minutes)"),
+    ("root_cause", "Root Cause"),
+    ("resolution", "Resolution"),
+    ("tickets_submitted", "Tickets Submitted"),
+    ("affected_users", "Affected Users"),
+    ("is_recurring", "Is Recurring"),
+    ("recurrence_of", "Recurrence Of"),
+    ("lessons_learned", "Lessons Learned"),
+    ("action_items", "Action Items"),
+    ("external_ref", "External Ref"),
+    ("notes", "Notes"),
+    ("created_at", "Created At"),
+    ("updated_at", "Updated At"),
+    ("estimated_cost", "Estimated Cost"),
+];
+
+/// Optional columns drawn from records related to the incident.
+const RELATED_COLUMNS: &[(&str, &str)] = &[
+    ("tags", "Tags"),
+    ("roles", "Roles"),
+    ("contributing_factors", "Contributing Factors"),
+    ("sla_response_target_minutes", "SLA Response Target (minutes)"),
+    ("sla_response_elapsed_minutes", "SLA Response Elapsed (minutes)"),
+    ("sla_response_met", "SLA Response Met"),
+    ("sla_resolve_target_minutes", "SLA Resolve Target (minutes)"),
+    ("sla_resolve_elapsed_minutes", "SLA Resolve Elapsed (minutes)"),
+    ("sla_resolve_met", "SLA Resolve Met"),
+];
+
+/// Timestamp columns reformatted by `ExportOptions::date_format`.
+const DATE_COLUMNS: &[&str] = &[
+    "started_at",
+    "detected_at",
+    "acknowledged_at",
+    "first_response_at",
+    "mitigation_started_at",
+    "responded_at",
+    "resolved_at",
+    "reopened_at",
+    "created_at",
+    "updated_at",
+];
+
+/// Prefix of the column key for a custom field, followed by the field id.
+const CUSTOM_COLUMN_PREFIX: &str = "custom:";
+
+/// Every column CSV and JSON exports can include: the standard columns, the
+/// related-record columns and one column per custom field definition.
+fn available_columns(definitions: &[CustomFieldDefinition]) -> Vec<ExportColumn> {
+    STANDARD_COLUMNS
+        .iter()
+        .chain(RELATED_COLUMNS)
+        .map(|(key, label)| ExportColumn {
+            key: key.to_string(),
+            label: label.to_string(),
+        })
+        .chain(definitions.iter().map(|def| ExportColumn {
+            key: format!("{}{}", CUSTOM_COLUMN_PREFIX, def.id),
+            label: def.name.clone(),
+        }))
+        .collect()
+}
 
-    let (sql, binds) = build_filtered_query(&filters);
+/// The columns to write, in order: the selected ones, or the standard columns
+/// when none are selected.
+fn selected_columns(options: &ExportOptions, definitions: &[CustomFieldDefinition]) -> AppResult<Vec<ExportColumn>> {
+    let available = available_columns(definitions);
+    if options.columns.is_empty() {
+        return Ok(available.into_iter().take(STANDARD_COLUMNS.len()).collect());
+    }
+    options
+        .columns
+        .iter()
+        .map(|key| {
+            available
+                .iter()
+                .find(|c| &c.key == key)
+                .cloned()
+                .ok_or_else(|| AppError::Validation(format!("Unknown export column '{}'", key)))
+        })
+        .collect()
+}
+
+/// An exported incident row with the related records its columns draw on.
+struct ExportRecord {
+    row: sqlx::sqlite::SqliteRow,
+    priority: String,
+    cost: f64,
+    tags: Vec<String>,
+    roles: Vec<IncidentRole>,
+    factors: Vec<ContributingFactor>,
+    sla: SlaStatus,
+    /// Non-empty custom field values keyed by field id.
+    custom_fields: HashMap<String, String>,
+    /// Only loaded for nested JSON.
+    action_items: Vec<ActionItem>,
+    postmortem: Option<Postmortem>,
+}
+
+async fn load_records(db: &SqlitePool, filters: &IncidentFilters, nested: bool) -> AppResult<Vec<ExportRecord>> {
+    let (sql, binds) = build_filtered_query(filters);
 
     let mut query = sqlx::query(&sql);
     for bind in &binds {
@@ -75,156 +200,260 @@ pub async fn export_incidents_csv(
     }
 
     let rows = query
-        .fetch_all(&*db)
+        .fetch_all(db)
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
+    let ids: Vec<String> = rows.iter().map(|r| r.get("id")).collect();
+    let cost_model = costs::get_cost_model(db).await?;
+    let cost_totals = costs::incident_cost_totals(db, &cost_model, &ids).await?;
 
-    let temp_dir = std::env::temp_dir();
-    let filename = format!("incidents_export_{}.csv", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
-    let path = temp_dir.join(&filename);
-
-    let mut wtr = csv::Writer::from_path(&path)
-        .map_err(|e| AppError::Csv(e.to_string()))?;
-
-    // Write header row
-    wtr.write_record([
-        "ID",
-        "Title",
-        "Service ID",
-        "Service Name",
-        "Severity",
-        "Impact",
-        "Priority",
-        "Status",
-        "Started At",
-        "Detected At",
-        "Acknowledged At",
-        "First Response At",
-        "Mitigation Started At",
-        "Responded At",
-        "Resolved At",
-        "Reopened At",
-        "Reopen Count",
-        "Duration (minutes)",
-        "Root Cause",
-        "Resolution",
-        "Tickets Submitted",
-        "Affected Users",
-        "Is Recurring",
-        "Recurrence Of",
-        "Lessons Learned",
-        "Action Items",
-        "External Ref",
-        "Notes",
-        "Created At",
-        "Updated At",
-    ])
-    .map_err(|e| AppError::Csv(e.to_string()))?;
-
-    for row in &rows {
+    let mut records = Vec::with_capacity(rows.len());
+    for row in rows {
+        let id: String = row.get("id");
+        let (tags, roles, factors, sla, values) = tokio::try_join!(
+            tags::get_incident_tags(db, &id),
+            roles::list_roles_for_incident(db, &id),
+            postmortems::list_contributing_factors(db, &id),
+            sla::compute_sla_status(db, &id),
+            custom_fields::get_incident_custom_fields(db, &id),
+        )?;
+        let (action_items, postmortem) = if nested {
+            tokio::try_join!(
+                incidents::list_action_items(db, Some(&id)),
+                postmortems::get_postmortem_by_incident(db, &id),
+            )?
+        } else {
+            (Vec::new(), None)
+        };
         let severity: String = row.get("severity");
         let impact: String = row.get("impact");
         let sev = Severity::from_str(&severity).unwrap_or(Severity::Medium);
         let imp = Impact::from_str(&impact).unwrap_or(Impact::Medium);
-        let priority = calculate_priority(&sev, &imp).to_string();
+        records.push(ExportRecord {
+            priority: calculate_priority(&sev, &imp).to_string(),
+            cost: cost_totals.get(&id).copied().unwrap_or_default(),
+            tags,
+            roles,
+            factors,
+            sla,
+            custom_fields: values
+                .into_iter()
+                .filter(|v| !v.value.trim().is_empty())
+                .map(|v| (v.field_id, v.value))
+                .collect(),
+            action_items,
+            postmortem,
+            row,
+        });
+    }
+    Ok(records)
+}
 
-        let id: String = row.get("id");
-        let title: String = row.get("title");
-        let service_id: String = row.get("service_id");
-        let service_name: String = row
-            .get::<Option<String>, _>("service_name")
-            .unwrap_or_else(|| "Unknown".to_string());
-        let status: String = row.get("status");
-        let started_at: String = row.get("started_at");
-        let detected_at: String = row.get("detected_at");
-        let acknowledged_at: String = row
-            .get::<Option<String>, _>("acknowledged_at")
-            .unwrap_or_default();
-        let first_response_at: String = row
-            .get::<Option<String>, _>("first_response_at")
-            .unwrap_or_default();
-        let mitigation_started_at: String = row
-            .get::<Option<String>, _>("mitigation_started_at")
-            .unwrap_or_default();
-        let responded_at: String = row
-            .get::<Option<String>, _>("responded_at")
-            .unwrap_or_default();
-        let resolved_at: String = row
-            .get::<Option<String>, _>("resolved_at")
-            .unwrap_or_default();
-        let reopened_at: String = row
-            .get::<Option<String>, _>("reopened_at")
-            .unwrap_or_default();
-        let reopen_count: i64 = row.get::<Option<i64>, _>("reopen_count").unwrap_or(0);
-        let duration_minutes: String = row
-            .get::<Option<i64>, _>("duration_minutes")
-            .map(|d| d.to_string())
-            .unwrap_or_default();
-        let root_cause: String = row
-            .get::<Option<String>, _>("root_cause")
-            .unwrap_or_default();
-        let resolution: String = row
-            .get::<Option<String>, _>("resolution")
-            .unwrap_or_default();
-        let tickets_submitted: i64 =
-            row.get::<Option<i64>, _>("tickets_submitted").unwrap_or(0);
-        let affected_users: i64 =
-            row.get::<Option<i64>, _>("affected_users").unwrap_or(0);
-        let is_recurring: bool = row.get::<bool, _>("is_recurring");
-        let recurrence_of: String = row
-            .get::<Option<String>, _>("recurrence_of")
-            .unwrap_or_default();
-        let lessons_learned: String = row
-            .get::<Option<String>, _>("lessons_learned")
-            .unwrap_or_default();
-        let action_items: String = row
-            .get::<Option<String>, _>("action_items")
-            .unwrap_or_default();
-        let external_ref: String = row
-            .get::<Option<String>, _>("external_ref")
-            .unwrap_or_default();
-        let notes: String = row
-            .get::<Option<String>, _>("notes")
-            .unwrap_or_default();
-        let created_at: String = row.get("created_at");
-        let updated_at: String = row.get("updated_at");
-
-        wtr.write_record([
-            &sanitize_csv_field(&id),
-            &sanitize_csv_field(&title),
-            &sanitize_csv_field(&service_id),
-            &sanitize_csv_field(&service_name),
-            &sanitize_csv_field(&severity),
-            &sanitize_csv_field(&impact),
-            &sanitize_csv_field(&priority),
-            &sanitize_csv_field(&status),
-            &sanitize_csv_field(&started_at),
-            &sanitize_csv_field(&detected_at),
-            &sanitize_csv_field(&acknowledged_at),
-            &sanitize_csv_field(&first_response_at),
-            &sanitize_csv_field(&mitigation_started_at),
-            &sanitize_csv_field(&responded_at),
-            &sanitize_csv_field(&resolved_at),
-            &sanitize_csv_field(&reopened_at),
-            &reopen_count.to_string(),
-            &duration_minutes,
-            &sanitize_csv_field(&root_cause),
-            &sanitize_csv_field(&resolution),
-            &tickets_submitted.to_string(),
-            &affected_users.to_string(),
-            &is_recurring.to_string(),
-            &sanitize_csv_field(&recurrence_of),
-            &sanitize_csv_field(&lessons_learned),
-            &sanitize_csv_field(&action_items),
-            &sanitize_csv_field(&external_ref),
-            &sanitize_csv_field(&notes),
-            &sanitize_csv_field(&created_at),
-            &sanitize_csv_field(&updated_at),
-        ])
+/// Value of one column for a record. Lists stay arrays; CSV joins them.
+fn column_value(key: &str, record: &ExportRecord, options: &ExportOptions) -> serde_json::Value {
+    use serde_json::Value;
+
+    let row = &record.row;
+    let text = |column: &str| Value::from(row.get::<Option<String>, _>(column).unwrap_or_default());
+    let optional_text = |column: &str| row.get::<Option<String>, _>(column).map(Value::from).unwrap_or(Value::Null);
+    let met = |target: Option<i64>, breached: bool| target.map(|_| Value::from(!breached)).unwrap_or(Value::Null);
+
+    let value = match key {
+        "id" | "title" | "service_id" | "severity" | "impact" | "status" | "started_at" | "detected_at"
+        | "root_cause" | "resolution" | "lessons_learned" | "action_items" | "external_ref" | "notes"
+        | "created_at" | "updated_at" => text(key),
+        "acknowledged_at" | "first_response_at" | "mitigation_started_at" | "responded_at" | "resolved_at"
+        | "reopened_at" | "recurrence_of" => optional_text(key),
+        "service_name" => Value::from(
+            row.get::<Option<String>, _>("service_name")
+                .unwrap_or_else(|| "Unknown".to_string()),
+        ),
+        "priority" => Value::from(record.priority.clone()),
+        "reopen_count" | "tickets_submitted" | "affected_users" => {
+            Value::from(row.get::<Option<i64>, _>(key).unwrap_or(0))
+        }
+        "duration_minutes" => Value::from(row.get::<Option<i64>, _>("duration_minutes")),
+        "is_recurring" => Value::from(row.get::<bool, _>("is_recurring")),
+        "estimated_cost" => Value::from(record.cost),
+        "tags" => Value::from(record.tags.clone()),
+        "roles" => Value::from(
+            record
+                .roles
+                .iter()
+                .map(|r| format!("{}: {}", r.role, r.assignee))
+                .collect::<Vec<_>>(),
+        ),
+        "contributing_factors" => Value::from(
+            record
+                .factors
+                .iter()
+                .map(|f| {
+                    let root = if f.is_root { " (root)" } else { "" };
+                    format!("{}{}: {}", f.category, root, f.description)
+                })
+                .collect::<Vec<_>>(),
+        ),
+        "sla_response_target_minutes" => Value::from(record.sla.response_target_minutes),
+        "sla_response_elapsed_minutes" => Value::from(record.sla.response_elapsed_minutes),
+        "sla_response_met" => met(record.sla.response_target_minutes, record.sla.response_breached),
+        "sla_resolve_target_minutes" => Value::from(record.sla.resolve_target_minutes),
+        "sla_resolve_elapsed_minutes" => Value::from(record.sla.resolve_elapsed_minutes),
+        "sla_resolve_met" => met(record.sla.resolve_target_minutes, record.sla.resolve_breached),
+        _ => key
+            .strip_prefix(CUSTOM_COLUMN_PREFIX)
+            .and_then(|field_id| record.custom_fields.get(field_id))
+            .map(|v| Value::from(v.clone()))
+            .unwrap_or(Value::Null),
+    };
+
+    match value {
+        Value::String(ref stamp) if DATE_COLUMNS.contains(&key) && !options.date_format.is_empty() => {
+            Value::from(format_timestamp(stamp, &options.date_format))
+        }
+        value => value,
+    }
+}
+
+/// Reformat an RFC 3339 or SQLite timestamp; values that fail to parse are kept.
+fn format_timestamp(value: &str, format: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_utc())
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .map(|dt| dt.format(format).to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// A column value as CSV text: lists joined, costs to the cent, strings sanitized.
+fn csv_field(key: &str, value: &serde_json::Value, options: &ExportOptions) -> String {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => sanitize_csv_field(text),
+        Value::Number(n) if key == "estimated_cost" => format!("{:.2}", n.as_f64().unwrap_or(0.0)),
+        Value::Array(items) => sanitize_csv_field(
+            &items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string).unwrap_or_else(|| item.to_string()))
+                .collect::<Vec<_>>()
+                .join(&options.list_delimiter),
+        ),
+        other => other.to_string(),
+    }
+}
+
+fn encode(text: String, encoding: ExportEncoding) -> Vec<u8> {
+    match encoding {
+        ExportEncoding::Utf8 => text.into_bytes(),
+        ExportEncoding::Utf8Bom => [&[0xEF, 0xBB, 0xBF][..], text.as_bytes()].concat(),
+        ExportEncoding::Utf16Le => [0xFF, 0xFE]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()))
+            .collect(),
+    }
+}
+
+fn build_csv(records: &[ExportRecord], columns: &[ExportColumn], options: &ExportOptions) -> AppResult<Vec<u8>> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(options.delimiter as u8)
+        .from_writer(Vec::new());
+
+    wtr.write_record(columns.iter().map(|c| c.label.as_str()))
+        .map_err(|e| AppError::Csv(e.to_string()))?;
+    for record in records {
+        wtr.write_record(
+            columns
+                .iter()
+                .map(|c| csv_field(&c.key, &column_value(&c.key, record, options), options)),
+        )
         .map_err(|e| AppError::Csv(e.to_string()))?;
     }
 
-    wtr.flush().map_err(|e| AppError::Csv(e.to_string()))?;
+    let bytes = wtr.into_inner().map_err(|e| AppError::Csv(e.to_string()))?;
+    let text = String::from_utf8(bytes).map_err(|e| AppError::Csv(e.to_string()))?;
+    Ok(encode(text, options.encoding))
+}
+
+fn build_json(
+    records: &[ExportRecord],
+    columns: &[ExportColumn],
+    definitions: &[CustomFieldDefinition],
+    options: &ExportOptions,
+) -> Vec<serde_json::Value> {
+    records
+        .iter()
+        .map(|record| {
+            let mut object: serde_json::Map<String, serde_json::Value> = columns
+                .iter()
+                .map(|c| (c.key.clone(), column_value(&c.key, record, options)))
+                .collect();
+            if options.json_mode == JsonExportMode::Nested {
+                let custom_fields: serde_json::Map<String, serde_json::Value> = definitions
+                    .iter()
+                    .filter_map(|def| {
+                        record
+                            .custom_fields
+                            .get(&def.id)
+                            .map(|v| (def.name.clone(), serde_json::Value::from(v.clone())))
+                    })
+                    .collect();
+                object.insert(
+                    "related".to_string(),
+                    serde_json::json!({
+                        "tags": record.tags,
+                        "roles": record.roles,
+                        "contributing_factors": record.factors,
+                        "sla": record.sla,
+                        "custom_fields": custom_fields,
+                        "action_items": record.action_items,
+                        "postmortem": record.postmortem,
+                    }),
+                );
+            }
+            serde_json::Value::Object(object)
+        })
+        .collect()
+}
+
+/// The JSON export of the incidents matching `filters`, one value per incident.
+pub(crate) async fn incidents_json(
+    db: &SqlitePool,
+    filters: &IncidentFilters,
+    options: &ExportOptions,
+) -> AppResult<Vec<serde_json::Value>> {
+    let definitions = custom_fields::list_custom_fields(db).await?;
+    let columns = selected_columns(options, &definitions)?;
+    let records = load_records(db, filters, options.json_mode == JsonExportMode::Nested).await?;
+    Ok(build_json(&records, &columns, &definitions, options))
+}
+
+#[tauri::command]
+pub async fn list_export_columns(db: State<'_, SqlitePool>) -> Result<Vec<ExportColumn>, AppError> {
+    let definitions = custom_fields::list_custom_fields(&*db).await?;
+    Ok(available_columns(&definitions))
+}
+
+#[tauri::command]
+pub async fn export_incidents_csv(
+    db: State<'_, SqlitePool>,
+    filters_json: String,
+    options: Option<ExportOptions>,
+) -> Result<String, AppError> {
+    let filters: IncidentFilters =
+        serde_json::from_str(&filters_json).unwrap_or_default();
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
+    let definitions = custom_fields::list_custom_fields(&*db).await?;
+    let columns = selected_columns(&options, &definitions)?;
+    let records = load_records(&db, &filters, false).await?;
+    let bytes = build_csv(&records, &columns, &options)?;
+
+    let temp_dir = std::env::temp_dir();
+    let filename = format!("incidents_export_{}.csv", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    let path = temp_dir.join(&filename);
+
+    tokio::fs::write(&path, &bytes).await?;
 
     let path_str = path
         .to_str()
@@ -236,7 +465,7 @@ pub async fn export_incidents_csv(
         "export",
         "csv",
         "created",
-        &format!("Exported {} incidents to CSV", rows.len()),
+        &format!("Exported {} incidents to CSV", records.len()),
         "",
     )
     .await;
@@ -248,93 +477,672 @@ pub async fn export_incidents_csv(
 pub async fn export_incidents_json(
     db: State<'_, SqlitePool>,
     filters_json: String,
+    options: Option<ExportOptions>,
 ) -> Result<String, AppError> {
     let filters: IncidentFilters =
         serde_json::from_str(&filters_json).unwrap_or_default();
+    let options = options.unwrap_or_default();
+    options.validate()?;
+
+    let incidents = incidents_json(&db, &filters, &options).await?;
+
+    let json_str = serde_json::to_string_pretty(&incidents)?;
+
+    let temp_dir = std::env::temp_dir();
+    let filename = format!(
+        "incidents_export_{}.json",
+        chrono::Utc::now().format("%Y%m%d_%H%M%S")
+    );
+    let path = temp_dir.join(&filename);
+
+    tokio::fs::write(&path, json_str.as_bytes()).await?;
+
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| AppError::Internal("Invalid path encoding".into()))?
+        .to_string();
+
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "export",
+        "json",
+        "created",
+        &format!("Exported {} incidents to JSON", incidents.len()),
+        "",
+    )
+    .await;
+
+    Ok(path_str)
+}
+
+/// A typed cell in the XLSX export. Dates that fail to parse are written as text.
+#[derive(Debug, Clone, PartialEq)]
+enum XlsxCell {
+    Text(String),
+    Number(f64),
+    Date(String),
+    Bool(bool),
+    Empty,
+}
+
+impl From<String> for XlsxCell {
+    fn from(text: String) -> Self {
+        XlsxCell::Text(text)
+    }
+}
+
+impl From<&str> for XlsxCell {
+    fn from(text: &str) -> Self {
+        XlsxCell::Text(text.to_string())
+    }
+}
+
+impl From<i64> for XlsxCell {
+    fn from(value: i64) -> Self {
+        XlsxCell::Number(value as f64)
+    }
+}
+
+impl From<f64> for XlsxCell {
+    fn from(value: f64) -> Self {
+        XlsxCell::Number(value)
+    }
+}
 
-    let (sql, binds) = build_filtered_query(&filters);
+impl From<bool> for XlsxCell {
+    fn from(value: bool) -> Self {
+        XlsxCell::Bool(value)
+    }
+}
 
+impl XlsxCell {
+    fn date(value: Option<String>) -> Self {
+        value.filter(|v| !v.trim().is_empty()).map(XlsxCell::Date).unwrap_or(XlsxCell::Empty)
+    }
+
+    fn number(value: Option<i64>) -> Self {
+        value.map(XlsxCell::from).unwrap_or(XlsxCell::Empty)
+    }
+}
+
+/// One worksheet: a header row followed by data rows.
+#[derive(Debug)]
+struct Sheet {
+    name: &'static str,
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<XlsxCell>>,
+}
+
+impl Sheet {
+    fn new(name: &'static str, headers: &[&'static str]) -> Self {
+        Self {
+            name,
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// Parse an RFC 3339, SQLite (`YYYY-MM-DD HH:MM:SS`) or date-only timestamp
+/// as an Excel datetime in UTC.
+fn excel_datetime(value: &str) -> Option<ExcelDateTime> {
+    let value = value.trim();
+    let naive = chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_utc())
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default())
+        })
+        .ok()?;
+    ExcelDateTime::from_ymd(u16::try_from(naive.year()).ok()?, naive.month() as u8, naive.day() as u8)
+        .and_then(|d| d.and_hms(naive.hour() as u16, naive.minute() as u8, naive.second()))
+        .ok()
+}
+
+/// Longest text Excel stores in a single cell, in characters.
+const XLSX_MAX_CELL_CHARS: usize = 32_767;
+/// Appended to text cut short to fit in a cell.
+const XLSX_TRUNCATED_MARKER: &str = "\n[truncated]";
+
+/// `text`, cut to fit in a single cell with a marker when it is too long.
+fn xlsx_text(text: &str) -> Cow<'_, str> {
+    if text.chars().count() <= XLSX_MAX_CELL_CHARS {
+        return text.into();
+    }
+    let keep = XLSX_MAX_CELL_CHARS - XLSX_TRUNCATED_MARKER.chars().count();
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.push_str(XLSX_TRUNCATED_MARKER);
+    truncated.into()
+}
+
+fn write_workbook(sheets: &[Sheet]) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold().set_background_color("E0E0E0");
+    let date = Format::new().set_num_format("yyyy-mm-dd hh:mm");
+    let number = Format::new().set_num_format("#,##0.##");
+
+    for sheet in sheets {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(sheet.name)?;
+        for (col, title) in sheet.headers.iter().enumerate() {
+            worksheet.write_string_with_format(0, col as u16, *title, &header)?;
+        }
+        for (i, row) in sheet.rows.iter().enumerate() {
+            let r = i as u32 + 1;
+            for (col, cell) in row.iter().enumerate() {
+                let c = col as u16;
+                match cell {
+                    XlsxCell::Text(text) => {
+                        worksheet.write_string(r, c, xlsx_text(text))?;
+                    }
+                    XlsxCell::Number(value) => {
+                        worksheet.write_number_with_format(r, c, *value, &number)?;
+                    }
+                    XlsxCell::Date(value) => match excel_datetime(value) {
+                        Some(dt) => {
+                            worksheet.write_datetime_with_format(r, c, &dt, &date)?;
+                        }
+                        None => {
+                            worksheet.write_string(r, c, xlsx_text(value))?;
+                        }
+                    },
+                    XlsxCell::Bool(value) => {
+                        worksheet.write_boolean(r, c, *value)?;
+                    }
+                    XlsxCell::Empty => {}
+                }
+            }
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+        let last_col = sheet.headers.len().saturating_sub(1) as u16;
+        worksheet.autofilter(0, 0, sheet.rows.len() as u32, last_col)?;
+        worksheet.autofit();
+    }
+
+    workbook.save_to_buffer()
+}
+
+fn summary_row(sheet: &mut Sheet, metric: &str, result: &MetricResult) {
+    sheet.rows.push(vec![
+        metric.into(),
+        result.value.into(),
+        result.previous_value.map(XlsxCell::from).unwrap_or(XlsxCell::Empty),
+        result.formatted_value.clone().into(),
+    ]);
+}
+
+/// Collect every sheet of the workbook for the incidents matching `filters`.
+async fn workbook_sheets(db: &SqlitePool, filters: &IncidentFilters) -> AppResult<Vec<Sheet>> {
+    let (sql, binds) = build_filtered_query(filters);
     let mut query = sqlx::query(&sql);
     for bind in &binds {
         query = query.bind(bind);
     }
-
     let rows = query
-        .fetch_all(&*db)
+        .fetch_all(db)
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
+    let ids: Vec<String> = rows.iter().map(|r| r.get("id")).collect();
+    let cost_model = costs::get_cost_model(db).await?;
+    let cost_totals = costs::incident_cost_totals(db, &cost_model, &ids).await?;
+    let definitions = custom_fields::list_custom_fields(db).await?;
+
+    let mut incident_sheet = Sheet::new(
+        "Incidents",
+        &[
+            "ID", "Title", "Service", "Severity", "Impact", "Priority", "Status", "Started At", "Detected At",
+            "Acknowledged At", "First Response At", "Mitigation Started At", "Responded At", "Resolved At",
+            "Reopen Count", "Duration (minutes)", "Tickets Submitted", "Affected Users", "Is Recurring",
+            "Recurrence Of", "Root Cause", "Resolution", "Lessons Learned", "External Ref", "Estimated Cost",
+            "Created At", "Updated At",
+        ],
+    );
+    let mut action_sheet = Sheet::new(
+        "Action Items",
+        &[
+            "Incident ID", "Incident", "Title", "Status", "Owner", "Due Date", "Completed At", "Validated At",
+            "Description", "Outcome Notes",
+        ],
+    );
+    let mut postmortem_sheet = Sheet::new(
+        "Post-mortems",
+        &[
+            "Incident ID", "Incident", "Status", "Completed At", "No Action Items Justified", "Justification",
+            "Content", "Updated At",
+        ],
+    );
+    let mut factor_sheet = Sheet::new("Contributing Factors", &["Incident ID", "Incident", "Category", "Root", "Description"]);
+    let mut sla_sheet = Sheet::new(
+        "SLA Status",
+        &[
+            "Incident ID", "Incident", "Priority", "Response Target (min)", "Response Elapsed (min)",
+            "Response Breached", "Resolve Target (min)", "Resolve Elapsed (min)", "Resolve Breached",
+        ],
+    );
+    let mut tag_sheet = Sheet::new("Tags", &["Incident ID", "Incident", "Tag"]);
+    let mut field_sheet = Sheet::new("Custom Fields", &["Incident ID", "Incident", "Field", "Value"]);
 
-    // Build JSON array from rows
-    let mut incidents: Vec<serde_json::Value> = Vec::with_capacity(rows.len());
     for row in &rows {
+        let id: String = row.get("id");
+        let title: String = row.get("title");
         let severity: String = row.get("severity");
         let impact: String = row.get("impact");
         let sev = Severity::from_str(&severity).unwrap_or(Severity::Medium);
         let imp = Impact::from_str(&impact).unwrap_or(Impact::Medium);
         let priority = calculate_priority(&sev, &imp).to_string();
+        let text = |column: &str| -> XlsxCell { row.get::<Option<String>, _>(column).unwrap_or_default().into() };
+        let date = |column: &str| XlsxCell::date(row.get::<Option<String>, _>(column));
 
-        let incident = serde_json::json!({
-            "id": row.get::<String, _>("id"),
-            "title": row.get::<String, _>("title"),
-            "service_id": row.get::<String, _>("service_id"),
-            "service_name": row.get::<Option<String>, _>("service_name").unwrap_or_else(|| "Unknown".to_string()),
-            "severity": severity,
-            "impact": impact,
-            "priority": priority,
-            "status": row.get::<String, _>("status"),
-            "started_at": row.get::<String, _>("started_at"),
-            "detected_at": row.get::<String, _>("detected_at"),
-            "acknowledged_at": row.get::<Option<String>, _>("acknowledged_at"),
-            "first_response_at": row.get::<Option<String>, _>("first_response_at"),
-            "mitigation_started_at": row.get::<Option<String>, _>("mitigation_started_at"),
-            "responded_at": row.get::<Option<String>, _>("responded_at"),
-            "resolved_at": row.get::<Option<String>, _>("resolved_at"),
-            "reopened_at": row.get::<Option<String>, _>("reopened_at"),
-            "reopen_count": row.get::<Option<i64>, _>("reopen_count").unwrap_or(0),
-            "duration_minutes": row.get::<Option<i64>, _>("duration_minutes"),
-            "root_cause": row.get::<Option<String>, _>("root_cause").unwrap_or_default(),
-            "resolution": row.get::<Option<String>, _>("resolution").unwrap_or_default(),
-            "tickets_submitted": row.get::<Option<i64>, _>("tickets_submitted").unwrap_or(0),
-            "affected_users": row.get::<Option<i64>, _>("affected_users").unwrap_or(0),
-            "is_recurring": row.get::<bool, _>("is_recurring"),
-            "recurrence_of": row.get::<Option<String>, _>("recurrence_of"),
-            "lessons_learned": row.get::<Option<String>, _>("lessons_learned").unwrap_or_default(),
-            "action_items": row.get::<Option<String>, _>("action_items").unwrap_or_default(),
-            "external_ref": row.get::<Option<String>, _>("external_ref").unwrap_or_default(),
-            "notes": row.get::<Option<String>, _>("notes").unwrap_or_default(),
-            "created_at": row.get::<String, _>("created_at"),
-            "updated_at": row.get::<String, _>("updated_at"),
-        });
-        incidents.push(incident);
+        incident_sheet.rows.push(vec![
+            id.clone().into(),
+            title.clone().into(),
+            row.get::<Option<String>, _>("service_name").unwrap_or_else(|| "Unknown".to_string()).into(),
+            severity.into(),
+            impact.into(),
+            priority.into(),
+            text("status"),
+            date("started_at"),
+            date("detected_at"),
+            date("acknowledged_at"),
+            date("first_response_at"),
+            date("mitigation_started_at"),
+            date("responded_at"),
+            date("resolved_at"),
+            row.get::<Option<i64>, _>("reopen_count").unwrap_or(0).into(),
+            XlsxCell::number(row.get::<Option<i64>, _>("duration_minutes")),
+            row.get::<Option<i64>, _>("tickets_submitted").unwrap_or(0).into(),
+            row.get::<Option<i64>, _>("affected_users").unwrap_or(0).into(),
+            row.get::<bool, _>("is_recurring").into(),
+            text("recurrence_of"),
+            text("root_cause"),
+            text("resolution"),
+            text("lessons_learned"),
+            text("external_ref"),
+            cost_totals.get(&id).copied().unwrap_or_default().into(),
+            date("created_at"),
+            date("updated_at"),
+        ]);
+
+        let (items, postmortem, factors, status, incident_tags, values) = tokio::try_join!(
+            incidents::list_action_items(db, Some(&id)),
+            postmortems::get_postmortem_by_incident(db, &id),
+            postmortems::list_contributing_factors(db, &id),
+            sla::compute_sla_status(db, &id),
+            tags::get_incident_tags(db, &id),
+            custom_fields::get_incident_custom_fields(db, &id),
+        )?;
+
+        for item in items {
+            action_sheet.rows.push(vec![
+                id.clone().into(),
+                title.clone().into(),
+                item.title.into(),
+                item.status.into(),
+                item.owner.into(),
+                XlsxCell::date(item.due_date),
+                XlsxCell::date(item.completed_at),
+                XlsxCell::date(item.validated_at),
+                item.description.into(),
+                item.outcome_notes.into(),
+            ]);
+        }
+        if let Some(pm) = postmortem {
+            postmortem_sheet.rows.push(vec![
+                id.clone().into(),
+                title.clone().into(),
+                pm.status.into(),
+                XlsxCell::date(pm.completed_at),
+                pm.no_action_items_justified.into(),
+                pm.no_action_items_justification.into(),
+                extract_markdown(&pm.content).into(),
+                XlsxCell::date(Some(pm.updated_at)),
+            ]);
+        }
+        for factor in factors {
+            factor_sheet.rows.push(vec![
+                id.clone().into(),
+                title.clone().into(),
+                factor.category.into(),
+                factor.is_root.into(),
+                factor.description.into(),
+            ]);
+        }
+        sla_sheet.rows.push(vec![
+            id.clone().into(),
+            title.clone().into(),
+            status.priority.into(),
+            XlsxCell::number(status.response_target_minutes),
+            XlsxCell::number(status.response_elapsed_minutes),
+            status.response_breached.into(),
+            XlsxCell::number(status.resolve_target_minutes),
+            XlsxCell::number(status.resolve_elapsed_minutes),
+            status.resolve_breached.into(),
+        ]);
+        for tag in incident_tags {
+            tag_sheet.rows.push(vec![id.clone().into(), title.clone().into(), tag.into()]);
+        }
+        for def in &definitions {
+            let Some(value) = values.iter().find(|v| v.field_id == def.id && !v.value.trim().is_empty()) else {
+                continue;
+            };
+            let cell = match value.value.trim().parse::<f64>() {
+                Ok(number) if def.field_type == "number" => number.into(),
+                _ => value.value.clone().into(),
+            };
+            field_sheet.rows.push(vec![id.clone().into(), title.clone().into(), def.name.clone().into(), cell]);
+        }
     }
 
-    let json_str = serde_json::to_string_pretty(&incidents)?;
+    // Dashboard metrics over the export's date range (or the span of the
+    // exported incidents) for the filtered service
+    let earliest = rows.iter().map(|r| r.get::<String, _>("started_at")).min();
+    let range = DateRange {
+        start: filters.date_from.clone().or(earliest).unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        end: filters.date_to.clone().unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+    };
+    let metric_filters = MetricFilters {
+        service_ids: filters.service_id.clone().map(|s| vec![s]),
+        ..Default::default()
+    };
+    let period = format!("{} to {}", range.start, range.end);
+    let dashboard = metrics::get_dashboard_data(db, &range, None, &metric_filters, &period).await?;
+
+    let mut summary = Sheet::new("Summary", &["Metric", "Value", "Previous", "Formatted"]);
+    summary.rows.push(vec!["Period".into(), XlsxCell::Empty, XlsxCell::Empty, period.into()]);
+    summary.rows.push(vec!["Exported incidents".into(), (rows.len() as i64).into(), XlsxCell::Empty, XlsxCell::Empty]);
+    summary.rows.push(vec!["Incidents in period".into(), dashboard.total_incidents.into(), XlsxCell::Empty, XlsxCell::Empty]);
+    summary_row(&mut summary, "MTTR (minutes)", &dashboard.mttr);
+    summary_row(&mut summary, "MTTA (minutes)", &dashboard.mtta);
+    summary_row(&mut summary, "Recurrence rate (%)", &dashboard.recurrence_rate);
+    summary_row(&mut summary, "Avg tickets per incident", &dashboard.avg_tickets);
+    summary_row(&mut summary, "Estimated cost", &dashboard.estimated_cost);
+    summary_row(&mut summary, "Checklist completion (%)", &dashboard.checklist_completion);
+    for (dimension, counts) in [
+        ("Severity", &dashboard.by_severity),
+        ("Impact", &dashboard.by_impact),
+        ("Service", &dashboard.by_service),
+    ] {
+        for count in counts {
+            summary.rows.push(vec![
+                format!("Incidents by {}: {}", dimension.to_lowercase(), count.category).into(),
+                count.count.into(),
+                XlsxCell::number(count.previous_count),
+                XlsxCell::Empty,
+            ]);
+        }
+    }
+
+    Ok(vec![
+        summary,
+        incident_sheet,
+        action_sheet,
+        postmortem_sheet,
+        factor_sheet,
+        sla_sheet,
+        tag_sheet,
+        field_sheet,
+    ])
+}
+
+#[tauri::command]
+pub async fn export_incidents_xlsx(
+    db: State<'_, SqlitePool>,
+    filters_json: String,
+) -> Result<String, AppError> {
+    let filters: IncidentFilters =
+        serde_json::from_str(&filters_json).unwrap_or_default();
+
+    let sheets = workbook_sheets(&db, &filters).await?;
+    let bytes = write_workbook(&sheets)
+        .map_err(|e| AppError::Report(format!("Failed to build XLSX: {}", e)))?;
 
     let temp_dir = std::env::temp_dir();
     let filename = format!(
-        "incidents_export_{}.json",
+        "incidents_export_{}.xlsx",
         chrono::Utc::now().format("%Y%m%d_%H%M%S")
     );
     let path = temp_dir.join(&filename);
 
-    tokio::fs::write(&path, json_str.as_bytes())
-        .await
-        .map_err(|e| AppError::Io(e))?;
+    tokio::fs::write(&path, &bytes).await?;
 
     let path_str = path
         .to_str()
         .ok_or_else(|| AppError::Internal("Invalid path encoding".into()))?
         .to_string();
 
+    let exported = sheets.iter().find(|s| s.name == "Incidents").map(|s| s.rows.len()).unwrap_or(0);
     let _ = audit::insert_audit_entry(
         &*db,
         "export",
-        "json",
+        "xlsx",
         "created",
-        &format!("Exported {} incidents to JSON", incidents.len()),
+        &format!("Exported {} incidents to XLSX", exported),
         "",
     )
     .await;
 
     Ok(path_str)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("export-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: the workbook has every sheet, typed incident cells, per-incident detail rows and respects the filters
+    #[tokio::test]
+    async fn test_xlsx_workbook_sheets() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at, \
+             tickets_submitted) VALUES \
+             ('inc-1', 'Slack outage', 'svc-slack', 'High', 'High', 'Resolved', '2026-03-10T10:00:00Z', \
+              '2026-03-10T10:05:00Z', '2026-03-10T12:00:00Z', 7), \
+             ('inc-2', 'Zoom lag', 'svc-zoom', 'Low', 'Low', 'Active', '2026-03-11T10:00:00Z', '2026-03-11T10:05:00Z', NULL, 0)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO action_items (id, incident_id, title, status, due_date) VALUES ('a1', 'inc-1', 'Add alerts', 'Open', '2026-04-01')")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO incident_tags (incident_id, tag) VALUES ('inc-1', 'network'), ('inc-2', 'video')")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO custom_field_definitions (id, name, field_type) VALUES ('cf-1', 'Revenue Lost', 'number')")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO custom_field_values (incident_id, field_id, value) VALUES ('inc-1', 'cf-1', '1500')")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let filters = IncidentFilters {
+            service_id: Some("svc-slack".into()),
+            ..Default::default()
+        };
+        let sheets = workbook_sheets(&db, &filters).await.unwrap();
+        let names: Vec<&str> = sheets.iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            ["Summary", "Incidents", "Action Items", "Post-mortems", "Contributing Factors", "SLA Status", "Tags", "Custom Fields"]
+        );
+        let sheet = |name: &str| sheets.iter().find(|s| s.name == name).unwrap();
+
+        let incidents = sheet("Incidents");
+        assert_eq!(incidents.rows.len(), 1);
+        let row = &incidents.rows[0];
+        assert_eq!(row.len(), incidents.headers.len());
+        assert_eq!(row[7], XlsxCell::Date("2026-03-10T10:00:00Z".into()));
+        assert_eq!(row[15], XlsxCell::Number(120.0));
+        assert_eq!(row[16], XlsxCell::Number(7.0));
+        assert_eq!(row[13], XlsxCell::Date("2026-03-10T12:00:00Z".into()));
+
+        assert_eq!(sheet("Action Items").rows[0][5], XlsxCell::Date("2026-04-01".into()));
+        assert_eq!(sheet("Tags").rows, vec![vec!["inc-1".into(), "Slack outage".into(), "network".into()]]);
+        assert_eq!(sheet("Custom Fields").rows[0][3], XlsxCell::Number(1500.0));
+        assert_eq!(sheet("SLA Status").rows.len(), 1);
+        assert!(sheet("Summary").rows.iter().any(|r| r[0] == "Exported incidents".into() && r[1] == XlsxCell::Number(1.0)));
+
+        assert!(excel_datetime("2026-03-10T10:00:00+02:00").is_some());
+        assert!(excel_datetime("2026-03-10 10:00:00").is_some());
+        assert!(excel_datetime("soon").is_none());
+
+        let bytes = write_workbook(&sheets).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+    }
+
+    /// Test: text longer than an Excel cell allows is truncated, not fatal
+    #[tokio::test]
+    async fn test_xlsx_truncates_long_postmortem() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at) VALUES \
+             ('inc-1', 'Slack outage', 'svc-slack', 'High', 'High', 'Active', '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        let markdown = "é".repeat(40_000);
+        sqlx::query("INSERT INTO postmortems (id, incident_id, content) VALUES ('pm-1', 'inc-1', ?)")
+            .bind(serde_json::json!({ "markdown": markdown }).to_string())
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let sheets = workbook_sheets(&db, &IncidentFilters::default()).await.unwrap();
+        let bytes = write_workbook(&sheets).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+
+        let text = xlsx_text(&markdown);
+        assert_eq!(text.chars().count(), XLSX_MAX_CELL_CHARS);
+        assert!(text.ends_with(XLSX_TRUNCATED_MARKER));
+        assert_eq!(xlsx_text("short"), "short");
+    }
+
+    async fn seed_related(db: &SqlitePool) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, \
+             responded_at, resolved_at) VALUES ('inc-1', '=Slack outage', 'svc-slack', 'High', 'High', 'Resolved', \
+             '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', '2026-03-10T10:20:00Z', '2026-03-10T12:00:00Z')",
+        )
+        .execute(db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO incident_tags (incident_id, tag) VALUES ('inc-1', 'network'), ('inc-1', 'proxy')")
+            .execute(db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO incident_roles (id, incident_id, role, assignee) VALUES ('r1', 'inc-1', 'Incident Commander', 'Alice')",
+        )
+        .execute(db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO custom_field_definitions (id, name, field_type) VALUES ('cf-1', 'Customer', 'text')")
+            .execute(db)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO custom_field_values (incident_id, field_id, value) VALUES ('inc-1', 'cf-1', 'ACME')")
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    /// Test: default CSV options keep the standard columns; selected columns add tags, roles, SLA and custom fields in the chosen format
+    #[tokio::test]
+    async fn test_csv_export_options() {
+        let (_dir, db) = setup_db().await;
+        seed_related(&db).await;
+        let definitions = custom_fields::list_custom_fields(&db).await.unwrap();
+        let records = load_records(&db, &IncidentFilters::default(), false).await.unwrap();
+
+        let options = ExportOptions::default();
+        let columns = selected_columns(&options, &definitions).unwrap();
+        assert_eq!(columns.len(), 31);
+        let csv = String::from_utf8(build_csv(&records, &columns, &options).unwrap()).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("ID,Title,Service ID,Service Name,"));
+        assert!(lines.next().unwrap().starts_with("inc-1,'=Slack outage,svc-slack,Slack,"));
+
+        let options = ExportOptions {
+            columns: ["id", "started_at", "tags", "roles", "sla_response_met", "sla_resolve_met", "custom:cf-1"]
+                .map(String::from)
+                .to_vec(),
+            delimiter: ';',
+            date_format: "%d/%m/%Y %H:%M".into(),
+            list_delimiter: "|".into(),
+            ..Default::default()
+        };
+        let columns = selected_columns(&options, &definitions).unwrap();
+        let csv = String::from_utf8(build_csv(&records, &columns, &options).unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "ID;Started At;Tags;Roles;SLA Response Met;SLA Resolve Met;Customer\n\
+             inc-1;10/03/2026 10:00;network|proxy;Incident Commander: Alice;true;true;ACME\n"
+        );
+
+        let utf16 = build_csv(&records, &columns, &ExportOptions { encoding: ExportEncoding::Utf16Le, ..options.clone() }).unwrap();
+        assert_eq!(&utf16[..4], &[0xFF, 0xFE, b'I', 0]);
+        let bom = build_csv(&records, &columns, &ExportOptions { encoding: ExportEncoding::Utf8Bom, ..options }).unwrap();
+        assert_eq!(&bom[..4], b"\xEF\xBB\xBFI");
+
+        let unknown = ExportOptions { columns: vec!["custom:missing".into()], ..Default::default() };
+        assert!(selected_columns(&unknown, &definitions).is_err());
+    }
+
+    /// Test: flat JSON keeps the legacy keys and nested JSON embeds the related records per incident
+    #[tokio::test]
+    async fn test_json_export_modes() {
+        let (_dir, db) = setup_db().await;
+        seed_related(&db).await;
+        sqlx::query("INSERT INTO action_items (id, incident_id, title, status) VALUES ('a1', 'inc-1', 'Add alerts', 'Open')")
+            .execute(&db)
+            .await
+            .unwrap();
+        let definitions = custom_fields::list_custom_fields(&db).await.unwrap();
+
+        let flat_options = ExportOptions::default();
+        let records = load_records(&db, &IncidentFilters::default(), false).await.unwrap();
+        let columns = selected_columns(&flat_options, &definitions).unwrap();
+        let flat = build_json(&records, &columns, &definitions, &flat_options);
+        assert_eq!(flat[0]["title"], "=Slack outage");
+        assert_eq!(flat[0]["acknowledged_at"], serde_json::Value::Null);
+        assert_eq!(flat[0]["reopen_count"], 0);
+        assert!(flat[0].get("related").is_none());
+
+        let nested_options = ExportOptions { json_mode: JsonExportMode::Nested, ..Default::default() };
+        let records = load_records(&db, &IncidentFilters::default(), true).await.unwrap();
+        let nested = build_json(&records, &columns, &definitions, &nested_options);
+        let related = &nested[0]["related"];
+        assert_eq!(related["tags"], serde_json::json!(["network", "proxy"]));
+        assert_eq!(related["roles"][0]["assignee"], "Alice");
+        assert_eq!(related["custom_fields"]["Customer"], "ACME");
+        assert_eq!(related["action_items"][0]["title"], "Add alerts");
+        assert_eq!(related["sla"]["priority"], "P1");
+        assert_eq!(related["postmortem"], serde_json::Value::Null);
+    }
+}
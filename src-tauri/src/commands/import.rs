@@ -1,13 +1,18 @@
 use serde::{Deserialize, Serialize};
 use sqlx::{Row, SqlitePool};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tauri::State;
 
-use crate::db::queries::incidents;
+use crate::db::queries::{audit, custom_fields, incidents, postmortems, tags};
 use crate::error::AppError;
 use crate::import::column_mapper::{self, ColumnMapping, MappedIncident};
 use crate::import::csv_parser;
-use crate::models::incident::CreateIncidentRequest;
+use crate::import::json_parser::{self, JsonIncident};
+use crate::import::presets;
+use crate::import::upsert::{self, FieldChange, ImportMode, ImportOptions, RowPlan};
+use crate::models::custom_field::{CustomFieldDefinition, CustomFieldValue};
+use crate::models::incident::{CreateActionItemRequest, CreateIncidentRequest, IncidentFilters};
+use crate::models::postmortem::CreateContributingFactorRequest;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportPreview {
@@ -16,6 +21,9 @@ pub struct ImportPreview {
     pub error_count: i64,
     pub ready_count: i64,
     pub warning_count: i64,
+    pub create_count: i64,
+    pub update_count: i64,
+    pub unchanged_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +37,10 @@ pub struct PreviewRow {
     pub detected_at: String,
     pub row_status: String, // "ready", "warning", "error"
     pub messages: Vec<String>,
+    pub action: String, // "create", "update", "unchanged", "conflict"
+    pub matched_incident_id: Option<String>,
+    /// Fields an upsert would change on the matched incident.
+    pub changes: Vec<FieldChange>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +53,8 @@ pub struct ImportWarning {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportResult {
     pub created: i64,
+    pub updated: i64,
+    pub unchanged: i64,
     pub skipped: i64,
     pub errors: Vec<String>,
 }
@@ -54,10 +68,26 @@ pub struct ImportTemplate {
     pub updated_at: String,
 }
 
+/// Column headers of a CSV file, with the built-in presets mapped onto them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvHeaders {
+    pub headers: Vec<String>,
+    pub presets: Vec<ImportPresetMapping>,
+    /// Id of the preset whose tool export the headers match, if any.
+    pub detected_preset: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPresetMapping {
+    pub id: String,
+    pub name: String,
+    pub mapping: ColumnMapping,
+}
+
 #[tauri::command]
-pub async fn parse_csv_headers(file_path: String) -> Result<Vec<String>, AppError> {
+pub async fn parse_csv_headers(file_path: String) -> Result<CsvHeaders, AppError> {
     let headers = csv_parser::parse_csv_headers(&file_path)?;
-    Ok(headers)
+    Ok(csv_headers(headers))
 }
 
 #[tauri::command]
@@ -65,31 +95,208 @@ pub async fn preview_csv_import(
     db: State<'_, SqlitePool>,
     file_path: String,
     mapping: ColumnMapping,
+    options: Option<ImportOptions>,
 ) -> Result<ImportPreview, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    mapping.validate()?;
     let rows = csv_parser::parse_csv_rows(&file_path)?;
-
-    if rows.is_empty() {
-        return Ok(ImportPreview {
-            incidents: vec![],
-            warnings: vec![],
-            error_count: 0,
-            ready_count: 0,
-            warning_count: 0,
-        });
-    }
-
+    let fields = mapped_fields(&mapping);
+    options.require_match_column(&fields)?;
     let mapped = column_mapper::apply_mapping(&rows, &mapping);
+    let provided = vec![fields; mapped.len()];
 
     // Load services for name matching
     let services = load_service_names(&db).await?;
+    let plans = plan_import(&db, &mapped, &provided, &services, &options).await?;
+
+    Ok(build_preview(&mapped, &services, &plans))
+}
+
+#[tauri::command]
+pub async fn execute_csv_import(
+    db: State<'_, SqlitePool>,
+    file_path: String,
+    mapping: ColumnMapping,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    mapping.validate()?;
+    let rows = csv_parser::parse_csv_rows(&file_path)?;
+    let fields = mapped_fields(&mapping);
+    options.require_match_column(&fields)?;
+    let mapped = column_mapper::apply_mapping(&rows, &mapping);
+    let provided = vec![fields; mapped.len()];
+    import_mapped(&db, &mapped, &provided, None, &options).await
+}
+
+/// Preview a JSON array or NDJSON file of incidents, such as the output of
+/// `export_incidents_json`. Related records are checked too; problems with
+/// them are warnings, since the incident itself can still be imported.
+#[tauri::command]
+pub async fn preview_json_import(
+    db: State<'_, SqlitePool>,
+    file_path: String,
+    options: Option<ImportOptions>,
+) -> Result<ImportPreview, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    let records = json_parser::parse_json_incidents(&file_path)?;
+    let mut mapped = map_json_incidents(&records);
+    let provided = json_fields(&records);
+
+    let (services, definitions) = tokio::try_join!(
+        load_service_names(&db),
+        custom_fields::list_custom_fields(&db),
+    )?;
+    add_related_warnings(&mut mapped, &records, &definitions);
+    let plans = plan_import(&db, &mapped, &provided, &services, &options).await?;
+
+    Ok(build_preview(&mapped, &services, &plans))
+}
+
+/// Import incidents from a JSON array or NDJSON file with their tags, custom
+/// fields, action items and contributing factors. Related records are only
+/// created for new incidents; upserted incidents keep their existing ones.
+#[tauri::command]
+pub async fn execute_json_import(
+    db: State<'_, SqlitePool>,
+    file_path: String,
+    options: Option<ImportOptions>,
+) -> Result<ImportResult, AppError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    let records = json_parser::parse_json_incidents(&file_path)?;
+    let mapped = map_json_incidents(&records);
+    let provided = json_fields(&records);
+    import_mapped(&db, &mapped, &provided, Some(&records), &options).await
+}
+
+#[tauri::command]
+pub async fn save_import_template(
+    db: State<'_, SqlitePool>,
+    name: String,
+    column_mapping: String,
+) -> Result<ImportTemplate, AppError> {
+    let id = format!("tpl-{}", uuid::Uuid::new_v4());
+
+    sqlx::query(
+        "INSERT INTO import_templates (id, name, column_mapping) VALUES (?, ?, ?)"
+    )
+    .bind(&id)
+    .bind(&name)
+    .bind(&column_mapping)
+    .execute(&*db)
+    .await
+    .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+
+    let row = sqlx::query("SELECT * FROM import_templates WHERE id = ?")
+        .bind(&id)
+        .fetch_one(&*db)
+        .await
+        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+
+    Ok(parse_template_row(&row))
+}
+
+#[tauri::command]
+pub async fn list_import_templates(
+    db: State<'_, SqlitePool>,
+) -> Result<Vec<ImportTemplate>, AppError> {
+    let rows = sqlx::query("SELECT * FROM import_templates ORDER BY name")
+        .fetch_all(&*db)
+        .await
+        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+
+    Ok(rows.iter().map(parse_template_row).collect())
+}
+
+#[tauri::command]
+pub async fn delete_import_template(
+    db: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM import_templates WHERE id = ?")
+        .bind(&id)
+        .execute(&*db)
+        .await
+        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Import template '{}' not found",
+            id
+        )));
+    }
 
+    Ok(())
+}
+
+// ---- Helper Functions ----
+
+fn csv_headers(headers: Vec<String>) -> CsvHeaders {
+    let presets = presets::PRESETS
+        .iter()
+        .map(|preset| ImportPresetMapping {
+            id: preset.id.to_string(),
+            name: preset.name.to_string(),
+            mapping: preset.mapping_for(&headers),
+        })
+        .collect();
+    let detected_preset = presets::detect_preset(&headers).map(|p| p.id.to_string());
+    CsvHeaders {
+        headers,
+        presets,
+        detected_preset,
+    }
+}
+
+/// Load all services as a map of lowercase_name -> (id, name)
+async fn load_service_names(
+    db: &SqlitePool,
+) -> Result<HashMap<String, (String, String)>, AppError> {
+    let rows = sqlx::query("SELECT id, name FROM services")
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let mut map = HashMap::new();
+    for row in rows {
+        let id: String = row.get("id");
+        let name: String = row.get("name");
+        map.insert(name.to_lowercase(), (id, name));
+    }
+    Ok(map)
+}
+
+/// Match a service name to its ID (case-insensitive).
+fn resolve_service_id(
+    services: &HashMap<String, (String, String)>,
+    name: &str,
+) -> Option<String> {
+    services
+        .get(&name.to_lowercase())
+        .map(|(id, _)| id.clone())
+}
+
+/// Build the preview for mapped rows: errors and upsert conflicts block a
+/// row, while unknown services and mapping warnings flag it.
+fn build_preview(
+    mapped: &[MappedIncident],
+    services: &HashMap<String, (String, String)>,
+    plans: &[RowPlan],
+) -> ImportPreview {
     let mut preview_rows = Vec::new();
     let mut all_warnings = Vec::new();
     let mut error_count: i64 = 0;
     let mut ready_count: i64 = 0;
     let mut warning_count: i64 = 0;
+    let mut create_count: i64 = 0;
+    let mut update_count: i64 = 0;
+    let mut unchanged_count: i64 = 0;
 
-    for (idx, incident) in mapped.iter().enumerate() {
+    for (idx, (incident, plan)) in mapped.iter().zip(plans).enumerate() {
         let mut messages: Vec<String> = Vec::new();
         let mut row_status = "ready".to_string();
 
@@ -111,6 +318,10 @@ pub async fn preview_csv_import(
             messages.push(err.clone());
             row_status = "error".to_string();
         }
+        if let RowPlan::Conflict(message) = plan {
+            messages.push(message.clone());
+            row_status = "error".to_string();
+        }
 
         // Collect warnings from mapping
         for warn in &incident.warnings {
@@ -130,6 +341,14 @@ pub async fn preview_csv_import(
             "warning" => warning_count += 1,
             _ => ready_count += 1,
         }
+        if row_status != "error" {
+            match plan {
+                RowPlan::Create => create_count += 1,
+                RowPlan::Update { .. } => update_count += 1,
+                RowPlan::Unchanged { .. } => unchanged_count += 1,
+                RowPlan::Conflict(_) => {}
+            }
+        }
 
         preview_rows.push(PreviewRow {
             title: incident.title.clone(),
@@ -141,42 +360,104 @@ pub async fn preview_csv_import(
             detected_at: incident.detected_at.clone(),
             row_status,
             messages,
+            action: plan.action().to_string(),
+            matched_incident_id: plan.incident_id().map(str::to_string),
+            changes: match plan {
+                RowPlan::Update { changes, .. } => changes.clone(),
+                _ => Vec::new(),
+            },
         });
     }
 
-    Ok(ImportPreview {
+    ImportPreview {
         incidents: preview_rows,
         warnings: all_warnings,
         error_count,
         ready_count,
         warning_count,
-    })
+        create_count,
+        update_count,
+        unchanged_count,
+    }
 }
 
-#[tauri::command]
-pub async fn execute_csv_import(
-    db: State<'_, SqlitePool>,
-    file_path: String,
-    mapping: ColumnMapping,
-) -> Result<ImportResult, AppError> {
-    let rows = csv_parser::parse_csv_rows(&file_path)?;
+/// Plan each row as a create, update or no-op. Existing incidents are only
+/// loaded for upserts.
+async fn plan_import(
+    db: &SqlitePool,
+    mapped: &[MappedIncident],
+    provided: &[HashSet<String>],
+    services: &HashMap<String, (String, String)>,
+    options: &ImportOptions,
+) -> Result<Vec<RowPlan>, AppError> {
+    let existing = match options.mode {
+        ImportMode::Create => Vec::new(),
+        ImportMode::Upsert => incidents::list_incidents(db, &IncidentFilters::default(), None).await?,
+    };
+    Ok(upsert::plan_rows(&existing, mapped, provided, services, options))
+}
+
+/// Incident fields a CSV mapping supplies, directly, as a default, or through
+/// a preset fallback.
+fn mapped_fields(mapping: &ColumnMapping) -> HashSet<String> {
+    let mut fields: HashSet<String> = mapping
+        .mappings
+        .values()
+        .chain(mapping.default_values.iter().filter(|(_, v)| !v.is_empty()).map(|(k, _)| k))
+        .cloned()
+        .collect();
+    if let Some(preset) = mapping.preset.as_deref().and_then(presets::find_preset) {
+        for field in column_mapper::INCIDENT_FIELDS {
+            if preset.fallback(field).is_some_and(|source| fields.contains(source)) {
+                fields.insert(field.to_string());
+            }
+        }
+    }
+    fields
+}
 
-    if rows.is_empty() {
+/// Incident fields each JSON record supplies.
+fn json_fields(records: &[JsonIncident]) -> Vec<HashSet<String>> {
+    records
+        .iter()
+        .map(|r| r.fields.keys().cloned().collect())
+        .collect()
+}
+
+/// Insert or update mapped rows, skipping those with errors, upsert conflicts
+/// or unknown services. With JSON records (index-aligned with `mapped`),
+/// their related records are created for each inserted incident.
+async fn import_mapped(
+    db: &SqlitePool,
+    mapped: &[MappedIncident],
+    provided: &[HashSet<String>],
+    records: Option<&[JsonIncident]>,
+    options: &ImportOptions,
+) -> Result<ImportResult, AppError> {
+    if mapped.is_empty() {
         return Ok(ImportResult {
             created: 0,
+            updated: 0,
+            unchanged: 0,
             skipped: 0,
             errors: vec![],
         });
     }
 
-    let mapped = column_mapper::apply_mapping(&rows, &mapping);
-    let services = load_service_names(&db).await?;
+    let services = load_service_names(db).await?;
+    let definitions = match records {
+        Some(_) => custom_fields::list_custom_fields(db).await?,
+        None => Vec::new(),
+    };
+    let plans = plan_import(db, mapped, provided, &services, options).await?;
 
     let mut created: i64 = 0;
+    let mut updated: i64 = 0;
+    let mut unchanged: i64 = 0;
     let mut skipped: i64 = 0;
     let mut errors: Vec<String> = Vec::new();
 
-    for (idx, incident) in mapped.iter().enumerate() {
+    for (idx, (incident, plan)) in mapped.iter().zip(&plans).enumerate() {
         // Skip rows with errors
         if !incident.errors.is_empty() {
             skipped += 1;
@@ -198,119 +479,216 @@ pub async fn execute_csv_import(
             }
         };
 
+        match plan {
+            RowPlan::Conflict(message) => {
+                skipped += 1;
+                errors.push(message.clone());
+                continue;
+            }
+            RowPlan::Unchanged { .. } => {
+                unchanged += 1;
+                continue;
+            }
+            RowPlan::Update { incident_id, changes, request } => {
+                let result = match request.validate() {
+                    Ok(()) => incidents::update_incident(db, incident_id, request).await.map(|_| ()),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(()) => {
+                        updated += 1;
+                        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+                        let summary = format!("Updated incident from import ({})", fields.join(", "));
+                        let _ = audit::insert_audit_entry(db, "incident", incident_id, "updated", &summary, "").await;
+                    }
+                    Err(e) => {
+                        skipped += 1;
+                        errors.push(format!("Row {}: {}", idx + 1, e));
+                    }
+                }
+                continue;
+            }
+            RowPlan::Create => {}
+        }
+
         // Insert the incident
-        match insert_imported_incident(&db, &service_id, incident).await {
-            Ok(_) => created += 1,
+        let incident_id = match insert_imported_incident(db, &service_id, incident).await {
+            Ok(id) => id,
             Err(e) => {
                 skipped += 1;
                 errors.push(format!("Row {}: {}", idx + 1, e));
+                continue;
             }
+        };
+        created += 1;
+
+        if let Some(record) = records.and_then(|r| r.get(idx)) {
+            errors.extend(insert_related(db, idx, &incident_id, record, &definitions).await);
         }
     }
 
     Ok(ImportResult {
         created,
+        updated,
+        unchanged,
         skipped,
         errors,
     })
 }
 
-#[tauri::command]
-pub async fn save_import_template(
-    db: State<'_, SqlitePool>,
-    name: String,
-    column_mapping: String,
-) -> Result<ImportTemplate, AppError> {
-    let id = format!("tpl-{}", uuid::Uuid::new_v4());
+/// Map JSON incidents through the column mapper so JSON and CSV imports share
+/// the same field validation.
+fn map_json_incidents(records: &[JsonIncident]) -> Vec<MappedIncident> {
+    let mapping = ColumnMapping {
+        mappings: column_mapper::INCIDENT_FIELDS
+            .iter()
+            .map(|field| (field.to_string(), field.to_string()))
+            .collect(),
+        default_values: HashMap::new(),
+        preset: None,
+    };
+    let rows: Vec<HashMap<String, String>> = records.iter().map(|r| r.fields.clone()).collect();
 
-    sqlx::query(
-        "INSERT INTO import_templates (id, name, column_mapping) VALUES (?, ?, ?)"
-    )
-    .bind(&id)
-    .bind(&name)
-    .bind(&column_mapping)
-    .execute(&*db)
-    .await
-    .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+    let mut mapped = column_mapper::apply_json_mapping(&rows, &mapping);
+    for (incident, record) in mapped.iter_mut().zip(records) {
+        incident.warnings.extend(record.warnings.iter().cloned());
+    }
+    mapped
+}
 
-    let row = sqlx::query("SELECT * FROM import_templates WHERE id = ?")
-        .bind(&id)
-        .fetch_one(&*db)
-        .await
-        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+/// Placeholder incident id used to validate related records during preview.
+const PREVIEW_INCIDENT_ID: &str = "inc-preview";
 
-    Ok(parse_template_row(&row))
+/// Flag each row whose related records will be partly skipped.
+fn add_related_warnings(
+    mapped: &mut [MappedIncident],
+    records: &[JsonIncident],
+    definitions: &[CustomFieldDefinition],
+) {
+    for (idx, (incident, record)) in mapped.iter_mut().zip(records).enumerate() {
+        let (_, warnings) = prepare_related(idx, PREVIEW_INCIDENT_ID, record, definitions);
+        incident.warnings.extend(warnings);
+    }
 }
 
-#[tauri::command]
-pub async fn list_import_templates(
-    db: State<'_, SqlitePool>,
-) -> Result<Vec<ImportTemplate>, AppError> {
-    let rows = sqlx::query("SELECT * FROM import_templates ORDER BY name")
-        .fetch_all(&*db)
-        .await
-        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
-
-    Ok(rows.iter().map(parse_template_row).collect())
+/// Related records of a JSON incident, ready to insert.
+struct RelatedRecords {
+    tags: Vec<String>,
+    custom_fields: Vec<CustomFieldValue>,
+    action_items: Vec<CreateActionItemRequest>,
+    contributing_factors: Vec<CreateContributingFactorRequest>,
 }
 
-#[tauri::command]
-pub async fn delete_import_template(
-    db: State<'_, SqlitePool>,
-    id: String,
-) -> Result<(), AppError> {
-    let result = sqlx::query("DELETE FROM import_templates WHERE id = ?")
-        .bind(&id)
-        .execute(&*db)
-        .await
-        .map_err(|e: sqlx::Error| AppError::Database(e.to_string()))?;
+/// Build and validate the related records for an incident, returning the
+/// valid ones and a warning for each one that will be skipped.
+fn prepare_related(
+    idx: usize,
+    incident_id: &str,
+    record: &JsonIncident,
+    definitions: &[CustomFieldDefinition],
+) -> (RelatedRecords, Vec<String>) {
+    let row = idx + 1;
+    let mut warnings = Vec::new();
 
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound(format!(
-            "Import template '{}' not found",
-            id
-        )));
+    let mut custom_values = Vec::new();
+    for (key, value) in &record.custom_fields {
+        let definition = definitions
+            .iter()
+            .find(|d| &d.id == key)
+            .or_else(|| definitions.iter().find(|d| d.name.eq_ignore_ascii_case(key)));
+        match definition {
+            Some(def) => custom_values.push(CustomFieldValue {
+                incident_id: incident_id.to_string(),
+                field_id: def.id.clone(),
+                value: value.clone(),
+            }),
+            None => warnings.push(format!("Row {}: Custom field '{}' not found, value skipped", row, key)),
+        }
     }
 
-    Ok(())
-}
+    let mut action_items = Vec::new();
+    for item in &record.action_items {
+        let req = CreateActionItemRequest {
+            incident_id: incident_id.to_string(),
+            title: item.title.clone(),
+            description: item.description.clone(),
+            status: item.status.clone().unwrap_or_else(|| "Open".to_string()),
+            owner: item.owner.clone(),
+            due_date: item.due_date.clone(),
+        };
+        match req.validate() {
+            Ok(()) => action_items.push(req),
+            Err(e) => warnings.push(format!("Row {}: Action item '{}' skipped: {}", row, item.title, e)),
+        }
+    }
 
-// ---- Helper Functions ----
+    let mut contributing_factors = Vec::new();
+    for factor in &record.contributing_factors {
+        let req = CreateContributingFactorRequest {
+            incident_id: incident_id.to_string(),
+            category: factor.category.clone(),
+            description: factor.description.clone(),
+            is_root: factor.is_root,
+        };
+        match req.validate() {
+            Ok(()) => contributing_factors.push(req),
+            Err(e) => warnings.push(format!("Row {}: Contributing factor skipped: {}", row, e)),
+        }
+    }
 
-/// Load all services as a map of lowercase_name -> (id, name)
-async fn load_service_names(
+    let related = RelatedRecords {
+        tags: record.tags.clone(),
+        custom_fields: custom_values,
+        action_items,
+        contributing_factors,
+    };
+    (related, warnings)
+}
+
+/// Create the related records of an imported incident. Failures are returned
+/// as messages rather than undoing the incident.
+async fn insert_related(
     db: &SqlitePool,
-) -> Result<HashMap<String, (String, String)>, AppError> {
-    let rows = sqlx::query("SELECT id, name FROM services")
-        .fetch_all(db)
-        .await
-        .map_err(|e| AppError::Database(e.to_string()))?;
+    idx: usize,
+    incident_id: &str,
+    record: &JsonIncident,
+    definitions: &[CustomFieldDefinition],
+) -> Vec<String> {
+    let row = idx + 1;
+    let (related, mut errors) = prepare_related(idx, incident_id, record, definitions);
 
-    let mut map = HashMap::new();
-    for row in rows {
-        let id: String = row.get("id");
-        let name: String = row.get("name");
-        map.insert(name.to_lowercase(), (id, name));
+    if !related.tags.is_empty() {
+        if let Err(e) = tags::set_incident_tags(db, incident_id, &related.tags).await {
+            errors.push(format!("Row {}: Tags not imported: {}", row, e));
+        }
+    }
+    if !related.custom_fields.is_empty() {
+        if let Err(e) = custom_fields::set_incident_custom_fields(db, incident_id, &related.custom_fields).await {
+            errors.push(format!("Row {}: Custom fields not imported: {}", row, e));
+        }
+    }
+    for item in &related.action_items {
+        let id = format!("ai-{}", uuid::Uuid::new_v4());
+        if let Err(e) = incidents::insert_action_item(db, &id, item).await {
+            errors.push(format!("Row {}: Action item '{}' not imported: {}", row, item.title, e));
+        }
+    }
+    for factor in &related.contributing_factors {
+        let id = format!("cf-{}", uuid::Uuid::new_v4());
+        if let Err(e) = postmortems::create_contributing_factor(db, &id, factor).await {
+            errors.push(format!("Row {}: Contributing factor not imported: {}", row, e));
+        }
     }
-    Ok(map)
-}
 
-/// Match a service name to its ID (case-insensitive).
-fn resolve_service_id(
-    services: &HashMap<String, (String, String)>,
-    name: &str,
-) -> Option<String> {
-    services
-        .get(&name.to_lowercase())
-        .map(|(id, _)| id.clone())
+    errors
 }
 
-/// Insert a single incident from import data.
+/// Insert a single incident from import data, returning its id.
 async fn insert_imported_incident(
     db: &SqlitePool,
     service_id: &str,
     incident: &MappedIncident,
-) -> Result<(), AppError> {
+) -> Result<String, AppError> {
     let id = format!("inc-{}", uuid::Uuid::new_v4());
 
     let req = CreateIncidentRequest {
@@ -340,7 +718,7 @@ async fn insert_imported_incident(
     req.validate()?;
     incidents::insert_incident(db, &id, &req).await?;
 
-    Ok(())
+    Ok(id)
 }
 
 fn parse_template_row(row: &sqlx::sqlite::SqliteRow) -> ImportTemplate {
@@ -355,8 +733,9 @@ fn parse_template_row(row: &sqlx::sqlite::SqliteRow) -> ImportTemplate {
 
 #[cfg(test)]
 mod tests {
-    use super::insert_imported_incident;
+    use super::{add_related_warnings, build_preview, import_mapped, insert_imported_incident, load_service_names, map_json_incidents};
     use crate::db::migrations::run_migrations;
+    use crate::import::json_parser;
     use crate::import::column_mapper::MappedIncident;
     use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
     use std::str::FromStr;
@@ -401,6 +780,7 @@ mod tests {
             notes: String::new(),
             warnings: vec![],
             errors: vec![],
+            defaulted: Default::default(),
         };
         overrides(&mut incident);
         incident
@@ -447,4 +827,351 @@ mod tests {
             .expect("count incidents");
         assert_eq!(count, 1);
     }
+
+    /// Test: JSON import previews related-record problems as warnings and creates incidents with their related records
+    #[tokio::test]
+    async fn json_import_creates_related_records() {
+        let (_dir, pool) = setup_db().await;
+        sqlx::query("INSERT INTO custom_field_definitions (id, name, field_type) VALUES ('cf-1', 'Region', 'text')")
+            .execute(&pool)
+            .await
+            .expect("custom field");
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("incidents.ndjson");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"title": "Queue backlog", "service_name": "slack", "severity": "high", "impact": "Medium", "status": "Resolved", "started_at": "2026-01-01T10:00:00Z", "detected_at": "2026-01-01T10:05:00Z", "resolved_at": "2026-01-01T11:00:00Z", "related": {"tags": ["queue", "backlog"], "custom_fields": {"Region": "EU", "Missing": "x"}, "action_items": [{"title": "Add alert", "status": "In-Progress"}, {"title": "Bad", "status": "Someday"}], "contributing_factors": [{"category": "Tooling", "description": "No queue alert", "is_root": true}]}}"#,
+                "\n",
+                r#"{"title": "No service", "severity": "Low", "impact": "Low", "status": "Active", "started_at": "2026-01-02T10:00:00Z", "detected_at": "2026-01-02T10:00:00Z"}"#,
+                "\n",
+            ),
+        )
+        .expect("write ndjson");
+
+        let records = json_parser::parse_json_incidents(path.to_str().expect("path")).expect("parse");
+        let mut mapped = map_json_incidents(&records);
+        assert_eq!(mapped[0].severity, "High");
+
+        let services = load_service_names(&pool).await.expect("services");
+        let definitions = crate::db::queries::custom_fields::list_custom_fields(&pool).await.expect("definitions");
+        add_related_warnings(&mut mapped, &records, &definitions);
+        let plans = vec![super::RowPlan::Create; mapped.len()];
+        let preview = build_preview(&mapped, &services, &plans);
+        assert_eq!((preview.ready_count, preview.warning_count, preview.error_count), (0, 1, 1));
+        assert_eq!(preview.incidents[0].messages.len(), 2);
+
+        let provided = super::json_fields(&records);
+        let result = import_mapped(&pool, &mapped, &provided, Some(&records), &Default::default())
+            .await
+            .expect("import");
+        assert_eq!((result.created, result.skipped), (1, 1));
+        assert_eq!(result.errors.len(), 3);
+
+        let incident_id: String = sqlx::query_scalar("SELECT id FROM incidents")
+            .fetch_one(&pool)
+            .await
+            .expect("incident");
+        let tags: Vec<String> = sqlx::query_scalar("SELECT tag FROM incident_tags WHERE incident_id = ? ORDER BY tag")
+            .bind(&incident_id)
+            .fetch_all(&pool)
+            .await
+            .expect("tags");
+        assert_eq!(tags, vec!["backlog", "queue"]);
+        let region: String = sqlx::query_scalar("SELECT value FROM custom_field_values WHERE incident_id = ? AND field_id = 'cf-1'")
+            .bind(&incident_id)
+            .fetch_one(&pool)
+            .await
+            .expect("custom value");
+        assert_eq!(region, "EU");
+        let action_status: String = sqlx::query_scalar("SELECT status FROM action_items WHERE incident_id = ?")
+            .bind(&incident_id)
+            .fetch_one(&pool)
+            .await
+            .expect("one action item");
+        assert_eq!(action_status, "In-Progress");
+        let root: bool = sqlx::query_scalar("SELECT is_root FROM contributing_factors WHERE incident_id = ?")
+            .bind(&incident_id)
+            .fetch_one(&pool)
+            .await
+            .expect("factor");
+        assert!(root);
+    }
+
+    /// Test: upsert imports match on external_ref, diff changed fields, skip unchanged rows and reject duplicate keys
+    #[tokio::test]
+    async fn upsert_import_updates_matching_incidents() {
+        use super::{mapped_fields, plan_import, ImportMode, ImportOptions};
+        use crate::import::column_mapper::{apply_mapping, ColumnMapping};
+        use crate::import::csv_parser;
+
+        let (_dir, pool) = setup_db().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let header = "Title,Service,Severity,Impact,Status,Started,Detected,Ref,Users\n";
+        let mapping = ColumnMapping {
+            mappings: [
+                ("Title", "title"),
+                ("Service", "service"),
+                ("Severity", "severity"),
+                ("Impact", "impact"),
+                ("Status", "status"),
+                ("Started", "started_at"),
+                ("Detected", "detected_at"),
+                ("Ref", "external_ref"),
+                ("Users", "affected_users"),
+            ]
+            .into_iter()
+            .map(|(col, field)| (col.to_string(), field.to_string()))
+            .collect(),
+            default_values: Default::default(),
+            preset: None,
+        };
+        let upsert = ImportOptions {
+            mode: ImportMode::Upsert,
+            ..Default::default()
+        };
+        let load = |name: &str, body: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("{}{}", header, body)).expect("write csv");
+            let rows = csv_parser::parse_csv_rows(path.to_str().expect("path")).expect("rows");
+            apply_mapping(&rows, &mapping)
+        };
+
+        let original = load(
+            "v1.csv",
+            "Login errors,Slack,High,High,Active,2026-01-01T10:00:00Z,2026-01-01T10:05:00Z,OPS-1,10\n\
+             Slow calls,Zoom,Low,Low,Active,2026-01-02T10:00:00Z,2026-01-02T10:05:00Z,OPS-2,5\n",
+        );
+        let provided = vec![mapped_fields(&mapping); original.len()];
+        let result = import_mapped(&pool, &original, &provided, None, &upsert).await.expect("first import");
+        assert_eq!((result.created, result.updated, result.unchanged), (2, 0, 0));
+
+        let rerun = import_mapped(&pool, &original, &provided, None, &upsert).await.expect("rerun");
+        assert_eq!((rerun.created, rerun.updated, rerun.unchanged), (0, 0, 2));
+
+        let changed = load(
+            "v2.csv",
+            "Login errors,Slack,Critical,High,Resolved,2026-01-01T10:00:00Z,2026-01-01T10:05:00Z,OPS-1,50\n\
+             Slow calls,Zoom,Low,Low,Active,2026-01-02T10:00:00Z,2026-01-02T10:05:00Z,OPS-2,5\n\
+             New one,Zoom,Low,Low,Active,2026-01-03T10:00:00Z,2026-01-03T10:05:00Z,OPS-3,1\n\
+             New twice,Zoom,Low,Low,Active,2026-01-03T10:00:00Z,2026-01-03T10:05:00Z,OPS-3,1\n",
+        );
+        let provided = vec![mapped_fields(&mapping); changed.len()];
+        let services = load_service_names(&pool).await.expect("services");
+        let plans = plan_import(&pool, &changed, &provided, &services, &upsert).await.expect("plan");
+        let preview = build_preview(&changed, &services, &plans);
+        let actions: Vec<&str> = preview.incidents.iter().map(|r| r.action.as_str()).collect();
+        assert_eq!(actions, vec!["update", "unchanged", "create", "conflict"]);
+        assert_eq!(
+            (preview.create_count, preview.update_count, preview.unchanged_count, preview.error_count),
+            (1, 1, 1, 1)
+        );
+        let changed_fields: Vec<&str> = preview.incidents[0].changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(changed_fields, vec!["severity", "status", "affected_users"]);
+        assert_eq!(preview.incidents[0].changes[0].old_value, "High");
+        assert!(preview.incidents[1].matched_incident_id.is_some());
+
+        let result = import_mapped(&pool, &changed, &provided, None, &upsert).await.expect("upsert");
+        assert_eq!((result.created, result.updated, result.unchanged, result.skipped), (1, 1, 1, 1));
+
+        let (severity, users, resolved_at): (String, i64, Option<String>) =
+            sqlx::query_as("SELECT severity, affected_users, resolved_at FROM incidents WHERE external_ref = 'OPS-1'")
+                .fetch_one(&pool)
+                .await
+                .expect("updated incident");
+        assert_eq!((severity.as_str(), users), ("Critical", 50));
+        assert!(resolved_at.is_some());
+        let summaries: Vec<String> = sqlx::query_scalar(
+            "SELECT a.summary FROM audit_entries a JOIN incidents i ON i.id = a.entity_id \
+             WHERE a.entity_type = 'incident' AND a.action = 'updated'",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("audit entries");
+        assert_eq!(
+            summaries,
+            vec!["Updated incident from import (severity, status, affected_users)".to_string()]
+        );
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM incidents")
+            .fetch_one(&pool)
+            .await
+            .expect("count");
+        assert_eq!(count, 3);
+
+        let create_only = import_mapped(&pool, &original, &provided[..2], None, &ImportOptions::default())
+            .await
+            .expect("create mode");
+        assert_eq!(create_only.created, 2);
+    }
+
+    /// Test: values exported as JSON re-import unchanged, without formula escaping
+    #[tokio::test]
+    async fn json_export_roundtrip_keeps_values() {
+        use super::{json_fields, plan_import, ImportMode, ImportOptions};
+        use crate::commands::export::incidents_json;
+        use crate::models::export::ExportOptions;
+
+        let (_dir, pool) = setup_db().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let write_json = |name: &str, body: String| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, body).expect("write json");
+            json_parser::parse_json_incidents(path.to_str().expect("path")).expect("parse")
+        };
+
+        let source = serde_json::json!([{
+            "title": "@oncall paged",
+            "service_name": "Slack",
+            "severity": "High",
+            "impact": "Medium",
+            "status": "Resolved",
+            "started_at": "2026-01-01T10:00:00Z",
+            "detected_at": "2026-01-01T10:05:00Z",
+            "resolved_at": "2026-01-01T11:00:00Z",
+            "root_cause": "=SUM(A1) in a config template",
+            "resolution": "-rollback of the deploy",
+            "external_ref": "OPS-7"
+        }]);
+        let records = write_json("source.json", source.to_string());
+        let mapped = map_json_incidents(&records);
+        let upsert = ImportOptions {
+            mode: ImportMode::Upsert,
+            ..Default::default()
+        };
+        let result = import_mapped(&pool, &mapped, &json_fields(&records), Some(&records), &upsert)
+            .await
+            .expect("import");
+        assert_eq!(result.created, 1);
+
+        let (title, root_cause, resolution): (String, String, String) =
+            sqlx::query_as("SELECT title, root_cause, resolution FROM incidents WHERE external_ref = 'OPS-7'")
+                .fetch_one(&pool)
+                .await
+                .expect("incident");
+        assert_eq!(title, "@oncall paged");
+        assert_eq!(root_cause, "=SUM(A1) in a config template");
+        assert_eq!(resolution, "-rollback of the deploy");
+
+        let exported = incidents_json(&pool, &Default::default(), &ExportOptions::default())
+            .await
+            .expect("export");
+        let records = write_json("export.json", serde_json::Value::Array(exported).to_string());
+        let mapped = map_json_incidents(&records);
+        assert_eq!(mapped[0].title, "@oncall paged");
+
+        let provided = json_fields(&records);
+        let services = load_service_names(&pool).await.expect("services");
+        let plans = plan_import(&pool, &mapped, &provided, &services, &upsert).await.expect("plan");
+        let preview = build_preview(&mapped, &services, &plans);
+        assert_eq!(preview.incidents[0].action, "unchanged");
+        assert!(preview.incidents[0].changes.is_empty());
+    }
+
+    /// Test: mapping defaults fill new incidents but never overwrite matched ones
+    #[tokio::test]
+    async fn upsert_defaults_only_apply_to_created_incidents() {
+        use super::{mapped_fields, ImportMode, ImportOptions};
+        use crate::import::column_mapper::{apply_mapping, ColumnMapping};
+        use crate::import::csv_parser;
+
+        let (_dir, pool) = setup_db().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mapping = ColumnMapping {
+            mappings: [
+                ("Title", "title"),
+                ("Service", "service"),
+                ("Severity", "severity"),
+                ("Impact", "impact"),
+                ("Status", "status"),
+                ("Started", "started_at"),
+                ("Detected", "detected_at"),
+                ("Ref", "external_ref"),
+                ("Notes", "notes"),
+            ]
+            .into_iter()
+            .map(|(col, field)| (col.to_string(), field.to_string()))
+            .collect(),
+            default_values: [("status", "Active"), ("notes", "Imported"), ("affected_users", "5")]
+                .into_iter()
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+                .collect(),
+            preset: None,
+        };
+        let upsert = ImportOptions {
+            mode: ImportMode::Upsert,
+            ..Default::default()
+        };
+        let load = |name: &str, body: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, format!("Title,Service,Severity,Impact,Status,Started,Detected,Ref,Notes\n{}", body))
+                .expect("write csv");
+            let rows = csv_parser::parse_csv_rows(path.to_str().expect("path")).expect("rows");
+            apply_mapping(&rows, &mapping)
+        };
+
+        let original = load(
+            "v1.csv",
+            "Login errors,Slack,High,High,Resolved,2026-01-01T10:00:00Z,2026-01-01T10:05:00Z,OPS-1,Fixed by rollback\n",
+        );
+        let provided = vec![mapped_fields(&mapping); original.len()];
+        let result = import_mapped(&pool, &original, &provided, None, &upsert).await.expect("first import");
+        assert_eq!(result.created, 1);
+        sqlx::query("UPDATE incidents SET affected_users = 40 WHERE external_ref = 'OPS-1'")
+            .execute(&pool)
+            .await
+            .expect("set users");
+
+        let rerun = load(
+            "v2.csv",
+            "Login errors,Slack,High,High,,2026-01-01T10:00:00Z,2026-01-01T10:05:00Z,OPS-1,\n\
+             Slow calls,Zoom,Low,Low,,2026-01-02T10:00:00Z,2026-01-02T10:05:00Z,OPS-2,\n",
+        );
+        assert_eq!(rerun[0].status, "Active");
+        let provided = vec![mapped_fields(&mapping); rerun.len()];
+        let result = import_mapped(&pool, &rerun, &provided, None, &upsert).await.expect("rerun");
+        assert_eq!((result.created, result.updated, result.unchanged), (1, 0, 1));
+
+        let rows: Vec<(String, String, String, i64)> =
+            sqlx::query_as("SELECT external_ref, status, notes, affected_users FROM incidents ORDER BY external_ref")
+                .fetch_all(&pool)
+                .await
+                .expect("incidents");
+        assert_eq!(
+            rows,
+            vec![
+                ("OPS-1".into(), "Resolved".into(), "Fixed by rollback".into(), 40),
+                ("OPS-2".into(), "Active".into(), "Imported".into(), 5),
+            ]
+        );
+    }
+
+    /// Test: upsert rejects unsupported match columns and unmapped match columns
+    #[test]
+    fn upsert_rejects_unsupported_match_field() {
+        use super::{mapped_fields, ImportMode, ImportOptions};
+        use crate::import::column_mapper::ColumnMapping;
+
+        let options = ImportOptions {
+            mode: ImportMode::Upsert,
+            match_field: "custom:upstream_id".into(),
+        };
+        let err = options.validate().expect_err("unsupported field").to_string();
+        assert!(err.contains("custom:upstream_id"));
+        assert!(err.contains("external_ref, title"));
+
+        let mapping = ColumnMapping {
+            mappings: [("Title".to_string(), "title".to_string())].into_iter().collect(),
+            default_values: Default::default(),
+            preset: None,
+        };
+        let by_ref = ImportOptions {
+            mode: ImportMode::Upsert,
+            ..Default::default()
+        };
+        assert!(by_ref.validate().is_ok());
+        let err = by_ref.require_match_column(&mapped_fields(&mapping)).expect_err("unmapped");
+        assert!(err.to_string().contains("external_ref"));
+        assert!(ImportOptions::default().require_match_column(&mapped_fields(&mapping)).is_ok());
+    }
 }
@@ -1,11 +1,16 @@
 use sqlx::SqlitePool;
 use tauri::State;
 
-use crate::db::queries::{dashboard, metrics};
+use crate::db::queries::{checklists, dashboard, fatigue, forecasts, metrics, reliability, rollups};
 use crate::error::AppError;
+use crate::models::checklist::ChecklistCompliance;
+use crate::models::fatigue::ResponderLoadReport;
+use crate::models::forecast::{ForecastRequest, IncidentForecast};
+use crate::models::reliability::ReliabilityHistoryEntry;
 use crate::models::metrics::{
     BacklogAgingBucket, DashboardData, DayCount, EscalationFunnelEntry, HourCount, MetricFilters,
-    ServiceReliabilityScore,
+    MetricTimeseries, RollupConsistencyReport, RollupRebuildResult, ServiceReliabilityScore,
+    TimeseriesRequest,
 };
 
 #[tauri::command]
@@ -59,11 +64,16 @@ pub async fn get_service_reliability(
     if start_date.is_empty() || end_date.is_empty() {
         return Err(AppError::Validation("Start and end dates are required".into()));
     }
-    let range = metrics::DateRange {
-        start: start_date,
-        end: end_date,
-    };
-    metrics::get_service_reliability(&*db, &range).await
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
+    reliability::get_service_reliability(&*db, &range).await
+}
+
+#[tauri::command]
+pub async fn get_reliability_history(
+    db: State<'_, SqlitePool>,
+    service_id: Option<String>,
+) -> Result<Vec<ReliabilityHistoryEntry>, AppError> {
+    reliability::get_reliability_history(&*db, service_id.as_deref()).await
 }
 
 #[tauri::command]
@@ -75,9 +85,62 @@ pub async fn get_escalation_funnel(
     if start_date.is_empty() || end_date.is_empty() {
         return Err(AppError::Validation("Start and end dates are required".into()));
     }
-    let range = metrics::DateRange {
-        start: start_date,
-        end: end_date,
-    };
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
     metrics::get_escalation_funnel(&*db, &range).await
 }
+
+#[tauri::command]
+pub async fn get_responder_load(
+    db: State<'_, SqlitePool>,
+    start_date: String,
+    end_date: String,
+) -> Result<ResponderLoadReport, AppError> {
+    if start_date.is_empty() || end_date.is_empty() {
+        return Err(AppError::Validation("Start and end dates are required".into()));
+    }
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
+    fatigue::get_responder_load(&*db, &range, &MetricFilters::default()).await
+}
+
+#[tauri::command]
+pub async fn get_checklist_compliance(
+    db: State<'_, SqlitePool>,
+    start_date: String,
+    end_date: String,
+) -> Result<ChecklistCompliance, AppError> {
+    if start_date.is_empty() || end_date.is_empty() {
+        return Err(AppError::Validation("Start and end dates are required".into()));
+    }
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
+    checklists::get_checklist_compliance(&*db, &range, &MetricFilters::default()).await
+}
+
+#[tauri::command]
+pub async fn get_metric_timeseries(
+    db: State<'_, SqlitePool>,
+    request: TimeseriesRequest,
+) -> Result<MetricTimeseries, AppError> {
+    metrics::get_metric_timeseries(&*db, &request).await
+}
+
+#[tauri::command]
+pub async fn get_incident_forecast(
+    db: State<'_, SqlitePool>,
+    request: ForecastRequest,
+) -> Result<IncidentForecast, AppError> {
+    forecasts::get_incident_forecast(&*db, &request).await
+}
+
+#[tauri::command]
+pub async fn rebuild_metric_rollups(
+    db: State<'_, SqlitePool>,
+) -> Result<RollupRebuildResult, AppError> {
+    rollups::rebuild_rollups(&*db).await
+}
+
+#[tauri::command]
+pub async fn check_metric_rollups(
+    db: State<'_, SqlitePool>,
+) -> Result<RollupConsistencyReport, AppError> {
+    rollups::check_rollup_consistency(&*db).await
+}
@@ -3,6 +3,7 @@ pub mod attachments;
 pub mod audit;
 pub mod backup;
 pub mod checklists;
+pub mod costs;
 pub mod custom_fields;
 pub mod export;
 pub mod incidents;
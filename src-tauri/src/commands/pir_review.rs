@@ -1,11 +1,20 @@
 use sqlx::{Row, SqlitePool};
-use std::io::{Cursor, Write};
+use std::io::Write;
 use tauri::State;
 
-use crate::db::queries::{incidents, postmortems, tags};
+use crate::commands::attachments::{list_incident_attachments, Attachment};
+use crate::db::queries::{custom_fields, incidents, postmortems, report_theme, sla, stakeholder_updates, tags};
 use crate::error::AppError;
 use crate::models::incident::{ActionItem, Incident};
+use crate::models::metrics::format_minutes;
 use crate::models::postmortem::{ContributingFactor, Postmortem};
+use crate::models::redaction::RedactionProfile;
+use crate::models::report_theme::ReportTheme;
+use crate::models::sla::SlaStatus;
+use crate::models::stakeholder_update::StakeholderUpdate;
+use crate::reports::document::ReportDocument;
+use crate::reports::redaction::Redactor;
+use crate::reports::sections::action_items;
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct PirBrief {
@@ -25,7 +34,7 @@ pub struct PirReviewInsights {
     pub external_root_no_action_items_justified: i64,
 }
 
-fn extract_markdown(content: &str) -> String {
+pub(crate) fn extract_markdown(content: &str) -> String {
     if content.trim().is_empty() || content.trim() == "{}" {
         return String::new();
     }
@@ -88,24 +97,26 @@ fn append_impact_section(out: &mut String, inc: &Incident) {
     ));
 }
 
+/// Recorded lifecycle timestamps in order, as (event, timestamp).
+fn timeline_events(inc: &Incident) -> Vec<(&'static str, &str)> {
+    [
+        ("Started", Some(inc.started_at.as_str())),
+        ("Detected", Some(inc.detected_at.as_str())),
+        ("Acknowledged", inc.acknowledged_at.as_deref()),
+        ("First response", inc.first_response_at.as_deref()),
+        ("Mitigation started", inc.mitigation_started_at.as_deref()),
+        ("Responded", inc.responded_at.as_deref()),
+        ("Resolved", inc.resolved_at.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(event, at)| at.map(|at| (event, at)))
+    .collect()
+}
+
 fn append_timeline_section(out: &mut String, inc: &Incident) {
     out.push_str("## Timeline\n\n");
-    out.push_str(&format!("- Started: {}\n", inc.started_at));
-    out.push_str(&format!("- Detected: {}\n", inc.detected_at));
-    if let Some(v) = inc.acknowledged_at.as_deref() {
-        out.push_str(&format!("- Acknowledged: {}\n", v));
-    }
-    if let Some(v) = inc.first_response_at.as_deref() {
-        out.push_str(&format!("- First response: {}\n", v));
-    }
-    if let Some(v) = inc.mitigation_started_at.as_deref() {
-        out.push_str(&format!("- Mitigation started: {}\n", v));
-    }
-    if let Some(v) = inc.responded_at.as_deref() {
-        out.push_str(&format!("- Responded: {}\n", v));
-    }
-    if let Some(v) = inc.resolved_at.as_deref() {
-        out.push_str(&format!("- Resolved: {}\n", v));
+    for (event, at) in timeline_events(inc) {
+        out.push_str(&format!("- {}: {}\n", event, at));
     }
     out.push_str("\n");
 }
@@ -202,6 +213,7 @@ fn append_references_section(
     out: &mut String,
     inc: &Incident,
     tags: &[String],
+    custom_fields: &[(String, String)],
 ) {
     out.push_str("## References\n\n");
     if !inc.external_ref.trim().is_empty() {
@@ -210,6 +222,9 @@ fn append_references_section(
     if !tags.is_empty() {
         out.push_str(&format!("- Tags: {}\n", tags.join(", ")));
     }
+    for (name, value) in custom_fields {
+        out.push_str(&format!("- {}: {}\n", md_escape_inline(name), md_escape_inline(value)));
+    }
     out.push_str(&format!("- Incident ID: {}\n", inc.id));
 }
 
@@ -219,26 +234,66 @@ struct PirBriefContext {
     factors: Vec<ContributingFactor>,
     action_items: Vec<ActionItem>,
     tags: Vec<String>,
+    /// Non-empty custom field values as (field name, value), in display order.
+    custom_fields: Vec<(String, String)>,
+    sla: SlaStatus,
+    attachments: Vec<Attachment>,
+    /// Oldest first.
+    stakeholder_updates: Vec<StakeholderUpdate>,
+    redactor: Option<Redactor>,
 }
 
 async fn load_pir_brief_context(
     db: &SqlitePool,
     incident_id: &str,
+    redaction: Option<&RedactionProfile>,
 ) -> Result<PirBriefContext, AppError> {
-    let (inc, pm, factors, action_items, tags) = tokio::try_join!(
+    let (inc, pm, factors, action_items, tags, definitions, values, sla, attachments, mut stakeholder_updates) = tokio::try_join!(
         incidents::get_incident_by_id(db, incident_id),
         postmortems::get_postmortem_by_incident(db, incident_id),
         postmortems::list_contributing_factors(db, incident_id),
         incidents::list_action_items(db, Some(incident_id)),
         tags::get_incident_tags(db, incident_id),
+        custom_fields::list_custom_fields(db),
+        custom_fields::get_incident_custom_fields(db, incident_id),
+        sla::compute_sla_status(db, incident_id),
+        list_incident_attachments(db, incident_id),
+        stakeholder_updates::list_by_incident(db, incident_id),
     )?;
-    Ok(PirBriefContext {
+    stakeholder_updates.reverse();
+    let custom_fields = definitions
+        .into_iter()
+        .filter_map(|def| {
+            values
+                .iter()
+                .find(|v| v.field_id == def.id && !v.value.trim().is_empty())
+                .map(|v| (def.name, v.value.clone()))
+        })
+        .collect();
+    let mut ctx = PirBriefContext {
         inc,
         pm,
         factors,
         action_items,
         tags,
-    })
+        custom_fields,
+        sla,
+        attachments,
+        stakeholder_updates,
+        redactor: None,
+    };
+    if let Some(profile) = redaction {
+        let redactor = Redactor::load(db, profile).await?;
+        redactor.incident(&mut ctx.inc);
+        for item in &mut ctx.action_items {
+            redactor.action_item(item);
+        }
+        ctx.tags = redactor.tags(&ctx.tags);
+        ctx.custom_fields.retain(|(name, _)| !profile.hides_custom_field(name));
+        redactor.attachments(&mut ctx.attachments);
+        ctx.redactor = Some(redactor);
+    }
+    Ok(ctx)
 }
 
 fn build_pir_brief_markdown(ctx: &PirBriefContext) -> String {
@@ -253,16 +308,197 @@ fn build_pir_brief_markdown(ctx: &PirBriefContext) -> String {
     append_contributing_factors_section(&mut out, &ctx.factors);
     append_action_items_section(&mut out, &ctx.action_items, ctx.pm.as_ref());
     append_lessons_section(&mut out, &ctx.inc);
-    append_references_section(&mut out, &ctx.inc, &ctx.tags);
-    out
+    append_references_section(&mut out, &ctx.inc, &ctx.tags, &ctx.custom_fields);
+    // The postmortem, factors and custom fields are free text; mask them last
+    match ctx.redactor {
+        Some(ref redactor) => redactor.text(&out),
+        None => out,
+    }
+}
+
+/// The brief as a report document for DOCX and PDF: the same sections as the
+/// markdown with tables for the timeline, factors and action items, plus SLA
+/// status, attachments and optionally the stakeholder updates as an appendix.
+fn build_pir_brief_document(
+    ctx: &PirBriefContext,
+    theme: ReportTheme,
+    include_stakeholder_updates: bool,
+) -> ReportDocument {
+    let inc = &ctx.inc;
+    let mut doc = ReportDocument::new(&format!("PIR Brief: {}", inc.title.trim()));
+    doc.subtitle.push(format!("{} \u{00B7} {} \u{00B7} {}", inc.service_name, inc.priority, inc.severity));
+    doc.subtitle.push(format!("Started {}", inc.started_at));
+    doc.theme = theme;
+
+    doc.heading1("Summary");
+    let summary_md = ctx.pm.as_ref().map(|p| extract_markdown(&p.content)).unwrap_or_default();
+    if !summary_md.trim().is_empty() {
+        doc.markdown(&summary_md);
+    } else if inc.root_cause.trim().is_empty() && inc.resolution.trim().is_empty() && inc.notes.trim().is_empty() {
+        doc.text("No summary content recorded.");
+    } else {
+        for (label, value) in [("Root Cause: ", &inc.root_cause), ("Resolution: ", &inc.resolution), ("Notes: ", &inc.notes)] {
+            if !value.trim().is_empty() {
+                doc.label_value(label, value.trim());
+            }
+        }
+    }
+    doc.spacer();
+
+    doc.heading1("Impact");
+    for (label, value) in [
+        ("Service: ", inc.service_name.clone()),
+        ("Severity: ", inc.severity.clone()),
+        ("Impact: ", inc.impact.clone()),
+        ("Priority: ", inc.priority.clone()),
+        ("Status: ", inc.status.clone()),
+        ("Tickets submitted: ", inc.tickets_submitted.to_string()),
+        ("Affected users: ", inc.affected_users.to_string()),
+    ] {
+        doc.label_value(label, &value);
+    }
+    doc.spacer();
+
+    doc.heading1("Timeline");
+    let rows = timeline_events(inc)
+        .into_iter()
+        .map(|(event, at)| vec![event.to_string(), at.to_string()])
+        .collect();
+    doc.table(&["Event", "Time"], rows);
+    doc.spacer();
+
+    doc.heading1("SLA Status");
+    match (ctx.sla.response_target_minutes, ctx.sla.resolve_target_minutes) {
+        (Some(response), Some(resolve)) => {
+            let rows = vec![
+                sla_row("Response", response, ctx.sla.response_elapsed_minutes, ctx.sla.response_breached, inc.responded_at.is_some()),
+                sla_row("Resolution", resolve, ctx.sla.resolve_elapsed_minutes, ctx.sla.resolve_breached, inc.resolved_at.is_some()),
+            ];
+            doc.table(&["Target", "Goal", "Elapsed", "Status"], rows);
+        }
+        _ => doc.text(&format!("No SLA is defined for priority {}.", ctx.sla.priority)),
+    }
+    doc.spacer();
+
+    doc.heading1("Contributing Factors");
+    if ctx.factors.is_empty() {
+        doc.text("No contributing factors recorded.");
+    } else {
+        let rows = ctx
+            .factors
+            .iter()
+            .map(|cf| {
+                let root = if cf.is_root { "Yes" } else { "" };
+                vec![cf.category.clone(), root.to_string(), cf.description.clone()]
+            })
+            .collect();
+        doc.table(&["Category", "Root", "Description"], rows);
+    }
+    doc.spacer();
+
+    if ctx.action_items.is_empty() {
+        doc.heading1("Action Items");
+        match ctx.pm.as_ref() {
+            Some(pm) if pm.no_action_items_justified => {
+                doc.text("No action items were justified for this incident.");
+                if !pm.no_action_items_justification.trim().is_empty() {
+                    doc.label_value("Justification: ", pm.no_action_items_justification.trim());
+                }
+            }
+            _ => doc.text("No action items recorded."),
+        }
+        doc.spacer();
+    } else {
+        action_items::build(&mut doc, &ctx.action_items, None);
+    }
+
+    doc.heading1("Lessons Learned");
+    if inc.lessons_learned.trim().is_empty() {
+        doc.text("No lessons learned recorded.");
+    } else {
+        doc.markdown(&inc.lessons_learned);
+    }
+    doc.spacer();
+
+    doc.heading1("Attachments");
+    if ctx.redactor.as_ref().is_some_and(Redactor::withholds_attachments) {
+        doc.text("Attachments withheld.");
+    } else if ctx.attachments.is_empty() {
+        doc.text("No attachments.");
+    } else {
+        let rows = ctx
+            .attachments
+            .iter()
+            .map(|a| vec![a.filename.clone(), a.mime_type.clone(), format_size(a.size_bytes)])
+            .collect();
+        doc.table(&["File", "Type", "Size"], rows);
+    }
+    doc.spacer();
+
+    doc.heading1("References");
+    if !inc.external_ref.trim().is_empty() {
+        doc.bullet(&format!("External ref: {}", inc.external_ref.trim()));
+    }
+    if !ctx.tags.is_empty() {
+        doc.bullet(&format!("Tags: {}", ctx.tags.join(", ")));
+    }
+    for (name, value) in &ctx.custom_fields {
+        doc.bullet(&format!("{}: {}", name, value));
+    }
+    doc.bullet(&format!("Incident ID: {}", inc.id));
+
+    if include_stakeholder_updates {
+        doc.spacer();
+        doc.heading1("Appendix: Stakeholder Updates");
+        if ctx.stakeholder_updates.is_empty() {
+            doc.text("No stakeholder updates were sent.");
+        }
+        for update in &ctx.stakeholder_updates {
+            doc.heading2(&format!("{} ({})", update.created_at, update.update_type));
+            doc.markdown(&update.content);
+            doc.spacer();
+        }
+    }
+
+    if let Some(ref redactor) = ctx.redactor {
+        redactor.document(&mut doc);
+    }
+    doc
+}
+
+fn sla_row(target: &str, goal: i64, elapsed: Option<i64>, breached: bool, done: bool) -> Vec<String> {
+    let status = if breached {
+        "Breached"
+    } else if done {
+        "Met"
+    } else {
+        "In progress"
+    };
+    vec![
+        target.to_string(),
+        format_minutes(goal as f64),
+        elapsed.map(|m| format_minutes(m as f64)).unwrap_or_else(|| "\u{2014}".to_string()),
+        status.to_string(),
+    ]
+}
+
+fn format_size(bytes: i64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
 }
 
 #[tauri::command]
 pub async fn generate_pir_brief_markdown(
     db: State<'_, SqlitePool>,
     incident_id: String,
+    redaction: Option<RedactionProfile>,
 ) -> Result<PirBrief, AppError> {
-    let ctx = load_pir_brief_context(&*db, &incident_id).await?;
+    let ctx = load_pir_brief_context(&*db, &incident_id, redaction.as_ref()).await?;
     Ok(PirBrief {
         markdown: build_pir_brief_markdown(&ctx),
     })
@@ -273,9 +509,12 @@ pub async fn generate_pir_brief_file(
     db: State<'_, SqlitePool>,
     incident_id: String,
     format: String, // "docx" or "pdf"
+    redaction: Option<RedactionProfile>,
+    include_stakeholder_updates: Option<bool>,
 ) -> Result<String, AppError> {
-    let ctx = load_pir_brief_context(&*db, &incident_id).await?;
-    let md = build_pir_brief_markdown(&ctx);
+    let ctx = load_pir_brief_context(&*db, &incident_id, redaction.as_ref()).await?;
+    let theme = report_theme::get_report_theme(&*db).await?;
+    let doc = build_pir_brief_document(&ctx, theme, include_stakeholder_updates.unwrap_or(false));
 
     let file_ext = if format.to_lowercase() == "pdf" { "pdf" } else { "docx" };
     let suffix = format!(".{}", file_ext);
@@ -286,9 +525,9 @@ pub async fn generate_pir_brief_file(
         .map_err(|e| AppError::Report(format!("Failed to create temp file: {}", e)))?;
 
     let bytes = if file_ext == "pdf" {
-        build_pdf_from_markdown(&md)?
+        crate::reports::pdf::render(&doc)?
     } else {
-        build_docx_from_markdown(&md)?
+        crate::reports::docx::render(&doc)?
     };
 
     tmp.write_all(&bytes)
@@ -302,91 +541,6 @@ pub async fn generate_pir_brief_file(
         .ok_or_else(|| AppError::Report("Invalid temp path encoding".into()))
 }
 
-fn build_docx_from_markdown(md: &str) -> Result<Vec<u8>, AppError> {
-    use docx_rs::Docx;
-
-    let docx = crate::reports::markdown::append_markdown(Docx::new(), md);
-    let mut buf: Vec<u8> = Vec::new();
-    let cursor = Cursor::new(&mut buf);
-    docx.build()
-        .pack(cursor)
-        .map_err(|e| AppError::Report(format!("Failed to build DOCX: {}", e)))?;
-    Ok(buf)
-}
-
-fn load_pdf_font_family() -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>, AppError> {
-    use genpdf::fonts;
-    fonts::from_files("", "LiberationSans", None)
-        .or_else(|_| fonts::from_files("/Library/Fonts", "Arial", None))
-        .or_else(|_| fonts::from_files("/System/Library/Fonts/Supplemental", "Arial", None))
-        .map_err(|e| {
-            AppError::Report(format!(
-                "Failed to load PDF fonts: {}. Install Liberation Sans or Arial.",
-                e
-            ))
-        })
-}
-
-fn markdown_to_paragraphs(md: &str) -> Vec<String> {
-    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
-
-    let parser = Parser::new_ext(md.trim(), Options::empty());
-    let mut out: Vec<String> = Vec::new();
-    let mut text_buf = String::new();
-
-    let flush = |buf: &mut String, out: &mut Vec<String>| {
-        if !buf.trim().is_empty() {
-            out.push(buf.trim().to_string());
-        }
-        buf.clear();
-    };
-
-    for event in parser {
-        match event {
-            Event::Start(Tag::Heading { .. }) => flush(&mut text_buf, &mut out),
-            Event::Text(t) => text_buf.push_str(&t),
-            Event::Code(c) => {
-                text_buf.push('`');
-                text_buf.push_str(&c);
-                text_buf.push('`');
-            }
-            Event::SoftBreak | Event::HardBreak => text_buf.push(' '),
-            Event::Start(Tag::Item) => {
-                flush(&mut text_buf, &mut out);
-                text_buf.push_str("\u{2022}  ");
-            }
-            Event::End(TagEnd::Paragraph) | Event::End(TagEnd::Item) | Event::End(TagEnd::Heading(_)) => {
-                flush(&mut text_buf, &mut out);
-            }
-            _ => {}
-        }
-    }
-    flush(&mut text_buf, &mut out);
-    out
-}
-
-fn build_pdf_from_markdown(md: &str) -> Result<Vec<u8>, AppError> {
-    use genpdf::elements::{Break, Paragraph};
-    use genpdf::{Document, SimplePageDecorator};
-    let font_family = load_pdf_font_family()?;
-
-    let mut doc = Document::new(font_family);
-    let mut decorator = SimplePageDecorator::new();
-    decorator.set_margins(20);
-    doc.set_page_decorator(decorator);
-
-    // Basic markdown -> plain-ish paragraphs (enough for sharing).
-    for p in markdown_to_paragraphs(md) {
-        doc.push(Paragraph::new(p));
-        doc.push(Break::new(0.2));
-    }
-
-    let mut buf: Vec<u8> = Vec::new();
-    doc.render(&mut buf)
-        .map_err(|e| AppError::Report(format!("Failed to render PDF: {}", e)))?;
-    Ok(buf)
-}
-
 #[tauri::command]
 pub async fn get_pir_review_insights(
     db: State<'_, SqlitePool>,
@@ -439,3 +593,206 @@ pub async fn get_pir_review_insights(
         external_root_no_action_items_justified,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::redaction::PeopleRedaction;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("pir-review-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: a redacted PIR brief drops hidden tags and fields, notes and refs, and masks names and patterns
+    #[tokio::test]
+    async fn test_redacted_brief_leaks_no_secrets() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, \
+             root_cause, lessons_learned, notes, external_ref) \
+             VALUES ('inc-1', 'Slack outage reported by bob@example.com', 'svc-slack', 'High', 'High', 'Resolved', \
+             '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', 'Proxy 10.4.4.4 dropped traffic', \
+             'Alice Smith found it fast', 'SECRET-NOTE', 'JIRA-123')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO action_items (id, incident_id, title, status, owner, outcome_notes) \
+             VALUES ('a1', 'inc-1', 'Add proxy alerts', 'Done', 'Alice Smith', 'SECRET-OUTCOME')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO incident_tags (incident_id, tag) VALUES ('inc-1', 'customer-acme'), ('inc-1', 'network')")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO attachments (id, incident_id, filename, file_path, mime_type, size_bytes) \
+             VALUES ('att-1', 'inc-1', 'acme-proxy-10.4.4.4.har', '/data/att-1', 'application/json', 2048)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO custom_field_definitions (id, name, field_type) VALUES ('cf-1', 'Customer', 'text'), ('cf-2', 'Region', 'text')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO custom_field_values (incident_id, field_id, value) VALUES ('inc-1', 'cf-1', 'ACME Corp'), ('inc-1', 'cf-2', 'EU')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let plain = build_pir_brief_markdown(&load_pir_brief_context(&db, "inc-1", None).await.unwrap());
+        assert!(plain.contains("- Customer: ACME Corp"));
+        assert!(plain.contains("customer-acme"));
+
+        let profile = RedactionProfile {
+            people: PeopleRedaction::Pseudonymize,
+            hidden_custom_fields: vec!["customer".into()],
+            hidden_tags: vec!["Customer-ACME".into()],
+            generalize_services: true,
+            strip_notes: true,
+            mask_patterns: vec![r"[\w.+-]+@[\w-]+(\.[\w-]+)+".into(), r"\b\d{1,3}(\.\d{1,3}){3}\b".into()],
+        };
+        let ctx = load_pir_brief_context(&db, "inc-1", Some(&profile)).await.unwrap();
+        assert!(ctx.attachments.is_empty());
+        let md = build_pir_brief_markdown(&ctx);
+        // DOCX and PDF are rendered from this document, so its blocks hold
+        // every string either file can contain.
+        let doc = build_pir_brief_document(&ctx, ReportTheme::default(), true);
+        let blocks = format!("{:?}", doc.blocks);
+        assert!(blocks.contains("Attachments withheld."));
+        for secret in [
+            "Slack",
+            "bob@example.com",
+            "10.4.4.4",
+            "Alice",
+            "SECRET-NOTE",
+            "SECRET-OUTCOME",
+            "JIRA-123",
+            "customer-acme",
+            "ACME",
+            "acme-proxy",
+        ] {
+            assert!(!md.contains(secret), "brief leaks {}", secret);
+            assert!(!blocks.contains(secret), "document leaks {}", secret);
+        }
+        assert_eq!(&crate::reports::docx::render(&doc).unwrap()[..2], b"PK");
+        assert!(crate::reports::pdf::render(&doc).unwrap().starts_with(b"%PDF"));
+        assert!(md.contains("- Service: Communication"));
+        assert!(md.contains("Owner: Person 1"));
+        assert!(md.contains("- Tags: network"));
+        assert!(md.contains("- Region: EU"));
+    }
+
+    /// Test: the PIR document carries postmortem markdown, tables, SLA status, attachments and the optional appendix, and renders to DOCX and PDF
+    #[tokio::test]
+    async fn test_pir_document_export() {
+        use crate::reports::document::Block;
+
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, \
+             responded_at, resolved_at, lessons_learned) \
+             VALUES ('inc-1', 'Slack outage', 'svc-slack', 'High', 'High', 'Resolved', '2026-03-10T10:00:00Z', \
+             '2026-03-10T10:05:00Z', '2026-03-10T10:20:00Z', '2026-03-10T12:00:00Z', 'Alert on **proxy** errors')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO postmortems (id, incident_id, content) \
+             VALUES ('pm-1', 'inc-1', '{\"markdown\": \"## What happened\\n\\nThe proxy dropped traffic.\"}')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO contributing_factors (id, incident_id, category, description, is_root) \
+             VALUES ('cf-1', 'inc-1', 'Tooling', 'No proxy alerting', 1)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO action_items (id, incident_id, title, status) VALUES ('a1', 'inc-1', 'Add proxy alerts', 'Open')")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO attachments (id, incident_id, filename, file_path, mime_type, size_bytes) \
+             VALUES ('att-1', 'inc-1', 'proxy-logs.txt', '/tmp/proxy-logs.txt', 'text/plain', 2048)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO stakeholder_updates (id, incident_id, content, update_type, created_at) VALUES \
+             ('su-1', 'inc-1', 'Investigating Slack issues', 'initial', '2026-03-10 10:10:00'), \
+             ('su-2', 'inc-1', 'Slack is restored', 'final', '2026-03-10 12:05:00')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let ctx = load_pir_brief_context(&db, "inc-1", None).await.unwrap();
+        let doc = build_pir_brief_document(&ctx, ReportTheme::default(), true);
+        let headings: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Heading { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        for heading in ["Summary", "What happened", "Timeline", "SLA Status", "Contributing Factors", "Action Items", "Attachments", "Appendix: Stakeholder Updates"] {
+            assert!(headings.contains(&heading), "missing heading {}", heading);
+        }
+        let tables: Vec<&Vec<Vec<_>>> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Table { rows, .. } => Some(rows),
+                _ => None,
+            })
+            .collect();
+        let cells: Vec<&str> = tables.iter().flat_map(|rows| rows.iter().flatten()).map(|c| c.text.as_str()).collect();
+        for cell in ["Resolved", "Met", "No proxy alerting", "Add proxy alerts", "proxy-logs.txt", "2.0 KB"] {
+            assert!(cells.contains(&cell), "missing cell {}", cell);
+        }
+        // The appendix lists updates oldest first
+        let initial = headings.iter().position(|h| h.contains("initial")).unwrap();
+        let last = headings.iter().position(|h| h.contains("final")).unwrap();
+        assert!(initial < last);
+
+        let without = build_pir_brief_document(&ctx, ReportTheme::default(), false);
+        assert!(!without.blocks.iter().any(|b| matches!(b, Block::Heading { text, .. } if text.starts_with("Appendix"))));
+
+        let docx = crate::reports::docx::render(&doc).unwrap();
+        assert!(docx.starts_with(b"PK"));
+        let pdf = crate::reports::pdf::render(&doc).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
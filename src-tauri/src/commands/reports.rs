@@ -3,23 +3,54 @@ use std::collections::HashMap;
 use sqlx::SqlitePool;
 use tauri::State;
 
-use crate::db::queries::report_history;
+use crate::db::queries::{audit, discussion_rules, redaction, report_history, report_templates, report_theme};
 use crate::error::AppError;
+use crate::models::discussion_rule::{
+    CreateDiscussionRuleRequest, DiscussionRule, RuleEvaluation, UpdateDiscussionRuleRequest,
+};
+use crate::models::redaction::RedactionProfile;
 use crate::models::report_history::ReportHistory;
+use crate::models::report_theme::ReportTheme;
+use crate::models::report_template::{
+    CreateReportTemplateRequest, ReportTemplate, UpdateReportTemplateRequest,
+};
 use crate::reports;
+use crate::reports::period::{ComparisonMode, ReportPeriod};
+use crate::reports::snapshot::{self, ReportSnapshot, SnapshotDiff};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportConfigCmd {
     pub quarter_id: Option<String>,
-    pub fiscal_year: Option<i32>,
+    /// Whole fiscal year, used when no quarter is given.
+    pub fiscal_year: Option<i64>,
+    /// Custom date range (YYYY-MM-DD, inclusive); takes precedence over quarter and fiscal year.
+    #[serde(default)]
+    pub date_from: Option<String>,
+    #[serde(default)]
+    pub date_to: Option<String>,
+    /// Limit the report to these services; empty covers every service.
+    #[serde(default)]
+    pub service_ids: Vec<String>,
+    /// "previous_period" (default), "same_period_last_year" or "none".
+    #[serde(default)]
+    pub comparison: String,
     pub title: String,
     pub introduction: String,
     pub sections: ReportSectionsCmd,
     pub chart_images: HashMap<String, String>, // base64-encoded PNGs
     #[serde(default = "default_format")]
-    pub format: String, // "docx" or "pdf"
+    pub format: String, // "docx", "pdf", "html" or "md"
+    /// Stored template whose section layout replaces `sections`.
+    #[serde(default)]
+    pub template_id: Option<String>,
+    /// Redact the report for sharing outside the team.
+    #[serde(default)]
+    pub redaction: Option<RedactionProfile>,
 }
 
+/// File extensions `save_report` accepts, one per report format.
+const REPORT_EXTENSIONS: &[&str] = &["docx", "pdf", "html", "md"];
+
 fn default_format() -> String {
     "docx".to_string()
 }
@@ -27,6 +58,8 @@ fn default_format() -> String {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportSectionsCmd {
     pub executive_summary: bool,
+    #[serde(default)]
+    pub service_context: bool,
     pub metrics_overview: bool,
     pub incident_timeline: bool,
     pub incident_breakdowns: bool,
@@ -34,6 +67,14 @@ pub struct ReportSectionsCmd {
     pub qoq_comparison: bool,
     pub discussion_points: bool,
     pub action_items: bool,
+    #[serde(default)]
+    pub cost_impact: bool,
+    #[serde(default)]
+    pub outlook: bool,
+    #[serde(default)]
+    pub responder_load: bool,
+    #[serde(default)]
+    pub checklist_compliance: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +84,24 @@ pub struct DiscussionPoint {
     pub severity: String,
 }
 
+/// The report period: a custom date range, else the quarter, else the fiscal year.
+fn report_period(config: &ReportConfigCmd) -> Result<ReportPeriod, AppError> {
+    match (&config.date_from, &config.date_to, &config.quarter_id, config.fiscal_year) {
+        (Some(start), Some(end), _, _) => Ok(ReportPeriod::DateRange {
+            start: start.clone(),
+            end: end.clone(),
+        }),
+        (Some(_), None, _, _) | (None, Some(_), _, _) => Err(AppError::Validation(
+            "A custom report period needs both a start and an end date".into(),
+        )),
+        (None, None, Some(id), _) => Ok(ReportPeriod::Quarter(id.clone())),
+        (None, None, None, Some(fy)) => Ok(ReportPeriod::FiscalYear(fy)),
+        (None, None, None, None) => Err(AppError::Validation(
+            "Select a quarter, fiscal year or date range for the report".into(),
+        )),
+    }
+}
+
 #[tauri::command]
 pub async fn generate_report(
     db: State<'_, SqlitePool>,
@@ -93,23 +152,15 @@ pub async fn generate_report(
     }
 
     // Parse format
-    let report_format = match config.format.to_lowercase().as_str() {
-        "pdf" => reports::ReportFormat::Pdf,
-        _ => reports::ReportFormat::Docx,
-    };
-    let file_ext = match report_format {
-        reports::ReportFormat::Pdf => "pdf",
-        reports::ReportFormat::Docx => "docx",
-    };
+    let report_format = reports::ReportFormat::parse(&config.format);
+    let file_ext = report_format.extension();
 
-    // Convert command config to internal report config
-    let report_config = reports::ReportConfig {
-        quarter_id: config.quarter_id,
-        fiscal_year: config.fiscal_year,
-        title: config.title,
-        introduction: config.introduction,
-        sections: reports::ReportSections {
+    // Section layout: the template's when one is given, else the toggles in classic order
+    let sections = match config.template_id {
+        Some(ref id) => report_templates::get_report_template(&*db, id).await?.sections,
+        None => reports::ReportSections {
             executive_summary: config.sections.executive_summary,
+            service_context: config.sections.service_context,
             metrics_overview: config.sections.metrics_overview,
             incident_timeline: config.sections.incident_timeline,
             incident_breakdowns: config.sections.incident_breakdowns,
@@ -117,15 +168,47 @@ pub async fn generate_report(
             qoq_comparison: config.sections.qoq_comparison,
             discussion_points: config.sections.discussion_points,
             action_items: config.sections.action_items,
-        },
+            cost_impact: config.sections.cost_impact,
+            outlook: config.sections.outlook,
+            responder_load: config.sections.responder_load,
+            checklist_compliance: config.sections.checklist_compliance,
+        }
+        .layout(),
+    };
+
+    // Convert command config to internal report config
+    let report_config = reports::ReportConfig {
+        period: report_period(&config)?,
+        comparison: ComparisonMode::parse(&config.comparison)?,
+        service_ids: config.service_ids,
+        title: config.title,
+        introduction: config.introduction,
+        sections,
         chart_images,
         format: report_format,
+        redaction: config.redaction,
     };
 
     // Generate the report
-    let report_bytes = reports::generate_quarterly_report(&*db, &report_config).await?;
+    let (report_bytes, snapshot) = reports::generate_quarterly_report(&*db, &report_config).await?;
+
+    write_temp_report(&report_bytes, file_ext, &snapshot).await
+}
+
+/// Path of the snapshot written next to a generated report until it is saved.
+fn snapshot_sidecar(report_path: &std::path::Path) -> std::path::PathBuf {
+    let mut path = report_path.as_os_str().to_owned();
+    path.push(".snapshot.json");
+    path.into()
+}
 
-    // Write to a temp file
+/// Write a generated report and its snapshot to the temp directory and
+/// return the report's path.
+async fn write_temp_report(
+    bytes: &[u8],
+    file_ext: &str,
+    snapshot: &ReportSnapshot,
+) -> Result<String, AppError> {
     let temp_dir = std::env::temp_dir();
     let filename = format!(
         "incident_report_{}.{}",
@@ -134,9 +217,12 @@ pub async fn generate_report(
     );
     let temp_path = temp_dir.join(&filename);
 
-    tokio::fs::write(&temp_path, &report_bytes)
+    tokio::fs::write(&temp_path, bytes)
         .await
         .map_err(|e| AppError::Report(format!("Failed to write temp file: {}", e)))?;
+    tokio::fs::write(snapshot_sidecar(&temp_path), snapshot.to_json()?)
+        .await
+        .map_err(|e| AppError::Report(format!("Failed to write report snapshot: {}", e)))?;
 
     temp_path
         .to_str()
@@ -170,15 +256,18 @@ pub async fn save_report(
             "Save path must not contain path traversal sequences".into(),
         ));
     }
-    // Must end in .docx or .pdf
+    // Must end in a supported report extension
     let ext = save.extension().and_then(|e| e.to_str()).unwrap_or("");
-    if ext != "docx" && ext != "pdf" {
+    if !REPORT_EXTENSIONS.contains(&ext) {
         return Err(AppError::Validation(
-            "Save path must have .docx or .pdf extension".into(),
+            "Save path must have a .docx, .pdf, .html or .md extension".into(),
         ));
     }
 
-    tokio::fs::copy(&temp_path, &save_path)
+    let report_bytes = tokio::fs::read(&canonical_temp)
+        .await
+        .map_err(|e| AppError::Report(format!("Failed to read report: {}", e)))?;
+    tokio::fs::write(&save_path, &report_bytes)
         .await
         .map_err(|e| AppError::Report(format!("Failed to save report: {}", e)))?;
 
@@ -189,7 +278,7 @@ pub async fn save_report(
     let file_size = metadata.len() as i64;
 
     // Record in history — detect format from extension
-    let format_str = if ext == "pdf" { "pdf" } else { "docx" };
+    let format_str = reports::ReportFormat::parse(ext).extension();
     let history = report_history::insert_report_history(
         &*db,
         &title,
@@ -201,10 +290,28 @@ pub async fn save_report(
     )
     .await?;
 
-    // Clean up temp file (best-effort)
+    // Keep the snapshot the report was rendered from, when generate_report left one
+    let sidecar = snapshot_sidecar(&canonical_temp);
+    let snapshot_json = tokio::fs::read_to_string(&sidecar).await.ok();
+    let content_sha256 = snapshot_json
+        .as_deref()
+        .and_then(|json| ReportSnapshot::from_json(json).ok())
+        .map(|s| reports::snapshot_content_hash(&s));
+    report_history::store_report_artifact(
+        &*db,
+        &history.id,
+        &snapshot::artifact_hash(&report_bytes),
+        content_sha256.as_deref(),
+        snapshot_json.as_ref().map(|_| snapshot::SNAPSHOT_VERSION),
+        snapshot_json.as_deref(),
+    )
+    .await?;
+
+    // Clean up temp files (best-effort)
     let _ = tokio::fs::remove_file(&temp_path).await;
+    let _ = tokio::fs::remove_file(&sidecar).await;
 
-    Ok(history)
+    report_history::get_report_history(&*db, &history.id).await
 }
 
 #[tauri::command]
@@ -224,8 +331,174 @@ pub async fn generate_discussion_points(
         .collect())
 }
 
+// ===================== Redaction Profile =====================
+
+#[tauri::command]
+pub async fn get_redaction_profile(
+    db: State<'_, SqlitePool>,
+) -> Result<RedactionProfile, AppError> {
+    redaction::get_redaction_profile(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_redaction_profile(
+    db: State<'_, SqlitePool>,
+    profile: RedactionProfile,
+) -> Result<RedactionProfile, AppError> {
+    profile.validate()?;
+    let result = redaction::save_redaction_profile(&*db, &profile).await?;
+    let _ = audit::insert_audit_entry(&*db, "redaction_profile", "redaction_profile", "updated", "Updated redaction profile", "").await;
+    Ok(result)
+}
+
+// ===================== Report Theme =====================
+
+#[tauri::command]
+pub async fn get_report_theme(
+    db: State<'_, SqlitePool>,
+) -> Result<ReportTheme, AppError> {
+    report_theme::get_report_theme(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_report_theme(
+    db: State<'_, SqlitePool>,
+    theme: ReportTheme,
+) -> Result<ReportTheme, AppError> {
+    theme.validate()?;
+    let result = report_theme::save_report_theme(&*db, &theme).await?;
+    let _ = audit::insert_audit_entry(&*db, "report_theme", "report_theme", "updated", "Updated report theme", "").await;
+    Ok(result)
+}
+
+// ===================== Discussion Rules =====================
+
+#[tauri::command]
+pub async fn list_discussion_rules(
+    db: State<'_, SqlitePool>,
+) -> Result<Vec<DiscussionRule>, AppError> {
+    discussion_rules::list_discussion_rules(&*db).await
+}
+
+#[tauri::command]
+pub async fn create_discussion_rule(
+    db: State<'_, SqlitePool>,
+    req: CreateDiscussionRuleRequest,
+) -> Result<DiscussionRule, AppError> {
+    req.validate()?;
+    let id = format!("dr-{}", uuid::Uuid::new_v4());
+    let result = discussion_rules::create_discussion_rule(&*db, &id, &req).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "discussion_rule",
+        &id,
+        "created",
+        &format!("Created discussion rule: {}", &req.name),
+        "",
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn update_discussion_rule(
+    db: State<'_, SqlitePool>,
+    id: String,
+    req: UpdateDiscussionRuleRequest,
+) -> Result<DiscussionRule, AppError> {
+    let result = discussion_rules::update_discussion_rule(&*db, &id, &req).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "discussion_rule",
+        &id,
+        "updated",
+        "Updated discussion rule",
+        "",
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_discussion_rule(
+    db: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), AppError> {
+    discussion_rules::delete_discussion_rule(&*db, &id).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "discussion_rule",
+        &id,
+        "deleted",
+        "Deleted discussion rule",
+        "",
+    )
+    .await;
+    Ok(())
+}
+
+/// Evaluate every rule for a quarter and report which fired and why.
+#[tauri::command]
+pub async fn dry_run_discussion_rules(
+    db: State<'_, SqlitePool>,
+    quarter_id: String,
+) -> Result<Vec<RuleEvaluation>, AppError> {
+    reports::evaluate_discussion_rules(&*db, &quarter_id).await
+}
+
 // ===================== Report History =====================
 
+/// A report rendered again from a stored snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegeneratedReport {
+    /// Temp file to pass to `save_report`, as with `generate_report`.
+    pub temp_path: String,
+    pub artifact_sha256: String,
+    /// Whether the rendered content matches the report originally saved.
+    /// Compared by document content, since DOCX and PDF bytes differ
+    /// between renders of the same document.
+    pub matches_original: bool,
+}
+
+async fn load_snapshot(db: &SqlitePool, history_id: &str) -> Result<ReportSnapshot, AppError> {
+    let json = report_history::get_report_snapshot_json(db, history_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::Validation("This report was saved before snapshots were recorded".into())
+        })?;
+    ReportSnapshot::from_json(&json)
+}
+
+/// Render a saved report again from its snapshot, ignoring later edits to incidents.
+#[tauri::command]
+pub async fn regenerate_from_snapshot(
+    db: State<'_, SqlitePool>,
+    history_id: String,
+) -> Result<RegeneratedReport, AppError> {
+    let entry = report_history::get_report_history(&*db, &history_id).await?;
+    let snapshot = load_snapshot(&*db, &history_id).await?;
+    let bytes = reports::render_snapshot(&snapshot)?;
+    let content_sha256 = reports::snapshot_content_hash(&snapshot);
+
+    Ok(RegeneratedReport {
+        temp_path: write_temp_report(&bytes, snapshot.format().extension(), &snapshot).await?,
+        matches_original: entry.content_sha256.as_deref() == Some(content_sha256.as_str()),
+        artifact_sha256: snapshot::artifact_hash(&bytes),
+    })
+}
+
+/// Incidents and metrics that changed between two saved reports.
+#[tauri::command]
+pub async fn diff_report_snapshots(
+    db: State<'_, SqlitePool>,
+    from_id: String,
+    to_id: String,
+) -> Result<SnapshotDiff, AppError> {
+    let from = load_snapshot(&*db, &from_id).await?;
+    let to = load_snapshot(&*db, &to_id).await?;
+    Ok(snapshot::diff(&from, &to))
+}
+
 #[tauri::command]
 pub async fn list_report_history(
     db: State<'_, SqlitePool>,
@@ -241,6 +514,73 @@ pub async fn delete_report_history_entry(
     report_history::delete_report_history(&*db, &id).await
 }
 
+// ===================== Report Templates =====================
+
+#[tauri::command]
+pub async fn list_report_templates(
+    db: State<'_, SqlitePool>,
+) -> Result<Vec<ReportTemplate>, AppError> {
+    report_templates::list_report_templates(&*db).await
+}
+
+#[tauri::command]
+pub async fn create_report_template(
+    db: State<'_, SqlitePool>,
+    req: CreateReportTemplateRequest,
+) -> Result<ReportTemplate, AppError> {
+    req.validate()?;
+    let id = format!("rpt-{}", uuid::Uuid::new_v4());
+    let result = report_templates::create_report_template(&*db, &id, &req).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "report_template",
+        &id,
+        "created",
+        &format!("Created report template: {}", &req.name),
+        "",
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn update_report_template(
+    db: State<'_, SqlitePool>,
+    id: String,
+    req: UpdateReportTemplateRequest,
+) -> Result<ReportTemplate, AppError> {
+    req.validate()?;
+    let result = report_templates::update_report_template(&*db, &id, &req).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "report_template",
+        &id,
+        "updated",
+        "Updated report template",
+        "",
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_report_template(
+    db: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), AppError> {
+    report_templates::delete_report_template(&*db, &id).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "report_template",
+        &id,
+        "deleted",
+        "Deleted report template",
+        "",
+    )
+    .await;
+    Ok(())
+}
+
 // ===================== Narrative Generation =====================
 
 #[tauri::command]
@@ -318,11 +658,60 @@ mod tests {
         assert_eq!(format, "docx");
     }
 
+    /// Test: every accepted save extension maps back to its own format
+    #[test]
+    fn test_report_extensions_round_trip() {
+        for ext in REPORT_EXTENSIONS {
+            assert_eq!(reports::ReportFormat::parse(ext).extension(), *ext);
+        }
+        assert_eq!(reports::ReportFormat::parse("HTML"), reports::ReportFormat::Html);
+        assert_eq!(reports::ReportFormat::parse("markdown"), reports::ReportFormat::Markdown);
+        assert_eq!(reports::ReportFormat::parse("odt"), reports::ReportFormat::Docx);
+    }
+
+    /// Test: a date range wins over the quarter, which wins over the fiscal year
+    #[test]
+    fn test_report_period_precedence() {
+        let config = |extra: serde_json::Value| {
+            let mut value = serde_json::json!({
+                "quarter_id": null,
+                "fiscal_year": null,
+                "title": "Review",
+                "introduction": "",
+                "sections": {
+                    "executive_summary": true, "metrics_overview": true, "incident_timeline": true,
+                    "incident_breakdowns": true, "service_reliability": true, "qoq_comparison": true,
+                    "discussion_points": true, "action_items": true
+                },
+                "chart_images": {}
+            });
+            value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            serde_json::from_value::<ReportConfigCmd>(value).unwrap()
+        };
+
+        let cmd = config(serde_json::json!({"quarter_id": "fy27-q1", "fiscal_year": 2027}));
+        assert_eq!(report_period(&cmd).unwrap(), ReportPeriod::Quarter("fy27-q1".into()));
+        assert_eq!(ComparisonMode::parse(&cmd.comparison).unwrap(), ComparisonMode::PreviousPeriod);
+
+        let cmd = config(serde_json::json!({"fiscal_year": 2027}));
+        assert_eq!(report_period(&cmd).unwrap(), ReportPeriod::FiscalYear(2027));
+
+        let cmd = config(serde_json::json!({
+            "quarter_id": "fy27-q1", "date_from": "2026-03-01", "date_to": "2026-03-31"
+        }));
+        assert!(matches!(report_period(&cmd).unwrap(), ReportPeriod::DateRange { .. }));
+
+        assert!(report_period(&config(serde_json::json!({"date_from": "2026-03-01"}))).is_err());
+        assert!(report_period(&config(serde_json::json!({}))).is_err());
+        assert!(ComparisonMode::parse("last_week").is_err());
+    }
+
     /// Test: ReportSectionsCmd can be created with all sections enabled
     #[test]
     fn test_report_sections_all_enabled() {
         let sections = ReportSectionsCmd {
             executive_summary: true,
+            service_context: true,
             metrics_overview: true,
             incident_timeline: true,
             incident_breakdowns: true,
@@ -330,6 +719,10 @@ mod tests {
             qoq_comparison: true,
             discussion_points: true,
             action_items: true,
+            cost_impact: true,
+            outlook: true,
+            responder_load: true,
+            checklist_compliance: true,
         };
 
         assert!(sections.executive_summary);
@@ -343,6 +736,7 @@ mod tests {
     fn test_report_sections_selective() {
         let sections = ReportSectionsCmd {
             executive_summary: true,
+            service_context: false,
             metrics_overview: false,
             incident_timeline: true,
             incident_breakdowns: false,
@@ -350,6 +744,10 @@ mod tests {
             qoq_comparison: false,
             discussion_points: true,
             action_items: false,
+            cost_impact: false,
+            outlook: false,
+            responder_load: false,
+            checklist_compliance: false,
         };
 
         assert!(sections.executive_summary);
@@ -1,12 +1,13 @@
 use sqlx::SqlitePool;
 use tauri::State;
 
-use crate::db::queries::{audit, service_dependencies, services};
+use crate::db::queries::{audit, metrics, service_dependencies, services};
 use crate::error::AppError;
 use crate::models::service::{
     CreateServiceDependencyRequest, CreateServiceRequest, Service, ServiceDependency,
     UpdateServiceRequest,
 };
+use crate::models::service_graph::{BlastRadius, CriticalPathService, ImpactedService};
 
 #[tauri::command]
 pub async fn create_service(
@@ -125,3 +126,32 @@ pub async fn list_service_dependents(
 ) -> Result<Vec<ServiceDependency>, AppError> {
     service_dependencies::list_dependents_of_service(&*db, &service_id).await
 }
+
+#[tauri::command]
+pub async fn get_blast_radius(
+    db: State<'_, SqlitePool>,
+    service_id: String,
+) -> Result<BlastRadius, AppError> {
+    service_dependencies::get_blast_radius(&*db, &service_id).await
+}
+
+#[tauri::command]
+pub async fn suggest_affected_services(
+    db: State<'_, SqlitePool>,
+    service_id: String,
+) -> Result<Vec<ImpactedService>, AppError> {
+    service_dependencies::suggest_affected_services(&*db, &service_id).await
+}
+
+#[tauri::command]
+pub async fn get_critical_path_services(
+    db: State<'_, SqlitePool>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<CriticalPathService>, AppError> {
+    if start_date.is_empty() || end_date.is_empty() {
+        return Err(AppError::Validation("Start and end dates are required".into()));
+    }
+    let range = metrics::resolve_date_range(&*db, &start_date, &end_date).await?;
+    service_dependencies::get_critical_path_services(&*db, &range).await
+}
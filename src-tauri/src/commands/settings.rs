@@ -2,9 +2,12 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Row, SqlitePool};
 use tauri::State;
 
-use crate::db::queries::settings;
+use crate::db::queries::{audit, fatigue, reliability, rollups, settings, time_zones};
 use crate::error::AppError;
+use crate::models::fatigue::WorkingHoursConfig;
 use crate::models::quarter::{QuarterConfig, UpsertQuarterRequest};
+use crate::models::reliability::ReliabilityScoringConfig;
+use crate::models::timezone::{self, TimeZoneConfig, TimeZoneOption};
 
 #[tauri::command]
 pub async fn get_quarter_configs(
@@ -47,6 +50,85 @@ pub async fn set_setting(
     settings::set_setting(&*db, &key, &value).await
 }
 
+#[tauri::command]
+pub async fn get_time_zone_config(
+    db: State<'_, SqlitePool>,
+) -> Result<TimeZoneConfig, AppError> {
+    time_zones::get_time_zone_config(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_time_zone_config(
+    db: State<'_, SqlitePool>,
+    config: TimeZoneConfig,
+) -> Result<TimeZoneConfig, AppError> {
+    config.validate()?;
+    let result = time_zones::save_time_zone_config(&*db, &config).await?;
+    // Rollups are keyed by local day, so a new org zone rebuilds them now
+    rollups::refresh_dirty_rollups(&*db).await?;
+    let _ = audit::insert_audit_entry(
+        &*db,
+        "time_zone_config",
+        "time_zone_config",
+        "updated",
+        &format!("Set organization time zone to {}", config.org_time_zone),
+        "",
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_working_hours_config(
+    db: State<'_, SqlitePool>,
+) -> Result<WorkingHoursConfig, AppError> {
+    fatigue::get_working_hours_config(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_working_hours_config(
+    db: State<'_, SqlitePool>,
+    config: WorkingHoursConfig,
+) -> Result<WorkingHoursConfig, AppError> {
+    config.validate()?;
+    let result = fatigue::save_working_hours_config(&*db, &config).await?;
+    let _ = audit::insert_audit_entry(&*db, "working_hours", "working_hours", "updated", "Updated working hours", "").await;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_reliability_scoring_config(
+    db: State<'_, SqlitePool>,
+) -> Result<ReliabilityScoringConfig, AppError> {
+    reliability::get_scoring_config(&*db).await
+}
+
+#[tauri::command]
+pub async fn update_reliability_scoring_config(
+    db: State<'_, SqlitePool>,
+    config: ReliabilityScoringConfig,
+) -> Result<ReliabilityScoringConfig, AppError> {
+    config.validate()?;
+    let result = reliability::save_scoring_config(&*db, &config).await?;
+    let _ = audit::insert_audit_entry(&*db, "reliability_scoring", "reliability_scoring", "updated", "Updated reliability scoring model", "").await;
+    Ok(result)
+}
+
+/// Rescore closed quarters with the current scoring model.
+#[tauri::command]
+pub async fn rebuild_reliability_history(
+    db: State<'_, SqlitePool>,
+) -> Result<u64, AppError> {
+    let cleared = reliability::clear_reliability_history(&*db).await?;
+    let _ = audit::insert_audit_entry(&*db, "reliability_scoring", "reliability_scoring", "updated", "Cleared recorded reliability score history", "").await;
+    Ok(cleared)
+}
+
+#[tauri::command]
+pub async fn list_time_zones() -> Result<Vec<TimeZoneOption>, AppError> {
+    Ok(timezone::list_time_zones())
+}
+
 // ===================== Data Export / Import =====================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +38,12 @@ pub async fn run_migrations(pool: &SqlitePool) -> AppResult<()> {
         (15, "UX features", include_str!("sql/015_ux_features.sql")),
         (16, "PIR readiness", include_str!("sql/016_pir_readiness.sql")),
         (17, "Action item follow-through", include_str!("sql/017_action_item_followthrough.sql")),
+        (18, "Incident cost overrides", include_str!("sql/018_incident_costs.sql")),
+        (19, "Metric rollups", include_str!("sql/019_metric_rollups.sql")),
+        (20, "Reliability score history", include_str!("sql/020_reliability_scores.sql")),
+        (21, "Report templates", include_str!("sql/021_report_templates.sql")),
+        (22, "Discussion rules", include_str!("sql/022_discussion_rules.sql")),
+        (23, "Report snapshots", include_str!("sql/023_report_snapshots.sql")),
     ];
 
     for (version, description, sql) in migrations {
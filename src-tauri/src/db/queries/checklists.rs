@@ -1,9 +1,15 @@
 use sqlx::{Row, SqlitePool};
 
+use crate::db::queries::metrics::{build_where_clause, severity_rank, DateRange};
 use crate::error::{AppError, AppResult};
 use crate::models::checklist::{
-    ChecklistItem, ChecklistTemplate, ChecklistTemplateItem, IncidentChecklist,
+    compliance_stats, group_compliance, item_compliance, most_skipped, ChecklistCompliance,
+    ChecklistItem, ChecklistTemplate, ChecklistTemplateItem, IncidentChecklist, ItemObservation,
 };
+use crate::models::metrics::MetricFilters;
+use crate::models::timezone::parse_timestamp;
+
+const MOST_SKIPPED_LIMIT: usize = 10;
 
 // ── Template CRUD ─────────────────────────────────────────────────
 
@@ -352,6 +358,111 @@ pub async fn toggle_checklist_item(
     Ok(parse_checklist_item(&updated_row))
 }
 
+// ── Compliance ────────────────────────────────────────────────────
+
+/// Completion of template checklists on incidents started in `range`.
+/// Ad-hoc checklists are left out. An item counts as unchecked at
+/// resolution when it was still open, or only checked after the incident
+/// was resolved.
+pub async fn get_checklist_compliance(
+    db: &SqlitePool,
+    range: &DateRange,
+    filters: &MetricFilters,
+) -> AppResult<ChecklistCompliance> {
+    let (wc, params) = build_where_clause(range, filters);
+    let sql = format!(
+        "SELECT ci.checklist_id, ci.label, ci.is_checked, ci.checked_at, \
+         t.id as template_id, t.name as template_name, \
+         i.started_at, i.resolved_at, i.severity, i.service_id, \
+         COALESCE(s.name, 'Unknown Service') as service_name \
+         FROM checklist_items ci \
+         JOIN incident_checklists ic ON ci.checklist_id = ic.id \
+         JOIN checklist_templates t ON ic.template_id = t.id \
+         JOIN incidents i ON ic.incident_id = i.id \
+         LEFT JOIN services s ON i.service_id = s.id \
+         WHERE {} \
+         ORDER BY t.name, ic.created_at, ci.sort_order",
+        wc
+    );
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let rows = query
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let observations: Vec<ItemObservation> = rows
+        .iter()
+        .map(|row| {
+            let template_id: String = row.get("template_id");
+            let label: String = row.get("label");
+            let is_checked = row.get::<bool, _>("is_checked");
+            let started_at = parse_timestamp(&row.get::<String, _>("started_at"));
+            let resolved_at = row
+                .get::<Option<String>, _>("resolved_at")
+                .and_then(|v| parse_timestamp(&v));
+            let checked_at = row
+                .get::<Option<String>, _>("checked_at")
+                .and_then(|v| parse_timestamp(&v))
+                .filter(|_| is_checked);
+            let minutes_to_check = match (started_at, checked_at) {
+                (Some(start), Some(checked)) => {
+                    Some(((checked - start).num_seconds() as f64 / 60.0).max(0.0))
+                }
+                _ => None,
+            };
+            let unchecked_at_resolution = match (resolved_at, checked_at) {
+                (Some(_), None) => true,
+                (Some(resolved), Some(checked)) => checked > resolved,
+                (None, _) => false,
+            };
+            ItemObservation {
+                checklist_id: row.get("checklist_id"),
+                item_key: format!("{}:{}", template_id, label.trim().to_lowercase()),
+                template_id,
+                template_name: row.get("template_name"),
+                label,
+                service_id: row.get("service_id"),
+                service_name: row.get("service_name"),
+                severity: row.get("severity"),
+                is_checked,
+                minutes_to_check,
+                resolved: resolved_at.is_some(),
+                unchecked_at_resolution,
+            }
+        })
+        .collect();
+
+    let items = item_compliance(&observations);
+    let mut by_service = group_compliance(&observations, |o| {
+        (o.service_id.clone(), o.service_name.clone())
+    });
+    by_service.sort_by(|a, b| {
+        b.stats
+            .item_count
+            .cmp(&a.stats.item_count)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    let mut by_severity =
+        group_compliance(&observations, |o| (o.severity.clone(), o.severity.clone()));
+    by_severity.sort_by_key(|g| severity_rank(&g.key));
+
+    Ok(ChecklistCompliance {
+        start_date: range.start.clone(),
+        end_date: range.end.clone(),
+        overall: compliance_stats(&observations),
+        templates: group_compliance(&observations, |o| {
+            (o.template_id.clone(), o.template_name.clone())
+        }),
+        most_skipped: most_skipped(&items, MOST_SKIPPED_LIMIT),
+        items,
+        by_service,
+        by_severity,
+    })
+}
+
 async fn get_incident_checklist_by_id(
     db: &SqlitePool,
     id: &str,
@@ -401,3 +512,112 @@ fn parse_checklist_item(row: &sqlx::sqlite::SqliteRow) -> ChecklistItem {
         sort_order: row.get::<i32, _>("sort_order"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timezone::AnalyticsZone;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("checklist-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(
+        db: &SqlitePool,
+        id: &str,
+        service_id: &str,
+        severity: &str,
+        started_at: &str,
+        resolved_at: Option<&str>,
+    ) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) VALUES (?, ?, ?, ?, 'High', 'Resolved', ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(severity)
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    async fn check_item(db: &SqlitePool, checklist: &IncidentChecklist, index: usize, at: &str) {
+        sqlx::query("UPDATE checklist_items SET is_checked = 1, checked_at = ? WHERE id = ?")
+            .bind(at)
+            .bind(&checklist.items[index].id)
+            .execute(db)
+            .await
+            .expect("check item");
+    }
+
+    fn january() -> DateRange {
+        DateRange::local_days("2025-01-01", "2025-01-31", &AnalyticsZone::utc()).unwrap()
+    }
+
+    /// Test: compliance is reported per template, item, service and severity
+    #[tokio::test]
+    async fn test_checklist_compliance() {
+        let (_dir, db) = setup_db().await;
+        let items = vec!["Page on-call".to_string(), "Post status update".to_string()];
+        create_template(&db, "tpl-1", "Major incident", None, None, &items).await.unwrap();
+
+        insert_incident(&db, "c-1", "svc-slack", "Critical", "2025-01-10T10:00:00Z", Some("2025-01-10T12:00:00Z")).await;
+        insert_incident(&db, "c-2", "svc-zoom", "Low", "2025-01-20T10:00:00Z", Some("2025-01-20T11:00:00Z")).await;
+        insert_incident(&db, "c-3", "svc-zoom", "Low", "2025-01-25T10:00:00Z", None).await;
+
+        let first = create_checklist_from_template(&db, "cl-1", "c-1", "tpl-1").await.unwrap();
+        check_item(&db, &first, 0, "2025-01-10T10:10:00Z").await;
+        check_item(&db, &first, 1, "2025-01-10T10:30:00Z").await;
+        let second = create_checklist_from_template(&db, "cl-2", "c-2", "tpl-1").await.unwrap();
+        check_item(&db, &second, 0, "2025-01-20T10:20:00Z").await;
+        // Checked after resolution still counts as skipped
+        check_item(&db, &second, 1, "2025-01-20T13:00:00Z").await;
+        create_checklist_from_template(&db, "cl-3", "c-3", "tpl-1").await.unwrap();
+        // Ad-hoc checklists are ignored
+        create_incident_checklist(&db, "cl-4", "c-3", None, "Ad hoc", &items).await.unwrap();
+
+        // Editing the template recreates its items; history still lines up by label
+        update_template(&db, "tpl-1", None, None, None, None, Some(&items)).await.unwrap();
+
+        let report = get_checklist_compliance(&db, &january(), &MetricFilters::default()).await.unwrap();
+        assert_eq!(report.overall.checklist_count, 3);
+        assert_eq!(report.overall.item_count, 6);
+        assert_eq!(report.overall.checked_count, 4);
+        assert_eq!(report.overall.unchecked_at_resolution, 1);
+        assert_eq!(report.templates.len(), 1);
+
+        assert_eq!(report.items.len(), 2);
+        let page = &report.items[0];
+        assert_eq!(page.label, "Page on-call");
+        assert_eq!(page.stats.median_minutes_to_check, Some(15.0));
+        assert_eq!(report.most_skipped.len(), 1);
+        assert_eq!(report.most_skipped[0].label, "Post status update");
+        assert!((report.most_skipped[0].stats.skip_rate - 50.0).abs() < 1e-9);
+
+        assert_eq!(report.by_severity[0].key, "Critical");
+        assert!((report.by_severity[0].stats.completion_rate - 100.0).abs() < 1e-9);
+        assert_eq!(report.by_service[0].key, "svc-zoom");
+        assert_eq!(report.by_service[0].stats.item_count, 4);
+    }
+}
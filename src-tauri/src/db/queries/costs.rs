@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use sqlx::{Row, SqlitePool};
+
+use crate::db::queries::metrics::{build_where_clause, DateRange};
+use crate::db::queries::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::cost::{estimate_cost, CostInputs, CostModel, IncidentCost};
+use crate::models::metrics::MetricFilters;
+
+pub const COST_MODEL_SETTING_KEY: &str = "cost_model";
+
+/// Load the configured cost model, falling back to defaults when unset.
+pub async fn get_cost_model(db: &SqlitePool) -> AppResult<CostModel> {
+    match settings::get_setting(db, COST_MODEL_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored cost model: {}", e))),
+        None => Ok(CostModel::default()),
+    }
+}
+
+pub async fn save_cost_model(db: &SqlitePool, model: &CostModel) -> AppResult<CostModel> {
+    let json = serde_json::to_string(model)?;
+    settings::set_setting(db, COST_MODEL_SETTING_KEY, &json).await?;
+    Ok(model.clone())
+}
+
+/// Shared SELECT for the facts the cost model needs. Active incidents are
+/// costed up to now, matching how downtime is computed elsewhere.
+const COST_SELECT: &str = "SELECT i.id, i.title, i.service_id, i.started_at, \
+     COALESCE(s.name, 'Unknown Service') as service_name, \
+     COALESCE(i.affected_users, 0) as affected_users, \
+     COALESCE(i.tickets_submitted, 0) as tickets_submitted, \
+     COALESCE(i.duration_minutes, CAST((julianday('now') - julianday(i.started_at)) * 1440 AS INTEGER)) as cost_minutes, \
+     (SELECT COUNT(DISTINCT r.assignee) FROM incident_roles r WHERE r.incident_id = i.id) as responder_count, \
+     o.amount as override_amount, \
+     o.note as override_note \
+     FROM incidents i \
+     LEFT JOIN services s ON i.service_id = s.id \
+     LEFT JOIN incident_cost_overrides o ON o.incident_id = i.id";
+
+fn parse_incident_cost(row: &sqlx::sqlite::SqliteRow, model: &CostModel) -> IncidentCost {
+    let service_id: String = row.get("service_id");
+    let inputs = CostInputs {
+        service_id: service_id.clone(),
+        affected_users: row.get::<Option<i64>, _>("affected_users").unwrap_or(0),
+        tickets_submitted: row.get::<Option<i64>, _>("tickets_submitted").unwrap_or(0),
+        duration_minutes: row.get::<Option<i64>, _>("cost_minutes").unwrap_or(0),
+        responder_count: row.get::<Option<i64>, _>("responder_count").unwrap_or(0),
+    };
+    let breakdown = estimate_cost(&inputs, model);
+    let override_amount: Option<f64> = row.get("override_amount");
+
+    IncidentCost {
+        incident_id: row.get("id"),
+        title: row.get("title"),
+        service_id,
+        service_name: row.get("service_name"),
+        started_at: row.get("started_at"),
+        total_cost: override_amount.unwrap_or(breakdown.estimated_total),
+        breakdown,
+        override_amount,
+        override_note: row.get::<Option<String>, _>("override_note").unwrap_or_default(),
+    }
+}
+
+/// Estimated cost of every incident in the range, oldest first.
+pub async fn list_incident_costs(
+    db: &SqlitePool,
+    range: &DateRange,
+    filters: &MetricFilters,
+    model: &CostModel,
+) -> AppResult<Vec<IncidentCost>> {
+    let (wc, params) = build_where_clause(range, filters);
+    let sql = format!("{} WHERE {} ORDER BY i.started_at ASC", COST_SELECT, wc);
+
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let rows = query
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(rows.iter().map(|r| parse_incident_cost(r, model)).collect())
+}
+
+pub async fn get_incident_cost(
+    db: &SqlitePool,
+    incident_id: &str,
+    model: &CostModel,
+) -> AppResult<IncidentCost> {
+    let sql = format!("{} WHERE i.id = ? AND i.deleted_at IS NULL", COST_SELECT);
+    let row = sqlx::query(&sql)
+        .bind(incident_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound(format!("Incident '{}' not found", incident_id)))?;
+
+    Ok(parse_incident_cost(&row, model))
+}
+
+/// Incident ids bound per query by `incident_cost_totals`, well under
+/// SQLite's host parameter limit.
+const COST_TOTALS_CHUNK: usize = 500;
+
+/// Total cost (override, else estimate) of each of `incident_ids` that is not
+/// deleted, keyed by incident id.
+pub async fn incident_cost_totals(
+    db: &SqlitePool,
+    model: &CostModel,
+    incident_ids: &[String],
+) -> AppResult<HashMap<String, f64>> {
+    let mut totals = HashMap::with_capacity(incident_ids.len());
+    for chunk in incident_ids.chunks(COST_TOTALS_CHUNK) {
+        let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!("{} WHERE i.deleted_at IS NULL AND i.id IN ({})", COST_SELECT, placeholders);
+        let mut query = sqlx::query(&sql);
+        for id in chunk {
+            query = query.bind(id);
+        }
+        let rows = query
+            .fetch_all(db)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        totals.extend(rows.iter().map(|r| {
+            let cost = parse_incident_cost(r, model);
+            (cost.incident_id, cost.total_cost)
+        }));
+    }
+    Ok(totals)
+}
+
+pub async fn set_cost_override(
+    db: &SqlitePool,
+    incident_id: &str,
+    amount: f64,
+    note: &str,
+) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO incident_cost_overrides (incident_id, amount, note) VALUES (?, ?, ?) \
+         ON CONFLICT(incident_id) DO UPDATE SET amount = excluded.amount, note = excluded.note, \
+         updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+    )
+    .bind(incident_id)
+    .bind(amount)
+    .bind(note)
+    .execute(db)
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("FOREIGN KEY") {
+            AppError::NotFound(format!("Incident '{}' not found", incident_id))
+        } else {
+            AppError::Database(e.to_string())
+        }
+    })?;
+
+    Ok(())
+}
+
+pub async fn clear_cost_override(db: &SqlitePool, incident_id: &str) -> AppResult<()> {
+    sqlx::query("DELETE FROM incident_cost_overrides WHERE incident_id = ?")
+        .bind(incident_id)
+        .execute(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("cost-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, started_at: &str, resolved_at: &str) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at, affected_users, tickets_submitted) VALUES (?, ?, 'svc-slack', 'High', 'High', 'Resolved', ?, ?, ?, 200, 3)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    /// Test: estimates use duration, users, tickets and distinct responders
+    #[tokio::test]
+    async fn test_incident_cost_estimate_from_db() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "c-1", "2025-01-10T10:00:00Z", "2025-01-10T11:00:00Z").await;
+        for (id, role, assignee) in [
+            ("r-1", "Incident Commander", "alice"),
+            ("r-2", "Technical Lead", "bob"),
+            ("r-3", "Scribe", "alice"),
+        ] {
+            sqlx::query("INSERT INTO incident_roles (id, incident_id, role, assignee) VALUES (?, 'c-1', ?, ?)")
+                .bind(id)
+                .bind(role)
+                .bind(assignee)
+                .execute(&db)
+                .await
+                .expect("insert role");
+        }
+
+        let model = CostModel::default();
+        let cost = get_incident_cost(&db, "c-1", &model).await.unwrap();
+        // 200 users * 60 min * 0.05 + 3 tickets * 25 + 2 responders * 1h * 100
+        assert!((cost.breakdown.user_impact - 600.0).abs() < 1e-9);
+        assert!((cost.breakdown.tickets - 75.0).abs() < 1e-9);
+        assert!((cost.breakdown.responder_effort - 200.0).abs() < 1e-9);
+        assert!((cost.total_cost - 875.0).abs() < 1e-9);
+        assert!(cost.override_amount.is_none());
+    }
+
+    /// Test: an override replaces the estimate until cleared
+    #[tokio::test]
+    async fn test_cost_override_precedence() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "c-1", "2025-01-10T10:00:00Z", "2025-01-10T11:00:00Z").await;
+        insert_incident(&db, "c-2", "2025-01-11T10:00:00Z", "2025-01-11T10:30:00Z").await;
+        let model = CostModel::default();
+
+        set_cost_override(&db, "c-1", 50000.0, "Finance estimate").await.unwrap();
+        set_cost_override(&db, "c-1", 42000.0, "Revised").await.unwrap();
+
+        let cost = get_incident_cost(&db, "c-1", &model).await.unwrap();
+        assert_eq!(cost.override_amount, Some(42000.0));
+        assert_eq!(cost.override_note, "Revised");
+        assert!((cost.total_cost - 42000.0).abs() < 1e-9);
+        assert!(cost.breakdown.estimated_total > 0.0);
+
+        let range = DateRange {
+            start: "2025-01-01".into(),
+            end: "2025-01-31".into(),
+        };
+        let costs = list_incident_costs(&db, &range, &MetricFilters::default(), &model)
+            .await
+            .unwrap();
+        assert_eq!(costs.len(), 2);
+        assert_eq!(costs[0].incident_id, "c-1");
+        assert!(costs[1].override_amount.is_none());
+        let totals = incident_cost_totals(&db, &model, &["c-1".to_string()]).await.unwrap();
+        assert_eq!(totals.get("c-1"), Some(&42000.0));
+        assert!(!totals.contains_key("c-2"));
+        let ids = ["c-1".to_string(), "c-2".to_string()];
+        let totals = incident_cost_totals(&db, &model, &ids).await.unwrap();
+        assert_eq!(totals.get("c-2"), Some(&costs[1].total_cost));
+
+        clear_cost_override(&db, "c-1").await.unwrap();
+        let cost = get_incident_cost(&db, "c-1", &model).await.unwrap();
+        assert!(cost.override_amount.is_none());
+        assert!((cost.total_cost - cost.breakdown.estimated_total).abs() < 1e-9);
+    }
+
+    /// Test: overriding an unknown incident is reported as not found
+    #[tokio::test]
+    async fn test_cost_override_unknown_incident() {
+        let (_dir, db) = setup_db().await;
+        let result = set_cost_override(&db, "missing", 10.0, "").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    /// Test: the cost model round-trips through settings
+    #[tokio::test]
+    async fn test_cost_model_round_trip() {
+        let (_dir, db) = setup_db().await;
+        assert_eq!(get_cost_model(&db).await.unwrap().currency, "USD");
+
+        let mut model = CostModel {
+            currency: "EUR".into(),
+            ..Default::default()
+        };
+        model.service_revenue_per_hour.insert("svc-slack".into(), 2500.0);
+        save_cost_model(&db, &model).await.unwrap();
+
+        let loaded = get_cost_model(&db).await.unwrap();
+        assert_eq!(loaded.currency, "EUR");
+        assert_eq!(loaded.service_revenue_per_hour.get("svc-slack"), Some(&2500.0));
+    }
+}
@@ -1,57 +1,92 @@
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, Timelike};
 use sqlx::{Row, SqlitePool};
 
+use crate::db::queries::time_zones;
 use crate::error::{AppError, AppResult};
 use crate::models::metrics::{DayCount, HourCount};
+use crate::models::timezone::{parse_timestamp, AnalyticsZone};
+
+/// Lower bound for `started_at`: plain dates start at local midnight.
+fn lower_bound(value: &str, tz: &AnalyticsZone) -> String {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) if value.len() == 10 => tz.day_bounds(date, date).0,
+        _ => value.to_string(),
+    }
+}
+
+/// Upper bound for `started_at`: plain dates include the whole local day.
+fn upper_bound(value: &str, tz: &AnalyticsZone) -> String {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) if value.len() == 10 => tz.day_bounds(date, date).1,
+        _ => value.to_string(),
+    }
+}
 
+/// Incidents per local calendar day in the organization time zone.
 pub async fn get_incident_heatmap(
     db: &SqlitePool,
     start_date: &str,
     end_date: &str,
 ) -> AppResult<Vec<DayCount>> {
+    let tz = time_zones::org_time_zone(db).await?;
+
     let rows = sqlx::query(
-        "SELECT date(started_at) as day, COUNT(*) as count \
-         FROM incidents \
-         WHERE started_at >= ? AND started_at <= ? \
-         GROUP BY day \
-         ORDER BY day ASC"
+        "SELECT started_at FROM incidents \
+         WHERE deleted_at IS NULL AND started_at >= ? AND started_at <= ?"
     )
-    .bind(start_date)
-    .bind(end_date)
+    .bind(lower_bound(start_date, &tz))
+    .bind(upper_bound(end_date, &tz))
     .fetch_all(db)
     .await
     .map_err(|e| AppError::Database(e.to_string()))?;
 
-    Ok(rows
-        .iter()
-        .map(|r| DayCount {
-            day: r.get("day"),
-            count: r.get("count"),
+    let mut counts: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for row in &rows {
+        let started_at: String = row.get("started_at");
+        if let Some(instant) = parse_timestamp(&started_at) {
+            *counts.entry(tz.local_date(&instant)).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts
+        .into_iter()
+        .map(|(day, count)| DayCount {
+            day: day.format("%Y-%m-%d").to_string(),
+            count,
         })
         .collect())
 }
 
+/// Incidents per local hour of day. Each incident is placed in its service's
+/// configured time zone, falling back to the organization zone.
 pub async fn get_incident_by_hour(
     db: &SqlitePool,
     start_date: Option<&str>,
     end_date: Option<&str>,
 ) -> AppResult<Vec<HourCount>> {
+    let config = time_zones::get_time_zone_config(db).await?;
+    let org_tz = AnalyticsZone::parse(&config.org_time_zone)?;
+    let mut service_tz: BTreeMap<String, AnalyticsZone> = BTreeMap::new();
+    for (service_id, zone) in &config.service_time_zones {
+        service_tz.insert(service_id.clone(), AnalyticsZone::parse(zone)?);
+    }
+
     let mut sql = String::from(
-        "SELECT CAST(strftime('%H', started_at) AS INTEGER) as hour, COUNT(*) as count \
-         FROM incidents WHERE 1=1"
+        "SELECT started_at, service_id FROM incidents WHERE deleted_at IS NULL"
     );
     let mut binds: Vec<String> = vec![];
 
     if let Some(start) = start_date {
         sql.push_str(" AND started_at >= ?");
-        binds.push(start.to_string());
+        binds.push(lower_bound(start, &org_tz));
     }
     if let Some(end) = end_date {
         sql.push_str(" AND started_at <= ?");
-        binds.push(end.to_string());
+        binds.push(upper_bound(end, &org_tz));
     }
 
-    sql.push_str(" GROUP BY hour ORDER BY hour ASC");
-
     let mut query = sqlx::query(&sql);
     for bind in &binds {
         query = query.bind(bind);
@@ -62,11 +97,141 @@ pub async fn get_incident_by_hour(
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
 
-    Ok(rows
-        .iter()
-        .map(|r| HourCount {
-            hour: r.get("hour"),
-            count: r.get("count"),
-        })
+    let mut counts: BTreeMap<i32, i64> = BTreeMap::new();
+    for row in &rows {
+        let started_at: String = row.get("started_at");
+        let service_id: String = row.get("service_id");
+        let Some(instant) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        let tz = service_tz.get(&service_id).unwrap_or(&org_tz);
+        *counts.entry(tz.to_local(&instant).hour() as i32).or_insert(0) += 1;
+    }
+
+    Ok(counts
+        .into_iter()
+        .map(|(hour, count)| HourCount { hour, count })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timezone::TimeZoneConfig;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("dashboard-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, service_id: &str, started_at: &str) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at) VALUES (?, ?, ?, 'High', 'High', 'Active', ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(started_at)
+        .bind(started_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    async fn set_zones(db: &SqlitePool, org: &str, services: &[(&str, &str)]) {
+        let config = TimeZoneConfig {
+            org_time_zone: org.into(),
+            service_time_zones: services
+                .iter()
+                .map(|(s, z)| (s.to_string(), z.to_string()))
+                .collect(),
+        };
+        time_zones::save_time_zone_config(db, &config).await.unwrap();
+    }
+
+    /// Test: without configuration, buckets are UTC and the end date is inclusive
+    #[tokio::test]
+    async fn test_heatmap_defaults_to_utc() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "h-1", "svc-slack", "2025-03-01T02:00:00Z").await;
+        insert_incident(&db, "h-2", "svc-slack", "2025-03-31T23:30:00Z").await;
+
+        let days = get_incident_heatmap(&db, "2025-03-01", "2025-03-31").await.unwrap();
+        let days: Vec<(&str, i64)> = days.iter().map(|d| (d.day.as_str(), d.count)).collect();
+        assert_eq!(days, vec![("2025-03-01", 1), ("2025-03-31", 1)]);
+    }
+
+    /// Test: days follow the org zone across the spring-forward change
+    #[tokio::test]
+    async fn test_heatmap_uses_org_time_zone() {
+        let (_dir, db) = setup_db().await;
+        set_zones(&db, "America/New_York", &[]).await;
+        // 2025-03-09 04:30Z is still 23:30 EST on the 8th
+        insert_incident(&db, "h-1", "svc-slack", "2025-03-09T04:30:00Z").await;
+        // 2025-03-10 03:30Z is 23:30 EDT on the 9th (after the change)
+        insert_incident(&db, "h-2", "svc-slack", "2025-03-10T03:30:00Z").await;
+        // 2025-03-10 04:30Z is 00:30 EDT on the 10th
+        insert_incident(&db, "h-3", "svc-slack", "2025-03-10T04:30:00Z").await;
+
+        let days = get_incident_heatmap(&db, "2025-03-08", "2025-03-10").await.unwrap();
+        let days: Vec<(&str, i64)> = days.iter().map(|d| (d.day.as_str(), d.count)).collect();
+        assert_eq!(days, vec![("2025-03-08", 1), ("2025-03-09", 1), ("2025-03-10", 1)]);
+
+        // The local range excludes instants before its first local midnight
+        let days = get_incident_heatmap(&db, "2025-03-09", "2025-03-10").await.unwrap();
+        assert_eq!(days.iter().map(|d| d.count).sum::<i64>(), 2);
+    }
+
+    /// Test: hours use the service zone when set, else the org zone, across DST
+    #[tokio::test]
+    async fn test_by_hour_uses_service_time_zones() {
+        let (_dir, db) = setup_db().await;
+        set_zones(&db, "Europe/London", &[("svc-zoom", "Asia/Tokyo")]).await;
+        // Winter: 09:00Z is 09:00 GMT; summer: 09:00Z is 10:00 BST
+        insert_incident(&db, "b-1", "svc-slack", "2025-01-15T09:00:00Z").await;
+        insert_incident(&db, "b-2", "svc-slack", "2025-07-15T09:00:00Z").await;
+        // Tokyo is UTC+9 all year: 09:00Z is 18:00
+        insert_incident(&db, "b-3", "svc-zoom", "2025-07-15T09:00:00Z").await;
+
+        let hours = get_incident_by_hour(&db, None, None).await.unwrap();
+        let hours: Vec<(i32, i64)> = hours.iter().map(|h| (h.hour, h.count)).collect();
+        assert_eq!(hours, vec![(9, 1), (10, 1), (18, 1)]);
+    }
+
+    /// Test: deleted incidents are excluded from both views
+    #[tokio::test]
+    async fn test_deleted_incidents_excluded() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "d-1", "svc-slack", "2025-03-01T10:00:00Z").await;
+        sqlx::query("UPDATE incidents SET deleted_at = '2025-03-02T00:00:00Z' WHERE id = 'd-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        assert!(get_incident_heatmap(&db, "2025-03-01", "2025-03-31").await.unwrap().is_empty());
+        assert!(get_incident_by_hour(&db, Some("2025-03-01"), None).await.unwrap().is_empty());
+    }
+
+    /// Test: plain dates expand to whole days, timestamps are used as given
+    #[test]
+    fn test_bounds_pass_timestamps_through() {
+        let tz = AnalyticsZone::utc();
+        assert_eq!(lower_bound("2025-03-01T10:00:00Z", &tz), "2025-03-01T10:00:00Z");
+        assert_eq!(upper_bound("2025-03-01", &tz), "2025-03-01T23:59:59Z");
+    }
+}
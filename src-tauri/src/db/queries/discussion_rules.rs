@@ -0,0 +1,220 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+
+use crate::error::{AppError, AppResult};
+use crate::models::discussion_rule::{
+    CreateDiscussionRuleRequest, DiscussionRule, UpdateDiscussionRuleRequest,
+};
+
+pub async fn list_discussion_rules(db: &SqlitePool) -> AppResult<Vec<DiscussionRule>> {
+    let rows = sqlx::query("SELECT * FROM discussion_rules ORDER BY sort_order ASC, name ASC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    rows.iter().map(parse_discussion_rule).collect()
+}
+
+/// Enabled rules in evaluation order.
+pub async fn list_enabled_discussion_rules(db: &SqlitePool) -> AppResult<Vec<DiscussionRule>> {
+    Ok(list_discussion_rules(db)
+        .await?
+        .into_iter()
+        .filter(|r| r.enabled)
+        .collect())
+}
+
+pub async fn get_discussion_rule(db: &SqlitePool, id: &str) -> AppResult<DiscussionRule> {
+    let row = sqlx::query("SELECT * FROM discussion_rules WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound(format!("Discussion rule '{}' not found", id)))?;
+
+    parse_discussion_rule(&row)
+}
+
+pub async fn create_discussion_rule(
+    db: &SqlitePool,
+    id: &str,
+    req: &CreateDiscussionRuleRequest,
+) -> AppResult<DiscussionRule> {
+    sqlx::query(
+        "INSERT INTO discussion_rules (id, name, metric, comparator, threshold, scope, message, severity, enabled, sort_order) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM discussion_rules))",
+    )
+    .bind(id)
+    .bind(&req.name)
+    .bind(enum_text(&req.metric))
+    .bind(enum_text(&req.comparator))
+    .bind(req.threshold)
+    .bind(enum_text(&req.scope))
+    .bind(&req.message)
+    .bind(&req.severity)
+    .bind(req.enabled)
+    .execute(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    get_discussion_rule(db, id).await
+}
+
+pub async fn update_discussion_rule(
+    db: &SqlitePool,
+    id: &str,
+    req: &UpdateDiscussionRuleRequest,
+) -> AppResult<DiscussionRule> {
+    let existing = get_discussion_rule(db, id).await?;
+    req.validate(&existing)?;
+
+    sqlx::query(
+        "UPDATE discussion_rules SET name=?, metric=?, comparator=?, threshold=?, scope=?, message=?, severity=?, enabled=?, updated_at=strftime('%Y-%m-%dT%H:%M:%SZ','now') WHERE id=?",
+    )
+    .bind(req.name.as_ref().unwrap_or(&existing.name))
+    .bind(enum_text(&req.metric.unwrap_or(existing.metric)))
+    .bind(enum_text(&req.comparator.unwrap_or(existing.comparator)))
+    .bind(req.threshold.unwrap_or(existing.threshold))
+    .bind(enum_text(&req.scope.unwrap_or(existing.scope)))
+    .bind(req.message.as_ref().unwrap_or(&existing.message))
+    .bind(req.severity.as_ref().unwrap_or(&existing.severity))
+    .bind(req.enabled.unwrap_or(existing.enabled))
+    .bind(id)
+    .execute(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    get_discussion_rule(db, id).await
+}
+
+pub async fn delete_discussion_rule(db: &SqlitePool, id: &str) -> AppResult<()> {
+    let existing = get_discussion_rule(db, id).await?;
+    if existing.is_builtin {
+        return Err(AppError::Validation(
+            "Built-in rules cannot be deleted; disable the rule instead".into(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM discussion_rules WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    Ok(())
+}
+
+/// The stored text of a metric, comparator or scope (its serde name).
+fn enum_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn parse_enum<T: DeserializeOwned>(text: String) -> AppResult<T> {
+    serde_json::from_value(serde_json::Value::String(text.clone()))
+        .map_err(|_| AppError::Database(format!("Invalid discussion rule value '{}'", text)))
+}
+
+fn parse_discussion_rule(row: &sqlx::sqlite::SqliteRow) -> AppResult<DiscussionRule> {
+    Ok(DiscussionRule {
+        id: row.get("id"),
+        name: row.get("name"),
+        metric: parse_enum(row.get("metric"))?,
+        comparator: parse_enum(row.get("comparator"))?,
+        threshold: row.get("threshold"),
+        scope: parse_enum(row.get("scope"))?,
+        message: row.get("message"),
+        severity: row.get("severity"),
+        enabled: row.get::<bool, _>("enabled"),
+        sort_order: row.get("sort_order"),
+        is_builtin: row.get::<bool, _>("is_builtin"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::discussion_rule::{Comparator, RuleMetric, RuleScope};
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("discussion-rule-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: the ten default rules are seeded in order and pass validation
+    #[tokio::test]
+    async fn test_seeded_rules() {
+        let (_dir, db) = setup_db().await;
+        let rules = list_discussion_rules(&db).await.unwrap();
+        assert_eq!(rules.len(), 10);
+        assert_eq!(rules[0].id, "dr-service-incidents");
+        assert_eq!(rules[0].comparator, Comparator::AtLeast);
+        assert_eq!(rules[0].scope, RuleScope::Service);
+        for rule in &rules {
+            assert!(rule.is_builtin && rule.enabled);
+            crate::models::discussion_rule::validate_rule(
+                &rule.name, rule.metric, rule.threshold, rule.scope, &rule.message, &rule.severity,
+            )
+            .unwrap();
+        }
+    }
+
+    /// Test: thresholds can be edited, rules disabled, and only custom rules deleted
+    #[tokio::test]
+    async fn test_rule_crud() {
+        let (_dir, db) = setup_db().await;
+        let update = UpdateDiscussionRuleRequest {
+            name: None,
+            metric: None,
+            comparator: None,
+            threshold: Some(5.0),
+            scope: None,
+            message: None,
+            severity: None,
+            enabled: Some(false),
+        };
+        let updated = update_discussion_rule(&db, "dr-service-incidents", &update).await.unwrap();
+        assert_eq!(updated.threshold, 5.0);
+        assert!(!list_enabled_discussion_rules(&db).await.unwrap().iter().any(|r| r.id == updated.id));
+        assert!(delete_discussion_rule(&db, "dr-service-incidents").await.is_err());
+
+        let bad_scope = UpdateDiscussionRuleRequest { scope: Some(RuleScope::Incident), enabled: None, threshold: None, ..update };
+        assert!(update_discussion_rule(&db, "dr-open-actions", &bad_scope).await.is_err());
+
+        let req = CreateDiscussionRuleRequest {
+            name: "Slow acknowledgement".into(),
+            metric: RuleMetric::MttrMinutes,
+            comparator: Comparator::GreaterThan,
+            threshold: 240.0,
+            scope: RuleScope::Service,
+            message: "{{service}} took {{value}} to resolve on average.".into(),
+            severity: "high".into(),
+            enabled: true,
+        };
+        let created = create_discussion_rule(&db, "dr-custom", &req).await.unwrap();
+        assert_eq!(created.sort_order, 11);
+        assert!(!created.is_builtin);
+        delete_discussion_rule(&db, "dr-custom").await.unwrap();
+        assert!(get_discussion_rule(&db, "dr-custom").await.is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::queries::metrics::{build_where_clause, DateRange};
+use crate::db::queries::{settings, time_zones};
+use crate::error::{AppError, AppResult};
+use crate::models::fatigue::{
+    engagement_window, summarize_load, Engagement, ResponderLoadReport, WorkingHours,
+    WorkingHoursConfig, UNOWNED_TEAM,
+};
+use crate::models::metrics::MetricFilters;
+use crate::models::timezone::{parse_timestamp, AnalyticsZone};
+
+pub const WORKING_HOURS_SETTING_KEY: &str = "working_hours_config";
+
+pub async fn get_working_hours_config(db: &SqlitePool) -> AppResult<WorkingHoursConfig> {
+    match settings::get_setting(db, WORKING_HOURS_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored working hours: {}", e))),
+        None => Ok(WorkingHoursConfig::default()),
+    }
+}
+
+pub async fn save_working_hours_config(
+    db: &SqlitePool,
+    config: &WorkingHoursConfig,
+) -> AppResult<WorkingHoursConfig> {
+    let json = serde_json::to_string(config)?;
+    settings::set_setting(db, WORKING_HOURS_SETTING_KEY, &json).await?;
+    Ok(config.clone())
+}
+
+/// Working hours bound to their configured zone, or the organization zone.
+pub async fn working_hours(db: &SqlitePool) -> AppResult<(WorkingHoursConfig, WorkingHours)> {
+    let config = get_working_hours_config(db).await?;
+    let tz = match config.time_zone {
+        Some(ref zone) => AnalyticsZone::parse(zone)?,
+        None => time_zones::org_time_zone(db).await?,
+    };
+    let hours = WorkingHours::new(&config, tz)?;
+    Ok((config, hours))
+}
+
+/// One engagement per responder and incident for incidents started in `range`.
+/// Several roles held by the same person on one incident are merged, and
+/// responder names are matched case-insensitively.
+pub async fn list_engagements(
+    db: &SqlitePool,
+    range: &DateRange,
+    filters: &MetricFilters,
+) -> AppResult<Vec<Engagement>> {
+    let (wc, params) = build_where_clause(range, filters);
+    let sql = format!(
+        "SELECT r.incident_id, r.assignee, r.assigned_at, r.unassigned_at, \
+         i.started_at, i.resolved_at, i.service_id, \
+         COALESCE(s.name, 'Unknown Service') as service_name, COALESCE(s.owner, '') as owner \
+         FROM incident_roles r \
+         JOIN incidents i ON r.incident_id = i.id \
+         LEFT JOIN services s ON i.service_id = s.id \
+         WHERE {} \
+         ORDER BY i.started_at, r.assigned_at",
+        wc
+    );
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let rows = query
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let now = Utc::now();
+    let mut merged: HashMap<(String, String), Engagement> = HashMap::new();
+    let mut order: Vec<(String, String)> = vec![];
+    for row in &rows {
+        let assignee: String = row.get("assignee");
+        let assignee = assignee.trim().to_string();
+        if assignee.is_empty() {
+            continue;
+        }
+        let started_at: String = row.get("started_at");
+        let Some(incident_start) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        let incident_end = row
+            .get::<Option<String>, _>("resolved_at")
+            .and_then(|v| parse_timestamp(&v))
+            .unwrap_or(now);
+        let assigned_at = parse_timestamp(&row.get::<String, _>("assigned_at"));
+        let unassigned_at = row
+            .get::<Option<String>, _>("unassigned_at")
+            .and_then(|v| parse_timestamp(&v));
+        let (start, end) = engagement_window(incident_start, incident_end, assigned_at, unassigned_at);
+
+        let incident_id: String = row.get("incident_id");
+        let key = (incident_id.clone(), assignee.to_lowercase());
+        if let Some(existing) = merged.get_mut(&key) {
+            existing.start = existing.start.min(start);
+            existing.end = existing.end.max(end);
+            continue;
+        }
+
+        let owner: String = row.get("owner");
+        let owner = owner.trim();
+        order.push(key.clone());
+        merged.insert(
+            key,
+            Engagement {
+                incident_id,
+                responder: assignee,
+                service_id: row.get("service_id"),
+                service_name: row.get("service_name"),
+                team: if owner.is_empty() { UNOWNED_TEAM.to_string() } else { owner.to_string() },
+                start,
+                end,
+            },
+        );
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|key| merged.remove(&key))
+        .collect())
+}
+
+/// Per-responder, per-team and per-service load for incidents started in `range`.
+pub async fn get_responder_load(
+    db: &SqlitePool,
+    range: &DateRange,
+    filters: &MetricFilters,
+) -> AppResult<ResponderLoadReport> {
+    let (config, hours) = working_hours(db).await?;
+    let engagements = list_engagements(db, range, filters).await?;
+
+    let responders = summarize_load(&engagements, &hours, |e| {
+        (e.responder.to_lowercase(), e.responder.clone())
+    });
+    let teams = summarize_load(&engagements, &hours, |e| (e.team.clone(), e.team.clone()));
+    let services = summarize_load(&engagements, &hours, |e| {
+        (e.service_id.clone(), e.service_name.clone())
+    });
+
+    Ok(ResponderLoadReport {
+        start_date: range.start.clone(),
+        end_date: range.end.clone(),
+        time_zone: hours.time_zone().name.clone(),
+        working_hours: config,
+        responders,
+        teams,
+        services,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("fatigue-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, service_id: &str, started_at: &str, resolved_at: &str) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) VALUES (?, ?, ?, 'High', 'High', 'Resolved', ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    async fn assign(db: &SqlitePool, incident_id: &str, role: &str, assignee: &str, assigned_at: &str) {
+        sqlx::query(
+            "INSERT INTO incident_roles (id, incident_id, role, assignee, assigned_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(format!("{}-{}-{}", incident_id, role, assignee))
+        .bind(incident_id)
+        .bind(role)
+        .bind(assignee)
+        .bind(assigned_at)
+        .execute(db)
+        .await
+        .expect("assign role");
+    }
+
+    fn january() -> DateRange {
+        DateRange::local_days("2025-01-01", "2025-01-31", &AnalyticsZone::utc()).unwrap()
+    }
+
+    /// Test: roles are merged per person and grouped by responder, team and service
+    #[tokio::test]
+    async fn test_responder_load_groups() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query("UPDATE services SET owner = 'Collab Team' WHERE id = 'svc-slack'")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        // Wednesday 02:00-04:00 at night
+        insert_incident(&db, "f-1", "svc-slack", "2025-01-15T02:00:00Z", "2025-01-15T04:00:00Z").await;
+        assign(&db, "f-1", "Incident Commander", "Alice", "2025-01-15T02:00:00Z").await;
+        assign(&db, "f-1", "Technical Lead", "alice ", "2025-01-15T02:05:00Z").await;
+        assign(&db, "f-1", "Scribe", "Bob", "2025-01-15T03:00:00Z").await;
+        // Thursday daytime
+        insert_incident(&db, "f-2", "svc-zoom", "2025-01-16T10:00:00Z", "2025-01-16T11:00:00Z").await;
+        assign(&db, "f-2", "Incident Commander", "Alice", "2025-01-16T10:00:00Z").await;
+        // Outside the range
+        insert_incident(&db, "f-3", "svc-zoom", "2025-02-03T02:00:00Z", "2025-02-03T03:00:00Z").await;
+        assign(&db, "f-3", "Incident Commander", "Bob", "2025-02-03T02:00:00Z").await;
+
+        let report = get_responder_load(&db, &january(), &MetricFilters::default()).await.unwrap();
+        assert_eq!(report.time_zone, "UTC");
+
+        let alice = &report.responders[0];
+        assert_eq!(alice.label, "Alice");
+        assert_eq!(alice.incident_count, 2);
+        assert_eq!(alice.after_hours_engagements, 1);
+        // Two roles on f-1 merge into one 2h engagement, plus 1h on f-2
+        assert!((alice.engaged_hours - 3.0).abs() < 1e-9);
+        assert_eq!(alice.longest_streak_days, 2);
+
+        let bob = report.responders.iter().find(|r| r.key == "bob").unwrap();
+        assert_eq!(bob.incident_count, 1);
+        assert!((bob.engaged_hours - 1.0).abs() < 1e-9);
+
+        let collab = report.teams.iter().find(|t| t.key == "Collab Team").unwrap();
+        assert_eq!(collab.incident_count, 1);
+        assert_eq!(collab.engagement_count, 2);
+        assert_eq!(collab.after_hours_engagements, 2);
+        assert!(report.teams.iter().any(|t| t.key == UNOWNED_TEAM));
+
+        assert_eq!(report.services[0].key, "svc-slack");
+        assert_eq!(report.services.len(), 2);
+    }
+
+    /// Test: working hours are configurable, including their zone
+    #[tokio::test]
+    async fn test_working_hours_config_changes_classification() {
+        let (_dir, db) = setup_db().await;
+        assert_eq!(get_working_hours_config(&db).await.unwrap(), WorkingHoursConfig::default());
+
+        // 02:00Z on a Wednesday is 11:00 in Tokyo
+        insert_incident(&db, "f-1", "svc-slack", "2025-01-15T02:00:00Z", "2025-01-15T03:00:00Z").await;
+        assign(&db, "f-1", "Incident Commander", "Alice", "2025-01-15T02:00:00Z").await;
+        let report = get_responder_load(&db, &january(), &MetricFilters::default()).await.unwrap();
+        assert_eq!(report.responders[0].after_hours_engagements, 1);
+
+        let config = WorkingHoursConfig {
+            time_zone: Some("Asia/Tokyo".into()),
+            ..Default::default()
+        };
+        save_working_hours_config(&db, &config).await.unwrap();
+        let report = get_responder_load(&db, &january(), &MetricFilters::default()).await.unwrap();
+        assert_eq!(report.time_zone, "Asia/Tokyo");
+        assert_eq!(report.responders[0].after_hours_engagements, 0);
+        assert_eq!(report.working_hours, config);
+    }
+}
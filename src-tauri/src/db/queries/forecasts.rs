@@ -0,0 +1,288 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::SqlitePool;
+
+use crate::db::queries::{metrics, time_zones};
+use crate::error::AppResult;
+use crate::models::forecast::{
+    backtest, forecast, observed_history, ForecastPoint, ForecastRequest, ForecastSeries,
+    IncidentForecast, BACKTEST_QUARTERS,
+};
+use crate::models::metrics::{TimeseriesBucket, TimeseriesRequest, TimeseriesSeries};
+
+/// First day of the last calendar quarter that is complete on `as_of`.
+fn last_complete_quarter(as_of: NaiveDate) -> NaiveDate {
+    let current = metrics::bucket_start(as_of, "quarter");
+    let next = metrics::next_bucket_start(current, "quarter");
+    if as_of >= next - Duration::days(1) {
+        current
+    } else {
+        metrics::bucket_start(current - Duration::days(1), "quarter")
+    }
+}
+
+fn quarter_bucket(start: NaiveDate) -> TimeseriesBucket {
+    TimeseriesBucket {
+        start: start.format("%Y-%m-%d").to_string(),
+        end: (metrics::next_bucket_start(start, "quarter") - Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string(),
+        label: metrics::bucket_label(start, "quarter"),
+    }
+}
+
+/// Fit one observed series and project it over `forecast_buckets`.
+fn forecast_series(
+    key: &str,
+    label: &str,
+    series: &TimeseriesSeries,
+    forecast_buckets: &[TimeseriesBucket],
+    is_count: bool,
+) -> Option<ForecastSeries> {
+    let history = observed_history(&series.values, &series.sample_counts, is_count);
+    let fit = forecast(&history, forecast_buckets.len(), is_count)?;
+
+    let points = forecast_buckets
+        .iter()
+        .enumerate()
+        .map(|(h, bucket)| {
+            let (lower_80, upper_80) = fit.interval_80(h);
+            let (lower_95, upper_95) = fit.interval_95(h);
+            ForecastPoint {
+                label: bucket.label.clone(),
+                start: bucket.start.clone(),
+                end: bucket.end.clone(),
+                expected: fit.expected[h],
+                lower_80,
+                upper_80,
+                lower_95,
+                upper_95,
+            }
+        })
+        .collect();
+
+    Some(ForecastSeries {
+        key: key.to_string(),
+        label: label.to_string(),
+        method: fit.method.to_string(),
+        history: series.values.clone(),
+        points,
+        backtest: backtest(&history, BACKTEST_QUARTERS, is_count),
+    })
+}
+
+/// Forecast incident volume or MTTR for the quarters after the last complete
+/// calendar quarter, overall and per service. History comes from the quarterly
+/// metric time series, so it follows the organization time zone and filters.
+pub async fn get_incident_forecast(
+    db: &SqlitePool,
+    request: &ForecastRequest,
+) -> AppResult<IncidentForecast> {
+    request.validate()?;
+
+    let as_of = match request.as_of {
+        Some(ref value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| crate::error::AppError::Validation(format!("Invalid date: {}", value)))?,
+        None => {
+            let tz = time_zones::org_time_zone(db).await?;
+            tz.local_date(&chrono::Utc::now())
+        }
+    };
+
+    let last = last_complete_quarter(as_of);
+    let mut first = last;
+    for _ in 1..request.history_quarters {
+        first = metrics::bucket_start(first - Duration::days(1), "quarter");
+    }
+    let history_end = metrics::next_bucket_start(last, "quarter") - Duration::days(1);
+
+    let mut forecast_buckets = Vec::with_capacity(request.horizon);
+    let mut cursor = metrics::next_bucket_start(last, "quarter");
+    for _ in 0..request.horizon {
+        forecast_buckets.push(quarter_bucket(cursor));
+        cursor = metrics::next_bucket_start(cursor, "quarter");
+    }
+
+    let is_count = request.metric == "count";
+    let mut timeseries_request = TimeseriesRequest {
+        metric: request.metric.clone(),
+        bucket: "quarter".to_string(),
+        group_by: None,
+        start_date: first.format("%Y-%m-%d").to_string(),
+        end_date: history_end.format("%Y-%m-%d").to_string(),
+        filters: request.filters.clone(),
+    };
+    let overall_series = metrics::get_metric_timeseries(db, &timeseries_request).await?;
+    let overall = overall_series.series.first().and_then(|s| {
+        forecast_series("overall", "All services", s, &forecast_buckets, is_count)
+    });
+
+    timeseries_request.group_by = Some("service".to_string());
+    let by_service_series = metrics::get_metric_timeseries(db, &timeseries_request).await?;
+    let mut by_service: Vec<ForecastSeries> = by_service_series
+        .series
+        .iter()
+        .filter_map(|s| forecast_series(&s.key, &s.label, s, &forecast_buckets, is_count))
+        .collect();
+    by_service.sort_by(|a, b| {
+        b.points[0]
+            .expected
+            .partial_cmp(&a.points[0].expected)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.label.cmp(&b.label))
+    });
+
+    Ok(IncidentForecast {
+        metric: request.metric.clone(),
+        history_buckets: overall_series.buckets,
+        forecast_buckets,
+        overall,
+        by_service,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::metrics::MetricFilters;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("forecast-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Insert `count` incidents for a service spread across the quarter starting at `start`.
+    async fn insert_quarter(db: &SqlitePool, service_id: &str, start: NaiveDate, count: usize, minutes: i64) {
+        for n in 0..count {
+            let started = start + Duration::days((n % 80) as i64);
+            let started_at = format!("{}T10:00:00Z", started.format("%Y-%m-%d"));
+            let resolved_at = (started.and_hms_opt(10, 0, 0).unwrap() + Duration::minutes(minutes))
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string();
+            sqlx::query(
+                "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) VALUES (?, 'Forecast', ?, 'High', 'High', 'Resolved', ?, ?, ?)",
+            )
+            .bind(format!("{}-{}-{}", service_id, start, n))
+            .bind(service_id)
+            .bind(&started_at)
+            .bind(&started_at)
+            .bind(&resolved_at)
+            .execute(db)
+            .await
+            .expect("insert incident");
+        }
+    }
+
+    fn quarter_starts(first: NaiveDate, count: usize) -> Vec<NaiveDate> {
+        let mut starts = vec![first];
+        while starts.len() < count {
+            let last = *starts.last().unwrap();
+            starts.push(metrics::next_bucket_start(last, "quarter"));
+        }
+        starts
+    }
+
+    fn request(metric: &str, as_of: &str, history_quarters: usize) -> ForecastRequest {
+        ForecastRequest {
+            metric: metric.into(),
+            horizon: 1,
+            history_quarters,
+            as_of: Some(as_of.into()),
+            filters: MetricFilters::default(),
+        }
+    }
+
+    /// Test: the last complete quarter depends on whether as_of ends its quarter
+    #[test]
+    fn test_last_complete_quarter() {
+        let d = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(last_complete_quarter(d("2025-09-30")), d("2025-07-01"));
+        assert_eq!(last_complete_quarter(d("2025-09-29")), d("2025-04-01"));
+        assert_eq!(last_complete_quarter(d("2025-01-15")), d("2024-10-01"));
+    }
+
+    /// Test: forecasts for held-out quarters land close to what actually happened
+    #[tokio::test]
+    async fn test_backtest_against_held_out_quarters() {
+        let (_dir, db) = setup_db().await;
+        // Three years of a seasonal Slack pattern and a flat Zoom volume
+        let slack = [12, 6, 5, 16];
+        let starts = quarter_starts(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 12);
+        for (i, start) in starts.iter().enumerate() {
+            insert_quarter(&db, "svc-slack", *start, slack[i % 4] + i / 4, 60).await;
+            insert_quarter(&db, "svc-zoom", *start, 4, 30).await;
+        }
+
+        // Fit on the first two years only and forecast the held-out third year
+        let mut req = request("count", "2023-12-31", 8);
+        req.horizon = 4;
+        let result = get_incident_forecast(&db, &req).await.unwrap();
+        assert_eq!(result.history_buckets.len(), 8);
+        assert_eq!(result.history_buckets[7].label, "2023-Q4");
+        let labels: Vec<&str> = result.forecast_buckets.iter().map(|b| b.label.as_str()).collect();
+        assert_eq!(labels, vec!["2024-Q1", "2024-Q2", "2024-Q3", "2024-Q4"]);
+
+        let overall = result.overall.unwrap();
+        assert_eq!(overall.method, "seasonal_naive");
+        let actual: Vec<f64> = (8..12).map(|i| (slack[i % 4] + i / 4 + 4) as f64).collect();
+        for (point, actual) in overall.points.iter().zip(&actual) {
+            assert!((point.expected - actual).abs() <= 1.0, "{:?} vs {}", point, actual);
+            assert!(point.lower_95 <= *actual && *actual <= point.upper_95);
+        }
+
+        assert_eq!(result.by_service[0].key, "svc-slack");
+        let zoom = result.by_service.iter().find(|s| s.key == "svc-zoom").unwrap();
+        assert_eq!(zoom.method, "exponential_smoothing");
+        assert!((zoom.points[0].expected - 4.0).abs() < 1e-9);
+
+        // With all three years the rolling backtest covers the held-out quarters
+        let result = get_incident_forecast(&db, &request("count", "2024-12-31", 12)).await.unwrap();
+        let summary = result.overall.unwrap().backtest.unwrap();
+        assert_eq!(summary.folds, 4);
+        assert!(summary.mae <= 1.0, "mae was {}", summary.mae);
+        assert!(summary.coverage_95 >= 0.75);
+    }
+
+    /// Test: MTTR forecasts skip quarters without resolved incidents
+    #[tokio::test]
+    async fn test_mttr_forecast() {
+        let (_dir, db) = setup_db().await;
+        let starts = quarter_starts(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 4);
+        insert_quarter(&db, "svc-slack", starts[1], 3, 90).await;
+        insert_quarter(&db, "svc-slack", starts[3], 3, 90).await;
+
+        let result = get_incident_forecast(&db, &request("mttr", "2024-12-31", 4)).await.unwrap();
+        let overall = result.overall.unwrap();
+        assert_eq!(overall.history, vec![0.0, 90.0, 0.0, 90.0]);
+        assert!((overall.points[0].expected - 90.0).abs() < 1e-9);
+        assert_eq!(result.forecast_buckets[0].label, "2025-Q1");
+    }
+
+    /// Test: an empty database still produces a zero forecast
+    #[tokio::test]
+    async fn test_empty_history() {
+        let (_dir, db) = setup_db().await;
+        let result = get_incident_forecast(&db, &request("count", "2025-06-30", 4)).await.unwrap();
+        let overall = result.overall.unwrap();
+        assert_eq!(overall.points[0].expected, 0.0);
+        assert!(result.by_service.is_empty());
+
+        let result = get_incident_forecast(&db, &request("mttr", "2025-06-30", 4)).await.unwrap();
+        assert!(result.overall.is_none());
+    }
+}
@@ -1,21 +1,54 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
 use sqlx::{Row, SqlitePool};
 
+use crate::db::queries::{checklists, costs, rollups, settings, time_zones};
 use crate::error::{AppError, AppResult};
+use crate::models::cost::{format_currency, summarize_costs};
 use crate::models::metrics::{
     BacklogAgingBucket, CategoryCount, DashboardData, EscalationFunnelEntry, MetricFilters,
-    MetricResult, QuarterlyTrends, ServiceDowntime, ServiceReliabilityScore,
+    MetricResult, MetricTimeseries, QuarterlyTrends, ServiceDowntime, SeverityTrend,
+    TimeseriesBucket, TimeseriesRequest, TimeseriesSeries, MAX_TIMESERIES_BUCKETS,
     calculate_trend, format_decimal, format_minutes, format_percentage,
 };
+use crate::models::quarter::QuarterConfig;
+use crate::models::timezone::{parse_timestamp, AnalyticsZone};
 
 pub struct DateRange {
     pub start: String,
     pub end: String,
 }
 
+impl DateRange {
+    /// Range covering local calendar days `start..=end` (YYYY-MM-DD) in `tz`.
+    pub fn local_days(start: &str, end: &str, tz: &AnalyticsZone) -> AppResult<Self> {
+        let parse = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map_err(|_| AppError::Validation(format!("Invalid date: {}", value)))
+        };
+        let (start, end) = tz.day_bounds(parse(start)?, parse(end)?);
+        Ok(Self { start, end })
+    }
+}
+
+/// Interpret plain start/end dates as whole local days in the organization
+/// time zone. Full timestamps are used as given.
+pub async fn resolve_date_range(db: &SqlitePool, start: &str, end: &str) -> AppResult<DateRange> {
+    if start.len() == 10 && end.len() == 10 {
+        let tz = time_zones::org_time_zone(db).await?;
+        return DateRange::local_days(start, end, &tz);
+    }
+    Ok(DateRange {
+        start: start.to_string(),
+        end: end.to_string(),
+    })
+}
+
 /// Build a WHERE clause and a vec of bind values for dynamic metric queries.
 /// Returns (where_clause_string, bind_values) where bind_values are applied
 /// in order using `?` placeholders.
-fn build_where_clause(range: &DateRange, filters: &MetricFilters) -> (String, Vec<String>) {
+pub fn build_where_clause(range: &DateRange, filters: &MetricFilters) -> (String, Vec<String>) {
     let mut conditions = vec![
         "i.deleted_at IS NULL".to_string(),
         "i.started_at >= ?".to_string(),
@@ -188,6 +221,49 @@ async fn downtime_by_service(db: &SqlitePool, range: &DateRange, filters: &Metri
     }).collect())
 }
 
+/// Headline metrics for one period.
+struct PeriodMetrics {
+    count: i64,
+    mttr: f64,
+    mtta: f64,
+    recurrence_rate: f64,
+    avg_tickets: f64,
+}
+
+/// Compute headline metrics from the daily rollups when the range covers whole
+/// local days, otherwise from raw incident rows.
+async fn period_metrics(db: &SqlitePool, range: &DateRange, filters: &MetricFilters, tz: &AnalyticsZone) -> AppResult<PeriodMetrics> {
+    if let Some(days) = rollups::local_days(range, tz) {
+        let totals = rollups::period_totals(db, days, filters).await?;
+        return Ok(PeriodMetrics {
+            count: totals.incident_count,
+            mttr: totals.mttr(),
+            mtta: totals.mtta(),
+            recurrence_rate: totals.recurrence_rate(),
+            avg_tickets: totals.avg_tickets(),
+        });
+    }
+
+    Ok(PeriodMetrics {
+        count: count_incidents(db, range, filters).await?,
+        mttr: calc_mttr(db, range, filters).await?,
+        mtta: calc_mtta(db, range, filters).await?,
+        recurrence_rate: calc_recurrence_rate(db, range, filters).await?,
+        avg_tickets: calc_avg_tickets(db, range, filters).await?,
+    })
+}
+
+/// Incident counts by severity, impact or service, from rollups when possible.
+async fn category_counts(db: &SqlitePool, range: &DateRange, filters: &MetricFilters, dimension: &str, tz: &AnalyticsZone) -> AppResult<Vec<CategoryCount>> {
+    if let Some(days) = rollups::local_days(range, tz) {
+        return rollups::category_counts(db, days, filters, dimension).await;
+    }
+    match dimension {
+        "service" => incidents_by_service(db, range, filters).await,
+        _ => incidents_by_category(db, range, filters, dimension).await,
+    }
+}
+
 pub async fn get_dashboard_data(
     db: &SqlitePool,
     current_range: &DateRange,
@@ -195,32 +271,40 @@ pub async fn get_dashboard_data(
     filters: &MetricFilters,
     period_label: &str,
 ) -> AppResult<DashboardData> {
-    let cur_mttr = calc_mttr(db, current_range, filters).await?;
-    let cur_mtta = calc_mtta(db, current_range, filters).await?;
-    let cur_recurrence = calc_recurrence_rate(db, current_range, filters).await?;
-    let cur_tickets = calc_avg_tickets(db, current_range, filters).await?;
-    let total = count_incidents(db, current_range, filters).await?;
+    // Bring rollups up to date once so every read below sees current data
+    rollups::refresh_dirty_rollups(db).await?;
+    let tz = time_zones::org_time_zone(db).await?;
+
+    let current = period_metrics(db, current_range, filters, &tz).await?;
+    let (cur_mttr, cur_mtta, cur_recurrence, cur_tickets, total) = (
+        current.mttr,
+        current.mtta,
+        current.recurrence_rate,
+        current.avg_tickets,
+        current.count,
+    );
 
     let (prev_mttr, prev_mtta, prev_recurrence, prev_tickets) = if let Some(prev) = previous_range {
+        let previous = period_metrics(db, prev, filters, &tz).await?;
         (
-            Some(calc_mttr(db, prev, filters).await?),
-            Some(calc_mtta(db, prev, filters).await?),
-            Some(calc_recurrence_rate(db, prev, filters).await?),
-            Some(calc_avg_tickets(db, prev, filters).await?),
+            Some(previous.mttr),
+            Some(previous.mtta),
+            Some(previous.recurrence_rate),
+            Some(previous.avg_tickets),
         )
     } else {
         (None, None, None, None)
     };
 
-    let mut by_severity = incidents_by_category(db, current_range, filters, "severity").await?;
-    let mut by_impact = incidents_by_category(db, current_range, filters, "impact").await?;
-    let mut by_svc = incidents_by_service(db, current_range, filters).await?;
+    let mut by_severity = category_counts(db, current_range, filters, "severity", &tz).await?;
+    let mut by_impact = category_counts(db, current_range, filters, "impact", &tz).await?;
+    let mut by_svc = category_counts(db, current_range, filters, "service", &tz).await?;
 
     // Add previous counts if available
     if let Some(prev) = previous_range {
-        let prev_sev = incidents_by_category(db, prev, filters, "severity").await?;
-        let prev_imp = incidents_by_category(db, prev, filters, "impact").await?;
-        let prev_svc = incidents_by_service(db, prev, filters).await?;
+        let prev_sev = category_counts(db, prev, filters, "severity", &tz).await?;
+        let prev_imp = category_counts(db, prev, filters, "impact", &tz).await?;
+        let prev_svc = category_counts(db, prev, filters, "service", &tz).await?;
 
         for item in &mut by_severity {
             item.previous_count = prev_sev.iter().find(|p| p.category == item.category).map(|p| p.count);
@@ -235,8 +319,31 @@ pub async fn get_dashboard_data(
 
     let downtime = downtime_by_service(db, current_range, filters).await?;
 
+    // Estimated cost, using the configured cost model
+    let cost_model = costs::get_cost_model(db).await?;
+    let cur_costs = summarize_costs(
+        &costs::list_incident_costs(db, current_range, filters, &cost_model).await?,
+        &cost_model.currency,
+    );
+    let prev_cost = if let Some(prev) = previous_range {
+        let prev_costs = costs::list_incident_costs(db, prev, filters, &cost_model).await?;
+        Some(prev_costs.iter().map(|c| c.total_cost).sum::<f64>())
+    } else {
+        None
+    };
+
+    // Template checklist completion
+    let cur_checklists = checklists::get_checklist_compliance(db, current_range, filters).await?;
+    let prev_completion = if let Some(prev) = previous_range {
+        let prev_checklists = checklists::get_checklist_compliance(db, prev, filters).await?;
+        (prev_checklists.overall.item_count > 0).then_some(prev_checklists.overall.completion_rate)
+    } else {
+        None
+    };
+    let cur_completion = cur_checklists.overall.completion_rate;
+
     // Build trends from last 4 quarters
-    let trends = build_quarterly_trends(db, filters).await?;
+    let trends = build_quarterly_trends(db, filters, &tz).await?;
 
     Ok(DashboardData {
         mttr: MetricResult {
@@ -263,6 +370,19 @@ pub async fn get_dashboard_data(
             trend: calculate_trend(cur_tickets, prev_tickets),
             formatted_value: if total == 0 { "\u{2014}".to_string() } else { format_decimal(cur_tickets) },
         },
+        estimated_cost: MetricResult {
+            value: cur_costs.total_cost,
+            previous_value: prev_cost,
+            trend: calculate_trend(cur_costs.total_cost, prev_cost),
+            formatted_value: if total == 0 { "\u{2014}".to_string() } else { format_currency(cur_costs.total_cost, &cost_model.currency) },
+        },
+        cost_by_service: cur_costs.by_service,
+        checklist_completion: MetricResult {
+            value: cur_completion,
+            previous_value: prev_completion,
+            trend: calculate_trend(cur_completion, prev_completion),
+            formatted_value: if cur_checklists.overall.item_count == 0 { "\u{2014}".to_string() } else { format_percentage(cur_completion) },
+        },
         by_severity,
         by_impact,
         by_service: by_svc,
@@ -273,48 +393,64 @@ pub async fn get_dashboard_data(
     })
 }
 
-async fn build_quarterly_trends(db: &SqlitePool, filters: &MetricFilters) -> AppResult<QuarterlyTrends> {
-    // Get last 4 quarters
-    let rows = sqlx::query(
-        "SELECT * FROM quarter_config ORDER BY fiscal_year DESC, quarter_number DESC LIMIT 4"
-    )
-    .fetch_all(db)
-    .await
-    .map_err(|e| AppError::Database(e.to_string()))?;
+async fn build_quarterly_trends(db: &SqlitePool, filters: &MetricFilters, tz: &AnalyticsZone) -> AppResult<QuarterlyTrends> {
+    // Last 4 quarters, oldest first
+    let mut quarters = settings::get_quarter_configs(db).await?;
+    quarters.truncate(4);
+    quarters.reverse();
+    quarter_trends(db, &quarters, filters, tz).await
+}
 
-    let mut quarters = Vec::new();
-    let mut mttr_vals = Vec::new();
-    let mut mtta_vals = Vec::new();
-    let mut count_vals = Vec::new();
-    let mut recurrence_vals = Vec::new();
-    let mut ticket_vals = Vec::new();
-
-    // Reverse so oldest is first
-    let mut quarter_data: Vec<_> = rows.iter().collect();
-    quarter_data.reverse();
-
-    for row in quarter_data {
-        let label: String = row.get::<Option<String>, _>("label").unwrap_or_default();
-        let start: String = row.get::<Option<String>, _>("start_date").unwrap_or_default();
-        let end: String = row.get::<Option<String>, _>("end_date").unwrap_or_default();
-
-        let range = DateRange { start, end };
-        quarters.push(label);
-        mttr_vals.push(calc_mttr(db, &range, filters).await?);
-        mtta_vals.push(calc_mtta(db, &range, filters).await?);
-        count_vals.push(count_incidents(db, &range, filters).await?);
-        recurrence_vals.push(calc_recurrence_rate(db, &range, filters).await?);
-        ticket_vals.push(calc_avg_tickets(db, &range, filters).await?);
-    }
-
-    Ok(QuarterlyTrends {
-        quarters,
-        mttr: mttr_vals,
-        mtta: mtta_vals,
-        incident_count: count_vals,
-        recurrence_rate: recurrence_vals,
-        avg_tickets: ticket_vals,
-    })
+/// Per-quarter metrics for `quarters`, in the order given.
+pub async fn get_quarterly_trends(
+    db: &SqlitePool,
+    quarters: &[QuarterConfig],
+    filters: &MetricFilters,
+) -> AppResult<QuarterlyTrends> {
+    let tz = time_zones::org_time_zone(db).await?;
+    quarter_trends(db, quarters, filters, &tz).await
+}
+
+async fn quarter_trends(
+    db: &SqlitePool,
+    quarters: &[QuarterConfig],
+    filters: &MetricFilters,
+    tz: &AnalyticsZone,
+) -> AppResult<QuarterlyTrends> {
+    let mut trends = QuarterlyTrends {
+        quarters: Vec::new(),
+        mttr: Vec::new(),
+        mtta: Vec::new(),
+        incident_count: Vec::new(),
+        recurrence_rate: Vec::new(),
+        avg_tickets: Vec::new(),
+        severity_counts: Vec::new(),
+    };
+
+    for (idx, quarter) in quarters.iter().enumerate() {
+        let range = DateRange::local_days(&quarter.start_date, &quarter.end_date, tz)?;
+        let period = period_metrics(db, &range, filters, tz).await?;
+        for count in category_counts(db, &range, filters, "severity", tz).await? {
+            let position = trends.severity_counts.iter().position(|t| t.severity == count.category);
+            let i = position.unwrap_or_else(|| {
+                trends.severity_counts.push(SeverityTrend {
+                    severity: count.category.clone(),
+                    counts: vec![0; quarters.len()],
+                });
+                trends.severity_counts.len() - 1
+            });
+            trends.severity_counts[i].counts[idx] = count.count;
+        }
+        trends.quarters.push(quarter.label.clone());
+        trends.mttr.push(period.mttr);
+        trends.mtta.push(period.mtta);
+        trends.incident_count.push(period.count);
+        trends.recurrence_rate.push(period.recurrence_rate);
+        trends.avg_tickets.push(period.avg_tickets);
+    }
+    trends.severity_counts.sort_by_key(|t| severity_rank(&t.severity));
+
+    Ok(trends)
 }
 
 /// Backlog aging: open incidents grouped by how long they've been open
@@ -369,83 +505,6 @@ pub async fn get_backlog_aging(db: &SqlitePool) -> AppResult<Vec<BacklogAgingBuc
     Ok(result)
 }
 
-/// Service reliability scorecard: per-service health metrics
-pub async fn get_service_reliability(
-    db: &SqlitePool,
-    range: &DateRange,
-) -> AppResult<Vec<ServiceReliabilityScore>> {
-    let rows = sqlx::query(
-        "SELECT
-            i.service_id,
-            s.name as service_name,
-            COUNT(*) as incident_count,
-            AVG(COALESCE(i.duration_minutes, 0)) as avg_mttr
-        FROM incidents i
-        LEFT JOIN services s ON i.service_id = s.id
-        WHERE i.deleted_at IS NULL
-          AND i.started_at >= ?
-          AND i.started_at <= ?
-        GROUP BY i.service_id, s.name
-        ORDER BY incident_count DESC"
-    )
-    .bind(&range.start)
-    .bind(&range.end)
-    .fetch_all(db)
-    .await
-    .map_err(|e| AppError::Database(e.to_string()))?;
-
-    let mut results = Vec::new();
-    for row in &rows {
-        let service_id: String = row.get::<Option<String>, _>("service_id").unwrap_or_default();
-        let service_name: String = row.get::<Option<String>, _>("service_name").unwrap_or_else(|| "Unknown".to_string());
-        let incident_count: i64 = row.get("incident_count");
-        let mttr_minutes: f64 = row.get::<Option<f64>, _>("avg_mttr").unwrap_or(0.0);
-
-        // Calculate SLA compliance: % of incidents where resolve time was within SLA target
-        let sla_row = sqlx::query(
-            "SELECT
-                COUNT(*) as total,
-                SUM(CASE WHEN i.duration_minutes <= sd.resolve_within_minutes THEN 1 ELSE 0 END) as compliant
-            FROM incidents i
-            JOIN sla_definitions sd ON sd.priority = i.priority
-            WHERE i.deleted_at IS NULL
-              AND i.service_id = ?
-              AND i.started_at >= ?
-              AND i.started_at <= ?
-              AND i.resolved_at IS NOT NULL"
-        )
-        .bind(&service_id)
-        .bind(&range.start)
-        .bind(&range.end)
-        .fetch_optional(db)
-        .await
-        .map_err(|e| AppError::Database(e.to_string()))?;
-
-        let sla_compliance_pct = if let Some(ref sr) = sla_row {
-            let total: i64 = sr.get::<Option<i64>, _>("total").unwrap_or(0);
-            let compliant: i64 = sr.get::<Option<i64>, _>("compliant").unwrap_or(0);
-            if total > 0 {
-                (compliant as f64 / total as f64) * 100.0
-            } else {
-                100.0 // No resolved incidents = 100% compliant
-            }
-        } else {
-            100.0
-        };
-
-        results.push(ServiceReliabilityScore {
-            service_id,
-            service_name,
-            incident_count,
-            mttr_minutes,
-            mttr_formatted: format_minutes(mttr_minutes),
-            sla_compliance_pct,
-        });
-    }
-
-    Ok(results)
-}
-
 /// Escalation funnel: severity distribution with percentages
 pub async fn get_escalation_funnel(
     db: &SqlitePool,
@@ -489,6 +548,270 @@ pub async fn get_escalation_funnel(
     }).collect())
 }
 
+/// Parse the date part of a date or RFC 3339 timestamp string.
+fn parse_date_prefix(value: &str) -> Option<NaiveDate> {
+    value
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// First day of the bucket containing `date`. Weeks start on Monday and
+/// quarters are calendar quarters.
+pub fn bucket_start(date: NaiveDate, bucket: &str) -> NaiveDate {
+    match bucket {
+        "week" => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        "month" => date.with_day(1).unwrap_or(date),
+        "quarter" => {
+            let month = ((date.month() - 1) / 3) * 3 + 1;
+            NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+        }
+        _ => date,
+    }
+}
+
+/// First day of the bucket following the one that starts at `start`.
+pub fn next_bucket_start(start: NaiveDate, bucket: &str) -> NaiveDate {
+    match bucket {
+        "week" => start + Duration::days(7),
+        "month" | "quarter" => {
+            let step = if bucket == "month" { 1 } else { 3 };
+            let months = start.year() * 12 + start.month0() as i32 + step;
+            NaiveDate::from_ymd_opt(months / 12, (months % 12) as u32 + 1, 1).unwrap_or(start)
+        }
+        _ => start + Duration::days(1),
+    }
+}
+
+pub fn bucket_label(start: NaiveDate, bucket: &str) -> String {
+    match bucket {
+        "week" => format!("Week of {}", start.format("%Y-%m-%d")),
+        "month" => start.format("%Y-%m").to_string(),
+        "quarter" => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+        _ => start.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// Build the gap-free list of bucket start dates covering `start..=end`.
+fn build_bucket_starts(start: NaiveDate, end: NaiveDate, bucket: &str) -> AppResult<Vec<NaiveDate>> {
+    let mut starts = Vec::new();
+    let mut cursor = bucket_start(start, bucket);
+    while cursor <= end {
+        if starts.len() >= MAX_TIMESERIES_BUCKETS {
+            return Err(AppError::Validation(format!(
+                "Date range produces more than {} {} buckets; choose a coarser bucket",
+                MAX_TIMESERIES_BUCKETS, bucket
+            )));
+        }
+        starts.push(cursor);
+        cursor = next_bucket_start(cursor, bucket);
+    }
+    Ok(starts)
+}
+
+/// SQL expression producing the per-incident value for a time series metric,
+/// plus any extra condition rows must satisfy to contribute a sample.
+fn timeseries_value_sql(metric: &str) -> AppResult<(&'static str, &'static str)> {
+    match metric {
+        "count" => Ok(("1.0", "")),
+        "mttr" => Ok((
+            "CAST(i.duration_minutes AS REAL)",
+            " AND i.resolved_at IS NOT NULL",
+        )),
+        "mtta" => Ok((
+            "CAST((julianday(COALESCE(i.acknowledged_at, i.responded_at)) - julianday(i.detected_at)) * 1440 AS REAL)",
+            " AND (i.acknowledged_at IS NOT NULL OR i.responded_at IS NOT NULL)",
+        )),
+        "downtime" => Ok((
+            "CAST(COALESCE(i.duration_minutes, CAST((julianday('now') - julianday(i.started_at)) * 1440 AS INTEGER)) AS REAL)",
+            "",
+        )),
+        "affected_users" => Ok(("CAST(COALESCE(i.affected_users, 0) AS REAL)", "")),
+        "tickets" => Ok(("CAST(COALESCE(i.tickets_submitted, 0) AS REAL)", "")),
+        _ => Err(AppError::Validation(format!("Invalid metric: {}", metric))),
+    }
+}
+
+/// SQL (key, label, extra join) for a time series grouping.
+fn timeseries_group_sql(group_by: Option<&str>) -> AppResult<(&'static str, &'static str, &'static str)> {
+    match group_by {
+        None => Ok(("'all'", "'All incidents'", "")),
+        Some("service") => Ok(("i.service_id", "COALESCE(s.name, 'Unknown Service')", "")),
+        Some("severity") => Ok(("i.severity", "i.severity", "")),
+        Some("tier") => Ok(("COALESCE(s.tier, 'Unknown')", "COALESCE(s.tier, 'Unknown')", "")),
+        Some("tag") => Ok((
+            "COALESCE(t.tag, '')",
+            "COALESCE(t.tag, 'Untagged')",
+            " LEFT JOIN incident_tags t ON t.incident_id = i.id",
+        )),
+        Some(other) => Err(AppError::Validation(format!("Invalid group_by: {}", other))),
+    }
+}
+
+/// Averaged metrics divide the bucket sum by its sample count; the rest are totals.
+fn timeseries_is_average(metric: &str) -> bool {
+    matches!(metric, "mttr" | "mtta")
+}
+
+pub(crate) fn severity_rank(severity: &str) -> usize {
+    match severity {
+        "Critical" => 0,
+        "High" => 1,
+        "Medium" => 2,
+        "Low" => 3,
+        _ => 4,
+    }
+}
+
+#[derive(Default)]
+struct SeriesAccumulator {
+    label: String,
+    sums: Vec<f64>,
+    counts: Vec<i64>,
+}
+
+/// Time series of one metric bucketed by day/week/month/quarter, optionally
+/// split into one series per service, severity, tag or tier. Every bucket in
+/// the requested range is present, even when no incidents fall in it.
+pub async fn get_metric_timeseries(
+    db: &SqlitePool,
+    request: &TimeseriesRequest,
+) -> AppResult<MetricTimeseries> {
+    request.validate()?;
+
+    let start = parse_date_prefix(&request.start_date).ok_or_else(|| {
+        AppError::Validation(format!("Invalid start date: {}", request.start_date))
+    })?;
+    let end = parse_date_prefix(&request.end_date).ok_or_else(|| {
+        AppError::Validation(format!("Invalid end date: {}", request.end_date))
+    })?;
+    if end < start {
+        return Err(AppError::Validation("End date must not be before start date".into()));
+    }
+
+    let bucket = request.bucket.as_str();
+    let bucket_starts = build_bucket_starts(start, end, bucket)?;
+    let bucket_index: HashMap<NaiveDate, usize> = bucket_starts
+        .iter()
+        .enumerate()
+        .map(|(idx, d)| (*d, idx))
+        .collect();
+
+    // Buckets are local calendar days in the organization time zone, and the
+    // end date covers its whole day so the final bucket is not empty by construction.
+    let tz = time_zones::org_time_zone(db).await?;
+    let (range_start, range_end) = tz.day_bounds(start, end);
+    let range = DateRange {
+        start: range_start,
+        end: range_end,
+    };
+
+    let (value_sql, value_condition) = timeseries_value_sql(&request.metric)?;
+    let (key_sql, label_sql, extra_join) = timeseries_group_sql(request.group_by.as_deref())?;
+    let (wc, params) = build_where_clause(&range, &request.filters);
+    let sql = format!(
+        "SELECT i.started_at as started_at, {} as group_key, {} as group_label, {} as value FROM incidents i LEFT JOIN services s ON i.service_id = s.id{} WHERE {}{}",
+        key_sql, label_sql, value_sql, extra_join, wc, value_condition
+    );
+
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let rows = query
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let mut accumulators: HashMap<String, SeriesAccumulator> = HashMap::new();
+    for row in &rows {
+        let started_at: String = row.get("started_at");
+        let Some(instant) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        let day = tz.local_date(&instant);
+        let Some(&idx) = bucket_index.get(&bucket_start(day, bucket)) else {
+            continue;
+        };
+        let key: String = row.get::<Option<String>, _>("group_key").unwrap_or_default();
+        let label: String = row.get::<Option<String>, _>("group_label").unwrap_or_default();
+        let value: f64 = row.get::<Option<f64>, _>("value").unwrap_or(0.0);
+
+        let acc = accumulators.entry(key).or_insert_with(|| SeriesAccumulator {
+            label,
+            sums: vec![0.0; bucket_starts.len()],
+            counts: vec![0; bucket_starts.len()],
+        });
+        acc.sums[idx] += value;
+        acc.counts[idx] += 1;
+    }
+
+    // An ungrouped series always exists so charts have a zero line to draw.
+    if request.group_by.is_none() && accumulators.is_empty() {
+        accumulators.insert(
+            "all".to_string(),
+            SeriesAccumulator {
+                label: "All incidents".to_string(),
+                sums: vec![0.0; bucket_starts.len()],
+                counts: vec![0; bucket_starts.len()],
+            },
+        );
+    }
+
+    let average = timeseries_is_average(&request.metric);
+    let mut series: Vec<TimeseriesSeries> = accumulators
+        .into_iter()
+        .map(|(key, acc)| {
+            let values = acc
+                .sums
+                .iter()
+                .zip(&acc.counts)
+                .map(|(sum, n)| {
+                    if average {
+                        if *n > 0 { sum / *n as f64 } else { 0.0 }
+                    } else {
+                        *sum
+                    }
+                })
+                .collect();
+            TimeseriesSeries {
+                key,
+                label: acc.label,
+                values,
+                sample_counts: acc.counts,
+            }
+        })
+        .collect();
+
+    if request.group_by.as_deref() == Some("severity") {
+        series.sort_by_key(|s| severity_rank(&s.key));
+    } else {
+        series.sort_by(|a, b| {
+            let a_total: i64 = a.sample_counts.iter().sum();
+            let b_total: i64 = b.sample_counts.iter().sum();
+            b_total.cmp(&a_total).then_with(|| a.label.cmp(&b.label))
+        });
+    }
+
+    let buckets = bucket_starts
+        .iter()
+        .map(|s| TimeseriesBucket {
+            start: s.format("%Y-%m-%d").to_string(),
+            end: (next_bucket_start(*s, bucket) - Duration::days(1))
+                .format("%Y-%m-%d")
+                .to_string(),
+            label: bucket_label(*s, bucket),
+        })
+        .collect();
+
+    Ok(MetricTimeseries {
+        metric: request.metric.clone(),
+        bucket: request.bucket.clone(),
+        group_by: request.group_by.clone(),
+        buckets,
+        series,
+    })
+}
+
 // Exported function to get dashboard data by quarter ID
 pub async fn get_dashboard_data_for_quarter(
     db: &SqlitePool,
@@ -509,7 +832,9 @@ pub async fn get_dashboard_data_for_quarter(
         let fy: i64 = q.get::<i64, _>("fiscal_year");
         let qn: i64 = q.get::<i64, _>("quarter_number");
 
-        let current_range = DateRange { start, end };
+        // Quarter boundaries are whole local days in the organization time zone
+        let tz = time_zones::org_time_zone(db).await?;
+        let current_range = DateRange::local_days(&start, &end, &tz)?;
 
         // Find previous quarter
         let prev_q = if qn == 1 { 4 } else { qn - 1 };
@@ -523,15 +848,20 @@ pub async fn get_dashboard_data_for_quarter(
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
 
-        let previous_range = prev_row.map(|pr| DateRange {
-            start: pr.get::<Option<String>, _>("start_date").unwrap_or_default(),
-            end: pr.get::<Option<String>, _>("end_date").unwrap_or_default(),
-        });
+        let previous_range = match prev_row {
+            Some(pr) => Some(DateRange::local_days(
+                &pr.get::<Option<String>, _>("start_date").unwrap_or_default(),
+                &pr.get::<Option<String>, _>("end_date").unwrap_or_default(),
+                &tz,
+            )?),
+            None => None,
+        };
 
         get_dashboard_data(db, &current_range, previous_range.as_ref(), filters, &label).await
     } else {
-        // No quarter specified -- use current quarter based on today's date
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        // No quarter specified -- use current quarter based on today's local date
+        let tz = time_zones::org_time_zone(db).await?;
+        let today = tz.local_date(&chrono::Utc::now()).format("%Y-%m-%d").to_string();
         let row = sqlx::query(
             "SELECT * FROM quarter_config WHERE start_date <= ? AND end_date >= ? LIMIT 1"
         )
@@ -552,6 +882,9 @@ pub async fn get_dashboard_data_for_quarter(
                 mtta: MetricResult::no_data(),
                 recurrence_rate: MetricResult::no_data(),
                 avg_tickets: MetricResult::no_data(),
+                estimated_cost: MetricResult::no_data(),
+                cost_by_service: vec![],
+                checklist_completion: MetricResult::no_data(),
                 by_severity: vec![],
                 by_impact: vec![],
                 by_service: vec![],
@@ -563,6 +896,7 @@ pub async fn get_dashboard_data_for_quarter(
                     incident_count: vec![],
                     recurrence_rate: vec![],
                     avg_tickets: vec![],
+                    severity_counts: vec![],
                 },
                 total_incidents: 0,
                 period_label: "No quarter configured".to_string(),
@@ -733,6 +1067,257 @@ mod tests {
         assert!(!trend.is_empty());
     }
 
+    /// Test: week buckets start on Monday, quarters on calendar quarter boundaries
+    #[test]
+    fn test_bucket_start_week_and_quarter() {
+        let sunday = NaiveDate::from_ymd_opt(2025, 1, 19).unwrap();
+        assert_eq!(bucket_start(sunday, "week"), NaiveDate::from_ymd_opt(2025, 1, 13).unwrap());
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+        assert_eq!(bucket_start(monday, "week"), monday);
+
+        let aug = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap();
+        assert_eq!(bucket_start(aug, "quarter"), NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(bucket_label(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(), "quarter"), "2025-Q3");
+    }
+
+    /// Test: month and quarter stepping crosses year boundaries
+    #[test]
+    fn test_next_bucket_start_crosses_year() {
+        let dec = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+        assert_eq!(next_bucket_start(dec, "month"), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        let q4 = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        assert_eq!(next_bucket_start(q4, "quarter"), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    }
+
+    /// Test: bucket list is gap-free and covers a partial first bucket
+    #[test]
+    fn test_build_bucket_starts_gap_free() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 4, 2).unwrap();
+        let months = build_bucket_starts(start, end, "month").unwrap();
+        assert_eq!(months.len(), 4);
+        assert_eq!(months[0], NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        assert_eq!(months[3], NaiveDate::from_ymd_opt(2025, 4, 1).unwrap());
+    }
+
+    /// Test: oversized ranges are rejected instead of producing huge payloads
+    #[test]
+    fn test_build_bucket_starts_rejects_too_many_buckets() {
+        let start = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert!(build_bucket_starts(start, end, "day").is_err());
+        assert!(build_bucket_starts(start, end, "month").is_ok());
+    }
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("metrics-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, service_id: &str, severity: &str, started_at: &str, resolved_at: Option<&str>) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at, affected_users) VALUES (?, ?, ?, ?, 'Medium', ?, ?, ?, ?, 10)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(severity)
+        .bind(if resolved_at.is_some() { "Resolved" } else { "Active" })
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    /// Test: timeseries fills empty buckets and includes incidents on the end date
+    #[tokio::test]
+    async fn test_metric_timeseries_gap_fills_buckets() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "ts-1", "svc-slack", "High", "2025-01-01T10:00:00Z", Some("2025-01-01T11:00:00Z")).await;
+        insert_incident(&db, "ts-2", "svc-slack", "High", "2025-01-03T10:00:00Z", Some("2025-01-03T10:30:00Z")).await;
+
+        let request = TimeseriesRequest {
+            metric: "count".into(),
+            bucket: "day".into(),
+            group_by: None,
+            start_date: "2025-01-01".into(),
+            end_date: "2025-01-03".into(),
+            filters: MetricFilters::default(),
+        };
+        let ts = get_metric_timeseries(&db, &request).await.expect("timeseries");
+        assert_eq!(ts.buckets.len(), 3);
+        assert_eq!(ts.series.len(), 1);
+        assert_eq!(ts.series[0].values, vec![1.0, 0.0, 1.0]);
+
+        let request = TimeseriesRequest { metric: "mttr".into(), bucket: "month".into(), ..request };
+        let ts = get_metric_timeseries(&db, &request).await.expect("timeseries");
+        assert_eq!(ts.series[0].values, vec![45.0]);
+        assert_eq!(ts.series[0].sample_counts, vec![2]);
+    }
+
+    /// Test: grouped timeseries returns one series per group, severity-ordered
+    #[tokio::test]
+    async fn test_metric_timeseries_groups_by_severity() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "ts-1", "svc-slack", "Low", "2025-02-03T10:00:00Z", None).await;
+        insert_incident(&db, "ts-2", "svc-zoom", "Critical", "2025-02-10T10:00:00Z", None).await;
+        insert_incident(&db, "ts-3", "svc-zoom", "Critical", "2025-02-11T10:00:00Z", None).await;
+
+        let request = TimeseriesRequest {
+            metric: "affected_users".into(),
+            bucket: "week".into(),
+            group_by: Some("severity".into()),
+            start_date: "2025-02-03".into(),
+            end_date: "2025-02-16".into(),
+            filters: MetricFilters::default(),
+        };
+        let ts = get_metric_timeseries(&db, &request).await.expect("timeseries");
+        assert_eq!(ts.buckets.len(), 2);
+        assert_eq!(ts.series.len(), 2);
+        assert_eq!(ts.series[0].key, "Critical");
+        assert_eq!(ts.series[0].values, vec![0.0, 20.0]);
+        assert_eq!(ts.series[1].key, "Low");
+        assert_eq!(ts.series[1].values, vec![10.0, 0.0]);
+    }
+
+    /// Test: quarterly trends count each severity per quarter, severity-ordered
+    #[tokio::test]
+    async fn test_quarterly_trends_severity_counts() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "qt-1", "svc-slack", "Low", "2025-02-03T10:00:00Z", None).await;
+        insert_incident(&db, "qt-2", "svc-zoom", "Critical", "2025-05-10T10:00:00Z", None).await;
+        insert_incident(&db, "qt-3", "svc-zoom", "Low", "2025-05-11T10:00:00Z", None).await;
+
+        let quarter = |n: i64, start: &str, end: &str| QuarterConfig {
+            id: format!("q{}", n),
+            fiscal_year: 2025,
+            quarter_number: n,
+            start_date: start.into(),
+            end_date: end.into(),
+            label: format!("Q{} FY25", n),
+            created_at: String::new(),
+        };
+        let quarters = [quarter(1, "2025-01-01", "2025-03-31"), quarter(2, "2025-04-01", "2025-06-30")];
+        rollups::refresh_dirty_rollups(&db).await.expect("refresh");
+        let trends = get_quarterly_trends(&db, &quarters, &MetricFilters::default()).await.expect("trends");
+        assert_eq!(trends.incident_count, vec![1, 2]);
+        let counts: Vec<(&str, &[i64])> = trends
+            .severity_counts
+            .iter()
+            .map(|t| (t.severity.as_str(), t.counts.as_slice()))
+            .collect();
+        assert_eq!(counts, vec![("Critical", &[0, 1][..]), ("Low", &[1, 1][..])]);
+    }
+
+    /// Test: unknown metric names are rejected before any SQL is built
+    #[tokio::test]
+    async fn test_metric_timeseries_rejects_unknown_metric() {
+        let (_dir, db) = setup_db().await;
+        let request = TimeseriesRequest {
+            metric: "duration; DROP TABLE incidents".into(),
+            bucket: "day".into(),
+            group_by: None,
+            start_date: "2025-01-01".into(),
+            end_date: "2025-01-03".into(),
+            filters: MetricFilters::default(),
+        };
+        assert!(matches!(
+            get_metric_timeseries(&db, &request).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    /// Test: rollup-backed period metrics agree with the raw queries
+    #[tokio::test]
+    async fn test_period_metrics_rollups_match_raw() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "pm-1", "svc-slack", "High", "2025-01-02T10:00:00Z", Some("2025-01-02T10:45:00Z")).await;
+        insert_incident(&db, "pm-2", "svc-slack", "Critical", "2025-01-15T08:00:00Z", Some("2025-01-15T12:00:00Z")).await;
+        insert_incident(&db, "pm-3", "svc-zoom", "Low", "2025-01-20T23:30:00Z", None).await;
+        insert_incident(&db, "pm-4", "svc-zoom", "High", "2025-01-31T09:00:00Z", Some("2025-01-31T09:30:00Z")).await;
+        sqlx::query("UPDATE incidents SET acknowledged_at = '2025-01-02T10:07:00Z', is_recurring = 1, tickets_submitted = 5 WHERE id = 'pm-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE incidents SET responded_at = '2025-01-15T08:20:00Z' WHERE id = 'pm-2'")
+            .execute(&db)
+            .await
+            .unwrap();
+        rollups::refresh_dirty_rollups(&db).await.unwrap();
+
+        let tz = AnalyticsZone::utc();
+        let range = DateRange::local_days("2025-01-01", "2025-01-31", &tz).unwrap();
+        for filters in [
+            MetricFilters::default(),
+            MetricFilters {
+                service_ids: Some(vec!["svc-slack".into()]),
+                ..Default::default()
+            },
+        ] {
+            assert!(rollups::local_days(&range, &tz).is_some());
+            let fast = period_metrics(&db, &range, &filters, &tz).await.unwrap();
+            assert_eq!(fast.count, count_incidents(&db, &range, &filters).await.unwrap());
+            assert!((fast.mttr - calc_mttr(&db, &range, &filters).await.unwrap()).abs() < 1e-6);
+            assert!((fast.mtta - calc_mtta(&db, &range, &filters).await.unwrap()).abs() < 1e-6);
+            assert!((fast.recurrence_rate - calc_recurrence_rate(&db, &range, &filters).await.unwrap()).abs() < 1e-6);
+            assert!((fast.avg_tickets - calc_avg_tickets(&db, &range, &filters).await.unwrap()).abs() < 1e-6);
+
+            let mut fast_sev = category_counts(&db, &range, &filters, "severity", &tz).await.unwrap();
+            let mut raw_sev = incidents_by_category(&db, &range, &filters, "severity").await.unwrap();
+            fast_sev.sort_by(|a, b| a.category.cmp(&b.category));
+            raw_sev.sort_by(|a, b| a.category.cmp(&b.category));
+            let pairs = |v: &[CategoryCount]| v.iter().map(|c| (c.category.clone(), c.count)).collect::<Vec<_>>();
+            assert_eq!(pairs(&fast_sev), pairs(&raw_sev));
+
+            let mut fast_svc = category_counts(&db, &range, &filters, "service", &tz).await.unwrap();
+            let mut raw_svc = incidents_by_service(&db, &range, &filters).await.unwrap();
+            fast_svc.sort_by(|a, b| a.category.cmp(&b.category));
+            raw_svc.sort_by(|a, b| a.category.cmp(&b.category));
+            assert_eq!(pairs(&fast_svc), pairs(&raw_svc));
+        }
+    }
+
+    /// Test: dashboard data reflects incident edits made after a previous read
+    #[tokio::test]
+    async fn test_dashboard_data_sees_incremental_changes() {
+        let (_dir, db) = setup_db().await;
+        let range = DateRange {
+            start: "2025-01-01".into(),
+            end: "2025-01-31".into(),
+        };
+        insert_incident(&db, "dd-1", "svc-slack", "High", "2025-01-05T10:00:00Z", None).await;
+        let first = get_dashboard_data(&db, &range, None, &MetricFilters::default(), "Jan").await.unwrap();
+        assert_eq!(first.total_incidents, 1);
+
+        insert_incident(&db, "dd-2", "svc-zoom", "Low", "2025-01-06T10:00:00Z", None).await;
+        sqlx::query("UPDATE incidents SET deleted_at = '2025-02-01T00:00:00Z' WHERE id = 'dd-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let second = get_dashboard_data(&db, &range, None, &MetricFilters::default(), "Jan").await.unwrap();
+        assert_eq!(second.total_incidents, 1);
+        assert_eq!(second.by_severity.len(), 1);
+        assert_eq!(second.by_severity[0].category, "Low");
+    }
+
     /// Test: MetricResult::no_data() returns sensible defaults
     #[test]
     fn test_metric_result_no_data() {
@@ -1,12 +1,21 @@
 pub mod audit;
 pub mod checklists;
+pub mod costs;
 pub mod custom_fields;
 pub mod dashboard;
+pub mod discussion_rules;
+pub mod fatigue;
+pub mod forecasts;
 pub mod incidents;
 pub mod metrics;
+pub mod redaction;
+pub mod reliability;
 pub mod report_history;
+pub mod report_templates;
+pub mod report_theme;
 pub mod postmortems;
 pub mod roles;
+pub mod rollups;
 pub mod service_dependencies;
 pub mod saved_filters;
 pub mod services;
@@ -15,3 +24,4 @@ pub mod shift_handoffs;
 pub mod sla;
 pub mod stakeholder_updates;
 pub mod tags;
+pub mod time_zones;
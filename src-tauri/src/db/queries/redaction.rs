@@ -0,0 +1,114 @@
+use sqlx::{Row, SqlitePool};
+
+use crate::db::queries::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::redaction::RedactionProfile;
+
+pub const REDACTION_PROFILE_SETTING_KEY: &str = "redaction_profile";
+
+pub async fn get_redaction_profile(db: &SqlitePool) -> AppResult<RedactionProfile> {
+    match settings::get_setting(db, REDACTION_PROFILE_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored redaction profile: {}", e))),
+        None => Ok(RedactionProfile::default()),
+    }
+}
+
+pub async fn save_redaction_profile(
+    db: &SqlitePool,
+    profile: &RedactionProfile,
+) -> AppResult<RedactionProfile> {
+    let json = serde_json::to_string(profile)?;
+    settings::set_setting(db, REDACTION_PROFILE_SETTING_KEY, &json).await?;
+    Ok(profile.clone())
+}
+
+/// Distinct names of incident role assignees and action-item owners, sorted
+/// case-insensitively; the people a redaction profile pseudonymizes.
+pub async fn list_people(db: &SqlitePool) -> AppResult<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT TRIM(assignee) AS name FROM incident_roles WHERE TRIM(assignee) != '' \
+         UNION SELECT TRIM(owner) FROM action_items WHERE TRIM(COALESCE(owner, '')) != '' \
+         ORDER BY name COLLATE NOCASE",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let mut names: Vec<String> = Vec::new();
+    for row in &rows {
+        let name: String = row.get("name");
+        if !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::redaction::PeopleRedaction;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("redaction-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: the profile round-trips through settings and people are listed once each
+    #[tokio::test]
+    async fn test_profile_and_people() {
+        let (_dir, db) = setup_db().await;
+        assert_eq!(get_redaction_profile(&db).await.unwrap(), RedactionProfile::default());
+
+        let profile = RedactionProfile {
+            people: PeopleRedaction::Drop,
+            mask_patterns: vec![r"\d+\.\d+\.\d+\.\d+".into()],
+            ..Default::default()
+        };
+        save_redaction_profile(&db, &profile).await.unwrap();
+        assert_eq!(get_redaction_profile(&db).await.unwrap(), profile);
+
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at) \
+             VALUES ('inc-1', 'Outage', 'svc-slack', 'High', 'High', 'Active', '2026-03-10T10:00:00Z', '2026-03-10T10:00:00Z')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO incident_roles (id, incident_id, role, assignee) VALUES \
+             ('r1', 'inc-1', 'Incident Commander', 'bob'), ('r2', 'inc-1', 'Scribe', ' Alice ')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO action_items (id, incident_id, title, status, owner) VALUES \
+             ('a1', 'inc-1', 'Fix', 'Open', 'Bob'), ('a2', 'inc-1', 'Docs', 'Open', '')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        let people = list_people(&db).await.unwrap();
+        assert_eq!(people.len(), 2);
+        assert_eq!(people[0], "Alice");
+        assert!(people[1].eq_ignore_ascii_case("bob"));
+    }
+}
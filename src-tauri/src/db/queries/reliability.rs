@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sqlx::{Row, SqlitePool};
+
+use crate::db::queries::metrics::{resolve_date_range, DateRange};
+use crate::db::queries::{settings, sla, time_zones};
+use crate::error::{AppError, AppResult};
+use crate::models::metrics::{format_minutes, ServiceReliabilityScore};
+use crate::models::quarter::QuarterConfig;
+use crate::models::reliability::{
+    score_service, ReliabilityHistoryEntry, ReliabilityScoringConfig, ScoreBreakdown, ScoreInputs,
+    MAX_SCORE,
+};
+
+pub const RELIABILITY_SCORING_SETTING_KEY: &str = "reliability_scoring";
+
+pub async fn get_scoring_config(db: &SqlitePool) -> AppResult<ReliabilityScoringConfig> {
+    match settings::get_setting(db, RELIABILITY_SCORING_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored reliability scoring: {}", e))),
+        None => Ok(ReliabilityScoringConfig::default()),
+    }
+}
+
+pub async fn save_scoring_config(
+    db: &SqlitePool,
+    config: &ReliabilityScoringConfig,
+) -> AppResult<ReliabilityScoringConfig> {
+    let json = serde_json::to_string(config)?;
+    settings::set_setting(db, RELIABILITY_SCORING_SETTING_KEY, &json).await?;
+    Ok(config.clone())
+}
+
+#[derive(Default)]
+struct ServiceTally {
+    name: String,
+    inputs: ScoreInputs,
+    duration_sum: f64,
+    sla_checked: i64,
+}
+
+/// Per-service reliability for incidents started in `range`, least reliable first.
+pub async fn get_service_reliability(
+    db: &SqlitePool,
+    range: &DateRange,
+) -> AppResult<Vec<ServiceReliabilityScore>> {
+    let config = get_scoring_config(db).await?;
+    score_services(db, range, &config).await
+}
+
+async fn score_services(
+    db: &SqlitePool,
+    range: &DateRange,
+    config: &ReliabilityScoringConfig,
+) -> AppResult<Vec<ServiceReliabilityScore>> {
+    // Resolve targets by priority; priority itself is derived from severity and impact
+    let sla_targets: HashMap<String, i64> = sqlx::query(
+        "SELECT priority, resolve_time_minutes FROM sla_definitions WHERE is_active = 1",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?
+    .iter()
+    .map(|row| (row.get("priority"), row.get("resolve_time_minutes")))
+    .collect();
+
+    let rows = sqlx::query(
+        "SELECT i.service_id, COALESCE(s.name, 'Unknown') as service_name, \
+         COALESCE(s.tier, 'T3') as tier, i.severity, i.impact, i.duration_minutes, \
+         i.resolved_at, i.is_recurring \
+         FROM incidents i \
+         LEFT JOIN services s ON i.service_id = s.id \
+         WHERE i.deleted_at IS NULL AND i.started_at >= ? AND i.started_at <= ? \
+         ORDER BY i.started_at",
+    )
+    .bind(&range.start)
+    .bind(&range.end)
+    .fetch_all(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let mut order: Vec<String> = vec![];
+    let mut tallies: HashMap<String, ServiceTally> = HashMap::new();
+    for row in &rows {
+        let service_id: String = row.get("service_id");
+        let tally = tallies.entry(service_id.clone()).or_insert_with(|| {
+            order.push(service_id.clone());
+            ServiceTally {
+                name: row.get("service_name"),
+                inputs: ScoreInputs {
+                    tier: row.get("tier"),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        });
+
+        let severity: String = row.get("severity");
+        let impact: String = row.get("impact");
+        let target = sla_targets.get(&sla::compute_priority(&severity, &impact)).copied();
+        let duration = row.get::<Option<i64>, _>("duration_minutes").unwrap_or(0) as f64;
+        tally.duration_sum += duration;
+        tally.inputs.incidents.push((severity, impact));
+        if row.get::<bool, _>("is_recurring") {
+            tally.inputs.recurring_count += 1;
+        }
+        let resolved = row.get::<Option<String>, _>("resolved_at").is_some();
+        if resolved {
+            tally.inputs.downtime_minutes += duration;
+        }
+        if let (true, Some(target)) = (resolved, target) {
+            tally.sla_checked += 1;
+            if duration > target as f64 {
+                tally.inputs.sla_breaches += 1;
+            }
+        }
+    }
+
+    let mut results: Vec<ServiceReliabilityScore> = order
+        .into_iter()
+        .filter_map(|id| tallies.remove(&id).map(|t| (id, t)))
+        .map(|(service_id, tally)| {
+            let incident_count = tally.inputs.incidents.len() as i64;
+            let mttr_minutes = tally.duration_sum / incident_count as f64;
+            let sla_compliance_pct = if tally.sla_checked > 0 {
+                (tally.sla_checked - tally.inputs.sla_breaches) as f64 / tally.sla_checked as f64
+                    * 100.0
+            } else {
+                100.0 // No resolved incidents = 100% compliant
+            };
+            let (score, breakdown) = score_service(&tally.inputs, config);
+            ServiceReliabilityScore {
+                service_id,
+                service_name: tally.name,
+                tier: tally.inputs.tier,
+                incident_count,
+                mttr_minutes,
+                mttr_formatted: format_minutes(mttr_minutes),
+                sla_compliance_pct,
+                sla_breaches: tally.inputs.sla_breaches,
+                downtime_minutes: tally.inputs.downtime_minutes,
+                recurring_count: tally.inputs.recurring_count,
+                score,
+                breakdown,
+            }
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        a.score
+            .partial_cmp(&b.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.incident_count.cmp(&a.incident_count))
+            .then_with(|| a.service_name.cmp(&b.service_name))
+    });
+    Ok(results)
+}
+
+fn history_entry(quarter: &QuarterConfig, score: &ServiceReliabilityScore, is_final: bool, computed_at: String) -> ReliabilityHistoryEntry {
+    ReliabilityHistoryEntry {
+        quarter_id: quarter.id.clone(),
+        quarter_label: quarter.label.clone(),
+        service_id: score.service_id.clone(),
+        service_name: score.service_name.clone(),
+        score: score.score,
+        incident_count: score.incident_count,
+        breakdown: score.breakdown.clone(),
+        is_final,
+        computed_at,
+    }
+}
+
+/// Freeze the scores of a closed quarter, unless they already are.
+async fn ensure_quarter_recorded(
+    db: &SqlitePool,
+    quarter: &QuarterConfig,
+    config: &ReliabilityScoringConfig,
+) -> AppResult<()> {
+    let recorded: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM reliability_score_history WHERE quarter_id = ?)",
+    )
+    .bind(&quarter.id)
+    .fetch_one(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+    if recorded {
+        return Ok(());
+    }
+
+    let range = resolve_date_range(db, &quarter.start_date, &quarter.end_date).await?;
+    for score in score_services(db, &range, config).await? {
+        let breakdown = serde_json::to_string(&score.breakdown)?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO reliability_score_history (quarter_id, service_id, score, incident_count, breakdown) \
+             SELECT ?, ?, ?, ?, ? WHERE EXISTS(SELECT 1 FROM services WHERE id = ?)",
+        )
+        .bind(&quarter.id)
+        .bind(&score.service_id)
+        .bind(score.score)
+        .bind(score.incident_count)
+        .bind(&breakdown)
+        .bind(&score.service_id)
+        .execute(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Reliability scores per quarter, oldest first. Closed quarters are
+/// recorded once and then read back unchanged; the quarter in progress is
+/// scored live. With a `service_id`, quarters without incidents are filled
+/// in at the full score.
+pub async fn get_reliability_history(
+    db: &SqlitePool,
+    service_id: Option<&str>,
+) -> AppResult<Vec<ReliabilityHistoryEntry>> {
+    let config = get_scoring_config(db).await?;
+    let tz = time_zones::org_time_zone(db).await?;
+    let today = tz.local_date(&Utc::now()).format("%Y-%m-%d").to_string();
+
+    let mut quarters = settings::get_quarter_configs(db).await?;
+    quarters.retain(|q| q.start_date.as_str() <= today.as_str());
+    quarters.sort_by_key(|q| (q.fiscal_year, q.quarter_number));
+
+    let service = match service_id {
+        Some(id) => Some(
+            sqlx::query("SELECT name, tier FROM services WHERE id = ?")
+                .bind(id)
+                .fetch_optional(db)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?
+                .ok_or_else(|| AppError::NotFound(format!("Service '{}' not found", id)))?,
+        ),
+        None => None,
+    };
+
+    let mut history = vec![];
+    for quarter in &quarters {
+        let mut entries = if quarter.end_date.as_str() < today.as_str() {
+            ensure_quarter_recorded(db, quarter, &config).await?;
+            let rows = sqlx::query(
+                "SELECT h.*, COALESCE(s.name, 'Unknown') as service_name \
+                 FROM reliability_score_history h \
+                 LEFT JOIN services s ON h.service_id = s.id \
+                 WHERE h.quarter_id = ? ORDER BY h.score",
+            )
+            .bind(&quarter.id)
+            .fetch_all(db)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            rows.iter()
+                .map(|row| ReliabilityHistoryEntry {
+                    quarter_id: quarter.id.clone(),
+                    quarter_label: quarter.label.clone(),
+                    service_id: row.get("service_id"),
+                    service_name: row.get("service_name"),
+                    score: row.get("score"),
+                    incident_count: row.get("incident_count"),
+                    breakdown: serde_json::from_str(&row.get::<String, _>("breakdown"))
+                        .unwrap_or_default(),
+                    is_final: true,
+                    computed_at: row.get("computed_at"),
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let range = resolve_date_range(db, &quarter.start_date, &quarter.end_date).await?;
+            let computed_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+            score_services(db, &range, &config)
+                .await?
+                .iter()
+                .map(|score| history_entry(quarter, score, false, computed_at.clone()))
+                .collect()
+        };
+
+        if let (Some(id), Some(row)) = (service_id, service.as_ref()) {
+            entries.retain(|e| e.service_id == id);
+            if entries.is_empty() {
+                let tier: String = row.get("tier");
+                entries.push(ReliabilityHistoryEntry {
+                    quarter_id: quarter.id.clone(),
+                    quarter_label: quarter.label.clone(),
+                    service_id: id.to_string(),
+                    service_name: row.get("name"),
+                    score: MAX_SCORE,
+                    incident_count: 0,
+                    breakdown: ScoreBreakdown {
+                        tier_multiplier: config.tier_multiplier(&tier),
+                        tier,
+                        ..Default::default()
+                    },
+                    is_final: quarter.end_date.as_str() < today.as_str(),
+                    computed_at: String::new(),
+                });
+            }
+        }
+        history.extend(entries);
+    }
+    Ok(history)
+}
+
+/// Drop recorded history so closed quarters are rescored with the current model.
+pub async fn clear_reliability_history(db: &SqlitePool) -> AppResult<u64> {
+    let result = sqlx::query("DELETE FROM reliability_score_history")
+        .execute(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timezone::AnalyticsZone;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("reliability-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(
+        db: &SqlitePool,
+        id: &str,
+        service_id: &str,
+        severity: &str,
+        impact: &str,
+        started_at: &str,
+        resolved_at: &str,
+    ) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) VALUES (?, ?, ?, ?, ?, 'Resolved', ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(severity)
+        .bind(impact)
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    fn january() -> DateRange {
+        DateRange::local_days("2025-01-01", "2025-01-31", &AnalyticsZone::utc()).unwrap()
+    }
+
+    /// Test: tier and severity drive the ranking, and SLA breaches use resolve_time_minutes
+    #[tokio::test]
+    async fn test_service_reliability_scoring() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query("UPDATE services SET tier = 'T1' WHERE id = 'svc-slack'").execute(&db).await.unwrap();
+        sqlx::query("UPDATE services SET tier = 'T4' WHERE id = 'svc-zoom'").execute(&db).await.unwrap();
+
+        // One long Critical incident on a T1 service, far beyond any SLA target
+        insert_incident(&db, "r-1", "svc-slack", "Critical", "Critical", "2025-01-10T10:00:00Z", "2025-01-11T10:00:00Z").await;
+        // Two short Low incidents on a T4 service
+        insert_incident(&db, "r-2", "svc-zoom", "Low", "Low", "2025-01-12T10:00:00Z", "2025-01-12T10:10:00Z").await;
+        insert_incident(&db, "r-3", "svc-zoom", "Low", "Low", "2025-01-13T10:00:00Z", "2025-01-13T10:10:00Z").await;
+
+        let scores = get_service_reliability(&db, &january()).await.unwrap();
+        assert_eq!(scores.len(), 2);
+        let slack = &scores[0];
+        assert_eq!(slack.service_id, "svc-slack");
+        assert_eq!(slack.tier, "T1");
+        assert_eq!(slack.sla_breaches, 1);
+        assert_eq!(slack.sla_compliance_pct, 0.0);
+        assert!((slack.downtime_minutes - 1440.0).abs() < 1e-9);
+        assert!((slack.breakdown.tier_multiplier - 2.0).abs() < 1e-9);
+        assert_eq!(slack.breakdown.by_severity[0].severity, "Critical");
+
+        let zoom = &scores[1];
+        assert_eq!(zoom.incident_count, 2);
+        assert_eq!(zoom.sla_breaches, 0);
+        assert!(zoom.score > slack.score);
+
+        // A heavier tier multiplier for T4 changes the outcome
+        let mut config = ReliabilityScoringConfig::default();
+        config.tier_multipliers.insert("T4".into(), 100.0);
+        save_scoring_config(&db, &config).await.unwrap();
+        let scores = get_service_reliability(&db, &january()).await.unwrap();
+        assert_eq!(scores[0].service_id, "svc-zoom");
+    }
+
+    /// Test: closed quarters are frozen while the history is read repeatedly
+    #[tokio::test]
+    async fn test_reliability_history_is_recorded_per_quarter() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query("DELETE FROM quarter_config").execute(&db).await.unwrap();
+        sqlx::query(
+            "INSERT INTO quarter_config (id, fiscal_year, quarter_number, start_date, end_date, label) VALUES \
+             ('q-old', 2025, 1, '2025-01-01', '2025-03-31', 'Q1 FY25')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        insert_incident(&db, "r-1", "svc-slack", "High", "High", "2025-02-10T10:00:00Z", "2025-02-10T11:00:00Z").await;
+
+        let history = get_reliability_history(&db, None).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].is_final);
+        let frozen = history[0].score;
+
+        // Later model changes do not rewrite a recorded quarter
+        let config = ReliabilityScoringConfig {
+            downtime_penalty_per_hour: 50.0,
+            ..Default::default()
+        };
+        save_scoring_config(&db, &config).await.unwrap();
+        let history = get_reliability_history(&db, Some("svc-slack")).await.unwrap();
+        assert_eq!(history[0].score, frozen);
+
+        // ...until history is cleared
+        assert_eq!(clear_reliability_history(&db).await.unwrap(), 1);
+        let history = get_reliability_history(&db, Some("svc-slack")).await.unwrap();
+        assert!(history[0].score < frozen);
+
+        // Quiet services get a full score for the quarter
+        let zoom = get_reliability_history(&db, Some("svc-zoom")).await.unwrap();
+        assert_eq!(zoom.len(), 1);
+        assert_eq!(zoom[0].score, MAX_SCORE);
+        assert_eq!(zoom[0].incident_count, 0);
+    }
+}
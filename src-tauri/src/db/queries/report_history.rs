@@ -6,7 +6,8 @@ use crate::models::report_history::ReportHistory;
 
 pub async fn list_report_history(db: &SqlitePool) -> AppResult<Vec<ReportHistory>> {
     let records = sqlx::query_as::<_, ReportHistory>(
-        "SELECT id, title, quarter_id, format, generated_at, file_path, config_json, file_size_bytes
+        "SELECT id, title, quarter_id, format, generated_at, file_path, config_json, file_size_bytes,
+                snapshot_version, artifact_sha256, content_sha256
          FROM report_history
          ORDER BY generated_at DESC"
     )
@@ -41,15 +42,59 @@ pub async fn insert_report_history(
     .await
     .map_err(|e| AppError::Database(e.to_string()))?;
 
-    let record = sqlx::query_as::<_, ReportHistory>(
-        "SELECT id, title, quarter_id, format, generated_at, file_path, config_json, file_size_bytes
+    get_report_history(db, &id).await
+}
+
+pub async fn get_report_history(db: &SqlitePool, id: &str) -> AppResult<ReportHistory> {
+    sqlx::query_as::<_, ReportHistory>(
+        "SELECT id, title, quarter_id, format, generated_at, file_path, config_json, file_size_bytes,
+                snapshot_version, artifact_sha256, content_sha256
          FROM report_history WHERE id = ?"
     )
-    .bind(&id)
-    .fetch_one(db)
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?
+    .ok_or_else(|| AppError::NotFound(format!("Report history entry '{}' not found", id)))
+}
+
+/// Record the saved file's hash and, when available, the snapshot it was
+/// rendered from with the hash of its document content.
+pub async fn store_report_artifact(
+    db: &SqlitePool,
+    id: &str,
+    artifact_sha256: &str,
+    content_sha256: Option<&str>,
+    snapshot_version: Option<i64>,
+    snapshot_json: Option<&str>,
+) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE report_history SET artifact_sha256 = ?, content_sha256 = ?, snapshot_version = ?, snapshot_json = ? \
+         WHERE id = ?",
+    )
+    .bind(artifact_sha256)
+    .bind(content_sha256)
+    .bind(snapshot_version)
+    .bind(snapshot_json)
+    .bind(id)
+    .execute(db)
     .await
     .map_err(|e| AppError::Database(e.to_string()))?;
-    Ok(record)
+    Ok(())
+}
+
+/// The stored snapshot JSON, `None` when the report was saved without one.
+pub async fn get_report_snapshot_json(db: &SqlitePool, id: &str) -> AppResult<Option<String>> {
+    let row: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT snapshot_json FROM report_history WHERE id = ?")
+            .bind(id)
+            .fetch_optional(db)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    match row {
+        Some((json,)) => Ok(json),
+        None => Err(AppError::NotFound(format!("Report history entry '{}' not found", id))),
+    }
 }
 
 pub async fn delete_report_history(db: &SqlitePool, id: &str) -> AppResult<()> {
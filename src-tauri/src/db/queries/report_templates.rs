@@ -0,0 +1,221 @@
+use sqlx::{Row, SqlitePool};
+
+use crate::error::{AppError, AppResult};
+use crate::models::report_template::{
+    CreateReportTemplateRequest, ReportTemplate, TemplateSection, UpdateReportTemplateRequest,
+};
+
+pub async fn list_report_templates(db: &SqlitePool) -> AppResult<Vec<ReportTemplate>> {
+    let rows = sqlx::query("SELECT * FROM report_templates ORDER BY is_default DESC, name ASC")
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    rows.iter().map(parse_report_template).collect()
+}
+
+pub async fn get_report_template(db: &SqlitePool, id: &str) -> AppResult<ReportTemplate> {
+    let row = sqlx::query("SELECT * FROM report_templates WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound(format!("Report template '{}' not found", id)))?;
+
+    parse_report_template(&row)
+}
+
+pub async fn create_report_template(
+    db: &SqlitePool,
+    id: &str,
+    req: &CreateReportTemplateRequest,
+) -> AppResult<ReportTemplate> {
+    let sections = serde_json::to_string(&req.sections)
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+    let mut tx = db.begin().await.map_err(|e| AppError::Database(e.to_string()))?;
+
+    // Only one template can be the default
+    if req.is_default {
+        sqlx::query("UPDATE report_templates SET is_default = 0 WHERE is_default = 1")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+
+    sqlx::query(
+        "INSERT INTO report_templates (id, name, description, sections, is_default) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&sections)
+    .bind(req.is_default)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    tx.commit().await.map_err(|e| AppError::Database(e.to_string()))?;
+
+    get_report_template(db, id).await
+}
+
+pub async fn update_report_template(
+    db: &SqlitePool,
+    id: &str,
+    req: &UpdateReportTemplateRequest,
+) -> AppResult<ReportTemplate> {
+    let existing = get_report_template(db, id).await?;
+
+    let name = req.name.as_ref().unwrap_or(&existing.name);
+    let description = req.description.as_ref().unwrap_or(&existing.description);
+    let sections = serde_json::to_string(req.sections.as_ref().unwrap_or(&existing.sections))
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+    let is_default = req.is_default.unwrap_or(existing.is_default);
+
+    let mut tx = db.begin().await.map_err(|e| AppError::Database(e.to_string()))?;
+
+    if is_default && !existing.is_default {
+        sqlx::query("UPDATE report_templates SET is_default = 0 WHERE is_default = 1 AND id != ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+
+    sqlx::query(
+        "UPDATE report_templates SET name=?, description=?, sections=?, is_default=?, updated_at=strftime('%Y-%m-%dT%H:%M:%SZ','now') WHERE id=?",
+    )
+    .bind(name)
+    .bind(description)
+    .bind(&sections)
+    .bind(is_default)
+    .bind(id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    tx.commit().await.map_err(|e| AppError::Database(e.to_string()))?;
+
+    get_report_template(db, id).await
+}
+
+pub async fn delete_report_template(db: &SqlitePool, id: &str) -> AppResult<()> {
+    let result = sqlx::query("DELETE FROM report_templates WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Report template '{}' not found",
+            id
+        )));
+    }
+    Ok(())
+}
+
+fn parse_report_template(row: &sqlx::sqlite::SqliteRow) -> AppResult<ReportTemplate> {
+    let sections_json: String = row.get("sections");
+    let sections: Vec<TemplateSection> = serde_json::from_str(&sections_json)
+        .map_err(|e| AppError::Database(format!("Invalid report template sections: {}", e)))?;
+    Ok(ReportTemplate {
+        id: row.get("id"),
+        name: row.get("name"),
+        description: row.get("description"),
+        sections,
+        is_default: row.get::<bool, _>("is_default"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::report_template::{ReportSectionKind, SectionOptions};
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("report-template-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: the seeded template has every organization-wide built-in section in order
+    #[tokio::test]
+    async fn test_seeded_default_template() {
+        let (_dir, db) = setup_db().await;
+        let template = get_report_template(&db, "rpt-quarterly").await.unwrap();
+        assert!(template.is_default);
+        let kinds: Vec<ReportSectionKind> = template.sections.iter().map(|s| s.kind).collect();
+        let expected: Vec<ReportSectionKind> = ReportSectionKind::BUILT_IN
+            .into_iter()
+            .filter(|kind| *kind != ReportSectionKind::ServiceContext)
+            .collect();
+        assert_eq!(kinds, expected);
+    }
+
+    /// Test: create, update and delete round-trip sections and move the default flag
+    #[tokio::test]
+    async fn test_template_crud() {
+        let (_dir, db) = setup_db().await;
+        let req = CreateReportTemplateRequest {
+            name: "Exec brief".into(),
+            description: String::new(),
+            sections: vec![
+                TemplateSection {
+                    kind: ReportSectionKind::Custom,
+                    title: "Headline".into(),
+                    body: "{{total_incidents}} incidents".into(),
+                    options: SectionOptions::default(),
+                },
+                TemplateSection::built_in(ReportSectionKind::ExecutiveSummary),
+            ],
+            is_default: true,
+        };
+        let created = create_report_template(&db, "rpt-brief", &req).await.unwrap();
+        assert_eq!(created.sections, req.sections);
+        assert!(!get_report_template(&db, "rpt-quarterly").await.unwrap().is_default);
+
+        let mut costs = TemplateSection::built_in(ReportSectionKind::CostImpact);
+        costs.options.top_n = Some(3);
+        let updated = update_report_template(
+            &db,
+            "rpt-brief",
+            &UpdateReportTemplateRequest {
+                name: None,
+                description: Some("Short".into()),
+                sections: Some(vec![costs.clone()]),
+                is_default: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.name, "Exec brief");
+        assert_eq!(updated.description, "Short");
+        assert_eq!(updated.sections, vec![costs]);
+
+        let names: Vec<String> = list_report_templates(&db).await.unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["Exec brief", "Quarterly Review"]);
+
+        delete_report_template(&db, "rpt-brief").await.unwrap();
+        assert!(matches!(get_report_template(&db, "rpt-brief").await, Err(AppError::NotFound(_))));
+        assert!(matches!(delete_report_template(&db, "rpt-brief").await, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,66 @@
+use sqlx::SqlitePool;
+
+use crate::db::queries::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::report_theme::ReportTheme;
+
+pub const REPORT_THEME_SETTING_KEY: &str = "report_theme";
+
+/// Load the report theme, falling back to the default styling when unset.
+pub async fn get_report_theme(db: &SqlitePool) -> AppResult<ReportTheme> {
+    match settings::get_setting(db, REPORT_THEME_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored report theme: {}", e))),
+        None => Ok(ReportTheme::default()),
+    }
+}
+
+pub async fn save_report_theme(db: &SqlitePool, theme: &ReportTheme) -> AppResult<ReportTheme> {
+    let json = serde_json::to_string(theme)?;
+    settings::set_setting(db, REPORT_THEME_SETTING_KEY, &json).await?;
+    Ok(theme.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::report_theme::CoverLayout;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("report-theme-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    /// Test: the theme defaults when unset and round-trips through settings
+    #[tokio::test]
+    async fn test_theme_round_trip() {
+        let (_dir, db) = setup_db().await;
+        assert_eq!(get_report_theme(&db).await.unwrap(), ReportTheme::default());
+
+        let theme = ReportTheme {
+            primary_color: "#0A4D8C".into(),
+            footer_text: "Acme Corp".into(),
+            confidentiality_banner: "Confidential".into(),
+            cover_layout: CoverLayout::FullPage,
+            ..Default::default()
+        };
+        save_report_theme(&db, &theme).await.unwrap();
+        assert_eq!(get_report_theme(&db).await.unwrap(), theme);
+    }
+}
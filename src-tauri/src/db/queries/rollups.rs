@@ -0,0 +1,758 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use sqlx::{Row, SqlitePool};
+
+use crate::db::queries::metrics::DateRange;
+use crate::db::queries::{settings, time_zones};
+use crate::error::{AppError, AppResult};
+use crate::models::metrics::{
+    CategoryCount, MetricFilters, RollupConsistencyReport, RollupMismatch, RollupRebuildResult,
+};
+use crate::models::timezone::{parse_timestamp, AnalyticsZone};
+
+/// Zone the stored rollups were computed in; a different org zone forces a rebuild.
+const ROLLUP_ZONE_SETTING_KEY: &str = "metric_rollups_time_zone";
+/// Max local days recomputed per statement, keeping bind counts well under SQLite's limit.
+const REFRESH_CHUNK: usize = 100;
+const MAX_REPORTED_MISMATCHES: usize = 50;
+
+/// Per-incident facts the rollups aggregate. Days are assigned in Rust because
+/// the local day depends on the organization time zone.
+const ROLLUP_SOURCE_SELECT: &str = "SELECT i.started_at, i.service_id, i.severity, i.impact, \
+     CASE WHEN i.resolved_at IS NOT NULL THEN CAST(i.duration_minutes AS REAL) END as resolve_minutes, \
+     CASE WHEN i.acknowledged_at IS NOT NULL OR i.responded_at IS NOT NULL \
+         THEN CAST((julianday(COALESCE(i.acknowledged_at, i.responded_at)) - julianday(i.detected_at)) * 1440 AS REAL) END as ack_minutes, \
+     i.is_recurring, CAST(i.tickets_submitted AS REAL) as tickets \
+     FROM incidents i WHERE i.deleted_at IS NULL";
+
+const ROLLUP_COLUMNS: &str = "day, service_id, severity, impact, incident_count, \
+     resolved_count, resolve_minutes_sum, ack_count, ack_minutes_sum, recurring_count, \
+     ticket_count, tickets_sum";
+
+/// Summed counters for a date range, read from the rollups.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeriodTotals {
+    pub incident_count: i64,
+    pub resolved_count: i64,
+    pub resolve_minutes_sum: f64,
+    pub ack_count: i64,
+    pub ack_minutes_sum: f64,
+    pub recurring_count: i64,
+    pub ticket_count: i64,
+    pub tickets_sum: f64,
+}
+
+impl PeriodTotals {
+    pub fn mttr(&self) -> f64 {
+        ratio(self.resolve_minutes_sum, self.resolved_count)
+    }
+
+    pub fn mtta(&self) -> f64 {
+        ratio(self.ack_minutes_sum, self.ack_count)
+    }
+
+    pub fn recurrence_rate(&self) -> f64 {
+        ratio(self.recurring_count as f64, self.incident_count) * 100.0
+    }
+
+    pub fn avg_tickets(&self) -> f64 {
+        ratio(self.tickets_sum, self.ticket_count)
+    }
+}
+
+fn ratio(sum: f64, count: i64) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// The local days a range covers when it is exactly `tz.day_bounds(start, end)`,
+/// which is when the rollups can answer for it.
+pub fn local_days(range: &DateRange, tz: &AnalyticsZone) -> Option<(NaiveDate, NaiveDate)> {
+    let start_instant = NaiveDateTime::parse_from_str(&range.start, "%Y-%m-%dT%H:%M:%SZ").ok()?;
+    let end_instant = NaiveDateTime::parse_from_str(&range.end, "%Y-%m-%dT%H:%M:%SZ").ok()?;
+
+    let start = tz.local_date(&start_instant.and_utc());
+    let end = tz.local_date(&end_instant.and_utc());
+    if end < start {
+        return None;
+    }
+    let (lower, upper) = tz.day_bounds(start, end);
+    (lower == range.start && upper == range.end).then_some((start, end))
+}
+
+fn rollup_where_clause(days: (NaiveDate, NaiveDate), filters: &MetricFilters) -> (String, Vec<String>) {
+    let mut conditions = vec!["r.day >= ?".to_string(), "r.day <= ?".to_string()];
+    let mut params = vec![days.0.to_string(), days.1.to_string()];
+
+    if let Some(ref sids) = filters.service_ids {
+        if !sids.is_empty() {
+            let placeholders: Vec<&str> = sids.iter().map(|_| "?").collect();
+            conditions.push(format!("r.service_id IN ({})", placeholders.join(",")));
+            params.extend(sids.iter().cloned());
+        }
+    }
+
+    (conditions.join(" AND "), params)
+}
+
+type RollupKey = (String, String, String, String);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RollupValues {
+    incident_count: i64,
+    resolved_count: i64,
+    resolve_minutes_sum: f64,
+    ack_count: i64,
+    ack_minutes_sum: f64,
+    recurring_count: i64,
+    ticket_count: i64,
+    tickets_sum: f64,
+}
+
+impl RollupValues {
+    fn add(&mut self, row: &sqlx::sqlite::SqliteRow) {
+        self.incident_count += 1;
+        if let Some(minutes) = row.get::<Option<f64>, _>("resolve_minutes") {
+            self.resolved_count += 1;
+            self.resolve_minutes_sum += minutes;
+        }
+        if let Some(minutes) = row.get::<Option<f64>, _>("ack_minutes") {
+            self.ack_count += 1;
+            self.ack_minutes_sum += minutes;
+        }
+        if row.get::<i64, _>("is_recurring") == 1 {
+            self.recurring_count += 1;
+        }
+        if let Some(tickets) = row.get::<Option<f64>, _>("tickets") {
+            self.ticket_count += 1;
+            self.tickets_sum += tickets;
+        }
+    }
+
+    /// Names and descriptions of the fields that differ between two rows.
+    fn differences(&self, expected: &RollupValues) -> Vec<String> {
+        let mut diffs = Vec::new();
+        let ints = [
+            ("incident_count", self.incident_count, expected.incident_count),
+            ("resolved_count", self.resolved_count, expected.resolved_count),
+            ("ack_count", self.ack_count, expected.ack_count),
+            ("recurring_count", self.recurring_count, expected.recurring_count),
+            ("ticket_count", self.ticket_count, expected.ticket_count),
+        ];
+        for (name, stored, raw) in ints {
+            if stored != raw {
+                diffs.push(format!("{}: stored {}, expected {}", name, stored, raw));
+            }
+        }
+        let floats = [
+            ("resolve_minutes_sum", self.resolve_minutes_sum, expected.resolve_minutes_sum),
+            ("ack_minutes_sum", self.ack_minutes_sum, expected.ack_minutes_sum),
+            ("tickets_sum", self.tickets_sum, expected.tickets_sum),
+        ];
+        for (name, stored, raw) in floats {
+            if (stored - raw).abs() > 1e-6 {
+                diffs.push(format!("{}: stored {:.3}, expected {:.3}", name, stored, raw));
+            }
+        }
+        diffs
+    }
+}
+
+/// Aggregate raw incident rows by local day. When `only` is given, rows on
+/// other days are ignored. Rows whose `started_at` cannot be parsed are skipped.
+fn aggregate(
+    rows: &[sqlx::sqlite::SqliteRow],
+    tz: &AnalyticsZone,
+    only: Option<&BTreeSet<NaiveDate>>,
+) -> HashMap<RollupKey, RollupValues> {
+    let mut totals: HashMap<RollupKey, RollupValues> = HashMap::new();
+    for row in rows {
+        let started_at: String = row.get("started_at");
+        let Some(instant) = parse_timestamp(&started_at) else {
+            continue;
+        };
+        let day = tz.local_date(&instant);
+        if only.is_some_and(|days| !days.contains(&day)) {
+            continue;
+        }
+        let key = (
+            day.to_string(),
+            row.get("service_id"),
+            row.get("severity"),
+            row.get("impact"),
+        );
+        totals.entry(key).or_default().add(row);
+    }
+    totals
+}
+
+async fn insert_rollups(
+    conn: &mut sqlx::SqliteConnection,
+    rollups: &HashMap<RollupKey, RollupValues>,
+) -> AppResult<()> {
+    let sql = format!(
+        "INSERT INTO metric_daily_rollups ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        ROLLUP_COLUMNS
+    );
+    for ((day, service_id, severity, impact), v) in rollups {
+        sqlx::query(&sql)
+            .bind(day)
+            .bind(service_id)
+            .bind(severity)
+            .bind(impact)
+            .bind(v.incident_count)
+            .bind(v.resolved_count)
+            .bind(v.resolve_minutes_sum)
+            .bind(v.ack_count)
+            .bind(v.ack_minutes_sum)
+            .bind(v.recurring_count)
+            .bind(v.ticket_count)
+            .bind(v.tickets_sum)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn shifted_days(days: impl IntoIterator<Item = NaiveDate>, reach: i64) -> BTreeSet<NaiveDate> {
+    days.into_iter()
+        .flat_map(|d| (-reach..=reach).map(move |offset| d + Duration::days(offset)))
+        .collect()
+}
+
+/// Recompute the rollups for every day marked dirty by the incident triggers,
+/// or everything when the organization time zone changed since the last build.
+/// Returns the number of local days refreshed.
+pub async fn refresh_dirty_rollups(db: &SqlitePool) -> AppResult<i64> {
+    let tz = time_zones::org_time_zone(db).await?;
+    let built_for = settings::get_setting(db, ROLLUP_ZONE_SETTING_KEY).await?;
+    if built_for.as_deref() != Some(tz.name.as_str()) {
+        return Ok(rebuild_rollups(db).await?.days);
+    }
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let markers: Vec<String> = sqlx::query_scalar("SELECT day FROM metric_rollup_dirty_days")
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    if markers.is_empty() {
+        return Ok(0);
+    }
+
+    // Markers hold the date prefix of started_at. The incident's local day can be
+    // up to two days away once its own UTC offset and the org offset are applied.
+    let marked = markers
+        .iter()
+        .filter_map(|m| NaiveDate::parse_from_str(m, "%Y-%m-%d").ok());
+    let local: Vec<NaiveDate> = shifted_days(marked, 2).into_iter().collect();
+
+    for chunk in local.chunks(REFRESH_CHUNK) {
+        let days: BTreeSet<NaiveDate> = chunk.iter().copied().collect();
+        let prefixes: Vec<String> = shifted_days(chunk.iter().copied(), 2)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        let placeholders = prefixes.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "{} AND substr(i.started_at, 1, 10) IN ({})",
+            ROLLUP_SOURCE_SELECT, placeholders
+        );
+        let mut query = sqlx::query(&sql);
+        for p in &prefixes {
+            query = query.bind(p);
+        }
+        let rows = query
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let totals = aggregate(&rows, &tz, Some(&days));
+
+        let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let delete_sql = format!("DELETE FROM metric_daily_rollups WHERE day IN ({})", placeholders);
+        let mut query = sqlx::query(&delete_sql);
+        for day in chunk {
+            query = query.bind(day.to_string());
+        }
+        query
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        insert_rollups(&mut tx, &totals).await?;
+    }
+
+    for marker in &markers {
+        sqlx::query("DELETE FROM metric_rollup_dirty_days WHERE day = ?")
+            .bind(marker)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(local.len() as i64)
+}
+
+/// Drop and recompute every rollup row from raw incidents in the org time zone.
+pub async fn rebuild_rollups(db: &SqlitePool) -> AppResult<RollupRebuildResult> {
+    let tz = time_zones::org_time_zone(db).await?;
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    for sql in ["DELETE FROM metric_daily_rollups", "DELETE FROM metric_rollup_dirty_days"] {
+        sqlx::query(sql)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+
+    let rows = sqlx::query(ROLLUP_SOURCE_SELECT)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let totals = aggregate(&rows, &tz, None);
+    insert_rollups(&mut tx, &totals).await?;
+
+    sqlx::query(
+        "INSERT INTO app_settings (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+    )
+    .bind(ROLLUP_ZONE_SETTING_KEY)
+    .bind(&tz.name)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let days: BTreeSet<&String> = totals.keys().map(|k| &k.0).collect();
+    Ok(RollupRebuildResult {
+        days: days.len() as i64,
+        rows: totals.len() as i64,
+    })
+}
+
+/// Summed counters for local days `days.0..=days.1`. Callers refresh dirty days first.
+pub async fn period_totals(
+    db: &SqlitePool,
+    days: (NaiveDate, NaiveDate),
+    filters: &MetricFilters,
+) -> AppResult<PeriodTotals> {
+    let (wc, params) = rollup_where_clause(days, filters);
+    let sql = format!(
+        "SELECT COALESCE(SUM(incident_count), 0) as incident_count, \
+         COALESCE(SUM(resolved_count), 0) as resolved_count, \
+         COALESCE(SUM(resolve_minutes_sum), 0.0) as resolve_minutes_sum, \
+         COALESCE(SUM(ack_count), 0) as ack_count, \
+         COALESCE(SUM(ack_minutes_sum), 0.0) as ack_minutes_sum, \
+         COALESCE(SUM(recurring_count), 0) as recurring_count, \
+         COALESCE(SUM(ticket_count), 0) as ticket_count, \
+         COALESCE(SUM(tickets_sum), 0.0) as tickets_sum \
+         FROM metric_daily_rollups r WHERE {}",
+        wc
+    );
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let row = query
+        .fetch_one(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(PeriodTotals {
+        incident_count: row.get("incident_count"),
+        resolved_count: row.get("resolved_count"),
+        resolve_minutes_sum: row.get("resolve_minutes_sum"),
+        ack_count: row.get("ack_count"),
+        ack_minutes_sum: row.get("ack_minutes_sum"),
+        recurring_count: row.get("recurring_count"),
+        ticket_count: row.get("ticket_count"),
+        tickets_sum: row.get("tickets_sum"),
+    })
+}
+
+/// Incident counts grouped by severity, impact or service name, largest first.
+pub async fn category_counts(
+    db: &SqlitePool,
+    days: (NaiveDate, NaiveDate),
+    filters: &MetricFilters,
+    dimension: &str,
+) -> AppResult<Vec<CategoryCount>> {
+    let (select, join, unknown) = match dimension {
+        "severity" => ("r.severity", "", "Unknown"),
+        "impact" => ("r.impact", "", "Unknown"),
+        "service" => ("s.name", "LEFT JOIN services s ON r.service_id = s.id", "Unknown Service"),
+        _ => return Err(AppError::Validation(format!("Invalid grouping column: {}", dimension))),
+    };
+    let (wc, params) = rollup_where_clause(days, filters);
+    let sql = format!(
+        "SELECT {sel} as category, SUM(r.incident_count) as cnt FROM metric_daily_rollups r {join} \
+         WHERE {wc} GROUP BY {sel} ORDER BY cnt DESC, category ASC",
+        sel = select,
+        join = join,
+        wc = wc
+    );
+    let mut query = sqlx::query(&sql);
+    for p in &params {
+        query = query.bind(p);
+    }
+    let rows = query
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| CategoryCount {
+            category: r
+                .get::<Option<String>, _>("category")
+                .unwrap_or_else(|| unknown.to_string()),
+            count: r.get::<i64, _>("cnt"),
+            previous_count: None,
+        })
+        .collect())
+}
+
+/// Compare the stored rollups against a recompute from raw incidents.
+/// Pending dirty days are refreshed first, so any mismatch means a change
+/// reached `incidents` without being captured by the triggers.
+pub async fn check_rollup_consistency(db: &SqlitePool) -> AppResult<RollupConsistencyReport> {
+    refresh_dirty_rollups(db).await?;
+    let tz = time_zones::org_time_zone(db).await?;
+
+    let stored_rows = sqlx::query(&format!("SELECT {} FROM metric_daily_rollups", ROLLUP_COLUMNS))
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let stored: HashMap<RollupKey, RollupValues> = stored_rows
+        .iter()
+        .map(|r| {
+            (
+                (r.get("day"), r.get("service_id"), r.get("severity"), r.get("impact")),
+                RollupValues {
+                    incident_count: r.get("incident_count"),
+                    resolved_count: r.get("resolved_count"),
+                    resolve_minutes_sum: r.get("resolve_minutes_sum"),
+                    ack_count: r.get("ack_count"),
+                    ack_minutes_sum: r.get("ack_minutes_sum"),
+                    recurring_count: r.get("recurring_count"),
+                    ticket_count: r.get("ticket_count"),
+                    tickets_sum: r.get("tickets_sum"),
+                },
+            )
+        })
+        .collect();
+
+    let raw_rows = sqlx::query(ROLLUP_SOURCE_SELECT)
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let raw = aggregate(&raw_rows, &tz, None);
+
+    let mut keys: Vec<&RollupKey> = stored.keys().chain(raw.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut mismatch_count = 0i64;
+    let mut mismatches = Vec::new();
+    for key in keys {
+        let detail = match (stored.get(key), raw.get(key)) {
+            (Some(s), Some(r)) => {
+                let diffs = s.differences(r);
+                if diffs.is_empty() {
+                    continue;
+                }
+                diffs.join("; ")
+            }
+            (Some(_), None) => "rollup row has no matching incidents".to_string(),
+            (None, Some(r)) => format!("missing rollup row for {} incident(s)", r.incident_count),
+            (None, None) => continue,
+        };
+        mismatch_count += 1;
+        if mismatches.len() < MAX_REPORTED_MISMATCHES {
+            mismatches.push(RollupMismatch {
+                day: key.0.clone(),
+                service_id: key.1.clone(),
+                severity: key.2.clone(),
+                impact: key.3.clone(),
+                detail,
+            });
+        }
+    }
+
+    Ok(RollupConsistencyReport {
+        consistent: mismatch_count == 0,
+        rollup_rows: stored.len() as i64,
+        raw_rows: raw.len() as i64,
+        mismatch_count,
+        mismatches,
+        checked_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("rollup-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, service_id: &str, started_at: &str, resolved_at: Option<&str>) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at, tickets_submitted) VALUES (?, ?, ?, 'High', 'Medium', ?, ?, ?, ?, 2)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(if resolved_at.is_some() { "Resolved" } else { "Active" })
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    fn days(start: &str, end: &str) -> (NaiveDate, NaiveDate) {
+        (start.parse().unwrap(), end.parse().unwrap())
+    }
+
+    async fn dirty_days(db: &SqlitePool) -> Vec<String> {
+        sqlx::query_scalar("SELECT day FROM metric_rollup_dirty_days ORDER BY day")
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    /// Test: only ranges spanning whole local days can be answered from rollups
+    #[test]
+    fn test_local_days_requires_whole_days() {
+        let utc = AnalyticsZone::utc();
+        let range = DateRange::local_days("2025-01-01", "2025-03-31", &utc).unwrap();
+        assert_eq!(local_days(&range, &utc), Some(days("2025-01-01", "2025-03-31")));
+
+        let partial = DateRange {
+            start: "2025-01-01T06:00:00Z".into(),
+            end: "2025-03-31T23:59:59Z".into(),
+        };
+        assert_eq!(local_days(&partial, &utc), None);
+        let open = DateRange {
+            start: String::new(),
+            end: "9999-12-31".into(),
+        };
+        assert_eq!(local_days(&open, &utc), None);
+
+        let tokyo = AnalyticsZone::parse("Asia/Tokyo").unwrap();
+        let range = DateRange::local_days("2025-01-01", "2025-01-31", &tokyo).unwrap();
+        assert_eq!(range.start, "2024-12-31T15:00:00Z");
+        assert_eq!(range.end, "2025-01-31T14:59:59Z");
+        assert_eq!(local_days(&range, &tokyo), Some(days("2025-01-01", "2025-01-31")));
+        // The same bounds are not whole days in another zone
+        assert_eq!(local_days(&range, &utc), None);
+    }
+
+    /// Test: triggers mark touched days dirty and refresh only recomputes those
+    #[tokio::test]
+    async fn test_triggers_mark_days_and_refresh_clears_them() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "r-1", "svc-slack", "2025-01-10T10:00:00Z", Some("2025-01-10T11:00:00Z")).await;
+        insert_incident(&db, "r-2", "svc-zoom", "2025-01-11T10:00:00Z", None).await;
+        assert_eq!(dirty_days(&db).await, vec!["2025-01-10", "2025-01-11"]);
+
+        assert_eq!(refresh_dirty_rollups(&db).await.unwrap(), 2);
+        assert!(dirty_days(&db).await.is_empty());
+        assert_eq!(refresh_dirty_rollups(&db).await.unwrap(), 0);
+
+        // Edits to columns outside the rollup do not dirty anything
+        sqlx::query("UPDATE incidents SET notes = 'x' WHERE id = 'r-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        assert!(dirty_days(&db).await.is_empty());
+
+        // Moving an incident dirties both the old and the new day
+        sqlx::query("UPDATE incidents SET started_at = '2025-01-12T09:00:00Z', detected_at = '2025-01-12T09:00:00Z' WHERE id = 'r-2'")
+            .execute(&db)
+            .await
+            .unwrap();
+        assert_eq!(dirty_days(&db).await, vec!["2025-01-11", "2025-01-12"]);
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        let totals = period_totals(&db, days("2025-01-11", "2025-01-11"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 0);
+        let totals = period_totals(&db, days("2025-01-12", "2025-01-12"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 1);
+    }
+
+    /// Test: totals and derived averages follow the raw definitions
+    #[tokio::test]
+    async fn test_period_totals() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "r-1", "svc-slack", "2025-01-10T10:00:00Z", Some("2025-01-10T11:00:00Z")).await;
+        insert_incident(&db, "r-2", "svc-slack", "2025-01-11T10:00:00Z", Some("2025-01-11T12:00:00Z")).await;
+        insert_incident(&db, "r-3", "svc-zoom", "2025-01-12T10:00:00Z", None).await;
+        sqlx::query("UPDATE incidents SET acknowledged_at = '2025-01-10T10:10:00Z', is_recurring = 1 WHERE id = 'r-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        let totals = period_totals(&db, days("2025-01-01", "2025-01-31"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 3);
+        assert_eq!(totals.resolved_count, 2);
+        assert!((totals.mttr() - 90.0).abs() < 1e-9);
+        assert_eq!(totals.ack_count, 1);
+        assert!((totals.mtta() - 10.0).abs() < 1e-6);
+        assert!((totals.recurrence_rate() - 100.0 / 3.0).abs() < 1e-9);
+        assert!((totals.avg_tickets() - 2.0).abs() < 1e-9);
+
+        let filters = MetricFilters {
+            service_ids: Some(vec!["svc-zoom".into()]),
+            ..Default::default()
+        };
+        let totals = period_totals(&db, days("2025-01-01", "2025-01-31"), &filters).await.unwrap();
+        assert_eq!(totals.incident_count, 1);
+        assert_eq!(totals.resolved_count, 0);
+        assert_eq!(totals.mttr(), 0.0);
+    }
+
+    /// Test: rollup days follow the org zone and a zone change rebuilds them
+    #[tokio::test]
+    async fn test_rollup_days_follow_org_time_zone() {
+        let (_dir, db) = setup_db().await;
+        // 03:00Z on Feb 1st is still Jan 31st in New York
+        insert_incident(&db, "r-1", "svc-slack", "2025-02-01T03:00:00Z", None).await;
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        let totals = period_totals(&db, days("2025-01-01", "2025-01-31"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 0);
+
+        let config = crate::models::timezone::TimeZoneConfig {
+            org_time_zone: "America/New_York".into(),
+            service_time_zones: HashMap::new(),
+        };
+        time_zones::save_time_zone_config(&db, &config).await.unwrap();
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        let totals = period_totals(&db, days("2025-01-01", "2025-01-31"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 1);
+        assert!(check_rollup_consistency(&db).await.unwrap().consistent);
+
+        // Incremental refreshes keep using the local day
+        insert_incident(&db, "r-2", "svc-slack", "2025-02-01T04:30:00Z", None).await;
+        refresh_dirty_rollups(&db).await.unwrap();
+        let totals = period_totals(&db, days("2025-01-31", "2025-01-31"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 2);
+        let totals = period_totals(&db, days("2025-02-01", "2025-02-01"), &MetricFilters::default())
+            .await
+            .unwrap();
+        assert_eq!(totals.incident_count, 0);
+    }
+
+    /// Test: soft-deleted incidents drop out of the rollups
+    #[tokio::test]
+    async fn test_soft_delete_removes_from_rollups() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "r-1", "svc-slack", "2025-01-10T10:00:00Z", None).await;
+        insert_incident(&db, "r-2", "svc-slack", "2025-01-10T12:00:00Z", None).await;
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        sqlx::query("UPDATE incidents SET deleted_at = '2025-02-01T00:00:00Z' WHERE id = 'r-2'")
+            .execute(&db)
+            .await
+            .unwrap();
+        refresh_dirty_rollups(&db).await.unwrap();
+
+        let counts = category_counts(&db, days("2025-01-01", "2025-01-31"), &MetricFilters::default(), "service")
+            .await
+            .unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].count, 1);
+        assert!(category_counts(&db, days("2025-01-01", "2025-01-31"), &MetricFilters::default(), "status")
+            .await
+            .is_err());
+    }
+
+    /// Test: the consistency check flags drift and a rebuild repairs it
+    #[tokio::test]
+    async fn test_consistency_check_and_rebuild() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "r-1", "svc-slack", "2025-01-10T10:00:00Z", Some("2025-01-10T11:00:00Z")).await;
+        insert_incident(&db, "r-2", "svc-zoom", "2025-01-11T10:00:00Z", None).await;
+
+        let report = check_rollup_consistency(&db).await.unwrap();
+        assert!(report.consistent);
+        assert_eq!(report.rollup_rows, 2);
+        assert_eq!(report.raw_rows, 2);
+
+        // Simulate drift the triggers could not see
+        sqlx::query("UPDATE metric_daily_rollups SET incident_count = 5 WHERE service_id = 'svc-slack'")
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM metric_daily_rollups WHERE service_id = 'svc-zoom'")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let report = check_rollup_consistency(&db).await.unwrap();
+        assert!(!report.consistent);
+        assert_eq!(report.mismatch_count, 2);
+        assert!(report.mismatches[0].detail.contains("incident_count: stored 5, expected 1"));
+        assert!(report.mismatches[1].detail.contains("missing rollup row"));
+
+        let rebuilt = rebuild_rollups(&db).await.unwrap();
+        assert_eq!(rebuilt.days, 2);
+        assert_eq!(rebuilt.rows, 2);
+        assert!(check_rollup_consistency(&db).await.unwrap().consistent);
+    }
+}
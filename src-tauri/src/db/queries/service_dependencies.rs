@@ -1,7 +1,16 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
 use sqlx::{Row, SqlitePool};
 
+use crate::db::queries::metrics::DateRange;
 use crate::error::{AppError, AppResult};
 use crate::models::service::ServiceDependency;
+use crate::models::service_graph::{
+    BlastRadius, CriticalPathService, DependencyEdge, Direction, ImpactedService, Reach,
+    ServiceGraph, PROPAGATION_MIN_WEIGHT,
+};
+use crate::models::timezone::parse_timestamp;
 
 pub async fn insert_dependency(
     db: &SqlitePool,
@@ -187,6 +196,178 @@ async fn would_create_cycle(
     Ok(false)
 }
 
+// ── Graph analysis ────────────────────────────────────────────────
+
+/// The full dependency graph plus service names keyed by ID.
+pub async fn load_graph(db: &SqlitePool) -> AppResult<(ServiceGraph, HashMap<String, String>)> {
+    let rows = sqlx::query(
+        "SELECT service_id, depends_on_service_id, dependency_type FROM service_dependencies",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+    let edges: Vec<DependencyEdge> = rows
+        .iter()
+        .map(|row| DependencyEdge {
+            service_id: row.get("service_id"),
+            depends_on_service_id: row.get("depends_on_service_id"),
+            dependency_type: row.get("dependency_type"),
+        })
+        .collect();
+
+    let names: HashMap<String, String> = sqlx::query("SELECT id, name FROM services")
+        .fetch_all(db)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .iter()
+        .map(|row| (row.get("id"), row.get("name")))
+        .collect();
+
+    Ok((ServiceGraph::new(&edges), names))
+}
+
+fn impacted(reach: Reach, names: &HashMap<String, String>) -> ImpactedService {
+    let name = |id: &String| names.get(id).cloned().unwrap_or_else(|| id.clone());
+    ImpactedService {
+        service_name: name(&reach.service_id),
+        path: reach.path.iter().map(name).collect(),
+        service_id: reach.service_id,
+        depth: reach.depth,
+        weight: reach.weight,
+        dependency_type: reach.dependency_type,
+    }
+}
+
+/// Transitive dependents and dependencies of a service, strongest first.
+pub async fn get_blast_radius(db: &SqlitePool, service_id: &str) -> AppResult<BlastRadius> {
+    let (graph, names) = load_graph(db).await?;
+    let service_name = names
+        .get(service_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Service '{}' not found", service_id)))?;
+
+    let dependents = graph.reach(service_id, Direction::Dependents);
+    let impact_score = dependents.iter().map(|r| r.weight).sum();
+    Ok(BlastRadius {
+        service_id: service_id.to_string(),
+        service_name,
+        upstream_dependents: dependents.into_iter().map(|r| impacted(r, &names)).collect(),
+        downstream_dependencies: graph
+            .reach(service_id, Direction::Dependencies)
+            .into_iter()
+            .map(|r| impacted(r, &names))
+            .collect(),
+        impact_score,
+    })
+}
+
+/// Dependents likely to be affected by an incident on `service_id`.
+pub async fn suggest_affected_services(
+    db: &SqlitePool,
+    service_id: &str,
+) -> AppResult<Vec<ImpactedService>> {
+    let radius = get_blast_radius(db, service_id).await?;
+    Ok(radius
+        .upstream_dependents
+        .into_iter()
+        .filter(|s| s.weight >= PROPAGATION_MIN_WEIGHT)
+        .collect())
+}
+
+/// Rank services by how many incidents propagated through them in `range`.
+///
+/// An incident on a dependent service counts as propagated when it started
+/// while an incident on one of its (transitive) dependencies was open. The
+/// source service and every service between it and the dependent are credited.
+pub async fn get_critical_path_services(
+    db: &SqlitePool,
+    range: &DateRange,
+) -> AppResult<Vec<CriticalPathService>> {
+    let (graph, names) = load_graph(db).await?;
+    let rows = sqlx::query(
+        "SELECT id, service_id, started_at, resolved_at FROM incidents \
+         WHERE deleted_at IS NULL AND started_at >= ? AND started_at <= ? \
+         ORDER BY started_at",
+    )
+    .bind(&range.start)
+    .bind(&range.end)
+    .fetch_all(db)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    let now = Utc::now();
+    let incidents: Vec<(String, String, chrono::DateTime<Utc>, chrono::DateTime<Utc>)> = rows
+        .iter()
+        .filter_map(|row| {
+            let start = parse_timestamp(&row.get::<String, _>("started_at"))?;
+            let end = row
+                .get::<Option<String>, _>("resolved_at")
+                .and_then(|v| parse_timestamp(&v))
+                .unwrap_or(now);
+            Some((row.get("id"), row.get("service_id"), start, end))
+        })
+        .collect();
+
+    let mut reach_cache: HashMap<String, Vec<Reach>> = HashMap::new();
+    let mut propagated: HashSet<(String, String)> = HashSet::new();
+    let mut sources: HashSet<(String, String)> = HashSet::new();
+    for (source_id, source_service, start, end) in &incidents {
+        let reached = reach_cache
+            .entry(source_service.clone())
+            .or_insert_with(|| graph.reach(source_service, Direction::Dependents));
+        for (incident_id, service_id, started, _) in &incidents {
+            if incident_id == source_id || started < start || started > end {
+                continue;
+            }
+            let Some(path) = reached
+                .iter()
+                .find(|r| &r.service_id == service_id && r.weight >= PROPAGATION_MIN_WEIGHT)
+                .map(|r| &r.path)
+            else {
+                continue;
+            };
+            sources.insert((source_service.clone(), source_id.clone()));
+            for through in &path[..path.len() - 1] {
+                propagated.insert((through.clone(), incident_id.clone()));
+            }
+        }
+    }
+
+    let mut counts: HashMap<String, (i64, i64)> = HashMap::new();
+    for (service_id, _) in &propagated {
+        counts.entry(service_id.clone()).or_default().0 += 1;
+    }
+    for (service_id, _) in &sources {
+        counts.entry(service_id.clone()).or_default().1 += 1;
+    }
+
+    let mut ranked: Vec<CriticalPathService> = counts
+        .into_iter()
+        .map(|(service_id, (propagated_incidents, source_incidents))| {
+            let dependents = graph.reach(&service_id, Direction::Dependents);
+            CriticalPathService {
+                service_name: names.get(&service_id).cloned().unwrap_or_else(|| service_id.clone()),
+                propagated_incidents,
+                source_incidents,
+                dependent_count: dependents.len() as i64,
+                impact_score: dependents.iter().map(|r| r.weight).sum(),
+                service_id,
+            }
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.propagated_incidents
+            .cmp(&a.propagated_incidents)
+            .then_with(|| {
+                b.impact_score
+                    .partial_cmp(&a.impact_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.service_name.cmp(&b.service_name))
+    });
+    Ok(ranked)
+}
+
 fn parse_dependency_row(row: &sqlx::sqlite::SqliteRow) -> ServiceDependency {
     ServiceDependency {
         id: row.get("id"),
@@ -197,3 +378,117 @@ fn parse_dependency_row(row: &sqlx::sqlite::SqliteRow) -> ServiceDependency {
         created_at: row.get("created_at"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::timezone::AnalyticsZone;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("dependency-query-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_service(db: &SqlitePool, id: &str, name: &str) {
+        sqlx::query(
+            "INSERT INTO services (id, name, category, default_severity, default_impact) VALUES (?, ?, 'Infrastructure', 'High', 'High')",
+        )
+        .bind(id)
+        .bind(name)
+        .execute(db)
+        .await
+        .expect("insert service");
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, service_id: &str, started_at: &str, resolved_at: &str) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) VALUES (?, ?, ?, 'High', 'High', 'Resolved', ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("Incident {}", id))
+        .bind(service_id)
+        .bind(started_at)
+        .bind(started_at)
+        .bind(resolved_at)
+        .execute(db)
+        .await
+        .expect("insert incident");
+    }
+
+    /// Seeds slack -> api -> db and zoom -(optional)-> db.
+    async fn seed_graph(db: &SqlitePool) {
+        insert_service(db, "svc-api", "API").await;
+        insert_service(db, "svc-db", "Database").await;
+        insert_dependency(db, "dep-1", "svc-slack", "svc-api", "runtime").await.unwrap();
+        insert_dependency(db, "dep-2", "svc-api", "svc-db", "runtime").await.unwrap();
+        insert_dependency(db, "dep-3", "svc-zoom", "svc-db", "optional").await.unwrap();
+    }
+
+    /// Test: blast radius walks both directions and suggestions skip weak edges
+    #[tokio::test]
+    async fn test_blast_radius_and_suggestions() {
+        let (_dir, db) = setup_db().await;
+        seed_graph(&db).await;
+
+        let radius = get_blast_radius(&db, "svc-db").await.unwrap();
+        assert_eq!(radius.service_name, "Database");
+        let ids: Vec<&str> = radius.upstream_dependents.iter().map(|s| s.service_id.as_str()).collect();
+        assert_eq!(ids, vec!["svc-api", "svc-slack", "svc-zoom"]);
+        assert_eq!(radius.upstream_dependents[1].depth, 2);
+        assert_eq!(radius.upstream_dependents[1].path, vec!["Database", "API", "Slack"]);
+        assert!(radius.downstream_dependencies.is_empty());
+        assert!((radius.impact_score - 2.2).abs() < 1e-9);
+
+        let slack = get_blast_radius(&db, "svc-slack").await.unwrap();
+        assert_eq!(slack.downstream_dependencies.len(), 2);
+
+        let suggested = suggest_affected_services(&db, "svc-db").await.unwrap();
+        assert_eq!(suggested.len(), 2);
+        assert!(suggested.iter().all(|s| s.service_id != "svc-zoom"));
+
+        assert!(matches!(get_blast_radius(&db, "svc-missing").await, Err(AppError::NotFound(_))));
+    }
+
+    /// Test: services are ranked by incidents that propagated through them
+    #[tokio::test]
+    async fn test_critical_path_ranking() {
+        let (_dir, db) = setup_db().await;
+        seed_graph(&db).await;
+
+        // Database outage with concurrent API and Slack incidents
+        insert_incident(&db, "i-db", "svc-db", "2025-01-10T10:00:00Z", "2025-01-10T12:00:00Z").await;
+        insert_incident(&db, "i-api", "svc-api", "2025-01-10T10:05:00Z", "2025-01-10T11:00:00Z").await;
+        insert_incident(&db, "i-slack", "svc-slack", "2025-01-10T10:10:00Z", "2025-01-10T11:00:00Z").await;
+        // Optional dependency and a later, unrelated incident do not count
+        insert_incident(&db, "i-zoom", "svc-zoom", "2025-01-10T10:15:00Z", "2025-01-10T11:00:00Z").await;
+        insert_incident(&db, "i-late", "svc-slack", "2025-01-20T10:00:00Z", "2025-01-20T11:00:00Z").await;
+
+        let range = DateRange::local_days("2025-01-01", "2025-01-31", &AnalyticsZone::utc()).unwrap();
+        let ranked = get_critical_path_services(&db, &range).await.unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].service_id, "svc-db");
+        assert_eq!(ranked[0].propagated_incidents, 2);
+        assert_eq!(ranked[0].source_incidents, 1);
+        assert_eq!(ranked[0].dependent_count, 3);
+        // Slack's incident passed through the API; the API's own incident was a source too
+        assert_eq!(ranked[1].service_id, "svc-api");
+        assert_eq!(ranked[1].propagated_incidents, 1);
+        assert_eq!(ranked[1].source_incidents, 1);
+    }
+}
@@ -17,7 +17,7 @@ fn parse_sla_definition(row: &sqlx::sqlite::SqliteRow) -> SlaDefinition {
     }
 }
 
-fn compute_priority(severity: &str, impact: &str) -> String {
+pub(crate) fn compute_priority(severity: &str, impact: &str) -> String {
     let sev = Severity::from_str(severity).unwrap_or(Severity::Medium);
     let imp = Impact::from_str(impact).unwrap_or(Impact::Medium);
     calculate_priority(&sev, &imp).to_string()
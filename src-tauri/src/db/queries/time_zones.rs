@@ -0,0 +1,28 @@
+use sqlx::SqlitePool;
+
+use crate::db::queries::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::timezone::{TimeZoneConfig, AnalyticsZone};
+
+pub const TIME_ZONE_SETTING_KEY: &str = "time_zone_config";
+
+/// Load the analytics time zone configuration, defaulting to UTC.
+pub async fn get_time_zone_config(db: &SqlitePool) -> AppResult<TimeZoneConfig> {
+    match settings::get_setting(db, TIME_ZONE_SETTING_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Invalid stored time zone config: {}", e))),
+        None => Ok(TimeZoneConfig::default()),
+    }
+}
+
+pub async fn save_time_zone_config(db: &SqlitePool, config: &TimeZoneConfig) -> AppResult<TimeZoneConfig> {
+    let json = serde_json::to_string(config)?;
+    settings::set_setting(db, TIME_ZONE_SETTING_KEY, &json).await?;
+    Ok(config.clone())
+}
+
+/// The organization zone used for day, week and quarter boundaries.
+pub async fn org_time_zone(db: &SqlitePool) -> AppResult<AnalyticsZone> {
+    let config = get_time_zone_config(db).await?;
+    AnalyticsZone::parse(&config.org_time_zone)
+}
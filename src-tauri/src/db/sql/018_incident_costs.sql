@@ -0,0 +1,10 @@
+-- Migration 018: Incident cost estimation
+-- The cost model itself is stored in app_settings under 'cost_model'.
+-- This table holds manual per-incident overrides of the estimated cost.
+
+CREATE TABLE IF NOT EXISTS incident_cost_overrides (
+    incident_id TEXT PRIMARY KEY NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    amount REAL NOT NULL CHECK (amount >= 0),
+    note TEXT NOT NULL DEFAULT '',
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
@@ -0,0 +1,48 @@
+-- Migration 019: Materialized daily metric rollups
+-- One row per (local day, service, severity, impact) holding the sums and counts
+-- the dashboard aggregates need, with days in the organization's time zone
+-- (configured in app_settings under 'time_zone_config').
+-- Triggers mark affected days dirty whenever an incident changes; dirty days are
+-- recomputed from raw rows before the next read.
+
+CREATE TABLE IF NOT EXISTS metric_daily_rollups (
+    day TEXT NOT NULL,
+    service_id TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    impact TEXT NOT NULL,
+    incident_count INTEGER NOT NULL DEFAULT 0,
+    resolved_count INTEGER NOT NULL DEFAULT 0,
+    resolve_minutes_sum REAL NOT NULL DEFAULT 0,
+    ack_count INTEGER NOT NULL DEFAULT 0,
+    ack_minutes_sum REAL NOT NULL DEFAULT 0,
+    recurring_count INTEGER NOT NULL DEFAULT 0,
+    ticket_count INTEGER NOT NULL DEFAULT 0,
+    tickets_sum REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, service_id, severity, impact)
+);
+
+CREATE INDEX IF NOT EXISTS idx_metric_daily_rollups_service ON metric_daily_rollups(service_id, day);
+
+CREATE TABLE IF NOT EXISTS metric_rollup_dirty_days (
+    day TEXT PRIMARY KEY NOT NULL
+);
+
+CREATE TRIGGER IF NOT EXISTS incidents_rollup_insert AFTER INSERT ON incidents BEGIN
+    INSERT OR IGNORE INTO metric_rollup_dirty_days(day) VALUES (substr(new.started_at, 1, 10));
+END;
+
+CREATE TRIGGER IF NOT EXISTS incidents_rollup_update AFTER UPDATE OF
+    started_at, detected_at, acknowledged_at, responded_at, resolved_at,
+    service_id, severity, impact, is_recurring, tickets_submitted, deleted_at
+ON incidents BEGIN
+    INSERT OR IGNORE INTO metric_rollup_dirty_days(day) VALUES (substr(old.started_at, 1, 10));
+    INSERT OR IGNORE INTO metric_rollup_dirty_days(day) VALUES (substr(new.started_at, 1, 10));
+END;
+
+CREATE TRIGGER IF NOT EXISTS incidents_rollup_delete AFTER DELETE ON incidents BEGIN
+    INSERT OR IGNORE INTO metric_rollup_dirty_days(day) VALUES (substr(old.started_at, 1, 10));
+END;
+
+-- Existing incidents are rolled up lazily on first read
+INSERT OR IGNORE INTO metric_rollup_dirty_days(day)
+SELECT DISTINCT substr(started_at, 1, 10) FROM incidents;
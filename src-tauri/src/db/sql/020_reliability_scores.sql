@@ -0,0 +1,16 @@
+-- Migration 020: Reliability score history
+-- The scoring model is stored in app_settings under 'reliability_scoring'.
+-- Scores for closed quarters are frozen here the first time they are read,
+-- so later changes to the model or late edits do not rewrite history.
+
+CREATE TABLE IF NOT EXISTS reliability_score_history (
+    quarter_id TEXT NOT NULL REFERENCES quarter_config(id) ON DELETE CASCADE,
+    service_id TEXT NOT NULL REFERENCES services(id) ON DELETE CASCADE,
+    score REAL NOT NULL,
+    incident_count INTEGER NOT NULL DEFAULT 0,
+    breakdown TEXT NOT NULL DEFAULT '{}',
+    computed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY (quarter_id, service_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_reliability_score_history_service ON reliability_score_history(service_id);
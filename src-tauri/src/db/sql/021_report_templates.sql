@@ -0,0 +1,23 @@
+-- Migration 021: Report templates
+-- `sections` is an ordered JSON array of {kind, title, body, options};
+-- see models::report_template::TemplateSection.
+
+CREATE TABLE IF NOT EXISTS report_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    sections TEXT NOT NULL DEFAULT '[]',
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- Seed the classic quarterly layout
+INSERT OR IGNORE INTO report_templates (id, name, description, sections, is_default)
+VALUES (
+    'rpt-quarterly',
+    'Quarterly Review',
+    'Every built-in section in the standard order.',
+    '[{"kind":"executive_summary"},{"kind":"metrics_overview"},{"kind":"incident_timeline"},{"kind":"incident_breakdowns"},{"kind":"service_reliability"},{"kind":"cost_impact"},{"kind":"responder_load"},{"kind":"checklist_compliance"},{"kind":"qoq_comparison"},{"kind":"outlook"},{"kind":"discussion_points"},{"kind":"action_items"}]',
+    1
+);
@@ -0,0 +1,43 @@
+-- Migration 022: Discussion-point rules
+-- Each rule measures `metric` over the period, each service or each incident
+-- (`scope`) and adds `message` as a discussion point when the value compares
+-- to `threshold`; see models::discussion_rule.
+
+CREATE TABLE IF NOT EXISTS discussion_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    comparator TEXT NOT NULL,
+    threshold REAL NOT NULL,
+    scope TEXT NOT NULL CHECK (scope IN ('period', 'service', 'incident')),
+    message TEXT NOT NULL,
+    severity TEXT NOT NULL CHECK (severity IN ('critical', 'high', 'medium', 'low')),
+    enabled INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- Seed the ten rules the report has always applied
+INSERT OR IGNORE INTO discussion_rules (id, name, metric, comparator, threshold, scope, message, severity, sort_order, is_builtin) VALUES
+('dr-service-incidents', 'Repeated incidents on a service', 'incident_count', '>=', 3, 'service',
+ '{{service}} had {{value}} incidents this period. Are there systemic improvements that should be prioritized?', 'high', 1, 1),
+('dr-recurring', 'Recurring incident', 'recurring_incidents', '>=', 1, 'incident',
+ '''{{incident}}'' is a recurring incident. Were the original remediation action items fully implemented?', 'high', 2, 1),
+('dr-mttr-increase', 'MTTR increase', 'mttr_change_pct', '>', 5, 'period',
+ 'MTTR increased from {{previous}} to {{current}}. What contributed to slower incident resolution?', 'medium', 3, 1),
+('dr-mttr-decrease', 'MTTR improvement', 'mttr_change_pct', '<', -5, 'period',
+ 'MTTR improved from {{previous}} to {{current}}. Which response practices or tooling should we continue investing in?', 'low', 4, 1),
+('dr-p0', 'P0 incident occurred', 'p0_incidents', '>=', 1, 'period',
+ '{{value}} P0 incident(s) occurred ({{incidents}}). Is our incident response process adequate for critical situations?', 'critical', 5, 1),
+('dr-volume-increase', 'Incident volume increase', 'incident_count_change_pct', '>', 25, 'period',
+ 'Total incidents increased by {{value}} (from {{previous}} to {{current}}). Is this a trend or seasonal variation?', 'medium', 6, 1),
+('dr-service-downtime', 'Service downtime', 'downtime_minutes', '>', 60, 'service',
+ '{{service}} had {{value}} of total downtime. Is additional redundancy or failover investment justified?', 'medium', 7, 1),
+('dr-quiet-service', 'Previously problematic service now quiet', 'previous_incidents_now_quiet', '>=', 2, 'service',
+ '{{service}} had {{value}} incidents in the comparison period but zero this period. What changed?', 'low', 8, 1),
+('dr-open-actions', 'Open action items', 'open_action_items', '>=', 1, 'period',
+ '{{value}} action item(s) from previous incidents are still open. What is the status and expected completion?', 'medium', 9, 1),
+('dr-ticket-volume', 'High ticket volume', 'avg_tickets', '>', 10, 'period',
+ 'Average tickets per incident was {{value}}. Should we improve proactive communication or self-service documentation?', 'medium', 10, 1);
@@ -0,0 +1,10 @@
+-- Migration 023: Report snapshots
+-- `snapshot_json` holds the versioned inputs a report was rendered from
+-- (see reports::snapshot::ReportSnapshot) so it can be regenerated exactly;
+-- `artifact_sha256` is the hash of the saved file; `content_sha256` hashes the
+-- rendered document, which stays stable where DOCX and PDF file bytes do not.
+
+ALTER TABLE report_history ADD COLUMN snapshot_json TEXT;
+ALTER TABLE report_history ADD COLUMN snapshot_version INTEGER;
+ALTER TABLE report_history ADD COLUMN artifact_sha256 TEXT;
+ALTER TABLE report_history ADD COLUMN content_sha256 TEXT;
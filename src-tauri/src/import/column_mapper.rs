@@ -1,7 +1,11 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::{AppError, AppResult};
+use super::presets::{self, ImportPreset};
+
 /// A single mapped incident row ready for import.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MappedIncident {
@@ -24,6 +28,10 @@ pub struct MappedIncident {
     pub notes: String,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
+    /// Fields filled from the mapping's default values because the row left
+    /// them empty. Upserts only apply these to newly created incidents.
+    #[serde(skip)]
+    pub defaulted: HashSet<String>,
 }
 
 /// Column mapping: CSV column name -> incident field name.
@@ -32,10 +40,24 @@ pub struct MappedIncident {
 pub struct ColumnMapping {
     pub mappings: HashMap<String, String>,
     pub default_values: HashMap<String, String>,
+    /// Built-in preset (see `presets::PRESETS`) whose value translations and
+    /// field fallbacks apply to the mapped values.
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+impl ColumnMapping {
+    pub fn validate(&self) -> AppResult<()> {
+        if let Some(id) = &self.preset {
+            if presets::find_preset(id).is_none() {
+                return Err(AppError::Validation(format!("Unknown import preset '{}'", id)));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// All incident fields that can be mapped to.
-#[allow(dead_code)]
 pub const INCIDENT_FIELDS: &[&str] = &[
     "title",
     "service",
@@ -75,6 +97,24 @@ const VALID_STATUSES: &[&str] = &["Active", "Acknowledged", "Monitoring", "Resol
 pub fn apply_mapping(
     rows: &[HashMap<String, String>],
     mapping: &ColumnMapping,
+) -> Vec<MappedIncident> {
+    map_rows(rows, mapping, true)
+}
+
+/// Like `apply_mapping`, for JSON records. Values are taken as written:
+/// formula-injection escaping only applies to spreadsheet sources, and would
+/// alter text exported by `export_incidents_json` on every round trip.
+pub fn apply_json_mapping(
+    rows: &[HashMap<String, String>],
+    mapping: &ColumnMapping,
+) -> Vec<MappedIncident> {
+    map_rows(rows, mapping, false)
+}
+
+fn map_rows(
+    rows: &[HashMap<String, String>],
+    mapping: &ColumnMapping,
+    sanitize: bool,
 ) -> Vec<MappedIncident> {
     // Build reverse mapping: incident_field -> csv_column
     let reverse: HashMap<&str, &str> = mapping
@@ -82,10 +122,11 @@ pub fn apply_mapping(
         .iter()
         .map(|(csv_col, field)| (field.as_str(), csv_col.as_str()))
         .collect();
+    let preset = mapping.preset.as_deref().and_then(presets::find_preset);
 
     rows.iter()
         .enumerate()
-        .map(|(idx, row)| map_single_row(idx, row, &reverse, &mapping.default_values))
+        .map(|(idx, row)| map_single_row(idx, row, &reverse, &mapping.default_values, preset, sanitize))
         .collect()
 }
 
@@ -140,19 +181,46 @@ fn map_single_row(
     row: &HashMap<String, String>,
     reverse: &HashMap<&str, &str>,
     defaults: &HashMap<String, String>,
+    preset: Option<&ImportPreset>,
+    sanitize: bool,
 ) -> MappedIncident {
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
+    let defaulted = RefCell::new(HashSet::new());
 
-    let get_val = |field: &str| -> String {
+    let raw_val = |field: &str| -> String {
         if let Some(csv_col) = reverse.get(field) {
             if let Some(val) = row.get(*csv_col) {
                 if !val.is_empty() {
-                    return sanitize_csv_field(val);
+                    return if sanitize { sanitize_csv_field(val) } else { val.trim().to_string() };
+                }
+            }
+        }
+        match defaults.get(field).filter(|v| !v.is_empty()) {
+            Some(val) => {
+                defaulted.borrow_mut().insert(field.to_string());
+                val.clone()
+            }
+            None => String::new(),
+        }
+    };
+
+    // Presets fill empty fields from related columns and translate the
+    // source tool's values before validation.
+    let get_val = |field: &str| -> String {
+        let Some(preset) = preset else {
+            return raw_val(field);
+        };
+        let mut val = raw_val(field);
+        if val.is_empty() {
+            if let Some(source) = preset.fallback(field) {
+                val = raw_val(source);
+                if defaulted.borrow().contains(source) {
+                    defaulted.borrow_mut().insert(field.to_string());
                 }
             }
         }
-        defaults.get(field).cloned().unwrap_or_default()
+        preset.translate(field, &val)
     };
 
     let title = get_val("title");
@@ -277,6 +345,7 @@ fn map_single_row(
         notes: get_val("notes"),
         warnings,
         errors,
+        defaulted: defaulted.into_inner(),
     }
 }
 
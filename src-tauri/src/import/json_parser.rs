@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{AppError, AppResult};
+
+/// Maximum JSON file size: 10MB
+const MAX_JSON_SIZE: u64 = 10 * 1024 * 1024;
+
+const MAX_TAGS: usize = 50;
+const MAX_TAG_LEN: usize = 100;
+
+/// Prefix of flat export keys holding a custom field value, followed by the field id.
+const CUSTOM_KEY_PREFIX: &str = "custom:";
+
+/// An incident read from a JSON or NDJSON file, with the related records to
+/// create alongside it.
+#[derive(Debug, Clone, Default)]
+pub struct JsonIncident {
+    /// Incident fields keyed by `column_mapper::INCIDENT_FIELDS` name.
+    pub fields: HashMap<String, String>,
+    pub tags: Vec<String>,
+    /// Custom field values keyed by field id or field name.
+    pub custom_fields: Vec<(String, String)>,
+    pub action_items: Vec<JsonActionItem>,
+    pub contributing_factors: Vec<JsonContributingFactor>,
+    /// Problems with the related records that do not block the incident itself.
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonActionItem {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub due_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonContributingFactor {
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub is_root: bool,
+}
+
+/// Read incidents from a JSON array, a single JSON object, or NDJSON (one
+/// object per line). Accepts both the flat and nested shapes written by
+/// `export_incidents_json`.
+pub fn parse_json_incidents(file_path: &str) -> AppResult<Vec<JsonIncident>> {
+    let content = read_file(file_path)?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+
+    let objects = match serde_json::from_str::<Value>(content) {
+        Ok(Value::Array(items)) => items,
+        Ok(object @ Value::Object(_)) => vec![object],
+        Ok(_) => {
+            return Err(AppError::Validation(
+                "JSON import must be an array of incident objects".into(),
+            ))
+        }
+        Err(_) => parse_ndjson(content)?,
+    };
+
+    objects
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| match value {
+            Value::Object(object) => Ok(parse_incident(idx, &object)),
+            _ => Err(AppError::Validation(format!(
+                "Record {}: expected a JSON object",
+                idx + 1
+            ))),
+        })
+        .collect()
+}
+
+fn read_file(file_path: &str) -> AppResult<String> {
+    // Reject path traversal attempts
+    if file_path.contains("..") {
+        return Err(AppError::Validation("File path must not contain '..'".into()));
+    }
+
+    let metadata = std::fs::metadata(file_path)
+        .map_err(|e| AppError::Validation(format!("Cannot open JSON file: {}", e)))?;
+    if metadata.len() > MAX_JSON_SIZE {
+        return Err(AppError::Validation(format!(
+            "JSON file too large ({:.1} MB). Maximum is {} MB.",
+            metadata.len() as f64 / (1024.0 * 1024.0),
+            MAX_JSON_SIZE / (1024 * 1024)
+        )));
+    }
+
+    std::fs::read_to_string(file_path)
+        .map_err(|e| AppError::Validation(format!("Cannot read JSON file: {}", e)))
+}
+
+fn parse_ndjson(content: &str) -> AppResult<Vec<Value>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                AppError::Validation(format!("Line {}: invalid JSON: {}", idx + 1, e))
+            })
+        })
+        .collect()
+}
+
+fn parse_incident(idx: usize, object: &serde_json::Map<String, Value>) -> JsonIncident {
+    let mut incident = JsonIncident::default();
+
+    for (key, value) in object {
+        let field = match key.as_str() {
+            "service_name" => "service",
+            "service" if !object.contains_key("service_name") => "service",
+            key if super::column_mapper::INCIDENT_FIELDS.contains(&key) => key,
+            _ => {
+                if let (Some(field_id), Some(text)) = (key.strip_prefix(CUSTOM_KEY_PREFIX), scalar_text(value)) {
+                    incident.custom_fields.push((field_id.to_string(), text));
+                }
+                continue;
+            }
+        };
+        if let Some(text) = scalar_text(value) {
+            incident.fields.insert(field.to_string(), text);
+        }
+    }
+
+    // Nested exports keep related records under "related"; flat files may
+    // carry them at the top level.
+    let related = object.get("related").and_then(Value::as_object).unwrap_or(object);
+    let row = idx + 1;
+
+    if let Some(tags) = related.get("tags") {
+        incident.tags = parse_tags(row, tags, &mut incident.warnings);
+    }
+    if let Some(Value::Object(values)) = related.get("custom_fields") {
+        incident.custom_fields.extend(
+            values
+                .iter()
+                .filter_map(|(name, value)| scalar_text(value).map(|text| (name.clone(), text))),
+        );
+    }
+    // A string "action_items" is the legacy free-text column, not records.
+    if let Some(Value::Array(items)) = related.get("action_items") {
+        for item in items {
+            match serde_json::from_value::<JsonActionItem>(item.clone()) {
+                Ok(action) => incident.action_items.push(action),
+                Err(e) => incident
+                    .warnings
+                    .push(format!("Row {}: Invalid action item skipped: {}", row, e)),
+            }
+        }
+    }
+    if let Some(Value::Array(factors)) = related.get("contributing_factors") {
+        for factor in factors {
+            match parse_factor(factor) {
+                Some(factor) => incident.contributing_factors.push(factor),
+                None => incident
+                    .warnings
+                    .push(format!("Row {}: Invalid contributing factor skipped", row)),
+            }
+        }
+    }
+
+    incident
+}
+
+/// A JSON scalar as import text; `None` for null, arrays and objects.
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Tags as an array, or as one string joined with `;` or `,`.
+fn parse_tags(row: usize, value: &Value, warnings: &mut Vec<String>) -> Vec<String> {
+    let raw: Vec<String> = match value {
+        Value::Array(items) => items.iter().filter_map(scalar_text).collect(),
+        Value::String(text) => text.split([';', ',']).map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+
+    let mut tags = Vec::new();
+    for tag in raw.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if tag.chars().count() > MAX_TAG_LEN {
+            warnings.push(format!(
+                "Row {}: Tag longer than {} characters skipped",
+                row, MAX_TAG_LEN
+            ));
+        } else if tags.len() == MAX_TAGS {
+            warnings.push(format!("Row {}: Only the first {} tags are imported", row, MAX_TAGS));
+            break;
+        } else {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// A contributing factor object, or the flat export's
+/// `"Category (root): description"` text.
+fn parse_factor(value: &Value) -> Option<JsonContributingFactor> {
+    match value {
+        Value::Object(_) => serde_json::from_value(value.clone()).ok(),
+        Value::String(text) => {
+            let (label, description) = text.split_once(':')?;
+            let (category, is_root) = match label.trim().strip_suffix("(root)") {
+                Some(category) => (category.trim(), true),
+                None => (label.trim(), false),
+            };
+            Some(JsonContributingFactor {
+                category: category.to_string(),
+                description: description.trim().to_string(),
+                is_root,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_json(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().expect("create temp file");
+        file.write_all(content.as_bytes()).expect("write json");
+        file.flush().expect("flush");
+        file
+    }
+
+    /// Test: arrays and NDJSON parse to the same incidents, flat related values included
+    #[test]
+    fn test_parse_array_and_ndjson() {
+        let array = write_json(
+            r#"[{"title": "DB outage", "service_name": "Slack", "severity": "High", "affected_users": 12,
+                 "is_recurring": true, "tags": ["db", "  "], "custom:cf-1": "EU",
+                 "contributing_factors": ["Tooling (root): Missing alert", "Process: No runbook"]},
+                {"title": "Second", "service": "Zoom", "resolved_at": null}]"#,
+        );
+        let ndjson = write_json(
+            "{\"title\": \"DB outage\", \"service_name\": \"Slack\", \"severity\": \"High\", \"affected_users\": 12, \"is_recurring\": true, \"tags\": [\"db\", \"  \"], \"custom:cf-1\": \"EU\", \"contributing_factors\": [\"Tooling (root): Missing alert\", \"Process: No runbook\"]}\n\n{\"title\": \"Second\", \"service\": \"Zoom\", \"resolved_at\": null}\n",
+        );
+
+        for file in [array, ndjson] {
+            let incidents = parse_json_incidents(file.path().to_str().expect("path")).expect("parse");
+            assert_eq!(incidents.len(), 2);
+            let first = &incidents[0];
+            assert_eq!(first.fields.get("service").map(String::as_str), Some("Slack"));
+            assert_eq!(first.fields.get("affected_users").map(String::as_str), Some("12"));
+            assert_eq!(first.fields.get("is_recurring").map(String::as_str), Some("true"));
+            assert_eq!(first.tags, vec!["db"]);
+            assert_eq!(first.custom_fields, vec![("cf-1".to_string(), "EU".to_string())]);
+            assert_eq!(first.contributing_factors.len(), 2);
+            assert!(first.contributing_factors[0].is_root);
+            assert_eq!(first.contributing_factors[0].category, "Tooling");
+            assert_eq!(first.contributing_factors[1].description, "No runbook");
+
+            let second = &incidents[1];
+            assert_eq!(second.fields.get("service").map(String::as_str), Some("Zoom"));
+            assert!(!second.fields.contains_key("resolved_at"));
+        }
+    }
+
+    /// Test: nested exports read related records, and bad lines or shapes are rejected
+    #[test]
+    fn test_parse_nested_and_invalid() {
+        let nested = write_json(
+            r#"{"title": "Nested", "action_items": "legacy text",
+                "related": {"tags": "a; b", "custom_fields": {"Region": "EU"},
+                            "action_items": [{"title": "Add alert", "owner": "sam", "id": "ai-1"}, 42],
+                            "contributing_factors": [{"category": "Process", "description": "No runbook"}]}}"#,
+        );
+        let incidents = parse_json_incidents(nested.path().to_str().expect("path")).expect("parse");
+        assert_eq!(incidents.len(), 1);
+        let incident = &incidents[0];
+        assert_eq!(incident.tags, vec!["a", "b"]);
+        assert_eq!(incident.custom_fields, vec![("Region".to_string(), "EU".to_string())]);
+        assert_eq!(incident.action_items.len(), 1);
+        assert_eq!(incident.action_items[0].owner, "sam");
+        assert_eq!(incident.contributing_factors.len(), 1);
+        assert_eq!(incident.warnings.len(), 1);
+
+        let bad_line = write_json("{\"title\": \"ok\"}\n{not json}\n");
+        let err = parse_json_incidents(bad_line.path().to_str().expect("path")).expect_err("bad line");
+        assert!(err.to_string().contains("Line 2"));
+
+        let scalar = write_json("[1, 2]");
+        assert!(parse_json_incidents(scalar.path().to_str().expect("path")).is_err());
+    }
+}
@@ -1,2 +1,5 @@
 pub mod csv_parser;
 pub mod column_mapper;
+pub mod json_parser;
+pub mod presets;
+pub mod upsert;
@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use super::column_mapper::ColumnMapping;
+
+/// A built-in mapping for the CSV export of an incident or ticketing tool.
+///
+/// Header names are compared normalized (lowercase letters and digits only),
+/// so `Issue key`, `issue_key` and `IssueKey` all match `issuekey`.
+#[derive(Debug)]
+pub struct ImportPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Headers that must all be present for the preset to be detected.
+    signature: &'static [&'static str],
+    /// (header, incident field) in priority order; the first present header
+    /// wins each field.
+    columns: &'static [(&'static str, &'static str)],
+    /// (field, source field): an empty field takes the source field's raw value.
+    fallbacks: &'static [(&'static str, &'static str)],
+    severities: &'static [(&'static str, &'static str)],
+    impacts: &'static [(&'static str, &'static str)],
+    statuses: &'static [(&'static str, &'static str)],
+    /// chrono formats for naive timestamps, read as UTC. RFC 3339 and epoch
+    /// seconds or milliseconds are always accepted.
+    date_formats: &'static [&'static str],
+    external_ref_prefix: &'static str,
+}
+
+const DATE_FIELDS: &[&str] = &["started_at", "detected_at", "responded_at", "resolved_at"];
+
+const JIRA_PRIORITIES: &[(&str, &str)] = &[
+    ("blocker", "Critical"),
+    ("highest", "Critical"),
+    ("critical", "High"),
+    ("high", "High"),
+    ("major", "Medium"),
+    ("medium", "Medium"),
+    ("minor", "Low"),
+    ("low", "Low"),
+    ("lowest", "Low"),
+    ("trivial", "Low"),
+];
+
+const P_LEVELS: &[(&str, &str)] = &[
+    ("p1", "Critical"),
+    ("p2", "High"),
+    ("p3", "Medium"),
+    ("p4", "Low"),
+    ("p5", "Low"),
+    ("high", "High"),
+    ("low", "Low"),
+];
+
+const SERVICENOW_PRIORITIES: &[(&str, &str)] = &[
+    ("1", "Critical"),
+    ("2", "High"),
+    ("3", "Medium"),
+    ("4", "Low"),
+    ("5", "Low"),
+    ("critical", "Critical"),
+    ("high", "High"),
+    ("moderate", "Medium"),
+    ("low", "Low"),
+    ("planning", "Low"),
+];
+
+const SERVICENOW_IMPACTS: &[(&str, &str)] = &[
+    ("1", "High"),
+    ("2", "Medium"),
+    ("3", "Low"),
+    ("high", "High"),
+    ("medium", "Medium"),
+    ("low", "Low"),
+];
+
+pub const PRESETS: &[ImportPreset] = &[
+    ImportPreset {
+        id: "jira",
+        name: "Jira",
+        signature: &["issuekey", "summary", "issuetype"],
+        columns: &[
+            ("summary", "title"),
+            ("components", "service"),
+            ("projectname", "service"),
+            ("priority", "severity"),
+            ("status", "status"),
+            ("created", "started_at"),
+            ("resolved", "resolved_at"),
+            ("resolution", "resolution"),
+            ("description", "notes"),
+            ("issuekey", "external_ref"),
+        ],
+        fallbacks: &[("detected_at", "started_at"), ("impact", "severity")],
+        severities: JIRA_PRIORITIES,
+        impacts: JIRA_PRIORITIES,
+        statuses: &[
+            ("to do", "Active"),
+            ("open", "Active"),
+            ("new", "Active"),
+            ("backlog", "Active"),
+            ("reopened", "Active"),
+            ("in progress", "Acknowledged"),
+            ("in review", "Acknowledged"),
+            ("monitoring", "Monitoring"),
+            ("done", "Resolved"),
+            ("resolved", "Resolved"),
+            ("closed", "Resolved"),
+        ],
+        date_formats: &["%d/%b/%y %I:%M %p", "%d/%b/%Y %I:%M %p", "%Y-%m-%d %H:%M"],
+        external_ref_prefix: "",
+    },
+    ImportPreset {
+        id: "pagerduty",
+        name: "PagerDuty",
+        signature: &["incidentnumber", "urgency", "servicename"],
+        columns: &[
+            ("title", "title"),
+            ("description", "title"),
+            ("servicename", "service"),
+            ("priority", "severity"),
+            ("urgency", "impact"),
+            ("status", "status"),
+            ("createdon", "started_at"),
+            ("createdat", "started_at"),
+            ("acknowledgedon", "responded_at"),
+            ("resolvedon", "resolved_at"),
+            ("resolvedat", "resolved_at"),
+            ("incidentnumber", "external_ref"),
+        ],
+        fallbacks: &[("detected_at", "started_at"), ("severity", "impact")],
+        severities: P_LEVELS,
+        impacts: &[("high", "High"), ("low", "Low")],
+        statuses: &[
+            ("triggered", "Active"),
+            ("acknowledged", "Acknowledged"),
+            ("resolved", "Resolved"),
+        ],
+        date_formats: &["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"],
+        external_ref_prefix: "PD-",
+    },
+    ImportPreset {
+        id: "opsgenie",
+        name: "Opsgenie",
+        signature: &["tinyid", "message", "priority"],
+        columns: &[
+            ("message", "title"),
+            ("impactedservices", "service"),
+            ("source", "service"),
+            ("priority", "severity"),
+            ("status", "status"),
+            ("createdat", "started_at"),
+            ("acknowledgedat", "responded_at"),
+            ("closedat", "resolved_at"),
+            ("resolvedat", "resolved_at"),
+            ("description", "notes"),
+            ("tinyid", "external_ref"),
+        ],
+        fallbacks: &[("detected_at", "started_at"), ("impact", "severity")],
+        severities: P_LEVELS,
+        impacts: P_LEVELS,
+        statuses: &[
+            ("open", "Active"),
+            ("acknowledged", "Acknowledged"),
+            ("resolved", "Resolved"),
+            ("closed", "Resolved"),
+        ],
+        date_formats: &["%Y-%m-%d %H:%M:%S%.f", "%Y/%m/%d %H:%M:%S%.f"],
+        external_ref_prefix: "OG-",
+    },
+    ImportPreset {
+        id: "servicenow",
+        name: "ServiceNow",
+        signature: &["number", "shortdescription", "state"],
+        columns: &[
+            ("shortdescription", "title"),
+            ("businessservice", "service"),
+            ("service", "service"),
+            ("cmdbci", "service"),
+            ("configurationitem", "service"),
+            ("priority", "severity"),
+            ("impact", "impact"),
+            ("state", "status"),
+            ("openedat", "started_at"),
+            ("opened", "started_at"),
+            ("resolvedat", "resolved_at"),
+            ("resolved", "resolved_at"),
+            ("closenotes", "resolution"),
+            ("resolutionnotes", "resolution"),
+            ("description", "notes"),
+            ("number", "external_ref"),
+        ],
+        fallbacks: &[("detected_at", "started_at"), ("impact", "severity")],
+        severities: SERVICENOW_PRIORITIES,
+        impacts: SERVICENOW_IMPACTS,
+        statuses: &[
+            ("1", "Active"),
+            ("2", "Acknowledged"),
+            ("3", "Monitoring"),
+            ("6", "Resolved"),
+            ("7", "Resolved"),
+            ("8", "Resolved"),
+            ("new", "Active"),
+            ("in progress", "Acknowledged"),
+            ("on hold", "Monitoring"),
+            ("resolved", "Resolved"),
+            ("closed", "Resolved"),
+            ("canceled", "Resolved"),
+        ],
+        date_formats: &["%Y-%m-%d %H:%M:%S", "%m/%d/%Y %I:%M:%S %p", "%m-%d-%Y %H:%M:%S"],
+        external_ref_prefix: "",
+    },
+];
+
+pub fn find_preset(id: &str) -> Option<&'static ImportPreset> {
+    PRESETS.iter().find(|p| p.id == id)
+}
+
+/// The preset whose signature headers all appear, preferring the one that
+/// maps the most columns.
+pub fn detect_preset(headers: &[String]) -> Option<&'static ImportPreset> {
+    let normalized: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+    PRESETS
+        .iter()
+        .filter(|p| p.signature.iter().all(|s| normalized.iter().any(|h| h == s)))
+        .max_by_key(|p| p.mapping_for(headers).mappings.len())
+}
+
+impl ImportPreset {
+    /// Column mapping for a file's headers, tagged with this preset so its
+    /// value translations apply.
+    pub fn mapping_for(&self, headers: &[String]) -> ColumnMapping {
+        let mut mappings = HashMap::new();
+        for (column, field) in self.columns {
+            if mappings.values().any(|f| f == field) {
+                continue;
+            }
+            if let Some(header) = headers
+                .iter()
+                .find(|h| normalize_header(h) == *column && !mappings.contains_key(*h))
+            {
+                mappings.insert(header.clone(), field.to_string());
+            }
+        }
+        ColumnMapping {
+            mappings,
+            default_values: HashMap::new(),
+            preset: Some(self.id.to_string()),
+        }
+    }
+
+    /// The field whose raw value stands in when `field` is empty.
+    pub fn fallback(&self, field: &str) -> Option<&'static str> {
+        self.fallbacks
+            .iter()
+            .find(|(target, _)| *target == field)
+            .map(|(_, source)| *source)
+    }
+
+    /// Translate a raw value for `field` into our vocabulary. Unrecognized
+    /// values pass through for the normal validation to flag.
+    pub fn translate(&self, field: &str, value: &str) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+        let vocabulary = match field {
+            "severity" => Some(self.severities),
+            "impact" => Some(self.impacts),
+            "status" => Some(self.statuses),
+            _ => None,
+        };
+        if let Some(vocabulary) = vocabulary {
+            return lookup(vocabulary, value).unwrap_or(value).to_string();
+        }
+        if DATE_FIELDS.contains(&field) {
+            return parse_date(value, self.date_formats).unwrap_or_else(|| value.to_string());
+        }
+        if field == "external_ref" && !value.starts_with(self.external_ref_prefix) {
+            return format!("{}{}", self.external_ref_prefix, value);
+        }
+        value.to_string()
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Case-insensitive vocabulary lookup. A key also matches values that start
+/// with it followed by a separator, so `1` matches ServiceNow's `1 - Critical`.
+fn lookup(vocabulary: &[(&str, &'static str)], value: &str) -> Option<&'static str> {
+    let value = value.trim().to_lowercase();
+    vocabulary
+        .iter()
+        .find(|(key, _)| value == *key)
+        .or_else(|| {
+            vocabulary.iter().find(|(key, _)| {
+                value
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with([' ', '-']))
+            })
+        })
+        .map(|(_, ours)| *ours)
+}
+
+/// Parse a timestamp into the stored `%Y-%m-%dT%H:%M:%SZ` form.
+fn parse_date(value: &str, formats: &[&str]) -> Option<String> {
+    let value = value.trim();
+    let parsed = DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            let epoch: i64 = value.parse().ok()?;
+            match value.len() {
+                13 => Utc.timestamp_millis_opt(epoch).single(),
+                10 => Utc.timestamp_opt(epoch, 0).single(),
+                _ => None,
+            }
+        })
+        .or_else(|| {
+            formats
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+                .map(|naive| naive.and_utc())
+        })?;
+    Some(parsed.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::column_mapper::apply_mapping;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|h| h.to_string()).collect()
+    }
+
+    /// Test: each tool's export headers detect its preset, and unrelated headers detect none
+    #[test]
+    fn test_detect_preset() {
+        let cases = [
+            (vec!["Summary", "Issue key", "Issue id", "Issue Type", "Status", "Priority", "Created"], "jira"),
+            (vec!["id", "incident_number", "description", "service_name", "urgency", "status", "created_on"], "pagerduty"),
+            (vec!["TinyId", "Message", "Status", "Priority", "CreatedAt", "Source"], "opsgenie"),
+            (vec!["number", "short_description", "priority", "state", "opened_at"], "servicenow"),
+        ];
+        for (names, expected) in cases {
+            assert_eq!(detect_preset(&headers(&names)).map(|p| p.id), Some(expected));
+        }
+        assert!(detect_preset(&headers(&["Title", "Service", "Severity"])).is_none());
+
+        let mapping = find_preset("jira").unwrap().mapping_for(&headers(&["Summary", "Issue key", "Component/s"]));
+        assert_eq!(mapping.mappings.get("Component/s").map(String::as_str), Some("service"));
+        assert_eq!(mapping.mappings.get("Issue key").map(String::as_str), Some("external_ref"));
+        assert_eq!(mapping.preset.as_deref(), Some("jira"));
+    }
+
+    /// Test: presets translate vocabularies, dates and references, and fill fields from fallbacks
+    #[test]
+    fn test_preset_translation() {
+        let jira = find_preset("jira").unwrap();
+        let names = headers(&["Summary", "Issue key", "Issue Type", "Component/s", "Priority", "Status", "Created", "Resolved"]);
+        let row: HashMap<String, String> = names
+            .iter()
+            .cloned()
+            .zip(["Checkout down", "OPS-42", "Incident", "Slack", "Highest", "Done", "12/Mar/24 3:45 PM", "12/Mar/24 4:15 PM"].map(String::from))
+            .collect();
+        let mapped = apply_mapping(&[row], &jira.mapping_for(&names));
+        let incident = &mapped[0];
+        assert!(incident.errors.is_empty(), "{:?}", incident.errors);
+        assert!(incident.warnings.is_empty(), "{:?}", incident.warnings);
+        assert_eq!(incident.severity, "Critical");
+        assert_eq!(incident.impact, "Critical");
+        assert_eq!(incident.status, "Resolved");
+        assert_eq!(incident.started_at, "2024-03-12T15:45:00Z");
+        assert_eq!(incident.detected_at, "2024-03-12T15:45:00Z");
+        assert_eq!(incident.resolved_at.as_deref(), Some("2024-03-12T16:15:00Z"));
+        assert_eq!(incident.external_ref, "OPS-42");
+
+        let pagerduty = find_preset("pagerduty").unwrap();
+        assert_eq!(pagerduty.translate("external_ref", "1234"), "PD-1234");
+        assert_eq!(pagerduty.translate("status", "Triggered"), "Active");
+        assert_eq!(pagerduty.translate("started_at", "2024-03-12T10:45:00-05:00"), "2024-03-12T15:45:00Z");
+
+        let opsgenie = find_preset("opsgenie").unwrap();
+        assert_eq!(opsgenie.translate("started_at", "1710258300000"), "2024-03-12T15:45:00Z");
+        assert_eq!(opsgenie.translate("severity", "P3"), "Medium");
+
+        let servicenow = find_preset("servicenow").unwrap();
+        assert_eq!(servicenow.translate("severity", "1 - Critical"), "Critical");
+        assert_eq!(servicenow.translate("status", "In Progress"), "Acknowledged");
+        assert_eq!(servicenow.translate("started_at", "03/12/2024 03:45:00 PM"), "2024-03-12T15:45:00Z");
+        assert_eq!(servicenow.translate("impact", "Unknown"), "Unknown");
+    }
+}
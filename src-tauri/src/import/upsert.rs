@@ -0,0 +1,308 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::models::incident::{Incident, UpdateIncidentRequest};
+use super::column_mapper::MappedIncident;
+
+/// Incident fields an upsert import can match existing incidents on. Custom
+/// fields are not supported; keep upstream IDs in `external_ref`.
+const MATCH_FIELDS: &[&str] = &["external_ref", "title"];
+
+/// Whether an import only creates incidents or also updates existing ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Every valid row creates a new incident.
+    #[default]
+    Create,
+    /// Rows whose match field equals an existing incident's update it; the
+    /// rest create new incidents.
+    Upsert,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    pub mode: ImportMode,
+    /// Incident field identifying the existing incident in upsert mode.
+    pub match_field: String,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            mode: ImportMode::Create,
+            match_field: "external_ref".to_string(),
+        }
+    }
+}
+
+impl ImportOptions {
+    pub fn validate(&self) -> AppResult<()> {
+        if !MATCH_FIELDS.contains(&self.match_field.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Cannot match incidents on '{}'. Supported match columns: {}",
+                self.match_field,
+                MATCH_FIELDS.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// In upsert mode, require the source to supply the match column.
+    pub fn require_match_column(&self, fields: &HashSet<String>) -> AppResult<()> {
+        if self.mode == ImportMode::Upsert && !fields.contains(&self.match_field) {
+            return Err(AppError::Validation(format!(
+                "Upsert matches on '{}', but no column is mapped to it",
+                self.match_field
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One field an upsert would change on an existing incident.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// What importing a row will do.
+#[derive(Debug, Clone)]
+pub enum RowPlan {
+    Create,
+    Update {
+        incident_id: String,
+        changes: Vec<FieldChange>,
+        request: Box<UpdateIncidentRequest>,
+    },
+    Unchanged {
+        incident_id: String,
+    },
+    /// The row cannot be matched unambiguously.
+    Conflict(String),
+}
+
+impl RowPlan {
+    pub fn action(&self) -> &'static str {
+        match self {
+            RowPlan::Create => "create",
+            RowPlan::Update { .. } => "update",
+            RowPlan::Unchanged { .. } => "unchanged",
+            RowPlan::Conflict(_) => "conflict",
+        }
+    }
+
+    /// The existing incident the row matched, if any.
+    pub fn incident_id(&self) -> Option<&str> {
+        match self {
+            RowPlan::Update { incident_id, .. } | RowPlan::Unchanged { incident_id } => Some(incident_id),
+            RowPlan::Create | RowPlan::Conflict(_) => None,
+        }
+    }
+}
+
+/// Plan each mapped row against the existing incidents.
+///
+/// `provided` holds, per row, the fields the source actually supplied, so
+/// that numeric and boolean defaults for missing columns do not overwrite
+/// stored values. Empty values never clear a stored value either, and
+/// mapping defaults (`MappedIncident::defaulted`) only apply to new incidents.
+pub fn plan_rows(
+    existing: &[Incident],
+    mapped: &[MappedIncident],
+    provided: &[HashSet<String>],
+    services: &HashMap<String, (String, String)>,
+    options: &ImportOptions,
+) -> Vec<RowPlan> {
+    if options.mode == ImportMode::Create {
+        return mapped.iter().map(|_| RowPlan::Create).collect();
+    }
+
+    let mut by_key: HashMap<&str, Vec<&Incident>> = HashMap::new();
+    for incident in existing {
+        let key = existing_key(incident, &options.match_field).trim();
+        if !key.is_empty() {
+            by_key.entry(key).or_default().push(incident);
+        }
+    }
+
+    let empty = HashSet::new();
+    let mut seen = HashSet::new();
+    mapped
+        .iter()
+        .enumerate()
+        .map(|(idx, incident)| {
+            let key = mapped_key(incident, &options.match_field).trim();
+            if key.is_empty() {
+                return RowPlan::Create;
+            }
+            if !seen.insert(key) {
+                return RowPlan::Conflict(format!(
+                    "Row {}: Duplicate {} '{}' in file",
+                    idx + 1,
+                    options.match_field,
+                    key
+                ));
+            }
+            match by_key.get(key).map(Vec::as_slice) {
+                None | Some([]) => RowPlan::Create,
+                Some([current]) => {
+                    let service = services.get(&incident.service_name.to_lowercase());
+                    let fields = provided.get(idx).unwrap_or(&empty);
+                    let (changes, request) = diff_incident(current, incident, service, fields);
+                    if changes.is_empty() {
+                        RowPlan::Unchanged {
+                            incident_id: current.id.clone(),
+                        }
+                    } else {
+                        RowPlan::Update {
+                            incident_id: current.id.clone(),
+                            changes,
+                            request: Box::new(request),
+                        }
+                    }
+                }
+                Some(matches) => RowPlan::Conflict(format!(
+                    "Row {}: {} '{}' matches {} existing incidents",
+                    idx + 1,
+                    options.match_field,
+                    key,
+                    matches.len()
+                )),
+            }
+        })
+        .collect()
+}
+
+fn existing_key<'a>(incident: &'a Incident, field: &str) -> &'a str {
+    match field {
+        "title" => &incident.title,
+        _ => &incident.external_ref,
+    }
+}
+
+fn mapped_key<'a>(incident: &'a MappedIncident, field: &str) -> &'a str {
+    match field {
+        "title" => &incident.title,
+        _ => &incident.external_ref,
+    }
+}
+
+/// Changed fields of an existing incident and the update that applies them.
+fn diff_incident(
+    existing: &Incident,
+    incident: &MappedIncident,
+    service: Option<&(String, String)>,
+    provided: &HashSet<String>,
+) -> (Vec<FieldChange>, UpdateIncidentRequest) {
+    let mut changes = Vec::new();
+    let mut request = UpdateIncidentRequest {
+        title: None,
+        service_id: None,
+        severity: None,
+        impact: None,
+        status: None,
+        started_at: None,
+        detected_at: None,
+        acknowledged_at: None,
+        first_response_at: None,
+        mitigation_started_at: None,
+        responded_at: None,
+        resolved_at: None,
+        root_cause: None,
+        resolution: None,
+        tickets_submitted: None,
+        affected_users: None,
+        is_recurring: None,
+        recurrence_of: None,
+        lessons_learned: None,
+        action_items: None,
+        external_ref: None,
+        notes: None,
+    };
+
+    let defaulted = |field: &str| incident.defaulted.contains(field);
+    let mut text = |field: &str, old: &str, new: &str, target: &mut Option<String>| {
+        if !new.is_empty() && new != old && !defaulted(field) {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                old_value: old.to_string(),
+                new_value: new.to_string(),
+            });
+            *target = Some(new.to_string());
+        }
+    };
+
+    text("title", &existing.title, &incident.title, &mut request.title);
+    text("severity", &existing.severity, &incident.severity, &mut request.severity);
+    text("impact", &existing.impact, &incident.impact, &mut request.impact);
+    text("status", &existing.status, &incident.status, &mut request.status);
+    text("started_at", &existing.started_at, &incident.started_at, &mut request.started_at);
+    text("detected_at", &existing.detected_at, &incident.detected_at, &mut request.detected_at);
+    text(
+        "responded_at",
+        existing.responded_at.as_deref().unwrap_or_default(),
+        incident.responded_at.as_deref().unwrap_or_default(),
+        &mut request.responded_at,
+    );
+    text(
+        "resolved_at",
+        existing.resolved_at.as_deref().unwrap_or_default(),
+        incident.resolved_at.as_deref().unwrap_or_default(),
+        &mut request.resolved_at,
+    );
+    text("root_cause", &existing.root_cause, &incident.root_cause, &mut request.root_cause);
+    text("resolution", &existing.resolution, &incident.resolution, &mut request.resolution);
+    text(
+        "lessons_learned",
+        &existing.lessons_learned,
+        &incident.lessons_learned,
+        &mut request.lessons_learned,
+    );
+    text("external_ref", &existing.external_ref, &incident.external_ref, &mut request.external_ref);
+    text("notes", &existing.notes, &incident.notes, &mut request.notes);
+
+    if let Some((service_id, service_name)) = service.filter(|_| !defaulted("service")) {
+        if service_id != &existing.service_id {
+            changes.push(FieldChange {
+                field: "service".into(),
+                old_value: existing.service_name.clone(),
+                new_value: service_name.clone(),
+            });
+            request.service_id = Some(service_id.clone());
+        }
+    }
+
+    let supplied = |field: &str| provided.contains(field) && !defaulted(field);
+    if supplied("tickets_submitted") && incident.tickets_submitted != existing.tickets_submitted {
+        changes.push(FieldChange {
+            field: "tickets_submitted".into(),
+            old_value: existing.tickets_submitted.to_string(),
+            new_value: incident.tickets_submitted.to_string(),
+        });
+        request.tickets_submitted = Some(incident.tickets_submitted);
+    }
+    if supplied("affected_users") && incident.affected_users != existing.affected_users {
+        changes.push(FieldChange {
+            field: "affected_users".into(),
+            old_value: existing.affected_users.to_string(),
+            new_value: incident.affected_users.to_string(),
+        });
+        request.affected_users = Some(incident.affected_users);
+    }
+    if supplied("is_recurring") && incident.is_recurring != existing.is_recurring {
+        changes.push(FieldChange {
+            field: "is_recurring".into(),
+            old_value: existing.is_recurring.to_string(),
+            new_value: incident.is_recurring.to_string(),
+        });
+        request.is_recurring = Some(incident.is_recurring);
+    }
+
+    (changes, request)
+}
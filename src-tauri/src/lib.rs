@@ -60,12 +60,23 @@ pub fn run() {
             commands::services::remove_service_dependency,
             commands::services::list_service_dependencies,
             commands::services::list_service_dependents,
+            commands::services::get_blast_radius,
+            commands::services::suggest_affected_services,
+            commands::services::get_critical_path_services,
             // Settings
             commands::settings::get_quarter_configs,
             commands::settings::upsert_quarter_config,
             commands::settings::delete_quarter_config,
             commands::settings::get_setting,
             commands::settings::set_setting,
+            commands::settings::get_time_zone_config,
+            commands::settings::update_time_zone_config,
+            commands::settings::list_time_zones,
+            commands::settings::get_working_hours_config,
+            commands::settings::update_working_hours_config,
+            commands::settings::get_reliability_scoring_config,
+            commands::settings::update_reliability_scoring_config,
+            commands::settings::rebuild_reliability_history,
             commands::settings::export_all_data,
             commands::settings::import_backup,
             // Tags
@@ -99,7 +110,21 @@ pub fn run() {
             commands::metrics::get_incident_by_hour,
             commands::metrics::get_backlog_aging,
             commands::metrics::get_service_reliability,
+            commands::metrics::get_reliability_history,
             commands::metrics::get_escalation_funnel,
+            commands::metrics::get_responder_load,
+            commands::metrics::get_checklist_compliance,
+            commands::metrics::get_metric_timeseries,
+            commands::metrics::get_incident_forecast,
+            commands::metrics::rebuild_metric_rollups,
+            commands::metrics::check_metric_rollups,
+            // Costs
+            commands::costs::get_cost_model,
+            commands::costs::update_cost_model,
+            commands::costs::get_incident_cost,
+            commands::costs::set_incident_cost_override,
+            commands::costs::clear_incident_cost_override,
+            commands::costs::get_cost_summary,
             // Saved Filters
             commands::saved_filters::list_saved_filters,
             commands::saved_filters::create_saved_filter,
@@ -109,9 +134,24 @@ pub fn run() {
             commands::reports::generate_report,
             commands::reports::save_report,
             commands::reports::generate_discussion_points,
+            commands::reports::list_discussion_rules,
+            commands::reports::create_discussion_rule,
+            commands::reports::update_discussion_rule,
+            commands::reports::delete_discussion_rule,
+            commands::reports::dry_run_discussion_rules,
             commands::reports::list_report_history,
             commands::reports::delete_report_history_entry,
+            commands::reports::regenerate_from_snapshot,
+            commands::reports::get_redaction_profile,
+            commands::reports::update_redaction_profile,
+            commands::reports::get_report_theme,
+            commands::reports::update_report_theme,
+            commands::reports::diff_report_snapshots,
             commands::reports::generate_narrative,
+            commands::reports::list_report_templates,
+            commands::reports::create_report_template,
+            commands::reports::update_report_template,
+            commands::reports::delete_report_template,
             // Roles
             commands::roles::assign_role,
             commands::roles::unassign_role,
@@ -138,6 +178,8 @@ pub fn run() {
             commands::import::parse_csv_headers,
             commands::import::preview_csv_import,
             commands::import::execute_csv_import,
+            commands::import::preview_json_import,
+            commands::import::execute_json_import,
             commands::import::list_import_templates,
             commands::import::save_import_template,
             commands::import::delete_import_template,
@@ -173,6 +215,8 @@ pub fn run() {
             // Export
             commands::export::export_incidents_csv,
             commands::export::export_incidents_json,
+            commands::export::export_incidents_xlsx,
+            commands::export::list_export_columns,
             // Backup
             commands::backup::create_backup,
             commands::backup::list_backups,
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::error::{AppError, AppResult};
@@ -74,6 +76,185 @@ pub struct ToggleChecklistItemRequest {
     pub checked_by: Option<String>,
 }
 
+/// Completion figures for a set of template checklist items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceStats {
+    pub checklist_count: i64,
+    pub item_count: i64,
+    pub checked_count: i64,
+    pub completion_rate: f64,
+    /// Items on incidents that have been resolved.
+    pub resolved_item_count: i64,
+    /// Items still unchecked when their incident was resolved.
+    pub unchecked_at_resolution: i64,
+    pub skip_rate: f64,
+    /// Median minutes from incident start to the item being checked.
+    pub median_minutes_to_check: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceGroup {
+    pub key: String,
+    pub label: String,
+    pub stats: ComplianceStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemCompliance {
+    pub key: String,
+    pub template_id: String,
+    pub template_name: String,
+    pub label: String,
+    pub stats: ComplianceStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistCompliance {
+    pub start_date: String,
+    pub end_date: String,
+    pub overall: ComplianceStats,
+    pub templates: Vec<ComplianceGroup>,
+    pub items: Vec<ItemCompliance>,
+    /// Items most often left unchecked at resolution, worst first.
+    pub most_skipped: Vec<ItemCompliance>,
+    pub by_service: Vec<ComplianceGroup>,
+    pub by_severity: Vec<ComplianceGroup>,
+}
+
+/// One template checklist item on one incident.
+#[derive(Debug, Clone)]
+pub struct ItemObservation {
+    pub checklist_id: String,
+    pub template_id: String,
+    pub template_name: String,
+    pub item_key: String,
+    pub label: String,
+    pub service_id: String,
+    pub service_name: String,
+    pub severity: String,
+    pub is_checked: bool,
+    pub minutes_to_check: Option<f64>,
+    pub resolved: bool,
+    pub unchecked_at_resolution: bool,
+}
+
+pub fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+pub fn compliance_stats<'a>(items: impl IntoIterator<Item = &'a ItemObservation>) -> ComplianceStats {
+    let mut stats = ComplianceStats::default();
+    let mut checklists: Vec<&str> = vec![];
+    let mut minutes: Vec<f64> = vec![];
+    for item in items {
+        if !checklists.contains(&item.checklist_id.as_str()) {
+            checklists.push(&item.checklist_id);
+        }
+        stats.item_count += 1;
+        if item.is_checked {
+            stats.checked_count += 1;
+        }
+        if item.resolved {
+            stats.resolved_item_count += 1;
+        }
+        if item.unchecked_at_resolution {
+            stats.unchecked_at_resolution += 1;
+        }
+        if let Some(m) = item.minutes_to_check {
+            minutes.push(m);
+        }
+    }
+    stats.checklist_count = checklists.len() as i64;
+    if stats.item_count > 0 {
+        stats.completion_rate = stats.checked_count as f64 / stats.item_count as f64 * 100.0;
+    }
+    if stats.resolved_item_count > 0 {
+        stats.skip_rate =
+            stats.unchecked_at_resolution as f64 / stats.resolved_item_count as f64 * 100.0;
+    }
+    stats.median_minutes_to_check = median(&mut minutes);
+    stats
+}
+
+/// Group observations by `(key, label)` in first-seen order.
+pub fn group_compliance<F>(items: &[ItemObservation], group_fn: F) -> Vec<ComplianceGroup>
+where
+    F: Fn(&ItemObservation) -> (String, String),
+{
+    let mut order: Vec<(String, String)> = vec![];
+    let mut groups: HashMap<String, Vec<&ItemObservation>> = HashMap::new();
+    for item in items {
+        let (key, label) = group_fn(item);
+        if !groups.contains_key(&key) {
+            order.push((key.clone(), label));
+        }
+        groups.entry(key).or_default().push(item);
+    }
+    order
+        .into_iter()
+        .map(|(key, label)| {
+            let stats = compliance_stats(groups[&key].iter().copied());
+            ComplianceGroup { key, label, stats }
+        })
+        .collect()
+}
+
+/// Per template item compliance, keyed by template and item label so items
+/// survive template edits.
+pub fn item_compliance(items: &[ItemObservation]) -> Vec<ItemCompliance> {
+    let mut order: Vec<&ItemObservation> = vec![];
+    let mut groups: HashMap<&str, Vec<&ItemObservation>> = HashMap::new();
+    for item in items {
+        let entry = groups.entry(item.item_key.as_str()).or_default();
+        if entry.is_empty() {
+            order.push(item);
+        }
+        entry.push(item);
+    }
+    order
+        .into_iter()
+        .map(|first| ItemCompliance {
+            key: first.item_key.clone(),
+            template_id: first.template_id.clone(),
+            template_name: first.template_name.clone(),
+            label: first.label.clone(),
+            stats: compliance_stats(groups[first.item_key.as_str()].iter().copied()),
+        })
+        .collect()
+}
+
+/// Items left unchecked at resolution at least once, most skipped first.
+pub fn most_skipped(items: &[ItemCompliance], limit: usize) -> Vec<ItemCompliance> {
+    let mut skipped: Vec<ItemCompliance> = items
+        .iter()
+        .filter(|i| i.stats.unchecked_at_resolution > 0)
+        .cloned()
+        .collect();
+    skipped.sort_by(|a, b| {
+        b.stats
+            .unchecked_at_resolution
+            .cmp(&a.stats.unchecked_at_resolution)
+            .then_with(|| {
+                b.stats
+                    .skip_rate
+                    .partial_cmp(&a.stats.skip_rate)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    skipped.truncate(limit);
+    skipped
+}
+
 const MAX_NAME_LEN: usize = 200;
 const MAX_ITEM_LABEL_LEN: usize = 500;
 const MAX_ITEMS: usize = 50;
@@ -149,3 +330,65 @@ impl CreateIncidentChecklistRequest {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(checklist: &str, label: &str, checked: Option<f64>, resolved: bool) -> ItemObservation {
+        ItemObservation {
+            checklist_id: checklist.into(),
+            template_id: "tpl-1".into(),
+            template_name: "Major incident".into(),
+            item_key: format!("tpl-1:{}", label.to_lowercase()),
+            label: label.into(),
+            service_id: "svc-slack".into(),
+            service_name: "Slack".into(),
+            severity: "High".into(),
+            is_checked: checked.is_some(),
+            minutes_to_check: checked,
+            resolved,
+            unchecked_at_resolution: resolved && checked.is_none(),
+        }
+    }
+
+    /// Test: median handles odd, even and empty inputs
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut []), None);
+        assert_eq!(median(&mut [5.0, 1.0, 3.0]), Some(3.0));
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), Some(2.5));
+    }
+
+    /// Test: completion, skip rate and time-to-check roll up per item
+    #[test]
+    fn test_item_compliance_and_most_skipped() {
+        let items = vec![
+            observation("c-1", "Page on-call", Some(5.0), true),
+            observation("c-1", "Post status update", None, true),
+            observation("c-2", "Page on-call", Some(15.0), true),
+            observation("c-2", "Post status update", None, true),
+            observation("c-3", "Page on-call", None, false),
+        ];
+        let overall = compliance_stats(&items);
+        assert_eq!(overall.checklist_count, 3);
+        assert_eq!(overall.item_count, 5);
+        assert_eq!(overall.checked_count, 2);
+        assert!((overall.completion_rate - 40.0).abs() < 1e-9);
+        assert_eq!(overall.resolved_item_count, 4);
+        assert_eq!(overall.unchecked_at_resolution, 2);
+        assert!((overall.skip_rate - 50.0).abs() < 1e-9);
+        assert_eq!(overall.median_minutes_to_check, Some(10.0));
+
+        let per_item = item_compliance(&items);
+        assert_eq!(per_item.len(), 2);
+        assert_eq!(per_item[0].label, "Page on-call");
+        assert_eq!(per_item[0].stats.item_count, 3);
+        assert_eq!(per_item[0].stats.unchecked_at_resolution, 0);
+
+        let skipped = most_skipped(&per_item, 5);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].label, "Post status update");
+        assert!((skipped[0].stats.skip_rate - 100.0).abs() < 1e-9);
+    }
+}
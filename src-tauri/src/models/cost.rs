@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+const MAX_NOTE_LEN: usize = 2000;
+
+/// Rates used to turn incident facts into an estimated cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostModel {
+    pub currency: String,
+    /// Cost of one affected user being impacted for one minute.
+    pub per_affected_user_minute: f64,
+    /// Handling cost of one support ticket.
+    pub per_ticket: f64,
+    /// Loaded cost of one responder working for one hour.
+    pub per_engineer_hour: f64,
+    /// Optional lost revenue per hour of downtime, keyed by service ID.
+    #[serde(default)]
+    pub service_revenue_per_hour: HashMap<String, f64>,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            currency: "USD".to_string(),
+            per_affected_user_minute: 0.05,
+            per_ticket: 25.0,
+            per_engineer_hour: 100.0,
+            service_revenue_per_hour: HashMap::new(),
+        }
+    }
+}
+
+impl CostModel {
+    pub fn validate(&self) -> AppResult<()> {
+        let currency = self.currency.trim();
+        if currency.is_empty() || currency.len() > 8 {
+            return Err(AppError::Validation(
+                "Currency must be 1-8 characters".into(),
+            ));
+        }
+        let rates = [
+            ("Cost per affected-user-minute", self.per_affected_user_minute),
+            ("Cost per ticket", self.per_ticket),
+            ("Cost per engineer-hour", self.per_engineer_hour),
+        ];
+        for (label, rate) in rates {
+            if !rate.is_finite() || rate < 0.0 {
+                return Err(AppError::Validation(format!(
+                    "{} must be a non-negative number",
+                    label
+                )));
+            }
+        }
+        for (service_id, rate) in &self.service_revenue_per_hour {
+            if !rate.is_finite() || *rate < 0.0 {
+                return Err(AppError::Validation(format!(
+                    "Revenue rate for service '{}' must be a non-negative number",
+                    service_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Facts about one incident that feed the cost estimate.
+#[derive(Debug, Clone)]
+pub struct CostInputs {
+    pub service_id: String,
+    pub affected_users: i64,
+    pub tickets_submitted: i64,
+    pub duration_minutes: i64,
+    /// Distinct people assigned an incident role; at least one responder is assumed.
+    pub responder_count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    pub user_impact: f64,
+    pub tickets: f64,
+    pub responder_effort: f64,
+    pub lost_revenue: f64,
+    pub estimated_total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentCost {
+    pub incident_id: String,
+    pub title: String,
+    pub service_id: String,
+    pub service_name: String,
+    pub started_at: String,
+    pub breakdown: CostBreakdown,
+    pub override_amount: Option<f64>,
+    pub override_note: String,
+    /// The override when one is set, otherwise the estimate.
+    pub total_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceCost {
+    pub service_id: String,
+    pub service_name: String,
+    pub incident_count: i64,
+    pub total_cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostSummary {
+    pub currency: String,
+    pub total_cost: f64,
+    pub incident_count: i64,
+    pub overridden_count: i64,
+    pub by_service: Vec<ServiceCost>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetCostOverrideRequest {
+    pub incident_id: String,
+    pub amount: f64,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl SetCostOverrideRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.incident_id.trim().is_empty() {
+            return Err(AppError::Validation("Incident ID is required".into()));
+        }
+        if !self.amount.is_finite() || self.amount < 0.0 {
+            return Err(AppError::Validation(
+                "Override amount must be a non-negative number".into(),
+            ));
+        }
+        if self.note.len() > MAX_NOTE_LEN {
+            return Err(AppError::Validation("Override note too long".into()));
+        }
+        Ok(())
+    }
+}
+
+pub fn estimate_cost(inputs: &CostInputs, model: &CostModel) -> CostBreakdown {
+    let minutes = inputs.duration_minutes.max(0) as f64;
+    let hours = minutes / 60.0;
+    let responders = inputs.responder_count.max(1) as f64;
+
+    let user_impact = inputs.affected_users.max(0) as f64 * minutes * model.per_affected_user_minute;
+    let tickets = inputs.tickets_submitted.max(0) as f64 * model.per_ticket;
+    let responder_effort = responders * hours * model.per_engineer_hour;
+    let lost_revenue = model
+        .service_revenue_per_hour
+        .get(&inputs.service_id)
+        .map(|rate| rate * hours)
+        .unwrap_or(0.0);
+
+    CostBreakdown {
+        user_impact,
+        tickets,
+        responder_effort,
+        lost_revenue,
+        estimated_total: user_impact + tickets + responder_effort + lost_revenue,
+    }
+}
+
+/// Roll incident costs up into a total and a per-service breakdown (most expensive first).
+pub fn summarize_costs(costs: &[IncidentCost], currency: &str) -> CostSummary {
+    let mut by_service: HashMap<String, ServiceCost> = HashMap::new();
+    for cost in costs {
+        let entry = by_service
+            .entry(cost.service_id.clone())
+            .or_insert_with(|| ServiceCost {
+                service_id: cost.service_id.clone(),
+                service_name: cost.service_name.clone(),
+                incident_count: 0,
+                total_cost: 0.0,
+            });
+        entry.incident_count += 1;
+        entry.total_cost += cost.total_cost;
+    }
+
+    let mut by_service: Vec<ServiceCost> = by_service.into_values().collect();
+    by_service.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.service_name.cmp(&b.service_name))
+    });
+
+    CostSummary {
+        currency: currency.to_string(),
+        total_cost: costs.iter().map(|c| c.total_cost).sum(),
+        incident_count: costs.len() as i64,
+        overridden_count: costs.iter().filter(|c| c.override_amount.is_some()).count() as i64,
+        by_service,
+    }
+}
+
+/// Format an amount with thousands separators, e.g. "USD 12,345".
+pub fn format_currency(amount: f64, currency: &str) -> String {
+    if amount.is_nan() || amount.is_infinite() {
+        return "\u{2014}".to_string();
+    }
+    let rounded = amount.round() as i64;
+    let digits = rounded.abs().to_string();
+    let mut grouped = String::new();
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    let sign = if rounded < 0 { "-" } else { "" };
+    format!("{} {}{}", currency, sign, grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(service_id: &str) -> CostInputs {
+        CostInputs {
+            service_id: service_id.into(),
+            affected_users: 100,
+            tickets_submitted: 4,
+            duration_minutes: 90,
+            responder_count: 2,
+        }
+    }
+
+    fn incident_cost(id: &str, service_id: &str, total: f64, overridden: bool) -> IncidentCost {
+        IncidentCost {
+            incident_id: id.into(),
+            title: format!("Incident {}", id),
+            service_id: service_id.into(),
+            service_name: service_id.to_uppercase(),
+            started_at: "2025-01-01T00:00:00Z".into(),
+            breakdown: CostBreakdown::default(),
+            override_amount: if overridden { Some(total) } else { None },
+            override_note: String::new(),
+            total_cost: total,
+        }
+    }
+
+    #[test]
+    fn test_estimate_cost_components() {
+        let mut model = CostModel::default();
+        model.service_revenue_per_hour.insert("svc-a".into(), 1000.0);
+
+        let b = estimate_cost(&inputs("svc-a"), &model);
+        assert!((b.user_impact - 100.0 * 90.0 * 0.05).abs() < 1e-9);
+        assert!((b.tickets - 100.0).abs() < 1e-9);
+        assert!((b.responder_effort - 2.0 * 1.5 * 100.0).abs() < 1e-9);
+        assert!((b.lost_revenue - 1500.0).abs() < 1e-9);
+        assert!((b.estimated_total - (450.0 + 100.0 + 300.0 + 1500.0)).abs() < 1e-9);
+
+        // No revenue configured for other services
+        let b = estimate_cost(&inputs("svc-b"), &model);
+        assert_eq!(b.lost_revenue, 0.0);
+    }
+
+    #[test]
+    fn test_estimate_cost_assumes_one_responder() {
+        let mut i = inputs("svc-a");
+        i.responder_count = 0;
+        let b = estimate_cost(&i, &CostModel::default());
+        assert!((b.responder_effort - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_model_validation() {
+        assert!(CostModel::default().validate().is_ok());
+
+        let model = CostModel {
+            per_ticket: -1.0,
+            ..Default::default()
+        };
+        assert!(model.validate().is_err());
+
+        let model = CostModel {
+            currency: "  ".into(),
+            ..Default::default()
+        };
+        assert!(model.validate().is_err());
+
+        let mut model = CostModel::default();
+        model.service_revenue_per_hour.insert("svc-a".into(), f64::NAN);
+        assert!(model.validate().is_err());
+    }
+
+    #[test]
+    fn test_summarize_costs_groups_by_service() {
+        let costs = vec![
+            incident_cost("1", "svc-a", 100.0, false),
+            incident_cost("2", "svc-b", 500.0, true),
+            incident_cost("3", "svc-a", 50.0, false),
+        ];
+        let summary = summarize_costs(&costs, "EUR");
+        assert_eq!(summary.currency, "EUR");
+        assert!((summary.total_cost - 650.0).abs() < 1e-9);
+        assert_eq!(summary.incident_count, 3);
+        assert_eq!(summary.overridden_count, 1);
+        assert_eq!(summary.by_service.len(), 2);
+        assert_eq!(summary.by_service[0].service_id, "svc-b");
+        assert_eq!(summary.by_service[1].incident_count, 2);
+        assert!((summary.by_service[1].total_cost - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_format_currency() {
+        assert_eq!(format_currency(0.0, "USD"), "USD 0");
+        assert_eq!(format_currency(999.4, "USD"), "USD 999");
+        assert_eq!(format_currency(12345.0, "USD"), "USD 12,345");
+        assert_eq!(format_currency(1234567.8, "EUR"), "EUR 1,234,568");
+        assert_eq!(format_currency(-1500.0, "USD"), "USD -1,500");
+        assert_eq!(format_currency(f64::NAN, "USD"), "\u{2014}");
+    }
+}
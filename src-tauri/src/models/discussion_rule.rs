@@ -0,0 +1,582 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::models::incident::{ActionItem, Incident};
+use crate::models::metrics::{format_minutes, format_percentage};
+use crate::models::report_template::{expand_placeholders, placeholder_names};
+
+/// A stored discussion-point rule: when `metric` measured over each `scope`
+/// subject compares to `threshold` with `comparator`, the report gets a
+/// discussion point built from `message`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscussionRule {
+    pub id: String,
+    pub name: String,
+    pub metric: RuleMetric,
+    pub comparator: Comparator,
+    pub threshold: f64,
+    pub scope: RuleScope,
+    /// Discussion point text with `{{placeholder}}` values from `MESSAGE_PLACEHOLDERS`.
+    pub message: String,
+    pub severity: String,
+    pub enabled: bool,
+    pub sort_order: i64,
+    /// Shipped with the app; can be edited and disabled but not deleted.
+    pub is_builtin: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMetric {
+    IncidentCount,
+    PreviousIncidentCount,
+    /// Change in incident count against the comparison period, in percent.
+    IncidentCountChangePct,
+    /// Comparison-period incident count when the current period has none, else 0.
+    PreviousIncidentsNowQuiet,
+    MttrMinutes,
+    /// Change in MTTR against the comparison period, in percent.
+    MttrChangePct,
+    DowntimeMinutes,
+    P0Incidents,
+    RecurringIncidents,
+    AvgTickets,
+    RecurrenceRate,
+    OpenActionItems,
+}
+
+/// What a rule is evaluated over: the whole period once, each service, or each incident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleScope {
+    Period,
+    Service,
+    Incident,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparator {
+    #[serde(rename = ">")]
+    GreaterThan,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    LessThan,
+    #[serde(rename = "<=")]
+    AtMost,
+    #[serde(rename = "=")]
+    Equal,
+}
+
+impl Comparator {
+    pub fn matches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparator::GreaterThan => value > threshold,
+            Comparator::AtLeast => value >= threshold,
+            Comparator::LessThan => value < threshold,
+            Comparator::AtMost => value <= threshold,
+            Comparator::Equal => (value - threshold).abs() < f64::EPSILON,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparator::GreaterThan => ">",
+            Comparator::AtLeast => ">=",
+            Comparator::LessThan => "<",
+            Comparator::AtMost => "<=",
+            Comparator::Equal => "=",
+        }
+    }
+}
+
+impl RuleMetric {
+    pub fn label(self) -> &'static str {
+        match self {
+            RuleMetric::IncidentCount => "incident count",
+            RuleMetric::PreviousIncidentCount => "comparison-period incident count",
+            RuleMetric::IncidentCountChangePct => "incident count change",
+            RuleMetric::PreviousIncidentsNowQuiet => "comparison-period incidents now at zero",
+            RuleMetric::MttrMinutes => "MTTR",
+            RuleMetric::MttrChangePct => "MTTR change",
+            RuleMetric::DowntimeMinutes => "downtime",
+            RuleMetric::P0Incidents => "P0 incidents",
+            RuleMetric::RecurringIncidents => "recurring incidents",
+            RuleMetric::AvgTickets => "average tickets",
+            RuleMetric::RecurrenceRate => "recurrence rate",
+            RuleMetric::OpenActionItems => "open action items",
+        }
+    }
+
+    /// Scopes the metric can be measured over.
+    pub fn supports(self, scope: RuleScope) -> bool {
+        match self {
+            RuleMetric::OpenActionItems => scope == RuleScope::Period,
+            RuleMetric::IncidentCount
+            | RuleMetric::PreviousIncidentCount
+            | RuleMetric::IncidentCountChangePct
+            | RuleMetric::PreviousIncidentsNowQuiet
+            | RuleMetric::MttrChangePct
+            | RuleMetric::RecurrenceRate => scope != RuleScope::Incident,
+            RuleMetric::MttrMinutes
+            | RuleMetric::DowntimeMinutes
+            | RuleMetric::P0Incidents
+            | RuleMetric::RecurringIncidents
+            | RuleMetric::AvgTickets => true,
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            RuleMetric::MttrMinutes | RuleMetric::DowntimeMinutes => format_minutes(value),
+            RuleMetric::IncidentCountChangePct | RuleMetric::MttrChangePct => format!("{:.0}%", value),
+            RuleMetric::RecurrenceRate => format_percentage(value),
+            RuleMetric::AvgTickets => format!("{:.1}", value),
+            _ => format!("{:.0}", value),
+        }
+    }
+
+    /// The metric `{{current}}` and `{{previous}}` show: the base value of a change metric, else itself.
+    fn base(self) -> RuleMetric {
+        match self {
+            RuleMetric::IncidentCountChangePct
+            | RuleMetric::PreviousIncidentCount
+            | RuleMetric::PreviousIncidentsNowQuiet => RuleMetric::IncidentCount,
+            RuleMetric::MttrChangePct => RuleMetric::MttrMinutes,
+            other => other,
+        }
+    }
+
+    /// Value over one subject, or `None` when it cannot be measured
+    /// (no incidents, nothing resolved, or no comparison baseline).
+    fn measure(self, subject: &Subject, action_items: &[ActionItem]) -> Option<f64> {
+        let (current, previous) = (&subject.current, &subject.previous);
+        match self {
+            RuleMetric::IncidentCount => Some(current.len() as f64),
+            RuleMetric::PreviousIncidentCount => Some(previous.len() as f64),
+            RuleMetric::IncidentCountChangePct => {
+                change_pct(current.len() as f64, previous.len() as f64)
+            }
+            RuleMetric::PreviousIncidentsNowQuiet => {
+                Some(if current.is_empty() { previous.len() as f64 } else { 0.0 })
+            }
+            RuleMetric::MttrMinutes => mttr(current),
+            RuleMetric::MttrChangePct => change_pct(mttr(current)?, mttr(previous)?),
+            RuleMetric::DowntimeMinutes => {
+                Some(current.iter().filter_map(|i| i.duration_minutes).sum::<i64>() as f64)
+            }
+            RuleMetric::P0Incidents => Some(current.iter().filter(|i| i.priority == "P0").count() as f64),
+            RuleMetric::RecurringIncidents => Some(current.iter().filter(|i| i.is_recurring).count() as f64),
+            RuleMetric::AvgTickets => (!current.is_empty()).then(|| {
+                current.iter().map(|i| i.tickets_submitted as f64).sum::<f64>() / current.len() as f64
+            }),
+            RuleMetric::RecurrenceRate => (!current.is_empty()).then(|| {
+                current.iter().filter(|i| i.is_recurring).count() as f64 / current.len() as f64 * 100.0
+            }),
+            RuleMetric::OpenActionItems => {
+                Some(action_items.iter().filter(|a| a.status != "Done").count() as f64)
+            }
+        }
+    }
+
+    /// Titles of the incidents behind the value, for `{{incidents}}`.
+    fn incident_titles(self, subject: &Subject) -> Vec<String> {
+        subject
+            .current
+            .iter()
+            .filter(|i| match self {
+                RuleMetric::P0Incidents => i.priority == "P0",
+                RuleMetric::RecurringIncidents => i.is_recurring,
+                _ => true,
+            })
+            .map(|i| i.title.clone())
+            .collect()
+    }
+}
+
+fn mttr(incidents: &[&Incident]) -> Option<f64> {
+    let durations: Vec<f64> = incidents.iter().filter_map(|i| i.duration_minutes).map(|d| d as f64).collect();
+    (!durations.is_empty()).then(|| durations.iter().sum::<f64>() / durations.len() as f64)
+}
+
+fn change_pct(current: f64, previous: f64) -> Option<f64> {
+    (previous > 0.0).then(|| (current - previous) / previous * 100.0)
+}
+
+/// Values rule messages can reference as `{{name}}`.
+pub const MESSAGE_PLACEHOLDERS: &[&str] = &[
+    "value",
+    "threshold",
+    "current",
+    "previous",
+    "service",
+    "incident",
+    "incidents",
+];
+
+pub const RULE_SEVERITIES: &[&str] = &["critical", "high", "medium", "low"];
+
+/// Incidents and action items the rules are evaluated against.
+pub struct RuleInput<'a> {
+    pub incidents: &'a [Incident],
+    /// Incidents in the comparison period.
+    pub prev_incidents: &'a [Incident],
+    pub action_items: &'a [ActionItem],
+}
+
+/// One thing a rule is measured over: the period, a service or an incident.
+struct Subject<'a> {
+    service: String,
+    incident: String,
+    current: Vec<&'a Incident>,
+    previous: Vec<&'a Incident>,
+}
+
+fn subjects<'a>(scope: RuleScope, input: &RuleInput<'a>) -> Vec<Subject<'a>> {
+    match scope {
+        RuleScope::Period => vec![Subject {
+            service: String::new(),
+            incident: String::new(),
+            current: input.incidents.iter().collect(),
+            previous: input.prev_incidents.iter().collect(),
+        }],
+        RuleScope::Service => {
+            // Services seen in either period, by name
+            let mut by_service: BTreeMap<&str, Subject<'a>> = BTreeMap::new();
+            for (inc, is_current) in input
+                .incidents
+                .iter()
+                .map(|i| (i, true))
+                .chain(input.prev_incidents.iter().map(|i| (i, false)))
+            {
+                let subject = by_service.entry(&inc.service_name).or_insert_with(|| Subject {
+                    service: inc.service_name.clone(),
+                    incident: String::new(),
+                    current: vec![],
+                    previous: vec![],
+                });
+                if is_current {
+                    subject.current.push(inc);
+                } else {
+                    subject.previous.push(inc);
+                }
+            }
+            by_service.into_values().collect()
+        }
+        RuleScope::Incident => input
+            .incidents
+            .iter()
+            .map(|inc| Subject {
+                service: inc.service_name.clone(),
+                incident: inc.title.clone(),
+                current: vec![inc],
+                previous: vec![],
+            })
+            .collect(),
+    }
+}
+
+/// The outcome of one rule over one subject.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleOutcome {
+    /// "All incidents", a service name or an incident title.
+    pub subject: String,
+    pub value: Option<f64>,
+    pub fired: bool,
+    /// Why the rule did or did not fire, e.g. "Slack: incident count 4 >= 3".
+    pub explanation: String,
+    /// The discussion point text, when the rule fired.
+    pub message: Option<String>,
+}
+
+/// A rule with its outcomes, as shown by the dry run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleEvaluation {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub enabled: bool,
+    pub severity: String,
+    pub fired: bool,
+    pub outcomes: Vec<RuleOutcome>,
+}
+
+/// Evaluate every rule, in rule order, enabled or not.
+pub fn evaluate_rules(rules: &[DiscussionRule], input: &RuleInput) -> Vec<RuleEvaluation> {
+    rules
+        .iter()
+        .map(|rule| {
+            let outcomes: Vec<RuleOutcome> = subjects(rule.scope, input)
+                .iter()
+                .map(|subject| evaluate_subject(rule, subject, input))
+                .collect();
+            RuleEvaluation {
+                rule_id: rule.id.clone(),
+                rule_name: rule.name.clone(),
+                enabled: rule.enabled,
+                severity: rule.severity.clone(),
+                fired: outcomes.iter().any(|o| o.fired),
+                outcomes,
+            }
+        })
+        .collect()
+}
+
+fn evaluate_subject(rule: &DiscussionRule, subject: &Subject, input: &RuleInput) -> RuleOutcome {
+    let metric = rule.metric;
+    let name = match rule.scope {
+        RuleScope::Period => "All incidents".to_string(),
+        RuleScope::Service => subject.service.clone(),
+        RuleScope::Incident => subject.incident.clone(),
+    };
+    let value = metric.measure(subject, input.action_items);
+    let threshold = metric.format(rule.threshold);
+
+    let Some(v) = value else {
+        return RuleOutcome {
+            explanation: format!("{}: {} not available", name, metric.label()),
+            subject: name,
+            value,
+            fired: false,
+            message: None,
+        };
+    };
+    let fired = rule.comparator.matches(v, rule.threshold);
+    let explanation = format!(
+        "{}: {} {} {}{} {}",
+        name,
+        metric.label(),
+        metric.format(v),
+        if fired { "" } else { "not " },
+        rule.comparator.symbol(),
+        threshold
+    );
+
+    let message = fired.then(|| {
+        let base = metric.base();
+        let both = Subject {
+            service: String::new(),
+            incident: String::new(),
+            current: subject.previous.clone(),
+            previous: vec![],
+        };
+        let shown = |value: Option<f64>| value.map(|v| base.format(v)).unwrap_or_else(|| "N/A".into());
+        let mut values: HashMap<&str, String> = HashMap::new();
+        values.insert("value", metric.format(v));
+        values.insert("threshold", threshold.clone());
+        values.insert("current", shown(base.measure(subject, input.action_items)));
+        values.insert("previous", shown(base.measure(&both, input.action_items)));
+        values.insert("service", subject.service.clone());
+        values.insert("incident", subject.incident.clone());
+        values.insert("incidents", metric.incident_titles(subject).join(", "));
+        expand_placeholders(&rule.message, &values)
+    });
+
+    RuleOutcome { subject: name, value, fired, explanation, message }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDiscussionRuleRequest {
+    pub name: String,
+    pub metric: RuleMetric,
+    pub comparator: Comparator,
+    pub threshold: f64,
+    pub scope: RuleScope,
+    pub message: String,
+    pub severity: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDiscussionRuleRequest {
+    pub name: Option<String>,
+    pub metric: Option<RuleMetric>,
+    pub comparator: Option<Comparator>,
+    pub threshold: Option<f64>,
+    pub scope: Option<RuleScope>,
+    pub message: Option<String>,
+    pub severity: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+const MAX_NAME_LEN: usize = 200;
+const MAX_MESSAGE_LEN: usize = 2_000;
+
+/// Check a complete rule definition.
+pub fn validate_rule(
+    name: &str,
+    metric: RuleMetric,
+    threshold: f64,
+    scope: RuleScope,
+    message: &str,
+    severity: &str,
+) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Rule name is required".into()));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(AppError::Validation("Rule name too long".into()));
+    }
+    if !metric.supports(scope) {
+        return Err(AppError::Validation(format!(
+            "The {} metric cannot be measured per {}",
+            metric.label(),
+            match scope {
+                RuleScope::Period => "period",
+                RuleScope::Service => "service",
+                RuleScope::Incident => "incident",
+            }
+        )));
+    }
+    if !threshold.is_finite() {
+        return Err(AppError::Validation("Threshold must be a number".into()));
+    }
+    if message.trim().is_empty() {
+        return Err(AppError::Validation("Rule message is required".into()));
+    }
+    if message.len() > MAX_MESSAGE_LEN {
+        return Err(AppError::Validation("Rule message too long".into()));
+    }
+    if let Some(unknown) = placeholder_names(message).into_iter().find(|n| !MESSAGE_PLACEHOLDERS.contains(n)) {
+        return Err(AppError::Validation(format!("Unknown placeholder '{{{{{}}}}}'", unknown)));
+    }
+    if !RULE_SEVERITIES.contains(&severity) {
+        return Err(AppError::Validation(format!(
+            "Invalid severity '{}'. Must be one of: {}",
+            severity,
+            RULE_SEVERITIES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+impl CreateDiscussionRuleRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        validate_rule(&self.name, self.metric, self.threshold, self.scope, &self.message, &self.severity)
+    }
+}
+
+impl UpdateDiscussionRuleRequest {
+    /// Validate the rule as it will be after applying this update to `existing`.
+    pub fn validate(&self, existing: &DiscussionRule) -> AppResult<()> {
+        validate_rule(
+            self.name.as_deref().unwrap_or(&existing.name),
+            self.metric.unwrap_or(existing.metric),
+            self.threshold.unwrap_or(existing.threshold),
+            self.scope.unwrap_or(existing.scope),
+            self.message.as_deref().unwrap_or(&existing.message),
+            self.severity.as_deref().unwrap_or(&existing.severity),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn incident(id: &str, service: &str, priority: &str, minutes: Option<i64>) -> Incident {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": format!("Outage {}", id),
+            "service_id": format!("svc-{}", service.to_lowercase()),
+            "service_name": service,
+            "severity": "High",
+            "impact": "High",
+            "priority": priority,
+            "status": "Resolved",
+            "started_at": "2026-03-10T10:00:00Z",
+            "detected_at": "2026-03-10T10:00:00Z",
+            "duration_minutes": minutes,
+            "created_at": "2026-03-10T10:00:00Z",
+            "updated_at": "2026-03-10T10:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    fn rule(metric: RuleMetric, comparator: Comparator, threshold: f64, scope: RuleScope, message: &str) -> DiscussionRule {
+        DiscussionRule {
+            id: "r".into(),
+            name: "Rule".into(),
+            metric,
+            comparator,
+            threshold,
+            scope,
+            message: message.into(),
+            severity: "medium".into(),
+            enabled: true,
+            sort_order: 0,
+            is_builtin: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    /// Test: service rules fire per service with the reason and filled-in message
+    #[test]
+    fn test_service_scope() {
+        let incidents = vec![
+            incident("1", "Slack", "P2", Some(30)),
+            incident("2", "Slack", "P0", Some(50)),
+            incident("3", "Zoom", "P2", Some(10)),
+        ];
+        let input = RuleInput { incidents: &incidents, prev_incidents: &[], action_items: &[] };
+        let rules = [rule(
+            RuleMetric::DowntimeMinutes,
+            Comparator::GreaterThan,
+            60.0,
+            RuleScope::Service,
+            "{{service}} had {{value}} of downtime ({{incidents}}).",
+        )];
+        let eval = &evaluate_rules(&rules, &input)[0];
+        assert!(eval.fired);
+        assert_eq!(eval.outcomes.len(), 2);
+        assert_eq!(eval.outcomes[0].explanation, "Slack: downtime 1h 20m > 1h");
+        assert_eq!(eval.outcomes[0].message.as_deref(), Some("Slack had 1h 20m of downtime (Outage 1, Outage 2)."));
+        assert!(!eval.outcomes[1].fired);
+        assert_eq!(eval.outcomes[1].explanation, "Zoom: downtime 10 min not > 1h");
+    }
+
+    /// Test: change metrics need a baseline and report current and previous values
+    #[test]
+    fn test_change_metrics() {
+        let current = vec![incident("1", "Slack", "P2", Some(90))];
+        let previous = vec![incident("2", "Slack", "P2", Some(60)), incident("3", "Zoom", "P2", Some(60))];
+        let input = RuleInput { incidents: &current, prev_incidents: &previous, action_items: &[] };
+        let rules = [
+            rule(RuleMetric::MttrChangePct, Comparator::GreaterThan, 5.0, RuleScope::Period, "MTTR went from {{previous}} to {{current}}."),
+            rule(RuleMetric::PreviousIncidentsNowQuiet, Comparator::AtLeast, 1.0, RuleScope::Service, "{{service}}: {{value}}"),
+        ];
+        let evals = evaluate_rules(&rules, &input);
+        assert_eq!(evals[0].outcomes[0].message.as_deref(), Some("MTTR went from 1h to 1h 30m."));
+        let quiet: Vec<&str> = evals[1].outcomes.iter().filter(|o| o.fired).map(|o| o.subject.as_str()).collect();
+        assert_eq!(quiet, vec!["Zoom"]);
+
+        let input = RuleInput { incidents: &current, prev_incidents: &[], action_items: &[] };
+        let eval = &evaluate_rules(&rules[..1], &input)[0];
+        assert!(!eval.fired);
+        assert_eq!(eval.outcomes[0].explanation, "All incidents: MTTR change not available");
+    }
+
+    /// Test: rules are checked for scope, placeholders and severity
+    #[test]
+    fn test_validate_rule() {
+        assert!(validate_rule("R", RuleMetric::IncidentCount, 3.0, RuleScope::Service, "{{service}}", "high").is_ok());
+        assert!(validate_rule("R", RuleMetric::IncidentCount, 3.0, RuleScope::Incident, "x", "high").is_err());
+        assert!(validate_rule("R", RuleMetric::OpenActionItems, 1.0, RuleScope::Service, "x", "high").is_err());
+        assert!(validate_rule("R", RuleMetric::AvgTickets, f64::NAN, RuleScope::Period, "x", "high").is_err());
+        assert!(validate_rule("R", RuleMetric::AvgTickets, 10.0, RuleScope::Period, "{{mttr}}", "high").is_err());
+        assert!(validate_rule("R", RuleMetric::AvgTickets, 10.0, RuleScope::Period, "x", "urgent").is_err());
+        assert!(validate_rule(" ", RuleMetric::AvgTickets, 10.0, RuleScope::Period, "x", "low").is_err());
+
+        let parsed: Comparator = serde_json::from_str("\">=\"").unwrap();
+        assert_eq!(parsed, Comparator::AtLeast);
+    }
+}
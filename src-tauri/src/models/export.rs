@@ -0,0 +1,122 @@
+use chrono::format::{Item, StrftimeItems};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+const VALID_DELIMITERS: &[char] = &[',', ';', '\t', '|'];
+const MAX_DATE_FORMAT_LEN: usize = 64;
+const MAX_LIST_DELIMITER_LEN: usize = 5;
+
+/// Character encoding of CSV exports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEncoding {
+    #[default]
+    Utf8,
+    /// UTF-8 with a byte order mark, which Excel needs to detect UTF-8.
+    Utf8Bom,
+    /// UTF-16 little-endian with a byte order mark.
+    Utf16Le,
+}
+
+/// Shape of JSON exports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonExportMode {
+    /// One object per incident holding the selected columns.
+    #[default]
+    Flat,
+    /// The selected columns plus tags, roles, contributing factors, SLA
+    /// status, custom fields, action items and the post-mortem embedded per
+    /// incident.
+    Nested,
+}
+
+/// How CSV and JSON exports lay out incidents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Column keys in output order; empty for the standard columns.
+    pub columns: Vec<String>,
+    pub delimiter: char,
+    pub encoding: ExportEncoding,
+    /// chrono strftime pattern for timestamp columns, e.g. `%Y-%m-%d %H:%M`;
+    /// empty keeps the stored ISO 8601 value.
+    pub date_format: String,
+    /// Separator used when joining tags, roles and factors into one CSV cell.
+    pub list_delimiter: String,
+    pub json_mode: JsonExportMode,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            delimiter: ',',
+            encoding: ExportEncoding::Utf8,
+            date_format: String::new(),
+            list_delimiter: "; ".to_string(),
+            json_mode: JsonExportMode::Flat,
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn validate(&self) -> AppResult<()> {
+        if !VALID_DELIMITERS.contains(&self.delimiter) {
+            return Err(AppError::Validation(
+                "Delimiter must be a comma, semicolon, tab or pipe".into(),
+            ));
+        }
+        if self.list_delimiter.is_empty() || self.list_delimiter.chars().count() > MAX_LIST_DELIMITER_LEN {
+            return Err(AppError::Validation(format!(
+                "List delimiter must be 1 to {} characters",
+                MAX_LIST_DELIMITER_LEN
+            )));
+        }
+        if self.date_format.chars().count() > MAX_DATE_FORMAT_LEN {
+            return Err(AppError::Validation(format!(
+                "Date format must be {} characters or fewer",
+                MAX_DATE_FORMAT_LEN
+            )));
+        }
+        if StrftimeItems::new(&self.date_format).any(|item| matches!(item, Item::Error)) {
+            return Err(AppError::Validation(format!(
+                "Invalid date format '{}'",
+                self.date_format
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A column that CSV and JSON exports can include.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportColumn {
+    pub key: String,
+    pub label: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test: export options default to the legacy layout and reject bad delimiters and date formats
+    #[test]
+    fn test_export_options_validation() {
+        let options: ExportOptions = serde_json::from_str(r#"{"delimiter": ";", "encoding": "utf8_bom"}"#).unwrap();
+        assert!(options.validate().is_ok());
+        assert_eq!(options.list_delimiter, "; ");
+        assert_eq!(options.json_mode, JsonExportMode::Flat);
+        assert!(ExportOptions::default().columns.is_empty());
+
+        for bad in [
+            ExportOptions { delimiter: 'x', ..Default::default() },
+            ExportOptions { list_delimiter: String::new(), ..Default::default() },
+            ExportOptions { date_format: "%Y-%Q".into(), ..Default::default() },
+        ] {
+            assert!(bad.validate().is_err());
+        }
+        assert!(ExportOptions { date_format: "%d/%m/%Y %H:%M".into(), ..Default::default() }.validate().is_ok());
+    }
+}
@@ -0,0 +1,452 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::models::timezone::AnalyticsZone;
+
+/// Team label for engagements on services without an owner.
+pub const UNOWNED_TEAM: &str = "Unowned";
+
+/// What counts as working time when classifying engagements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkingHoursConfig {
+    /// Local start of the working day, "HH:MM".
+    pub workday_start: String,
+    /// Local end of the working day, "HH:MM".
+    pub workday_end: String,
+    /// ISO weekdays that are working days (1 = Monday .. 7 = Sunday).
+    pub working_days: Vec<u32>,
+    /// Dates (YYYY-MM-DD) treated as non-working days.
+    #[serde(default)]
+    pub holidays: Vec<String>,
+    /// Zone the hours apply in; the organization time zone when unset.
+    #[serde(default)]
+    pub time_zone: Option<String>,
+}
+
+impl Default for WorkingHoursConfig {
+    fn default() -> Self {
+        Self {
+            workday_start: "09:00".to_string(),
+            workday_end: "17:00".to_string(),
+            working_days: vec![1, 2, 3, 4, 5],
+            holidays: vec![],
+            time_zone: None,
+        }
+    }
+}
+
+fn parse_time(value: &str) -> AppResult<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| AppError::Validation(format!("Invalid time '{}'. Use HH:MM", value)))
+}
+
+impl WorkingHoursConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        let start = parse_time(&self.workday_start)?;
+        let end = parse_time(&self.workday_end)?;
+        if end <= start {
+            return Err(AppError::Validation(
+                "Working day must end after it starts".into(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        for day in &self.working_days {
+            if !(1..=7).contains(day) {
+                return Err(AppError::Validation(format!(
+                    "Invalid working day {}. Use 1 (Monday) to 7 (Sunday)",
+                    day
+                )));
+            }
+            if !seen.insert(day) {
+                return Err(AppError::Validation(format!("Working day {} listed twice", day)));
+            }
+        }
+        for holiday in &self.holidays {
+            if NaiveDate::parse_from_str(holiday, "%Y-%m-%d").is_err() {
+                return Err(AppError::Validation(format!("Invalid holiday date: {}", holiday)));
+            }
+        }
+        if let Some(ref zone) = self.time_zone {
+            AnalyticsZone::parse(zone)?;
+        }
+        Ok(())
+    }
+}
+
+/// A validated `WorkingHoursConfig` bound to a time zone.
+#[derive(Debug, Clone)]
+pub struct WorkingHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    days: HashSet<u32>,
+    holidays: HashSet<NaiveDate>,
+    tz: AnalyticsZone,
+}
+
+impl WorkingHours {
+    pub fn new(config: &WorkingHoursConfig, tz: AnalyticsZone) -> AppResult<Self> {
+        config.validate()?;
+        Ok(Self {
+            start: parse_time(&config.workday_start)?,
+            end: parse_time(&config.workday_end)?,
+            days: config.working_days.iter().copied().collect(),
+            holidays: config
+                .holidays
+                .iter()
+                .filter_map(|h| NaiveDate::parse_from_str(h, "%Y-%m-%d").ok())
+                .collect(),
+            tz,
+        })
+    }
+
+    pub fn time_zone(&self) -> &AnalyticsZone {
+        &self.tz
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.days.contains(&date.weekday().number_from_monday()) && !self.holidays.contains(&date)
+    }
+
+    pub fn is_working_time(&self, instant: &DateTime<Utc>) -> bool {
+        let local = self.tz.to_local(instant);
+        self.is_working_day(local.date()) && local.time() >= self.start && local.time() < self.end
+    }
+
+    /// Seconds of `start..end` that fall outside working hours.
+    pub fn off_hours_seconds(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+        if end <= start {
+            return 0;
+        }
+        let mut working = 0;
+        let mut date = self.tz.local_date(&start);
+        let last = self.tz.local_date(&end);
+        while date <= last {
+            if self.is_working_day(date) {
+                let window_start = self.tz.to_utc(date.and_time(self.start));
+                let window_end = self.tz.to_utc(date.and_time(self.end));
+                let overlap_start = window_start.max(start);
+                let overlap_end = window_end.min(end);
+                if overlap_end > overlap_start {
+                    working += (overlap_end - overlap_start).num_seconds();
+                }
+            }
+            date += Duration::days(1);
+        }
+        (end - start).num_seconds() - working
+    }
+}
+
+/// Span a responder was engaged on an incident. The assignment times are used
+/// when they fall inside the incident; otherwise the incident's own start and
+/// end (resolution, or `now` while open) bound the engagement.
+pub fn engagement_window(
+    incident_start: DateTime<Utc>,
+    incident_end: DateTime<Utc>,
+    assigned_at: Option<DateTime<Utc>>,
+    unassigned_at: Option<DateTime<Utc>>,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let incident_end = incident_end.max(incident_start);
+    let start = assigned_at
+        .filter(|a| *a >= incident_start && *a < incident_end)
+        .unwrap_or(incident_start);
+    let end = unassigned_at
+        .filter(|u| *u > start && *u < incident_end)
+        .unwrap_or(incident_end);
+    (start, end)
+}
+
+/// One responder's involvement in one incident, across all their roles on it.
+#[derive(Debug, Clone)]
+pub struct Engagement {
+    pub incident_id: String,
+    pub responder: String,
+    pub service_id: String,
+    pub service_name: String,
+    pub team: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Load figures for a responder, team or service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadSummary {
+    pub key: String,
+    pub label: String,
+    pub incident_count: i64,
+    /// Responder-incident engagements; equals `incident_count` for a single responder.
+    pub engagement_count: i64,
+    /// Engagements that started outside working hours, weekends included.
+    pub after_hours_engagements: i64,
+    /// Engagements that started on a non-working day or holiday.
+    pub weekend_engagements: i64,
+    /// Responder-hours engaged.
+    pub engaged_hours: f64,
+    /// Responder-hours engaged outside working hours.
+    pub after_hours_hours: f64,
+    /// Distinct local days with at least one active engagement.
+    pub engaged_days: i64,
+    /// Longest run of consecutive engaged local days.
+    pub longest_streak_days: i64,
+    /// Percentage of engagements that started outside working hours.
+    pub after_hours_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponderLoadReport {
+    pub start_date: String,
+    pub end_date: String,
+    pub time_zone: String,
+    pub working_hours: WorkingHoursConfig,
+    /// Responders with the most after-hours engagements first.
+    pub responders: Vec<LoadSummary>,
+    /// Grouped by the owner of the incident's service.
+    pub teams: Vec<LoadSummary>,
+    pub services: Vec<LoadSummary>,
+}
+
+/// Longest run of consecutive dates in a sorted set.
+pub fn longest_streak(days: &BTreeSet<NaiveDate>) -> i64 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        current = match previous {
+            Some(p) if *day == p + Duration::days(1) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(*day);
+    }
+    longest
+}
+
+#[derive(Default)]
+struct LoadAccumulator {
+    label: String,
+    incidents: HashSet<String>,
+    engagements: i64,
+    after_hours: i64,
+    weekend: i64,
+    seconds: i64,
+    off_seconds: i64,
+    days: BTreeSet<NaiveDate>,
+}
+
+/// Summarize engagements grouped by `group`, which returns `(key, label)`.
+pub fn summarize_load<F>(engagements: &[Engagement], hours: &WorkingHours, group: F) -> Vec<LoadSummary>
+where
+    F: Fn(&Engagement) -> (String, String),
+{
+    let tz = hours.time_zone();
+    let mut groups: BTreeMap<String, LoadAccumulator> = BTreeMap::new();
+    for e in engagements {
+        let (key, label) = group(e);
+        let acc = groups.entry(key).or_insert_with(|| LoadAccumulator {
+            label,
+            ..Default::default()
+        });
+        acc.incidents.insert(e.incident_id.clone());
+        acc.engagements += 1;
+        if !hours.is_working_time(&e.start) {
+            acc.after_hours += 1;
+        }
+        if !hours.is_working_day(tz.local_date(&e.start)) {
+            acc.weekend += 1;
+        }
+        acc.seconds += (e.end - e.start).num_seconds().max(0);
+        acc.off_seconds += hours.off_hours_seconds(e.start, e.end);
+
+        let first = tz.local_date(&e.start);
+        let last = tz.local_date(&(e.end - Duration::seconds(1)).max(e.start));
+        let mut day = first;
+        while day <= last {
+            acc.days.insert(day);
+            day += Duration::days(1);
+        }
+    }
+
+    let mut summaries: Vec<LoadSummary> = groups
+        .into_iter()
+        .map(|(key, acc)| LoadSummary {
+            key,
+            label: acc.label,
+            incident_count: acc.incidents.len() as i64,
+            engagement_count: acc.engagements,
+            after_hours_engagements: acc.after_hours,
+            weekend_engagements: acc.weekend,
+            engaged_hours: acc.seconds as f64 / 3600.0,
+            after_hours_hours: acc.off_seconds as f64 / 3600.0,
+            engaged_days: acc.days.len() as i64,
+            longest_streak_days: longest_streak(&acc.days),
+            after_hours_rate: if acc.engagements > 0 {
+                acc.after_hours as f64 / acc.engagements as f64 * 100.0
+            } else {
+                0.0
+            },
+        })
+        .collect();
+
+    summaries.sort_by(|a, b| {
+        b.after_hours_engagements
+            .cmp(&a.after_hours_engagements)
+            .then_with(|| {
+                b.engaged_hours
+                    .partial_cmp(&a.engaged_hours)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        crate::models::timezone::parse_timestamp(value).unwrap()
+    }
+
+    fn hours_in(zone: &str) -> WorkingHours {
+        WorkingHours::new(&WorkingHoursConfig::default(), AnalyticsZone::parse(zone).unwrap()).unwrap()
+    }
+
+    fn engagement(incident: &str, responder: &str, start: &str, end: &str) -> Engagement {
+        Engagement {
+            incident_id: incident.into(),
+            responder: responder.into(),
+            service_id: "svc-slack".into(),
+            service_name: "Slack".into(),
+            team: "Platform".into(),
+            start: at(start),
+            end: at(end),
+        }
+    }
+
+    /// Test: config validation
+    #[test]
+    fn test_config_validation() {
+        assert!(WorkingHoursConfig::default().validate().is_ok());
+        let mut config = WorkingHoursConfig {
+            workday_start: "18:00".into(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        config.workday_start = "9am".into();
+        assert!(config.validate().is_err());
+        config.workday_start = "08:30".into();
+        config.working_days = vec![1, 8];
+        assert!(config.validate().is_err());
+        config.working_days = vec![1, 1];
+        assert!(config.validate().is_err());
+        config.working_days = vec![1];
+        config.holidays = vec!["2025-12-25".into()];
+        config.time_zone = Some("Mars/Olympus".into());
+        assert!(config.validate().is_err());
+        config.time_zone = Some("Europe/Berlin".into());
+        assert!(config.validate().is_ok());
+    }
+
+    /// Test: working time follows the zone, weekdays and holidays
+    #[test]
+    fn test_working_time_classification() {
+        let hours = hours_in("America/New_York");
+        // Tuesday 14:00Z is 09:00 EST
+        assert!(hours.is_working_time(&at("2025-01-14T14:00:00Z")));
+        // 13:59Z is 08:59 EST, and 22:00Z is 17:00 (end is exclusive)
+        assert!(!hours.is_working_time(&at("2025-01-14T13:59:00Z")));
+        assert!(!hours.is_working_time(&at("2025-01-14T22:00:00Z")));
+        // In July 13:00Z is already 09:00 EDT
+        assert!(hours.is_working_time(&at("2025-07-15T13:00:00Z")));
+        // Saturday
+        assert!(!hours.is_working_time(&at("2025-01-18T15:00:00Z")));
+
+        let config = WorkingHoursConfig {
+            holidays: vec!["2025-12-25".into()],
+            ..Default::default()
+        };
+        let hours = WorkingHours::new(&config, AnalyticsZone::utc()).unwrap();
+        assert!(!hours.is_working_time(&at("2025-12-25T10:00:00Z")));
+        assert!(hours.is_working_time(&at("2025-12-24T10:00:00Z")));
+    }
+
+    /// Test: off-hours seconds across a night and a weekend
+    #[test]
+    fn test_off_hours_seconds() {
+        let hours = hours_in("UTC");
+        // Thursday 16:00 to Friday 10:00: 1h + 1h working of 18h
+        let off = hours.off_hours_seconds(at("2025-01-16T16:00:00Z"), at("2025-01-17T10:00:00Z"));
+        assert_eq!(off, 16 * 3600);
+        // Friday 16:00 to Monday 10:00: 2h working of 66h
+        let off = hours.off_hours_seconds(at("2025-01-17T16:00:00Z"), at("2025-01-20T10:00:00Z"));
+        assert_eq!(off, 64 * 3600);
+        assert_eq!(hours.off_hours_seconds(at("2025-01-16T10:00:00Z"), at("2025-01-16T11:00:00Z")), 0);
+        assert_eq!(hours.off_hours_seconds(at("2025-01-16T11:00:00Z"), at("2025-01-16T10:00:00Z")), 0);
+    }
+
+    /// Test: assignment times are used only when they fall inside the incident
+    #[test]
+    fn test_engagement_window() {
+        let start = at("2025-01-16T10:00:00Z");
+        let end = at("2025-01-16T14:00:00Z");
+        let (s, e) = engagement_window(start, end, Some(at("2025-01-16T11:00:00Z")), Some(at("2025-01-16T12:00:00Z")));
+        assert_eq!((s, e), (at("2025-01-16T11:00:00Z"), at("2025-01-16T12:00:00Z")));
+        // Roles recorded after the fact fall back to the incident bounds
+        let (s, e) = engagement_window(start, end, Some(at("2025-01-20T09:00:00Z")), None);
+        assert_eq!((s, e), (start, end));
+        // An unassignment after resolution does not extend the engagement
+        let (_, e) = engagement_window(start, end, None, Some(at("2025-01-17T00:00:00Z")));
+        assert_eq!(e, end);
+    }
+
+    /// Test: streaks count consecutive local days only
+    #[test]
+    fn test_longest_streak() {
+        let d = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let days: BTreeSet<NaiveDate> =
+            ["2025-01-01", "2025-01-02", "2025-01-03", "2025-01-05", "2025-01-06"].iter().map(|s| d(s)).collect();
+        assert_eq!(longest_streak(&days), 3);
+        assert_eq!(longest_streak(&BTreeSet::new()), 0);
+    }
+
+    /// Test: per-responder summary counts nights, weekends, hours and streaks
+    #[test]
+    fn test_summarize_by_responder() {
+        let hours = hours_in("UTC");
+        let engagements = vec![
+            // Thursday night, 2h
+            engagement("i-1", "alice", "2025-01-16T23:00:00Z", "2025-01-17T01:00:00Z"),
+            // Friday daytime, 1h
+            engagement("i-2", "alice", "2025-01-17T10:00:00Z", "2025-01-17T11:00:00Z"),
+            // Saturday, 3h
+            engagement("i-3", "alice", "2025-01-18T12:00:00Z", "2025-01-18T15:00:00Z"),
+            engagement("i-2", "bob", "2025-01-17T10:00:00Z", "2025-01-17T12:00:00Z"),
+        ];
+        let summaries = summarize_load(&engagements, &hours, |e| (e.responder.clone(), e.responder.clone()));
+        assert_eq!(summaries[0].key, "alice");
+        let alice = &summaries[0];
+        assert_eq!(alice.incident_count, 3);
+        assert_eq!(alice.after_hours_engagements, 2);
+        assert_eq!(alice.weekend_engagements, 1);
+        assert!((alice.engaged_hours - 6.0).abs() < 1e-9);
+        assert!((alice.after_hours_hours - 5.0).abs() < 1e-9);
+        // Thursday through Saturday
+        assert_eq!(alice.engaged_days, 3);
+        assert_eq!(alice.longest_streak_days, 3);
+        assert!((alice.after_hours_rate - 200.0 / 3.0).abs() < 1e-9);
+
+        let bob = &summaries[1];
+        assert_eq!(bob.after_hours_engagements, 0);
+        assert_eq!(bob.longest_streak_days, 1);
+
+        // Per service, the shared incident counts once but both responders' hours add up
+        let by_service = summarize_load(&engagements, &hours, |e| (e.service_id.clone(), e.service_name.clone()));
+        assert_eq!(by_service.len(), 1);
+        assert_eq!(by_service[0].incident_count, 3);
+        assert_eq!(by_service[0].engagement_count, 4);
+        assert!((by_service[0].engaged_hours - 8.0).abs() < 1e-9);
+    }
+}
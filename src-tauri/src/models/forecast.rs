@@ -0,0 +1,441 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::models::metrics::{MetricFilters, TimeseriesBucket};
+
+pub const FORECAST_METRICS: &[&str] = &["count", "mttr"];
+
+pub const METHOD_SEASONAL_NAIVE: &str = "seasonal_naive";
+pub const METHOD_EXPONENTIAL_SMOOTHING: &str = "exponential_smoothing";
+
+/// Quarters per year; the seasonal period of a quarterly series.
+pub const SEASON_LENGTH: usize = 4;
+/// Held-out quarters used to report backtest accuracy alongside a forecast.
+pub const BACKTEST_QUARTERS: usize = 4;
+
+const Z_80: f64 = 1.2816;
+const Z_95: f64 = 1.96;
+const SMOOTHING_GRID: [f64; 9] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+const DEFAULT_SMOOTHING: f64 = 0.3;
+
+fn default_horizon() -> usize {
+    1
+}
+
+fn default_history_quarters() -> usize {
+    12
+}
+
+/// Request for a quarterly forecast of incident volume or MTTR.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastRequest {
+    pub metric: String,
+    /// Quarters to forecast past the last complete quarter.
+    #[serde(default = "default_horizon")]
+    pub horizon: usize,
+    /// Complete quarters of history the models are fitted on.
+    #[serde(default = "default_history_quarters")]
+    pub history_quarters: usize,
+    /// Forecast from the last quarter complete on this date (YYYY-MM-DD); defaults to today.
+    #[serde(default)]
+    pub as_of: Option<String>,
+    #[serde(default)]
+    pub filters: MetricFilters,
+}
+
+impl ForecastRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        if !FORECAST_METRICS.contains(&self.metric.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid metric '{}'. Must be one of: {}",
+                self.metric,
+                FORECAST_METRICS.join(", ")
+            )));
+        }
+        if !(1..=4).contains(&self.horizon) {
+            return Err(AppError::Validation(
+                "Forecast horizon must be between 1 and 4 quarters".into(),
+            ));
+        }
+        if !(2..=40).contains(&self.history_quarters) {
+            return Err(AppError::Validation(
+                "History must be between 2 and 40 quarters".into(),
+            ));
+        }
+        if let Some(ref as_of) = self.as_of {
+            if chrono::NaiveDate::parse_from_str(as_of, "%Y-%m-%d").is_err() {
+                return Err(AppError::Validation(format!("Invalid date: {}", as_of)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Expected value for one future quarter with 80% and 95% prediction intervals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastPoint {
+    pub label: String,
+    pub start: String,
+    pub end: String,
+    pub expected: f64,
+    pub lower_80: f64,
+    pub upper_80: f64,
+    pub lower_95: f64,
+    pub upper_95: f64,
+}
+
+/// Rolling-origin one-step-ahead accuracy over the most recent quarters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacktestSummary {
+    pub folds: usize,
+    /// Mean absolute error in the metric's unit.
+    pub mae: f64,
+    /// Mean absolute percentage error over quarters with a non-zero actual.
+    pub mape: Option<f64>,
+    /// Share of held-out actuals inside the 80% interval (0..1).
+    pub coverage_80: f64,
+    /// Share of held-out actuals inside the 95% interval (0..1).
+    pub coverage_95: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastSeries {
+    /// "overall" or the service ID.
+    pub key: String,
+    pub label: String,
+    pub method: String,
+    /// Observed values aligned with `IncidentForecast::history_buckets`.
+    pub history: Vec<f64>,
+    pub points: Vec<ForecastPoint>,
+    pub backtest: Option<BacktestSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentForecast {
+    pub metric: String,
+    pub history_buckets: Vec<TimeseriesBucket>,
+    pub forecast_buckets: Vec<TimeseriesBucket>,
+    pub overall: Option<ForecastSeries>,
+    /// Per-service forecasts, highest expected next-quarter value first.
+    pub by_service: Vec<ForecastSeries>,
+}
+
+/// Point forecasts and their standard errors for each step ahead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    pub method: &'static str,
+    pub expected: Vec<f64>,
+    pub std_errors: Vec<f64>,
+}
+
+impl Forecast {
+    /// `(lower, upper)` for step `h` (0-based) at the given z-score.
+    pub fn interval(&self, h: usize, z: f64) -> (f64, f64) {
+        let expected = self.expected[h];
+        let margin = z * self.std_errors[h];
+        ((expected - margin).max(0.0), expected + margin)
+    }
+
+    pub fn interval_80(&self, h: usize) -> (f64, f64) {
+        self.interval(h, Z_80)
+    }
+
+    pub fn interval_95(&self, h: usize) -> (f64, f64) {
+        self.interval(h, Z_95)
+    }
+}
+
+/// Simple exponential smoothing with the smoothing factor picked from a grid by
+/// in-sample one-step squared error. Returns `(alpha, final level, residuals)`
+/// where `residuals[i]` is the one-step error for `history[i + 1]`.
+fn fit_exponential_smoothing(history: &[f64]) -> (f64, f64, Vec<f64>) {
+    let run = |alpha: f64| {
+        let mut level = history[0];
+        let mut residuals = Vec::with_capacity(history.len().saturating_sub(1));
+        for &y in &history[1..] {
+            residuals.push(y - level);
+            level = alpha * y + (1.0 - alpha) * level;
+        }
+        (level, residuals)
+    };
+
+    if history.len() < 3 {
+        let (level, residuals) = run(DEFAULT_SMOOTHING);
+        return (DEFAULT_SMOOTHING, level, residuals);
+    }
+
+    let mut best: Option<(f64, f64, Vec<f64>, f64)> = None;
+    for alpha in SMOOTHING_GRID {
+        let (level, residuals) = run(alpha);
+        let sse: f64 = residuals.iter().map(|e| e * e).sum();
+        if best.as_ref().is_none_or(|b| sse < b.3) {
+            best = Some((alpha, level, residuals, sse));
+        }
+    }
+    let (alpha, level, residuals, _) = best.expect("grid is non-empty");
+    (alpha, level, residuals)
+}
+
+/// Errors of forecasting each quarter with the same quarter a year earlier.
+fn seasonal_naive_residuals(history: &[f64]) -> Vec<f64> {
+    (SEASON_LENGTH..history.len())
+        .map(|t| history[t] - history[t - SEASON_LENGTH])
+        .collect()
+}
+
+fn mean_abs(values: &[f64]) -> f64 {
+    values.iter().map(|v| v.abs()).sum::<f64>() / values.len() as f64
+}
+
+fn rmse(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    Some((values.iter().map(|v| v * v).sum::<f64>() / values.len() as f64).sqrt())
+}
+
+/// Forecast `horizon` quarters past the end of `history`.
+///
+/// Uses seasonal naive when at least two full years are available and it beat
+/// exponential smoothing on the same in-sample quarters; exponential smoothing
+/// otherwise. Intervals come from the in-sample residuals. Counts are never
+/// given less spread than a Poisson process with the same mean, and series too
+/// short to estimate an error fall back to a ±50% band.
+pub fn forecast(history: &[f64], horizon: usize, is_count: bool) -> Option<Forecast> {
+    if history.is_empty() || horizon == 0 {
+        return None;
+    }
+    let n = history.len();
+    let (alpha, level, ses_residuals) = fit_exponential_smoothing(history);
+
+    let use_seasonal = n >= 2 * SEASON_LENGTH && {
+        let seasonal = seasonal_naive_residuals(history);
+        // SES residual i is for t = i + 1; compare over the same t >= SEASON_LENGTH
+        let ses_overlap = &ses_residuals[SEASON_LENGTH - 1..];
+        mean_abs(&seasonal) < mean_abs(ses_overlap)
+    };
+
+    let (method, residuals) = if use_seasonal {
+        (METHOD_SEASONAL_NAIVE, seasonal_naive_residuals(history))
+    } else {
+        (METHOD_EXPONENTIAL_SMOOTHING, ses_residuals)
+    };
+    let sigma = rmse(&residuals);
+
+    let mut expected = Vec::with_capacity(horizon);
+    let mut std_errors = Vec::with_capacity(horizon);
+    for h in 1..=horizon {
+        let (point, scale) = if use_seasonal {
+            let seasons_ahead = (h - 1) / SEASON_LENGTH;
+            let point = history[n - SEASON_LENGTH + (h - 1) % SEASON_LENGTH];
+            (point, ((seasons_ahead + 1) as f64).sqrt())
+        } else {
+            (level, (1.0 + (h - 1) as f64 * alpha * alpha).sqrt())
+        };
+        let point = point.max(0.0);
+
+        let mut se = match sigma {
+            Some(s) => s * scale,
+            None => point * 0.5,
+        };
+        if is_count {
+            se = se.max(point.sqrt());
+        }
+        expected.push(point);
+        std_errors.push(se);
+    }
+
+    Some(Forecast {
+        method,
+        expected,
+        std_errors,
+    })
+}
+
+/// Refit on each prefix ending before one of the last `holdout` values and score
+/// the one-step forecast against it. Needs at least two quarters of training data.
+pub fn backtest(history: &[f64], holdout: usize, is_count: bool) -> Option<BacktestSummary> {
+    const MIN_TRAIN: usize = 2;
+    let folds = holdout.min(history.len().saturating_sub(MIN_TRAIN));
+    if folds == 0 {
+        return None;
+    }
+
+    let mut abs_errors = Vec::with_capacity(folds);
+    let mut pct_errors = Vec::new();
+    let mut inside_80 = 0usize;
+    let mut inside_95 = 0usize;
+    for origin in history.len() - folds..history.len() {
+        let fit = forecast(&history[..origin], 1, is_count)?;
+        let actual = history[origin];
+        let error = actual - fit.expected[0];
+        abs_errors.push(error.abs());
+        if actual != 0.0 {
+            pct_errors.push((error / actual).abs() * 100.0);
+        }
+        let (lo, hi) = fit.interval_80(0);
+        if actual >= lo && actual <= hi {
+            inside_80 += 1;
+        }
+        let (lo, hi) = fit.interval_95(0);
+        if actual >= lo && actual <= hi {
+            inside_95 += 1;
+        }
+    }
+
+    Some(BacktestSummary {
+        folds,
+        mae: mean_abs(&abs_errors),
+        mape: if pct_errors.is_empty() {
+            None
+        } else {
+            Some(pct_errors.iter().sum::<f64>() / pct_errors.len() as f64)
+        },
+        coverage_80: inside_80 as f64 / folds as f64,
+        coverage_95: inside_95 as f64 / folds as f64,
+    })
+}
+
+/// Observed history for a series whose quarters may have no samples (an MTTR
+/// quarter without resolved incidents). Leading empty quarters are dropped and
+/// later gaps carry the previous value forward. Counts are used as-is.
+pub fn observed_history(values: &[f64], sample_counts: &[i64], is_count: bool) -> Vec<f64> {
+    if is_count {
+        return values.to_vec();
+    }
+    let mut history = Vec::with_capacity(values.len());
+    let mut last: Option<f64> = None;
+    for (value, samples) in values.iter().zip(sample_counts) {
+        if *samples > 0 {
+            last = Some(*value);
+        }
+        if let Some(v) = last {
+            history.push(v);
+        }
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-noise in [-1, 1] so tests are reproducible.
+    fn noise(i: usize) -> f64 {
+        (((i * 7919 + 17) % 101) as f64 / 50.0) - 1.0
+    }
+
+    fn seasonal_series(quarters: usize) -> Vec<f64> {
+        let pattern = [40.0, 25.0, 20.0, 55.0];
+        (0..quarters)
+            .map(|i| pattern[i % 4] + i as f64 * 0.5 + noise(i) * 2.0)
+            .collect()
+    }
+
+    fn level_series(quarters: usize) -> Vec<f64> {
+        (0..quarters).map(|i| 30.0 + noise(i) * 4.0).collect()
+    }
+
+    /// Test: request validation
+    #[test]
+    fn test_request_validation() {
+        let mut req = ForecastRequest {
+            metric: "count".into(),
+            horizon: 1,
+            history_quarters: 12,
+            as_of: Some("2025-09-30".into()),
+            filters: MetricFilters::default(),
+        };
+        assert!(req.validate().is_ok());
+        req.metric = "downtime".into();
+        assert!(req.validate().is_err());
+        req.metric = "mttr".into();
+        req.horizon = 5;
+        assert!(req.validate().is_err());
+        req.horizon = 2;
+        req.as_of = Some("2025-13-01".into());
+        assert!(req.validate().is_err());
+    }
+
+    /// Test: a strongly seasonal history selects seasonal naive
+    #[test]
+    fn test_seasonal_history_uses_seasonal_naive() {
+        let history = seasonal_series(12);
+        let fit = forecast(&history, 4, true).unwrap();
+        assert_eq!(fit.method, METHOD_SEASONAL_NAIVE);
+        // Each step repeats the same quarter from the last year
+        assert_eq!(fit.expected, history[8..12].to_vec());
+        for h in 0..4 {
+            let (lo80, hi80) = fit.interval_80(h);
+            let (lo95, hi95) = fit.interval_95(h);
+            assert!(lo95 <= lo80 && lo80 <= fit.expected[h]);
+            assert!(fit.expected[h] <= hi80 && hi80 <= hi95);
+        }
+    }
+
+    /// Test: a flat noisy history selects exponential smoothing with a flat path
+    #[test]
+    fn test_level_history_uses_exponential_smoothing() {
+        let history = level_series(12);
+        let fit = forecast(&history, 3, true).unwrap();
+        assert_eq!(fit.method, METHOD_EXPONENTIAL_SMOOTHING);
+        assert!((fit.expected[0] - 30.0).abs() < 4.0);
+        assert_eq!(fit.expected[0], fit.expected[2]);
+        // Uncertainty does not shrink further out
+        assert!(fit.std_errors[2] >= fit.std_errors[0]);
+    }
+
+    /// Test: short histories still forecast, with a Poisson floor on count spread
+    #[test]
+    fn test_short_history_fallbacks() {
+        assert!(forecast(&[], 1, true).is_none());
+
+        let fit = forecast(&[16.0], 1, true).unwrap();
+        assert_eq!(fit.expected, vec![16.0]);
+        assert!((fit.std_errors[0] - 8.0).abs() < 1e-9);
+
+        let fit = forecast(&[10.0, 10.0, 10.0], 1, true).unwrap();
+        assert!((fit.std_errors[0] - 10f64.sqrt()).abs() < 1e-9);
+
+        let fit = forecast(&[120.0], 1, false).unwrap();
+        assert_eq!(fit.interval_95(0).0, 120.0 - 1.96 * 60.0);
+        let fit = forecast(&[0.0, 0.0], 1, true).unwrap();
+        assert_eq!(fit.interval_80(0), (0.0, 0.0));
+    }
+
+    /// Test: backtest on held-out quarters of a seasonal series
+    #[test]
+    fn test_backtest_seasonal_accuracy() {
+        let history = seasonal_series(16);
+        let summary = backtest(&history, BACKTEST_QUARTERS, true).unwrap();
+        assert_eq!(summary.folds, 4);
+        // Seasonal swings are ~35 incidents; the model should be far closer
+        assert!(summary.mae < 6.0, "mae was {}", summary.mae);
+        assert!(summary.mape.unwrap() < 15.0);
+        assert!(summary.coverage_95 >= 0.75);
+
+        // A naive last-value forecast does much worse on the same quarters
+        let naive_mae = (12..16).map(|t| (history[t] - history[t - 1]).abs()).sum::<f64>() / 4.0;
+        assert!(summary.mae < naive_mae / 2.0);
+    }
+
+    /// Test: backtest on held-out quarters of a level series
+    #[test]
+    fn test_backtest_level_accuracy() {
+        let history = level_series(16);
+        let summary = backtest(&history, BACKTEST_QUARTERS, true).unwrap();
+        assert!(summary.mae < 6.0, "mae was {}", summary.mae);
+        assert!(summary.coverage_80 >= 0.5);
+        assert!(backtest(&history[..2], BACKTEST_QUARTERS, true).is_none());
+        assert_eq!(backtest(&history[..4], BACKTEST_QUARTERS, true).unwrap().folds, 2);
+    }
+
+    /// Test: MTTR gaps carry forward and leading gaps are dropped
+    #[test]
+    fn test_observed_history_fills_gaps() {
+        let values = [0.0, 45.0, 0.0, 60.0];
+        let samples = [0, 3, 0, 2];
+        assert_eq!(observed_history(&values, &samples, false), vec![45.0, 45.0, 60.0]);
+        assert_eq!(observed_history(&values, &samples, true), values.to_vec());
+    }
+}
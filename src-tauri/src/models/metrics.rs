@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::error::{AppError, AppResult};
+use crate::models::cost::ServiceCost;
+use crate::models::reliability::ScoreBreakdown;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct MetricFilters {
     pub service_ids: Option<Vec<String>>,
@@ -38,6 +42,15 @@ pub struct QuarterlyTrends {
     pub incident_count: Vec<i64>,
     pub recurrence_rate: Vec<f64>,
     pub avg_tickets: Vec<f64>,
+    /// Incident counts per severity, aligned with `quarters`.
+    #[serde(default)]
+    pub severity_counts: Vec<SeverityTrend>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeverityTrend {
+    pub severity: String,
+    pub counts: Vec<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +59,9 @@ pub struct DashboardData {
     pub mtta: MetricResult,
     pub recurrence_rate: MetricResult,
     pub avg_tickets: MetricResult,
+    pub estimated_cost: MetricResult,
+    pub cost_by_service: Vec<ServiceCost>,
+    pub checklist_completion: MetricResult,
     pub by_severity: Vec<CategoryCount>,
     pub by_impact: Vec<CategoryCount>,
     pub by_service: Vec<CategoryCount>,
@@ -145,10 +161,17 @@ pub struct BacklogAgingBucket {
 pub struct ServiceReliabilityScore {
     pub service_id: String,
     pub service_name: String,
+    pub tier: String,
     pub incident_count: i64,
     pub mttr_minutes: f64,
     pub mttr_formatted: String,
     pub sla_compliance_pct: f64,
+    pub sla_breaches: i64,
+    pub downtime_minutes: f64,
+    pub recurring_count: i64,
+    /// 0-100 under the configured scoring model; higher is healthier.
+    pub score: f64,
+    pub breakdown: ScoreBreakdown,
 }
 
 /// Escalation funnel: severity distribution
@@ -158,3 +181,116 @@ pub struct EscalationFunnelEntry {
     pub count: i64,
     pub percentage: f64,
 }
+
+pub const TIMESERIES_METRICS: &[&str] = &[
+    "count",
+    "mttr",
+    "mtta",
+    "downtime",
+    "affected_users",
+    "tickets",
+];
+pub const TIMESERIES_BUCKETS: &[&str] = &["day", "week", "month", "quarter"];
+pub const TIMESERIES_GROUP_BY: &[&str] = &["service", "severity", "tag", "tier"];
+
+/// Maximum number of buckets a single time series request may produce.
+pub const MAX_TIMESERIES_BUCKETS: usize = 2000;
+
+/// Request for an arbitrary metric time series.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeseriesRequest {
+    pub metric: String,
+    pub bucket: String,
+    pub group_by: Option<String>,
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub filters: MetricFilters,
+}
+
+impl TimeseriesRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        if !TIMESERIES_METRICS.contains(&self.metric.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid metric '{}'. Must be one of: {}",
+                self.metric,
+                TIMESERIES_METRICS.join(", ")
+            )));
+        }
+        if !TIMESERIES_BUCKETS.contains(&self.bucket.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid bucket '{}'. Must be one of: {}",
+                self.bucket,
+                TIMESERIES_BUCKETS.join(", ")
+            )));
+        }
+        if let Some(ref group_by) = self.group_by {
+            if !TIMESERIES_GROUP_BY.contains(&group_by.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "Invalid group_by '{}'. Must be one of: {}",
+                    group_by,
+                    TIMESERIES_GROUP_BY.join(", ")
+                )));
+            }
+        }
+        if self.start_date.trim().is_empty() || self.end_date.trim().is_empty() {
+            return Err(AppError::Validation("Start and end dates are required".into()));
+        }
+        Ok(())
+    }
+}
+
+/// One bucket on the time axis. `start` and `end` are inclusive calendar dates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeseriesBucket {
+    pub start: String,
+    pub end: String,
+    pub label: String,
+}
+
+/// One line of a time series. `values` and `sample_counts` are aligned with
+/// `MetricTimeseries::buckets`; empty buckets are present with a value of 0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeseriesSeries {
+    pub key: String,
+    pub label: String,
+    pub values: Vec<f64>,
+    pub sample_counts: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricTimeseries {
+    pub metric: String,
+    pub bucket: String,
+    pub group_by: Option<String>,
+    pub buckets: Vec<TimeseriesBucket>,
+    pub series: Vec<TimeseriesSeries>,
+}
+
+/// Result of comparing the materialized daily rollups against a raw recompute.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupConsistencyReport {
+    pub consistent: bool,
+    pub rollup_rows: i64,
+    pub raw_rows: i64,
+    /// Number of keys whose stored values differ from the recompute (including missing/extra rows).
+    pub mismatch_count: i64,
+    /// First mismatches found, for diagnosis.
+    pub mismatches: Vec<RollupMismatch>,
+    pub checked_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupMismatch {
+    pub day: String,
+    pub service_id: String,
+    pub severity: String,
+    pub impact: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupRebuildResult {
+    pub days: i64,
+    pub rows: i64,
+}
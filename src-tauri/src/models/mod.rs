@@ -1,15 +1,26 @@
 pub mod audit;
 pub mod checklist;
+pub mod cost;
 pub mod custom_field;
+pub mod discussion_rule;
+pub mod export;
+pub mod fatigue;
+pub mod forecast;
 pub mod incident;
 pub mod metrics;
 pub mod priority;
 pub mod quarter;
+pub mod redaction;
+pub mod reliability;
 pub mod report_history;
+pub mod report_template;
+pub mod report_theme;
 pub mod role;
 pub mod service;
+pub mod service_graph;
 pub mod saved_filter;
 pub mod postmortem;
 pub mod shift_handoff;
 pub mod sla;
 pub mod stakeholder_update;
+pub mod timezone;
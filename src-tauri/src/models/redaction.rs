@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// Maximum number of mask patterns in a profile.
+const MAX_MASK_PATTERNS: usize = 50;
+
+/// How people's names appear in a redacted report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeopleRedaction {
+    Keep,
+    /// Remove names; owner columns show as unassigned.
+    Drop,
+    /// Replace each name with a stable alias such as "Person 3".
+    #[default]
+    Pseudonymize,
+}
+
+/// What to remove from reports shared outside the team, e.g. with customers
+/// or auditors. Applied to quarterly reports in every format and to PIR briefs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionProfile {
+    /// Incident role assignees, action-item owners and service owners.
+    pub people: PeopleRedaction,
+    /// Custom field names left out of outputs that list custom fields.
+    pub hidden_custom_fields: Vec<String>,
+    /// Tags left out of outputs that list tags.
+    pub hidden_tags: Vec<String>,
+    /// Replace service names with their category.
+    pub generalize_services: bool,
+    /// Drop incident notes, external refs, action-item outcome notes,
+    /// runbooks and attachment listings.
+    pub strip_notes: bool,
+    /// Regexes (e.g. for IP addresses, emails or hostnames) whose matches
+    /// are replaced with `[redacted]` in all text.
+    pub mask_patterns: Vec<String>,
+}
+
+impl RedactionProfile {
+    pub fn validate(&self) -> AppResult<()> {
+        if self.mask_patterns.len() > MAX_MASK_PATTERNS {
+            return Err(AppError::Validation(format!(
+                "A redaction profile can have at most {} mask patterns",
+                MAX_MASK_PATTERNS
+            )));
+        }
+        for pattern in &self.mask_patterns {
+            if pattern.trim().is_empty() {
+                return Err(AppError::Validation("Mask patterns cannot be empty".into()));
+            }
+            regex::Regex::new(pattern).map_err(|e| {
+                AppError::Validation(format!("Invalid mask pattern '{}': {}", pattern, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn hides_tag(&self, tag: &str) -> bool {
+        self.hidden_tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn hides_custom_field(&self, name: &str) -> bool {
+        self.hidden_custom_fields.iter().any(|f| f.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test: profiles deserialize with defaults and reject bad patterns
+    #[test]
+    fn test_profile_validation() {
+        let profile: RedactionProfile = serde_json::from_str(r#"{"strip_notes": true}"#).unwrap();
+        assert_eq!(profile.people, PeopleRedaction::Pseudonymize);
+        assert!(profile.strip_notes && !profile.generalize_services);
+        assert!(profile.validate().is_ok());
+
+        let bad = RedactionProfile { mask_patterns: vec!["(unclosed".into()], ..Default::default() };
+        assert!(bad.validate().is_err());
+
+        let tags = RedactionProfile { hidden_tags: vec!["Customer-X".into()], ..Default::default() };
+        assert!(tags.hides_tag("customer-x"));
+        assert!(!tags.hides_tag("network"));
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::queries::metrics::severity_rank;
+use crate::error::{AppError, AppResult};
+
+/// Score a service starts from before penalties.
+pub const MAX_SCORE: f64 = 100.0;
+
+/// How incidents and their side effects count against a service's score.
+///
+/// Each incident costs `severity weight x impact multiplier`; downtime,
+/// recurrences and SLA breaches add their own penalties. The total is scaled
+/// by the service tier's multiplier and subtracted from `MAX_SCORE`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReliabilityScoringConfig {
+    /// Base penalty per incident, by severity.
+    pub severity_weights: BTreeMap<String, f64>,
+    /// Multiplier on the severity weight, by impact.
+    pub impact_multipliers: BTreeMap<String, f64>,
+    /// Multiplier on the whole penalty, by service tier.
+    pub tier_multipliers: BTreeMap<String, f64>,
+    /// Penalty per hour of incident downtime.
+    pub downtime_penalty_per_hour: f64,
+    /// Penalty per incident flagged as recurring.
+    pub recurrence_penalty: f64,
+    /// Penalty per resolved incident that missed its SLA resolve target.
+    pub sla_breach_penalty: f64,
+}
+
+fn weights(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+    entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+impl Default for ReliabilityScoringConfig {
+    fn default() -> Self {
+        Self {
+            severity_weights: weights(&[("Critical", 10.0), ("High", 5.0), ("Medium", 2.0), ("Low", 1.0)]),
+            impact_multipliers: weights(&[("Critical", 2.0), ("High", 1.5), ("Medium", 1.0), ("Low", 0.5)]),
+            tier_multipliers: weights(&[("T1", 2.0), ("T2", 1.5), ("T3", 1.0), ("T4", 0.5)]),
+            downtime_penalty_per_hour: 1.0,
+            recurrence_penalty: 3.0,
+            sla_breach_penalty: 4.0,
+        }
+    }
+}
+
+fn check_weight(name: &str, value: f64) -> AppResult<()> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(AppError::Validation(format!(
+            "{} must be a non-negative number",
+            name
+        )));
+    }
+    Ok(())
+}
+
+impl ReliabilityScoringConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        for (label, map) in [
+            ("Severity weight", &self.severity_weights),
+            ("Impact multiplier", &self.impact_multipliers),
+            ("Tier multiplier", &self.tier_multipliers),
+        ] {
+            for (key, value) in map {
+                check_weight(&format!("{} for {}", label, key), *value)?;
+            }
+        }
+        check_weight("Downtime penalty", self.downtime_penalty_per_hour)?;
+        check_weight("Recurrence penalty", self.recurrence_penalty)?;
+        check_weight("SLA breach penalty", self.sla_breach_penalty)?;
+        Ok(())
+    }
+
+    /// Unlisted keys fall back to a neutral weight of 1.
+    fn lookup(map: &BTreeMap<String, f64>, key: &str) -> f64 {
+        map.get(key).copied().unwrap_or(1.0)
+    }
+
+    pub fn tier_multiplier(&self, tier: &str) -> f64 {
+        Self::lookup(&self.tier_multipliers, tier)
+    }
+
+    pub fn incident_weight(&self, severity: &str, impact: &str) -> f64 {
+        Self::lookup(&self.severity_weights, severity) * Self::lookup(&self.impact_multipliers, impact)
+    }
+}
+
+/// What a service's incidents looked like over the scored period.
+#[derive(Debug, Clone, Default)]
+pub struct ScoreInputs {
+    pub tier: String,
+    /// `(severity, impact)` for each incident.
+    pub incidents: Vec<(String, String)>,
+    pub downtime_minutes: f64,
+    pub recurring_count: i64,
+    pub sla_breaches: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeverityPenalty {
+    pub severity: String,
+    pub count: i64,
+    pub penalty: f64,
+}
+
+/// Why a service scored as it did. Penalties already include the tier multiplier.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub tier: String,
+    pub tier_multiplier: f64,
+    pub incident_penalty: f64,
+    pub by_severity: Vec<SeverityPenalty>,
+    pub downtime_penalty: f64,
+    pub recurrence_penalty: f64,
+    pub sla_breach_penalty: f64,
+    pub total_penalty: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReliabilityHistoryEntry {
+    pub quarter_id: String,
+    pub quarter_label: String,
+    pub service_id: String,
+    pub service_name: String,
+    pub score: f64,
+    pub incident_count: i64,
+    pub breakdown: ScoreBreakdown,
+    /// False for the quarter in progress, which is recomputed on every read.
+    pub is_final: bool,
+    pub computed_at: String,
+}
+
+/// Score between 0 and `MAX_SCORE`, with the breakdown that produced it.
+pub fn score_service(inputs: &ScoreInputs, config: &ReliabilityScoringConfig) -> (f64, ScoreBreakdown) {
+    let tier_multiplier = config.tier_multiplier(&inputs.tier);
+
+    let mut by_severity: Vec<SeverityPenalty> = vec![];
+    for (severity, impact) in &inputs.incidents {
+        let penalty = config.incident_weight(severity, impact) * tier_multiplier;
+        match by_severity.iter_mut().find(|s| &s.severity == severity) {
+            Some(entry) => {
+                entry.count += 1;
+                entry.penalty += penalty;
+            }
+            None => by_severity.push(SeverityPenalty {
+                severity: severity.clone(),
+                count: 1,
+                penalty,
+            }),
+        }
+    }
+    by_severity.sort_by_key(|s| severity_rank(&s.severity));
+
+    let incident_penalty: f64 = by_severity.iter().map(|s| s.penalty).sum();
+    let downtime_penalty =
+        inputs.downtime_minutes.max(0.0) / 60.0 * config.downtime_penalty_per_hour * tier_multiplier;
+    let recurrence_penalty = inputs.recurring_count as f64 * config.recurrence_penalty * tier_multiplier;
+    let sla_breach_penalty = inputs.sla_breaches as f64 * config.sla_breach_penalty * tier_multiplier;
+    let total_penalty = incident_penalty + downtime_penalty + recurrence_penalty + sla_breach_penalty;
+
+    let breakdown = ScoreBreakdown {
+        tier: inputs.tier.clone(),
+        tier_multiplier,
+        incident_penalty,
+        by_severity,
+        downtime_penalty,
+        recurrence_penalty,
+        sla_breach_penalty,
+        total_penalty,
+    };
+    ((MAX_SCORE - total_penalty).clamp(0.0, MAX_SCORE), breakdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(tier: &str, incidents: &[(&str, &str)]) -> ScoreInputs {
+        ScoreInputs {
+            tier: tier.into(),
+            incidents: incidents.iter().map(|(s, i)| (s.to_string(), i.to_string())).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Test: a T1 Critical incident outweighs two Low incidents on a T4 service
+    #[test]
+    fn test_tier_and_severity_ordering() {
+        let config = ReliabilityScoringConfig::default();
+        let (t1, _) = score_service(&inputs("T1", &[("Critical", "High")]), &config);
+        let (t4, _) = score_service(&inputs("T4", &[("Low", "Low"), ("Low", "Low")]), &config);
+        assert!(t1 < t4);
+        assert!((t1 - 70.0).abs() < 1e-9);
+        assert!((t4 - 99.5).abs() < 1e-9);
+    }
+
+    /// Test: the breakdown itemises every penalty and the score is clamped at zero
+    #[test]
+    fn test_breakdown_and_clamp() {
+        let config = ReliabilityScoringConfig::default();
+        let mut svc = inputs("T3", &[("High", "Medium"), ("Critical", "Critical"), ("High", "Low")]);
+        svc.downtime_minutes = 180.0;
+        svc.recurring_count = 1;
+        svc.sla_breaches = 2;
+        let (score, breakdown) = score_service(&svc, &config);
+
+        assert_eq!(breakdown.by_severity[0].severity, "Critical");
+        assert_eq!(breakdown.by_severity[1].count, 2);
+        assert!((breakdown.incident_penalty - 27.5).abs() < 1e-9);
+        assert!((breakdown.downtime_penalty - 3.0).abs() < 1e-9);
+        assert!((breakdown.recurrence_penalty - 3.0).abs() < 1e-9);
+        assert!((breakdown.sla_breach_penalty - 8.0).abs() < 1e-9);
+        assert!((score - (100.0 - 41.5)).abs() < 1e-9);
+
+        svc.downtime_minutes = 100_000.0;
+        assert_eq!(score_service(&svc, &config).0, 0.0);
+    }
+
+    /// Test: negative or non-finite weights are rejected
+    #[test]
+    fn test_config_validation() {
+        assert!(ReliabilityScoringConfig::default().validate().is_ok());
+        let mut config = ReliabilityScoringConfig::default();
+        config.tier_multipliers.insert("T1".into(), -1.0);
+        assert!(config.validate().is_err());
+        let config = ReliabilityScoringConfig {
+            sla_breach_penalty: f64::NAN,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -10,4 +10,10 @@ pub struct ReportHistory {
     pub file_path: String,
     pub config_json: String,
     pub file_size_bytes: Option<i64>,
+    /// Version of the stored snapshot; `None` for reports saved before snapshots were recorded.
+    pub snapshot_version: Option<i64>,
+    /// SHA-256 of the saved file, as lowercase hex.
+    pub artifact_sha256: Option<String>,
+    /// SHA-256 of the rendered document content, independent of the file format.
+    pub content_sha256: Option<String>,
 }
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// A stored report layout: which sections appear, in what order, and how
+/// each one is configured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub sections: Vec<TemplateSection>,
+    pub is_default: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSectionKind {
+    ExecutiveSummary,
+    /// Owner, tier, runbook and dependencies of the services a report covers.
+    ServiceContext,
+    MetricsOverview,
+    IncidentTimeline,
+    IncidentBreakdowns,
+    ServiceReliability,
+    CostImpact,
+    ResponderLoad,
+    ChecklistCompliance,
+    QoqComparison,
+    Outlook,
+    DiscussionPoints,
+    ActionItems,
+    /// Free-text markdown with `{{placeholder}}` values filled in.
+    Custom,
+}
+
+impl ReportSectionKind {
+    /// Built-in sections in the classic report order.
+    pub const BUILT_IN: [ReportSectionKind; 13] = [
+        ReportSectionKind::ExecutiveSummary,
+        ReportSectionKind::ServiceContext,
+        ReportSectionKind::MetricsOverview,
+        ReportSectionKind::IncidentTimeline,
+        ReportSectionKind::IncidentBreakdowns,
+        ReportSectionKind::ServiceReliability,
+        ReportSectionKind::CostImpact,
+        ReportSectionKind::ResponderLoad,
+        ReportSectionKind::ChecklistCompliance,
+        ReportSectionKind::QoqComparison,
+        ReportSectionKind::Outlook,
+        ReportSectionKind::DiscussionPoints,
+        ReportSectionKind::ActionItems,
+    ];
+
+    fn supports_top_n(self) -> bool {
+        matches!(
+            self,
+            ReportSectionKind::IncidentBreakdowns
+                | ReportSectionKind::ServiceReliability
+                | ReportSectionKind::CostImpact
+                | ReportSectionKind::ResponderLoad
+                | ReportSectionKind::DiscussionPoints
+                | ReportSectionKind::ActionItems
+        )
+    }
+
+    /// Chart keys the section can show, in default order.
+    pub fn chart_keys(self) -> &'static [&'static str] {
+        match self {
+            ReportSectionKind::MetricsOverview => crate::reports::charts::OVERVIEW_CHARTS,
+            ReportSectionKind::QoqComparison => crate::reports::charts::TREND_CHARTS,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSection {
+    pub kind: ReportSectionKind,
+    /// Heading for custom sections; ignored for built-in ones.
+    #[serde(default)]
+    pub title: String,
+    /// Markdown body for custom sections.
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub options: SectionOptions,
+}
+
+impl TemplateSection {
+    pub fn built_in(kind: ReportSectionKind) -> Self {
+        Self {
+            kind,
+            title: String::new(),
+            body: String::new(),
+            options: SectionOptions::default(),
+        }
+    }
+}
+
+/// Per-section settings. `None` keeps the section's default behaviour.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SectionOptions {
+    /// Row limit for ranked tables (services, costly incidents, responders, ...).
+    #[serde(default)]
+    pub top_n: Option<usize>,
+    /// Metric rows to show, from `METRIC_KEYS`, in order.
+    #[serde(default)]
+    pub metrics: Option<Vec<String>>,
+    /// Charts to show, in order. An empty list hides the section's charts.
+    #[serde(default)]
+    pub charts: Option<Vec<String>>,
+}
+
+impl SectionOptions {
+    /// The selected metric keys, or all of them.
+    pub fn metric_keys(&self) -> Vec<&str> {
+        match self.metrics {
+            Some(ref keys) => keys.iter().map(String::as_str).collect(),
+            None => METRIC_KEYS.to_vec(),
+        }
+    }
+
+    /// The selected chart keys, or the section's defaults.
+    pub fn chart_keys<'a>(&'a self, defaults: &[&'a str]) -> Vec<&'a str> {
+        match self.charts {
+            Some(ref keys) => keys.iter().map(String::as_str).collect(),
+            None => defaults.to_vec(),
+        }
+    }
+
+    pub fn top_n_or(&self, default: usize) -> usize {
+        self.top_n.unwrap_or(default)
+    }
+}
+
+/// Metric rows available to the metrics overview and quarter comparison.
+pub const METRIC_KEYS: &[&str] = &["mttr", "mtta", "total_incidents", "recurrence_rate", "avg_tickets"];
+
+/// Values custom sections can reference as `{{name}}`.
+pub const PLACEHOLDERS: &[&str] = &[
+    "title",
+    "quarter",
+    "period_start",
+    "period_end",
+    "total_incidents",
+    "critical_incidents",
+    "mttr",
+    "mtta",
+    "recurrence_rate",
+    "avg_tickets",
+    "prev_total_incidents",
+    "prev_mttr",
+    "total_cost",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateReportTemplateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub sections: Vec<TemplateSection>,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReportTemplateRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub sections: Option<Vec<TemplateSection>>,
+    pub is_default: Option<bool>,
+}
+
+const MAX_NAME_LEN: usize = 200;
+const MAX_DESCRIPTION_LEN: usize = 2_000;
+const MAX_SECTIONS: usize = 50;
+const MAX_TITLE_LEN: usize = 200;
+const MAX_BODY_LEN: usize = 20_000;
+const MAX_TOP_N: usize = 100;
+
+fn validate_name(name: &str) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Template name is required".into()));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(AppError::Validation("Template name too long".into()));
+    }
+    Ok(())
+}
+
+fn validate_description(description: &str) -> AppResult<()> {
+    if description.len() > MAX_DESCRIPTION_LEN {
+        return Err(AppError::Validation("Template description too long".into()));
+    }
+    Ok(())
+}
+
+/// Check a section list: built-in sections appear at most once, custom
+/// sections have a title and only known placeholders, and options apply to
+/// the section they are set on.
+pub fn validate_sections(sections: &[TemplateSection]) -> AppResult<()> {
+    if sections.is_empty() {
+        return Err(AppError::Validation("A template needs at least one section".into()));
+    }
+    if sections.len() > MAX_SECTIONS {
+        return Err(AppError::Validation(format!(
+            "A template can have at most {} sections",
+            MAX_SECTIONS
+        )));
+    }
+
+    let mut seen = Vec::new();
+    for section in sections {
+        let kind = section.kind;
+        if kind == ReportSectionKind::Custom {
+            if section.title.trim().is_empty() {
+                return Err(AppError::Validation("Custom sections need a title".into()));
+            }
+            if section.title.len() > MAX_TITLE_LEN {
+                return Err(AppError::Validation("Custom section title too long".into()));
+            }
+            if section.body.len() > MAX_BODY_LEN {
+                return Err(AppError::Validation("Custom section text too long".into()));
+            }
+            for name in placeholder_names(&section.title).into_iter().chain(placeholder_names(&section.body)) {
+                if !PLACEHOLDERS.contains(&name) {
+                    return Err(AppError::Validation(format!("Unknown placeholder '{{{{{}}}}}'", name)));
+                }
+            }
+        } else if seen.contains(&kind) {
+            return Err(AppError::Validation(format!(
+                "Section '{}' appears more than once",
+                section_key(kind)
+            )));
+        } else {
+            seen.push(kind);
+        }
+
+        let options = &section.options;
+        if let Some(n) = options.top_n {
+            if !kind.supports_top_n() {
+                return Err(AppError::Validation(format!(
+                    "Section '{}' does not support a row limit",
+                    section_key(kind)
+                )));
+            }
+            if n == 0 || n > MAX_TOP_N {
+                return Err(AppError::Validation(format!(
+                    "Row limit must be between 1 and {}",
+                    MAX_TOP_N
+                )));
+            }
+        }
+        if let Some(ref metrics) = options.metrics {
+            if !matches!(kind, ReportSectionKind::MetricsOverview | ReportSectionKind::QoqComparison) {
+                return Err(AppError::Validation(format!(
+                    "Section '{}' does not support metric selection",
+                    section_key(kind)
+                )));
+            }
+            if let Some(unknown) = metrics.iter().find(|m| !METRIC_KEYS.contains(&m.as_str())) {
+                return Err(AppError::Validation(format!("Unknown metric '{}'", unknown)));
+            }
+        }
+        if let Some(ref charts) = options.charts {
+            let allowed = kind.chart_keys();
+            if allowed.is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Section '{}' has no charts",
+                    section_key(kind)
+                )));
+            }
+            if let Some(unknown) = charts.iter().find(|c| !allowed.contains(&c.as_str())) {
+                return Err(AppError::Validation(format!(
+                    "Chart '{}' is not available in section '{}'",
+                    unknown,
+                    section_key(kind)
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The snake_case name used in the stored JSON.
+fn section_key(kind: ReportSectionKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+impl CreateReportTemplateRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        validate_name(&self.name)?;
+        validate_description(&self.description)?;
+        validate_sections(&self.sections)
+    }
+}
+
+impl UpdateReportTemplateRequest {
+    pub fn validate(&self) -> AppResult<()> {
+        if let Some(ref name) = self.name {
+            validate_name(name)?;
+        }
+        if let Some(ref description) = self.description {
+            validate_description(description)?;
+        }
+        if let Some(ref sections) = self.sections {
+            validate_sections(sections)?;
+        }
+        Ok(())
+    }
+}
+
+/// Names inside `{{ ... }}` markers, trimmed.
+pub(crate) fn placeholder_names(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                names.push(after[..end].trim());
+                rest = &after[end + 2..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+/// Replace `{{name}}` markers with their values. Unknown names are left as written.
+pub fn expand_placeholders(text: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                match values.get(after[..end].trim()) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(title: &str, body: &str) -> TemplateSection {
+        TemplateSection {
+            kind: ReportSectionKind::Custom,
+            title: title.into(),
+            body: body.into(),
+            options: SectionOptions::default(),
+        }
+    }
+
+    /// Test: placeholders are filled in, with whitespace allowed and unknown names kept
+    #[test]
+    fn test_expand_placeholders() {
+        let mut values = HashMap::new();
+        values.insert("total_incidents", "12".to_string());
+        values.insert("mttr", "2h 5m".to_string());
+        assert_eq!(
+            expand_placeholders("{{total_incidents}} incidents, MTTR {{ mttr }}, {{nope}} {{open", &values),
+            "12 incidents, MTTR 2h 5m, {{nope}} {{open"
+        );
+    }
+
+    /// Test: section lists parse from the stored snake_case JSON
+    #[test]
+    fn test_sections_json() {
+        let sections: Vec<TemplateSection> = serde_json::from_str(
+            r#"[{"kind":"metrics_overview","options":{"metrics":["mttr"],"charts":[]}},{"kind":"custom","title":"Notes","body":"{{mttr}}"}]"#,
+        )
+        .unwrap();
+        assert_eq!(sections[0].options.metric_keys(), vec!["mttr"]);
+        assert!(sections[0].options.chart_keys(&["severity_chart"]).is_empty());
+        assert_eq!(sections[1].kind, ReportSectionKind::Custom);
+        assert!(validate_sections(&sections).is_ok());
+    }
+
+    /// Test: invalid layouts and options are rejected
+    #[test]
+    fn test_validate_sections() {
+        let overview = TemplateSection::built_in(ReportSectionKind::MetricsOverview);
+        assert!(validate_sections(&[]).is_err());
+        assert!(validate_sections(&[overview.clone(), overview.clone()]).is_err());
+        assert!(validate_sections(&[custom("", "text")]).is_err());
+        assert!(validate_sections(&[custom("Notes", "{{unknown}}")]).is_err());
+        assert!(validate_sections(&[custom("Notes", "a"), custom("More", "{{ mttr }}")]).is_ok());
+
+        let mut timeline = TemplateSection::built_in(ReportSectionKind::IncidentTimeline);
+        timeline.options.top_n = Some(5);
+        assert!(validate_sections(&[timeline]).is_err());
+
+        let mut costs = TemplateSection::built_in(ReportSectionKind::CostImpact);
+        costs.options.top_n = Some(0);
+        assert!(validate_sections(&[costs.clone()]).is_err());
+        costs.options.top_n = Some(3);
+        assert!(validate_sections(&[costs]).is_ok());
+
+        let mut qoq = TemplateSection::built_in(ReportSectionKind::QoqComparison);
+        qoq.options.charts = Some(vec!["severity_chart".into()]);
+        assert!(validate_sections(&[qoq.clone()]).is_err());
+        qoq.options.charts = Some(vec!["trend_heatmap".into()]);
+        qoq.options.metrics = Some(vec!["cost".into()]);
+        assert!(validate_sections(&[qoq]).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// Largest logo accepted, in bytes of PNG data.
+const MAX_LOGO_BYTES: usize = 1024 * 1024;
+const MAX_TEXT_LEN: usize = 200;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Font families bundled with the app, so PDF output and chart text look the
+/// same on every machine. DOCX files name the family; viewers without it
+/// installed substitute a similar font.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFont {
+    #[default]
+    Sans,
+    Serif,
+}
+
+impl ReportFont {
+    pub fn family_name(&self) -> &'static str {
+        match self {
+            ReportFont::Sans => "DejaVu Sans",
+            ReportFont::Serif => "DejaVu Serif",
+        }
+    }
+}
+
+/// How the first page presents the title.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverLayout {
+    /// Logo, title and period lines at the top of the first page, with the
+    /// report starting below them.
+    #[default]
+    Compact,
+    /// A cover page of its own with a large logo and title, without the
+    /// page header and footer.
+    FullPage,
+}
+
+/// Branding and styling applied to DOCX and PDF reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportTheme {
+    /// Base64-encoded PNG shown on the cover.
+    pub logo_png: Option<String>,
+    /// Hex colour (`#RRGGBB`) of the title, headings and banner.
+    pub primary_color: String,
+    /// Hex colour of table header rows and heading rules.
+    pub accent_color: String,
+    pub font: ReportFont,
+    /// Body text size in points; headings and tables scale with it.
+    pub font_size: u8,
+    /// Shown at the top of every page.
+    pub header_text: String,
+    /// Shown at the bottom of every page.
+    pub footer_text: String,
+    pub page_numbers: bool,
+    /// Banner such as "Confidential - internal use only" shown on the cover
+    /// and every page header; empty for none.
+    pub confidentiality_banner: String,
+    pub cover_layout: CoverLayout,
+}
+
+impl Default for ReportTheme {
+    fn default() -> Self {
+        Self {
+            logo_png: None,
+            primary_color: "#1F2937".to_string(),
+            accent_color: "#E0E0E0".to_string(),
+            font: ReportFont::Sans,
+            font_size: 11,
+            header_text: String::new(),
+            footer_text: String::new(),
+            page_numbers: true,
+            confidentiality_banner: String::new(),
+            cover_layout: CoverLayout::Compact,
+        }
+    }
+}
+
+impl ReportTheme {
+    pub fn validate(&self) -> AppResult<()> {
+        for (label, color) in [("Primary colour", &self.primary_color), ("Accent colour", &self.accent_color)] {
+            if parse_hex_color(color).is_none() {
+                return Err(AppError::Validation(format!("{} must be a hex colour like #1F2937", label)));
+            }
+        }
+        if !(8..=16).contains(&self.font_size) {
+            return Err(AppError::Validation("Font size must be between 8 and 16 points".into()));
+        }
+        for (label, text) in [
+            ("Header text", &self.header_text),
+            ("Footer text", &self.footer_text),
+            ("Confidentiality banner", &self.confidentiality_banner),
+        ] {
+            if text.chars().count() > MAX_TEXT_LEN {
+                return Err(AppError::Validation(format!("{} must be {} characters or fewer", label, MAX_TEXT_LEN)));
+            }
+        }
+        if self.logo_png.is_some() {
+            let logo = self.logo()?;
+            if logo.len() > MAX_LOGO_BYTES {
+                return Err(AppError::Validation("Logo must be 1 MB or smaller".into()));
+            }
+            if !logo.starts_with(PNG_SIGNATURE) {
+                return Err(AppError::Validation("Logo must be a PNG image".into()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decoded logo bytes, empty when there is no logo.
+    pub fn logo(&self) -> AppResult<Vec<u8>> {
+        match self.logo_png.as_deref() {
+            Some(data) => base64::engine::general_purpose::STANDARD
+                .decode(data.trim())
+                .map_err(|e| AppError::Validation(format!("Invalid logo data: {}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// A size given for the default 11pt body, scaled to the theme's body size.
+    pub fn scaled(&self, points: u8) -> u8 {
+        ((points as f32 * self.font_size as f32 / 11.0).round() as u8).max(1)
+    }
+
+    pub fn primary_rgb(&self) -> (u8, u8, u8) {
+        parse_hex_color(&self.primary_color).unwrap_or((0x1F, 0x29, 0x37))
+    }
+
+    pub fn accent_rgb(&self) -> (u8, u8, u8) {
+        parse_hex_color(&self.accent_color).unwrap_or((0xE0, 0xE0, 0xE0))
+    }
+
+    /// Black or white, whichever reads better on the accent colour.
+    pub fn on_accent_rgb(&self) -> (u8, u8, u8) {
+        let (r, g, b) = self.accent_rgb();
+        let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+        if luminance > 140.0 {
+            (0, 0, 0)
+        } else {
+            (255, 255, 255)
+        }
+    }
+}
+
+/// `(r, g, b)` for a `#RRGGBB` colour.
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// `RRGGBB` without the `#`, as DOCX expects.
+pub fn hex(rgb: (u8, u8, u8)) -> String {
+    format!("{:02X}{:02X}{:02X}", rgb.0, rgb.1, rgb.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test: themes deserialize with defaults and reject bad colours, sizes and logos
+    #[test]
+    fn test_theme_validation() {
+        let theme: ReportTheme = serde_json::from_str(r##"{"primary_color": "#0a4d8c"}"##).unwrap();
+        assert!(theme.validate().is_ok());
+        assert_eq!(theme.primary_rgb(), (0x0A, 0x4D, 0x8C));
+        assert_eq!(hex(theme.primary_rgb()), "0A4D8C");
+        assert_eq!(theme.font_size, 11);
+        assert_eq!(theme.scaled(28), 28);
+        assert_eq!(ReportTheme { font_size: 16, ..Default::default() }.scaled(11), 16);
+        assert_eq!(theme.on_accent_rgb(), (0, 0, 0));
+        assert_eq!(ReportTheme { accent_color: "#1F4E79".into(), ..Default::default() }.on_accent_rgb(), (255, 255, 255));
+
+        for bad in [
+            ReportTheme { accent_color: "blue".into(), ..Default::default() },
+            ReportTheme { font_size: 30, ..Default::default() },
+            ReportTheme { logo_png: Some("not base64!".into()), ..Default::default() },
+            ReportTheme { logo_png: Some("R0lGODlh".into()), ..Default::default() },
+        ] {
+            assert!(bad.validate().is_err());
+        }
+    }
+}
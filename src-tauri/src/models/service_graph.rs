@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum path weight for a failure to be treated as reaching a dependent.
+pub const PROPAGATION_MIN_WEIGHT: f64 = 0.5;
+
+/// How strongly a failure travels along an edge of the given type.
+pub fn dependency_weight(dependency_type: &str) -> f64 {
+    match dependency_type {
+        "runtime" => 1.0,
+        "data" => 0.7,
+        "build" => 0.3,
+        "optional" => 0.2,
+        _ => 0.5,
+    }
+}
+
+/// `service_id` depends on `depends_on_service_id`.
+#[derive(Debug, Clone)]
+pub struct DependencyEdge {
+    pub service_id: String,
+    pub depends_on_service_id: String,
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Services that depend on the start, i.e. who is hit when it fails.
+    Dependents,
+    /// Services the start depends on, i.e. where a failure may come from.
+    Dependencies,
+}
+
+/// A service reached from the start of a traversal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reach {
+    pub service_id: String,
+    /// Fewest hops from the start.
+    pub depth: u32,
+    /// Product of edge weights along the strongest path.
+    pub weight: f64,
+    /// Service IDs along the strongest path, start first.
+    pub path: Vec<String>,
+    /// Type of the last edge on the strongest path.
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactedService {
+    pub service_id: String,
+    pub service_name: String,
+    pub depth: u32,
+    pub weight: f64,
+    pub dependency_type: String,
+    /// Service names along the strongest path, starting at the analysed service.
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlastRadius {
+    pub service_id: String,
+    pub service_name: String,
+    /// Services that transitively depend on this one.
+    pub upstream_dependents: Vec<ImpactedService>,
+    /// Services this one transitively depends on.
+    pub downstream_dependencies: Vec<ImpactedService>,
+    /// Sum of dependent path weights.
+    pub impact_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriticalPathService {
+    pub service_id: String,
+    pub service_name: String,
+    /// Dependent-service incidents that propagated through this service.
+    pub propagated_incidents: i64,
+    /// Incidents on this service that other incidents propagated from.
+    pub source_incidents: i64,
+    pub dependent_count: i64,
+    pub impact_score: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct ServiceGraph {
+    dependencies: HashMap<String, Vec<(String, String)>>,
+    dependents: HashMap<String, Vec<(String, String)>>,
+}
+
+impl ServiceGraph {
+    pub fn new(edges: &[DependencyEdge]) -> Self {
+        let mut graph = Self::default();
+        for edge in edges {
+            graph
+                .dependencies
+                .entry(edge.service_id.clone())
+                .or_default()
+                .push((edge.depends_on_service_id.clone(), edge.dependency_type.clone()));
+            graph
+                .dependents
+                .entry(edge.depends_on_service_id.clone())
+                .or_default()
+                .push((edge.service_id.clone(), edge.dependency_type.clone()));
+        }
+        graph
+    }
+
+    fn neighbours(&self, service_id: &str, direction: Direction) -> &[(String, String)] {
+        let edges = match direction {
+            Direction::Dependents => &self.dependents,
+            Direction::Dependencies => &self.dependencies,
+        };
+        edges.get(service_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Every service transitively reachable from `start`, strongest first.
+    /// Tolerates cycles even though dependency inserts reject them.
+    pub fn reach(&self, start: &str, direction: Direction) -> Vec<Reach> {
+        // Fewest hops, breadth first
+        let mut depths: HashMap<&str, u32> = HashMap::new();
+        let mut frontier = vec![start];
+        let mut depth = 0;
+        depths.insert(start, 0);
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next = vec![];
+            for id in frontier {
+                for (neighbour, _) in self.neighbours(id, direction) {
+                    if !depths.contains_key(neighbour.as_str()) {
+                        depths.insert(neighbour, depth);
+                        next.push(neighbour.as_str());
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        // Strongest path: edge weights are at most 1, so the best unsettled
+        // node can be settled greedily.
+        let mut best: HashMap<&str, (f64, Vec<String>, String)> = HashMap::new();
+        let mut settled: Vec<&str> = vec![start];
+        let mut current = (start, 1.0, vec![start.to_string()]);
+        loop {
+            let (id, weight, path) = current;
+            for (neighbour, kind) in self.neighbours(id, direction) {
+                if settled.contains(&neighbour.as_str()) {
+                    continue;
+                }
+                let candidate = weight * dependency_weight(kind);
+                let improves = best
+                    .get(neighbour.as_str())
+                    .is_none_or(|(w, p, _)| candidate > *w || (candidate == *w && path.len() + 1 < p.len()));
+                if improves {
+                    let mut next_path = path.clone();
+                    next_path.push(neighbour.clone());
+                    best.insert(neighbour, (candidate, next_path, kind.clone()));
+                }
+            }
+            let next = best
+                .iter()
+                .filter(|(id, _)| !settled.contains(id))
+                .max_by(|a, b| {
+                    a.1 .0
+                        .partial_cmp(&b.1 .0)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then_with(|| b.0.cmp(a.0))
+                })
+                .map(|(id, (w, p, _))| (*id, *w, p.clone()));
+            match next {
+                Some(node) => {
+                    settled.push(node.0);
+                    current = node;
+                }
+                None => break,
+            }
+        }
+
+        let mut reached: Vec<Reach> = best
+            .into_iter()
+            .map(|(id, (weight, path, dependency_type))| Reach {
+                service_id: id.to_string(),
+                depth: depths.get(id).copied().unwrap_or(path.len() as u32 - 1),
+                weight,
+                path,
+                dependency_type,
+            })
+            .collect();
+        reached.sort_by(|a, b| {
+            b.weight
+                .partial_cmp(&a.weight)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.depth.cmp(&b.depth))
+                .then_with(|| a.service_id.cmp(&b.service_id))
+        });
+        reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from: &str, to: &str, kind: &str) -> DependencyEdge {
+        DependencyEdge {
+            service_id: from.into(),
+            depends_on_service_id: to.into(),
+            dependency_type: kind.into(),
+        }
+    }
+
+    /// Test: dependents are found transitively with depth and weighted paths
+    #[test]
+    fn test_reach_dependents_weighted() {
+        // web -> api -> db, reports -(data)-> db, ci -(build)-> api
+        let graph = ServiceGraph::new(&[
+            edge("web", "api", "runtime"),
+            edge("api", "db", "runtime"),
+            edge("reports", "db", "data"),
+            edge("ci", "api", "build"),
+        ]);
+        let reached = graph.reach("db", Direction::Dependents);
+        let ids: Vec<&str> = reached.iter().map(|r| r.service_id.as_str()).collect();
+        assert_eq!(ids, vec!["api", "web", "reports", "ci"]);
+
+        let web = &reached[1];
+        assert_eq!(web.depth, 2);
+        assert!((web.weight - 1.0).abs() < 1e-9);
+        assert_eq!(web.path, vec!["db", "api", "web"]);
+        assert!((reached[2].weight - 0.7).abs() < 1e-9);
+        assert!((reached[3].weight - 0.3).abs() < 1e-9);
+        assert_eq!(reached[3].dependency_type, "build");
+    }
+
+    /// Test: the strongest path wins even when it has more hops
+    #[test]
+    fn test_reach_prefers_strongest_path() {
+        let graph = ServiceGraph::new(&[
+            edge("app", "db", "optional"),
+            edge("app", "cache", "runtime"),
+            edge("cache", "db", "runtime"),
+        ]);
+        let reached = graph.reach("app", Direction::Dependencies);
+        let db = reached.iter().find(|r| r.service_id == "db").unwrap();
+        assert_eq!(db.depth, 1);
+        assert!((db.weight - 1.0).abs() < 1e-9);
+        assert_eq!(db.path, vec!["app", "cache", "db"]);
+        assert!(graph.reach("db", Direction::Dependencies).is_empty());
+    }
+}
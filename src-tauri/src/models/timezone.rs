@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, Tz, TZ_VARIANTS};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+pub const DEFAULT_TIME_ZONE: &str = "UTC";
+
+/// Top-level tz database areas offered in the zone picker. Other entries are
+/// legacy aliases (e.g. "US/Eastern") or fixed `Etc/` offsets.
+const PICKER_AREAS: &[&str] = &[
+    "Africa", "America", "Antarctica", "Asia", "Atlantic", "Australia", "Europe", "Indian", "Pacific",
+];
+
+/// Organization-wide analytics time zone plus optional per-service zones.
+/// Per-service zones only affect hour-of-day analysis; day, week and quarter
+/// boundaries always follow the organization zone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeZoneConfig {
+    pub org_time_zone: String,
+    #[serde(default)]
+    pub service_time_zones: HashMap<String, String>,
+}
+
+impl Default for TimeZoneConfig {
+    fn default() -> Self {
+        Self {
+            org_time_zone: DEFAULT_TIME_ZONE.to_string(),
+            service_time_zones: HashMap::new(),
+        }
+    }
+}
+
+impl TimeZoneConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        AnalyticsZone::parse(&self.org_time_zone)?;
+        for zone in self.service_time_zones.values() {
+            AnalyticsZone::parse(zone)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeZoneOption {
+    pub name: String,
+    /// Current standard offset, e.g. "UTC-05:00".
+    pub standard_offset: String,
+    pub observes_dst: bool,
+}
+
+/// An IANA time zone used to bucket analytics by local time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsZone {
+    pub name: String,
+    tz: Tz,
+}
+
+impl AnalyticsZone {
+    pub fn utc() -> Self {
+        Self {
+            name: DEFAULT_TIME_ZONE.to_string(),
+            tz: Tz::UTC,
+        }
+    }
+
+    /// Resolve an IANA zone name such as "Europe/Berlin".
+    pub fn parse(name: &str) -> AppResult<Self> {
+        let name = name.trim();
+        let tz: Tz = name
+            .parse()
+            .map_err(|_| AppError::Validation(format!("Unknown time zone: '{}'", name)))?;
+        Ok(Self {
+            name: name.to_string(),
+            tz,
+        })
+    }
+
+    pub fn to_local(&self, utc: &DateTime<Utc>) -> NaiveDateTime {
+        utc.with_timezone(&self.tz).naive_local()
+    }
+
+    pub fn local_date(&self, utc: &DateTime<Utc>) -> NaiveDate {
+        self.to_local(utc).date()
+    }
+
+    /// The UTC instant of a local wall-clock time. Ambiguous times (clocks
+    /// falling back) resolve to the first occurrence; times skipped by a
+    /// spring-forward gap resolve to the instant of the transition onwards.
+    pub fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self.tz.from_local_datetime(&local).earliest() {
+            Some(dt) => dt.with_timezone(&Utc),
+            None => {
+                // In a gap: interpret with the offset in force before the transition
+                let before = self.tz.offset_from_utc_datetime(&(local - Duration::days(1)));
+                (local - Duration::seconds(before.fix().local_minus_utc() as i64)).and_utc()
+            }
+        }
+    }
+
+    /// UTC instant at which the given local calendar day begins.
+    pub fn day_start(&self, date: NaiveDate) -> DateTime<Utc> {
+        self.to_utc(date.and_hms_opt(0, 0, 0).unwrap_or_default())
+    }
+
+    /// Inclusive UTC bounds covering local days `start..=end`, in the same
+    /// RFC 3339 form as stored `started_at` strings so they compare as text.
+    pub fn day_bounds(&self, start: NaiveDate, end: NaiveDate) -> (String, String) {
+        let lower = self.day_start(start);
+        let upper = self.day_start(end + Duration::days(1)) - Duration::seconds(1);
+        let format = |instant: DateTime<Utc>| instant.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        (format(lower), format(upper))
+    }
+
+    /// Whether the zone currently shifts its clocks during the year.
+    pub fn observes_dst(&self) -> bool {
+        let year = Utc::now().year();
+        let offset = |month| {
+            let instant = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+            self.tz.offset_from_utc_datetime(&instant.and_time(chrono::NaiveTime::MIN)).fix()
+        };
+        offset(1) != offset(7)
+    }
+
+    /// Current standard offset, e.g. "UTC-05:00".
+    pub fn standard_offset_label(&self) -> String {
+        let offset = self.tz.offset_from_utc_datetime(&Utc::now().naive_utc());
+        let seconds = offset.base_utc_offset().num_seconds();
+        let sign = if seconds < 0 { '-' } else { '+' };
+        let abs = seconds.abs();
+        format!("UTC{}{:02}:{:02}", sign, abs / 3600, (abs % 3600) / 60)
+    }
+}
+
+/// Parse a stored timestamp. Accepts RFC 3339, naive date-times (taken as UTC)
+/// and bare dates (midnight UTC).
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for fmt in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), fmt) {
+            return Some(naive.and_utc());
+        }
+    }
+    value
+        .get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+}
+
+/// UTC followed by the canonical tz database zones.
+pub fn list_time_zones() -> Vec<TimeZoneOption> {
+    let areas = TZ_VARIANTS.iter().map(|tz| tz.name()).filter(|name| {
+        name.split_once('/')
+            .is_some_and(|(area, _)| PICKER_AREAS.contains(&area))
+    });
+    std::iter::once(AnalyticsZone::utc())
+        .chain(areas.filter_map(|name| AnalyticsZone::parse(name).ok()))
+        .map(|zone| TimeZoneOption {
+            standard_offset: zone.standard_offset_label(),
+            observes_dst: zone.observes_dst(),
+            name: zone.name,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn local(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_zone_list() {
+        let zones = list_time_zones();
+        assert_eq!(zones[0].name, "UTC");
+        let new_york = zones.iter().find(|z| z.name == "America/New_York").unwrap();
+        assert_eq!(new_york.standard_offset, "UTC-05:00");
+        assert!(new_york.observes_dst);
+        assert!(zones.iter().any(|z| z.name == "Asia/Kolkata" && z.standard_offset == "UTC+05:30" && !z.observes_dst));
+        assert!(!zones.iter().any(|z| z.name.starts_with("US/") || z.name.starts_with("Etc/")));
+        assert!(matches!(AnalyticsZone::parse("Mars/Olympus"), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_gap_and_overlap_resolution() {
+        let tz = AnalyticsZone::parse("America/New_York").unwrap();
+        // 02:30 does not exist on 2025-03-09; resolves past the transition
+        assert_eq!(tz.to_utc(local("2025-03-09T02:30:00")), utc("2025-03-09T07:30:00Z"));
+        // 01:30 happens twice on 2025-11-02; the first (EDT) occurrence wins
+        assert_eq!(tz.to_utc(local("2025-11-02T01:30:00")), utc("2025-11-02T05:30:00Z"));
+
+        // DST starts at local midnight in Santiago, so that day begins at 01:00 daylight time
+        let tz = AnalyticsZone::parse("America/Santiago").unwrap();
+        assert_eq!(tz.day_start(NaiveDate::from_ymd_opt(2025, 9, 7).unwrap()), utc("2025-09-07T04:00:00Z"));
+    }
+
+    #[test]
+    fn test_day_bounds() {
+        let tz = AnalyticsZone::utc();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        assert_eq!(
+            tz.day_bounds(start, end),
+            ("2025-01-01T00:00:00Z".to_string(), "2025-03-31T23:59:59Z".to_string())
+        );
+
+        let tz = AnalyticsZone::parse("America/New_York").unwrap();
+        // Quarter straddling the March DST change
+        assert_eq!(
+            tz.day_bounds(start, end),
+            ("2025-01-01T05:00:00Z".to_string(), "2025-04-01T03:59:59Z".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        let expected = utc("2025-01-10T10:00:00Z");
+        assert_eq!(parse_timestamp("2025-01-10T10:00:00Z"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-10T12:00:00+02:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-10T10:00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-10 10:00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-10T10:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-10"), Some(utc("2025-01-10T00:00:00Z")));
+        assert_eq!(parse_timestamp("yesterday"), None);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(TimeZoneConfig::default().validate().is_ok());
+        let mut config = TimeZoneConfig {
+            org_time_zone: "Europe/Paris".into(),
+            service_time_zones: HashMap::new(),
+        };
+        config.service_time_zones.insert("svc-slack".into(), "Asia/Tokyo".into());
+        assert!(config.validate().is_ok());
+        config.service_time_zones.insert("svc-zoom".into(), "Nowhere".into());
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,4 +1,579 @@
+//! Report charts rendered in Rust.
+//!
+//! Charts are drawn as SVG and rasterized with resvg, so DOCX and PDF reports
+//! no longer depend on the frontend sending PNG captures. Images supplied by
+//! the caller still take precedence over the rendered ones.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, OnceLock};
+
 use docx_rs::*;
+use resvg::{tiny_skia, usvg};
+
+use crate::error::{AppError, AppResult};
+use crate::models::metrics::{CategoryCount, DashboardData, QuarterlyTrends};
+use crate::reports::fonts;
+
+/// Rendered chart size in pixels; matches the 6 x 3.5 inch DOCX frame.
+pub const CHART_WIDTH: u32 = 960;
+pub const CHART_HEIGHT: u32 = 560;
+
+/// Chart keys in report order, with the caption shown above each image.
+pub const CHART_CAPTIONS: &[(&str, &str)] = &[
+    ("severity_chart", "Incidents by severity"),
+    ("service_chart", "Incidents by service"),
+    ("trend_chart", "Incident volume by quarter"),
+    ("severity_mix_chart", "Severity mix by quarter"),
+    ("resolution_trend_chart", "Response and resolution times by quarter"),
+    ("trend_heatmap", "Quarterly metrics heatmap"),
+];
+
+/// Charts placed in the Metrics Overview section.
+pub const OVERVIEW_CHARTS: &[&str] = &["severity_chart", "service_chart", "trend_chart", "severity_mix_chart"];
+/// Charts placed in the Quarter-over-Quarter section.
+pub const TREND_CHARTS: &[&str] = &["resolution_trend_chart", "trend_heatmap"];
+
+const PNG_MAGIC: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+const PALETTE: &[&str] = &["#2563eb", "#f97316", "#16a34a", "#dc2626", "#9333ea", "#0891b2", "#ca8a04", "#64748b"];
+const FONT_FAMILY: &str = "Liberation Sans, Arial, Helvetica, DejaVu Sans, sans-serif";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartKind {
+    /// Grouped bars, one bar per series in each category.
+    Bar,
+    Line,
+    /// Series stacked on top of each other, in order.
+    StackedArea,
+    /// One row per series, one column per category. With `per_row_scale`
+    /// each row is shaded against its own maximum, for rows in different units.
+    Heatmap { per_row_scale: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    /// One value per chart category.
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    pub kind: ChartKind,
+    pub title: String,
+    pub categories: Vec<String>,
+    pub series: Vec<Series>,
+}
+
+impl Chart {
+    fn is_empty(&self) -> bool {
+        self.categories.is_empty() || self.series.iter().all(|s| s.values.iter().all(|v| *v == 0.0))
+    }
+}
+
+fn series(name: &str, values: Vec<f64>) -> Series {
+    Series {
+        name: name.to_string(),
+        values,
+    }
+}
+
+fn category_chart(title: &str, counts: &[CategoryCount], limit: usize) -> Chart {
+    let counts = &counts[..counts.len().min(limit)];
+    let mut chart_series = vec![series("This period", counts.iter().map(|c| c.count as f64).collect())];
+    if counts.iter().any(|c| c.previous_count.is_some()) {
+        chart_series.push(series(
+            "Previous period",
+            counts.iter().map(|c| c.previous_count.unwrap_or(0) as f64).collect(),
+        ));
+    }
+    Chart {
+        kind: ChartKind::Bar,
+        title: title.to_string(),
+        categories: counts.iter().map(|c| c.category.clone()).collect(),
+        series: chart_series,
+    }
+}
+
+fn severity_mix_chart(trends: &QuarterlyTrends) -> Chart {
+    Chart {
+        kind: ChartKind::StackedArea,
+        title: "Severity Mix by Quarter".to_string(),
+        categories: trends.quarters.clone(),
+        series: trends
+            .severity_counts
+            .iter()
+            .map(|t| series(&t.severity, t.counts.iter().map(|c| *c as f64).collect()))
+            .collect(),
+    }
+}
+
+fn hours(minutes: &[f64]) -> Vec<f64> {
+    minutes.iter().map(|m| m / 60.0).collect()
+}
+
+fn trend_charts(trends: &QuarterlyTrends) -> Vec<(&'static str, Chart)> {
+    let counts: Vec<f64> = trends.incident_count.iter().map(|c| *c as f64).collect();
+    vec![
+        (
+            "trend_chart",
+            Chart {
+                kind: ChartKind::Line,
+                title: "Incidents per Quarter".to_string(),
+                categories: trends.quarters.clone(),
+                series: vec![series("Incidents", counts.clone())],
+            },
+        ),
+        (
+            "resolution_trend_chart",
+            Chart {
+                kind: ChartKind::Line,
+                title: "MTTR and MTTA (hours)".to_string(),
+                categories: trends.quarters.clone(),
+                series: vec![series("MTTR", hours(&trends.mttr)), series("MTTA", hours(&trends.mtta))],
+            },
+        ),
+        (
+            "trend_heatmap",
+            Chart {
+                kind: ChartKind::Heatmap { per_row_scale: true },
+                title: "Quarterly Metrics".to_string(),
+                categories: trends.quarters.clone(),
+                series: vec![
+                    series("Incidents", counts),
+                    series("MTTR (h)", hours(&trends.mttr)),
+                    series("MTTA (h)", hours(&trends.mtta)),
+                    series("Recurrence %", trends.recurrence_rate.clone()),
+                    series("Avg tickets", trends.avg_tickets.clone()),
+                ],
+            },
+        ),
+    ]
+}
+
+/// The standard report charts for a period, skipping any without data.
+pub fn report_charts(dashboard: &DashboardData, trends: &QuarterlyTrends) -> Vec<(&'static str, Chart)> {
+    let mut charts = vec![
+        ("severity_chart", category_chart("Incidents by Severity", &dashboard.by_severity, 8)),
+        ("service_chart", category_chart("Incidents by Service", &dashboard.by_service, 8)),
+    ];
+    charts.extend(trend_charts(trends));
+    charts.push(("severity_mix_chart", severity_mix_chart(trends)));
+    charts.sort_by_key(|(key, _)| CHART_CAPTIONS.iter().position(|(k, _)| k == key));
+    charts.retain(|(_, chart)| !chart.is_empty());
+    charts
+}
+
+/// A chart ready for a report: PNG for DOCX/PDF, plus the SVG source when
+/// the chart was rendered here rather than supplied by the caller.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChartImage {
+    #[serde(with = "png_base64")]
+    pub png: Vec<u8>,
+    pub svg: Option<String>,
+}
+
+/// PNG bytes as a base64 string, keeping report snapshots compact.
+mod png_base64 {
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Images for every report chart. Valid PNGs supplied by the caller
+/// replace the rendered chart with the same key; other keys are kept as is.
+pub fn resolve_chart_images(
+    supplied: &HashMap<String, Vec<u8>>,
+    dashboard: &DashboardData,
+    trends: &QuarterlyTrends,
+) -> AppResult<HashMap<String, ChartImage>> {
+    let mut images: HashMap<String, ChartImage> = supplied
+        .iter()
+        .filter(|(_, bytes)| is_png(bytes))
+        .map(|(k, v)| (k.clone(), ChartImage { png: v.clone(), svg: None }))
+        .collect();
+    for (key, chart) in report_charts(dashboard, trends) {
+        if !images.contains_key(key) {
+            let image = ChartImage {
+                png: render_png(&chart, CHART_WIDTH, CHART_HEIGHT)?,
+                svg: Some(render_svg(&chart, CHART_WIDTH, CHART_HEIGHT)),
+            };
+            images.insert(key.to_string(), image);
+        }
+    }
+    Ok(images)
+}
+
+pub fn is_png(bytes: &[u8]) -> bool {
+    bytes.len() >= 8 && bytes[..8] == *PNG_MAGIC
+}
+
+pub fn caption(key: &str) -> String {
+    CHART_CAPTIONS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, c)| c.to_string())
+        .unwrap_or_else(|| key.replace('_', " "))
+}
+
+// -- SVG rendering --
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}\u{2026}", text.chars().take(max - 1).collect::<String>())
+    }
+}
+
+/// Smallest 1/2/5 x 10^n at or above `value`.
+fn nice_ceiling(value: f64) -> f64 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|step| step * magnitude)
+        .find(|candidate| *candidate >= value - 1e-9)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn format_value(value: f64) -> String {
+    if (value - value.round()).abs() < 1e-9 {
+        format!("{}", value.round() as i64)
+    } else if value.abs() >= 10.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+fn text(svg: &mut String, x: f64, y: f64, size: u32, anchor: &str, extra: &str, content: &str) {
+    let _ = write!(
+        svg,
+        r##"<text x="{:.1}" y="{:.1}" font-size="{}" text-anchor="{}" fill="#1f2937"{}>{}</text>"##,
+        x,
+        y,
+        size,
+        anchor,
+        extra,
+        escape(content)
+    );
+}
+
+/// Interpolate from white to the palette blue by `t` in [0, 1].
+fn heat_colour(t: f64) -> String {
+    let t = t.clamp(0.0, 1.0);
+    let mix = |to: f64| (255.0 + (to - 255.0) * t).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", mix(37.0), mix(99.0), mix(235.0))
+}
+
+struct Plot {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Plot {
+    /// Centre of category `i` of `n`.
+    fn x(&self, i: usize, n: usize) -> f64 {
+        self.left + self.width * (i as f64 + 0.5) / n as f64
+    }
+
+    fn y(&self, value: f64, max: f64) -> f64 {
+        self.top + self.height - self.height * (value / max).clamp(0.0, 1.0)
+    }
+}
+
+fn draw_axes(svg: &mut String, plot: &Plot, max: f64, categories: &[String]) {
+    for tick in 0..=4 {
+        let value = max * tick as f64 / 4.0;
+        let y = plot.y(value, max);
+        let _ = write!(
+            svg,
+            r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#e5e7eb" stroke-width="1"/>"##,
+            plot.left,
+            y,
+            plot.left + plot.width,
+            y
+        );
+        text(svg, plot.left - 8.0, y + 5.0, 14, "end", "", &format_value(value));
+    }
+    let baseline = plot.top + plot.height;
+    let _ = write!(
+        svg,
+        r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#9ca3af" stroke-width="1.5"/>"##,
+        plot.left,
+        baseline,
+        plot.left + plot.width,
+        baseline
+    );
+    for (i, category) in categories.iter().enumerate() {
+        text(svg, plot.x(i, categories.len()), baseline + 22.0, 14, "middle", "", &truncate(category, 16));
+    }
+}
+
+fn draw_legend(svg: &mut String, names: &[&str], y: f64, width: f64) {
+    let item_width = 150.0;
+    let start = (width - item_width * names.len() as f64).max(0.0) / 2.0;
+    for (i, name) in names.iter().enumerate() {
+        let x = start + item_width * i as f64;
+        let _ = write!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="12" height="12" fill="{}"/>"#,
+            x,
+            y - 11.0,
+            PALETTE[i % PALETTE.len()]
+        );
+        text(svg, x + 18.0, y, 14, "start", "", &truncate(name, 16));
+    }
+}
+
+fn draw_bars(svg: &mut String, chart: &Chart, plot: &Plot, max: f64) {
+    let n = chart.categories.len();
+    let group = plot.width / n as f64 * 0.75;
+    let bar = group / chart.series.len() as f64;
+    for (s, series) in chart.series.iter().enumerate() {
+        for (i, value) in series.values.iter().enumerate().take(n) {
+            let x = plot.x(i, n) - group / 2.0 + bar * s as f64;
+            let y = plot.y(*value, max);
+            let _ = write!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                x,
+                y,
+                (bar - 2.0).max(1.0),
+                plot.top + plot.height - y,
+                PALETTE[s % PALETTE.len()]
+            );
+            if *value > 0.0 {
+                text(svg, x + bar / 2.0 - 1.0, y - 6.0, 12, "middle", "", &format_value(*value));
+            }
+        }
+    }
+}
+
+fn draw_lines(svg: &mut String, chart: &Chart, plot: &Plot, max: f64) {
+    let n = chart.categories.len();
+    for (s, series) in chart.series.iter().enumerate() {
+        let colour = PALETTE[s % PALETTE.len()];
+        let points: Vec<(f64, f64)> = series
+            .values
+            .iter()
+            .take(n)
+            .enumerate()
+            .map(|(i, v)| (plot.x(i, n), plot.y(*v, max)))
+            .collect();
+        let path: Vec<String> = points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+        let _ = write!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="3"/>"#,
+            path.join(" "),
+            colour
+        );
+        for (x, y) in points {
+            let _ = write!(svg, r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="{}"/>"#, x, y, colour);
+        }
+    }
+}
+
+fn draw_stacked(svg: &mut String, chart: &Chart, plot: &Plot, max: f64) {
+    let n = chart.categories.len();
+    let mut floor = vec![0.0; n];
+    for (s, series) in chart.series.iter().enumerate() {
+        let ceiling: Vec<f64> = (0..n)
+            .map(|i| floor[i] + series.values.get(i).copied().unwrap_or(0.0))
+            .collect();
+        let mut points: Vec<String> = (0..n)
+            .map(|i| format!("{:.1},{:.1}", plot.x(i, n), plot.y(ceiling[i], max)))
+            .collect();
+        points.extend((0..n).rev().map(|i| format!("{:.1},{:.1}", plot.x(i, n), plot.y(floor[i], max))));
+        let _ = write!(
+            svg,
+            r#"<polygon points="{}" fill="{}" fill-opacity="0.85" stroke="white" stroke-width="1"/>"#,
+            points.join(" "),
+            PALETTE[s % PALETTE.len()]
+        );
+        floor = ceiling;
+    }
+}
+
+fn draw_heatmap(svg: &mut String, chart: &Chart, plot: &Plot, per_row_scale: bool) {
+    let cols = chart.categories.len();
+    let rows = chart.series.len();
+    let cell_w = plot.width / cols as f64;
+    let cell_h = plot.height / rows as f64;
+    let row_max = |s: &Series| s.values.iter().cloned().fold(0.0, f64::max);
+    let global_max = chart.series.iter().map(row_max).fold(0.0, f64::max);
+
+    for (r, series) in chart.series.iter().enumerate() {
+        let max = if per_row_scale { row_max(series) } else { global_max };
+        let y = plot.top + cell_h * r as f64;
+        text(svg, plot.left - 8.0, y + cell_h / 2.0 + 5.0, 14, "end", "", &truncate(&series.name, 16));
+        for (c, value) in series.values.iter().enumerate().take(cols) {
+            let t = if max > 0.0 { value / max } else { 0.0 };
+            let x = plot.left + cell_w * c as f64;
+            let _ = write!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" stroke="white" stroke-width="2"/>"#,
+                x,
+                y,
+                cell_w,
+                cell_h,
+                heat_colour(t)
+            );
+            let _ = write!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="13" text-anchor="middle" fill="{}">{}</text>"#,
+                x + cell_w / 2.0,
+                y + cell_h / 2.0 + 5.0,
+                if t > 0.55 { "#ffffff" } else { "#1f2937" },
+                escape(&format_value(*value))
+            );
+        }
+    }
+    for (c, category) in chart.categories.iter().enumerate() {
+        text(svg, plot.x(c, cols), plot.top + plot.height + 22.0, 14, "middle", "", &truncate(category, 16));
+    }
+}
+
+/// Render a chart as a standalone SVG document.
+pub fn render_svg(chart: &Chart, width: u32, height: u32) -> String {
+    let (w, h) = (width as f64, height as f64);
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{FONT_FAMILY}"><rect width="{w}" height="{h}" fill="white"/>"#
+    );
+    text(&mut svg, w / 2.0, 30.0, 20, "middle", r#" font-weight="bold""#, &chart.title);
+
+    if chart.is_empty() {
+        text(&mut svg, w / 2.0, h / 2.0, 16, "middle", "", "No data for this period");
+        svg.push_str("</svg>");
+        return svg;
+    }
+
+    let show_legend = chart.series.len() > 1 && !matches!(chart.kind, ChartKind::Heatmap { .. });
+    let left = if matches!(chart.kind, ChartKind::Heatmap { .. }) { 140.0 } else { 64.0 };
+    let bottom = if show_legend { 80.0 } else { 48.0 };
+    let plot = Plot {
+        left,
+        top: 56.0,
+        width: w - left - 24.0,
+        height: h - 56.0 - bottom,
+    };
+
+    let max = match chart.kind {
+        ChartKind::StackedArea => (0..chart.categories.len())
+            .map(|i| chart.series.iter().map(|s| s.values.get(i).copied().unwrap_or(0.0)).sum::<f64>())
+            .fold(0.0, f64::max),
+        _ => chart.series.iter().flat_map(|s| s.values.iter().cloned()).fold(0.0, f64::max),
+    };
+    let max = nice_ceiling(max);
+
+    match chart.kind {
+        ChartKind::Bar => {
+            draw_axes(&mut svg, &plot, max, &chart.categories);
+            draw_bars(&mut svg, chart, &plot, max);
+        }
+        ChartKind::Line => {
+            draw_axes(&mut svg, &plot, max, &chart.categories);
+            draw_lines(&mut svg, chart, &plot, max);
+        }
+        ChartKind::StackedArea => {
+            draw_axes(&mut svg, &plot, max, &chart.categories);
+            draw_stacked(&mut svg, chart, &plot, max);
+        }
+        ChartKind::Heatmap { per_row_scale } => draw_heatmap(&mut svg, chart, &plot, per_row_scale),
+    }
+
+    if show_legend {
+        let names: Vec<&str> = chart.series.iter().map(|s| s.name.as_str()).collect();
+        draw_legend(&mut svg, &names, h - 20.0, w);
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+// -- Rasterizing --
+
+/// System fonts plus the bundled report fonts, loaded once, so chart text
+/// renders even when no font is installed.
+fn font_db() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut db = usvg::fontdb::Database::new();
+            db.load_system_fonts();
+            for face in fonts::all_faces() {
+                db.load_font_data(face.to_vec());
+            }
+            // Point the generic family at an installed face so text always renders
+            let fallback = db
+                .faces()
+                .find_map(|face| face.families.first().map(|(name, _)| name.clone()));
+            if let Some(name) = fallback {
+                if db.query(&usvg::fontdb::Query { families: &[usvg::fontdb::Family::SansSerif], ..Default::default() }).is_none() {
+                    db.set_sans_serif_family(name);
+                }
+            }
+            Arc::new(db)
+        })
+        .clone()
+}
+
+fn rasterize(chart: &Chart, width: u32, height: u32) -> AppResult<tiny_skia::Pixmap> {
+    let options = usvg::Options {
+        fontdb: font_db(),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(&render_svg(chart, width, height), &options)
+        .map_err(|e| AppError::Report(format!("Failed to render chart: {}", e)))?;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| AppError::Report("Invalid chart size".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+/// Render a chart to PNG bytes.
+pub fn render_png(chart: &Chart, width: u32, height: u32) -> AppResult<Vec<u8>> {
+    rasterize(chart, width, height)?
+        .encode_png()
+        .map_err(|e| AppError::Report(format!("Failed to encode chart: {}", e)))
+}
+
+/// Decode a PNG into RGB pixels flattened onto white, as the PDF writer
+/// cannot embed images with an alpha channel. Returns `(width, height, rgb)`.
+pub fn png_to_rgb(png_bytes: &[u8]) -> AppResult<(u32, u32, Vec<u8>)> {
+    let pixmap = tiny_skia::Pixmap::decode_png(png_bytes)
+        .map_err(|e| AppError::Report(format!("Invalid chart image: {}", e)))?;
+    let mut rgb = Vec::with_capacity(pixmap.data().len() / 4 * 3);
+    for px in pixmap.data().chunks_exact(4) {
+        // Premultiplied alpha, so compositing over white adds the uncovered share
+        let uncovered = 255 - px[3];
+        rgb.extend([px[0], px[1], px[2]].iter().map(|c| c.saturating_add(uncovered)));
+    }
+    Ok((pixmap.width(), pixmap.height(), rgb))
+}
+
+// -- DOCX helpers --
 
 /// Create a Pic from raw PNG bytes with a given width in EMUs.
 /// Standard DOCX image widths: 5486400 EMU = ~6 inches.
@@ -15,3 +590,108 @@ pub fn add_chart_image(docx: Docx, png_bytes: &[u8], width_emu: u32, height_emu:
             .add_run(Run::new().add_image(pic))
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::metrics::SeverityTrend;
+
+    fn trends() -> QuarterlyTrends {
+        QuarterlyTrends {
+            quarters: vec!["Q1 FY25".into(), "Q2 FY25".into()],
+            mttr: vec![120.0, 90.0],
+            mtta: vec![15.0, 10.0],
+            incident_count: vec![4, 6],
+            recurrence_rate: vec![25.0, 0.0],
+            avg_tickets: vec![1.5, 2.0],
+            severity_counts: vec![
+                SeverityTrend { severity: "Critical".into(), counts: vec![1, 0] },
+                SeverityTrend { severity: "High".into(), counts: vec![3, 6] },
+            ],
+        }
+    }
+
+    /// Test: every chart kind renders to a parseable SVG and a PNG
+    #[test]
+    fn test_render_all_kinds() {
+        for kind in [
+            ChartKind::Bar,
+            ChartKind::Line,
+            ChartKind::StackedArea,
+            ChartKind::Heatmap { per_row_scale: true },
+        ] {
+            let chart = Chart {
+                kind,
+                title: "A & B <test>".into(),
+                categories: vec!["Critical".into(), "High".into()],
+                series: vec![series("Now", vec![3.0, 1.0]), series("Before", vec![2.0, 0.0])],
+            };
+            let svg = render_svg(&chart, 480, 280);
+            assert!(svg.contains("A &amp; B &lt;test&gt;"));
+            assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+
+            let png = render_png(&chart, 480, 280).unwrap();
+            assert!(is_png(&png));
+            let (w, h, rgb) = png_to_rgb(&png).unwrap();
+            assert_eq!((w, h), (480, 280));
+            assert_eq!(rgb.len(), 480 * 280 * 3);
+        }
+    }
+
+    /// Test: the standard charts skip empty data and supplied images win
+    #[test]
+    fn test_report_charts_and_overrides() {
+        let mut dashboard: DashboardData = serde_json::from_value(serde_json::json!({
+            "mttr": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "mtta": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "recurrence_rate": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "avg_tickets": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "estimated_cost": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "cost_by_service": [],
+            "checklist_completion": {"value": 0.0, "previous_value": null, "trend": "NoData", "formatted_value": "-"},
+            "by_severity": [{"category": "Critical", "count": 2, "previous_count": 1}],
+            "by_impact": [],
+            "by_service": [],
+            "downtime_by_service": [],
+            "trends": {"quarters": [], "mttr": [], "mtta": [], "incident_count": [], "recurrence_rate": [], "avg_tickets": []},
+            "total_incidents": 2,
+            "period_label": "Q2 FY25"
+        }))
+        .unwrap();
+        let keys: Vec<&str> = report_charts(&dashboard, &dashboard.trends).iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec!["severity_chart"]);
+
+        dashboard.trends = trends();
+        let keys: Vec<&str> = report_charts(&dashboard, &dashboard.trends).iter().map(|(k, _)| *k).collect();
+        assert_eq!(
+            keys,
+            vec!["severity_chart", "trend_chart", "severity_mix_chart", "resolution_trend_chart", "trend_heatmap"]
+        );
+
+        let supplied_png = render_png(&report_charts(&dashboard, &dashboard.trends)[1].1, 64, 32).unwrap();
+        let mut supplied = HashMap::new();
+        supplied.insert("severity_chart".to_string(), supplied_png.clone());
+        supplied.insert("service_chart".to_string(), b"not a png".to_vec());
+        let images = resolve_chart_images(&supplied, &dashboard, &dashboard.trends).unwrap();
+        assert_eq!(images["severity_chart"], ChartImage { png: supplied_png, svg: None });
+        assert!(images["trend_chart"].svg.as_deref().is_some_and(|svg| svg.starts_with("<svg")));
+        assert!(!images.contains_key("service_chart"));
+        let (_, mix) = report_charts(&dashboard, &dashboard.trends)
+            .into_iter()
+            .find(|(k, _)| *k == "severity_mix_chart")
+            .unwrap();
+        assert_eq!(mix.categories, vec!["Q1 FY25", "Q2 FY25"]);
+        assert_eq!(mix.series[1], series("High", vec![3.0, 6.0]));
+        assert_eq!(images.len(), 5);
+    }
+
+    /// Test: axis maxima round up to 1/2/5 steps
+    #[test]
+    fn test_nice_ceiling() {
+        assert_eq!(nice_ceiling(0.0), 1.0);
+        assert_eq!(nice_ceiling(7.0), 10.0);
+        assert_eq!(nice_ceiling(13.0), 20.0);
+        assert_eq!(nice_ceiling(50.0), 50.0);
+        assert!((nice_ceiling(0.3) - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,273 @@
+//! Format-neutral report document.
+//!
+//! Sections describe their content once as a list of blocks; the DOCX, PDF,
+//! HTML and Markdown renderers turn the same document into their output
+//! format, so all of them carry the same sections, tables, charts and
+//! markdown content.
+
+use std::collections::HashMap;
+
+use crate::models::report_theme::ReportTheme;
+use crate::reports::charts::{self, ChartImage};
+use crate::reports::markdown;
+
+/// A run of text with inline formatting.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+}
+
+impl Span {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn bold(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            bold: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// A table cell. `link` is an in-document anchor (without `#`) that the
+/// HTML and Markdown renderers turn into a link; DOCX and PDF show the text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cell {
+    pub text: String,
+    pub link: Option<String>,
+}
+
+impl Cell {
+    pub fn linked(text: impl Into<String>, anchor: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            link: Some(anchor.into()),
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self { text, link: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Level 1 is a section, 2 a subsection; 3 and 4 come from markdown content.
+    /// `anchor` is the link target id for formats that support links.
+    Heading { level: u8, text: String, anchor: Option<String> },
+    Paragraph(Vec<Span>),
+    /// `number` is set for ordered list items, `None` for bullets.
+    ListItem { number: Option<u64>, spans: Vec<Span> },
+    /// Preformatted text, one entry per line.
+    Code(Vec<String>),
+    Table { headers: Vec<String>, rows: Vec<Vec<Cell>> },
+    /// A chart with the caption shown above it. `svg` is used by HTML output
+    /// when the chart was rendered rather than supplied as a PNG.
+    Image { caption: String, png: Vec<u8>, svg: Option<String> },
+    Spacer,
+}
+
+/// A complete report: title page lines followed by the section blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportDocument {
+    pub title: String,
+    /// Lines under the title, most prominent first (e.g. quarter label, period).
+    pub subtitle: Vec<String>,
+    pub blocks: Vec<Block>,
+    /// Branding for DOCX and PDF output; HTML and Markdown ignore it.
+    pub theme: ReportTheme,
+}
+
+impl ReportDocument {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// A section heading, anchored by its slug so it can be linked from a
+    /// table of contents.
+    pub fn heading1(&mut self, text: &str) {
+        self.blocks.push(Block::Heading {
+            level: 1,
+            text: text.to_string(),
+            anchor: Some(slug(text)),
+        });
+    }
+
+    pub fn heading2(&mut self, text: &str) {
+        self.blocks.push(Block::Heading {
+            level: 2,
+            text: text.to_string(),
+            anchor: None,
+        });
+    }
+
+    /// A subsection heading with an explicit link target.
+    pub fn anchored_heading2(&mut self, text: &str, anchor: &str) {
+        self.blocks.push(Block::Heading {
+            level: 2,
+            text: text.to_string(),
+            anchor: Some(anchor.to_string()),
+        });
+    }
+
+    pub fn text(&mut self, text: &str) {
+        self.blocks.push(Block::Paragraph(vec![Span::plain(text)]));
+    }
+
+    /// A paragraph in bold, used for labels above markdown content.
+    pub fn label(&mut self, text: &str) {
+        self.blocks.push(Block::Paragraph(vec![Span::bold(text)]));
+    }
+
+    /// A bold label followed by a value on one line.
+    pub fn label_value(&mut self, label: &str, value: &str) {
+        self.blocks
+            .push(Block::Paragraph(vec![Span::bold(label), Span::plain(value)]));
+    }
+
+    pub fn bullet(&mut self, text: &str) {
+        self.blocks.push(Block::ListItem {
+            number: None,
+            spans: vec![Span::plain(text)],
+        });
+    }
+
+    pub fn table(&mut self, headers: &[&str], rows: Vec<Vec<String>>) {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Cell::from).collect())
+            .collect();
+        self.linked_table(headers, rows);
+    }
+
+    /// A table whose cells may link to anchors elsewhere in the report.
+    pub fn linked_table(&mut self, headers: &[&str], rows: Vec<Vec<Cell>>) {
+        self.blocks.push(Block::Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows,
+        });
+    }
+
+    pub fn spacer(&mut self) {
+        self.blocks.push(Block::Spacer);
+    }
+
+    pub fn markdown(&mut self, md: &str) {
+        self.blocks.extend(markdown::markdown_to_blocks(md));
+    }
+
+    /// Add the charts in `keys` that have an image, each under its caption.
+    pub fn charts(&mut self, images: &HashMap<String, ChartImage>, keys: &[&str]) {
+        for key in keys {
+            if let Some(image) = images.get(*key) {
+                self.blocks.push(Block::Image {
+                    caption: format!("Chart: {}", charts::caption(key)),
+                    png: image.png.clone(),
+                    svg: image.svg.clone(),
+                });
+                self.spacer();
+            }
+        }
+    }
+}
+
+/// Lower-case anchor id for a heading: alphanumerics joined by single dashes.
+pub fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Anchor id of an incident's breakdown, linked from the timeline.
+pub fn incident_anchor(incident_id: &str) -> String {
+    format!("incident-{}", incident_id)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A small report touching every block type, rendered by the snapshot tests.
+    pub(crate) fn sample_document() -> ReportDocument {
+        let mut doc = ReportDocument::new("FY27 Q1 Incident Review");
+        doc.subtitle.push("FY27 Q1".to_string());
+        doc.subtitle.push("Period: 2026-02-02 to 2026-04-30".to_string());
+
+        doc.heading1("Executive Summary");
+        doc.text("2 incidents this quarter; MTTR < 2h & improving.");
+        doc.label_value("Started: ", "2026-03-10T09:00:00Z");
+        doc.markdown("Root cause was **disk** exhaustion on `db-1`.\n\n- rotate logs\n- add *alerting*\n\n```\ndf -h\n```");
+        doc.spacer();
+
+        doc.heading1("Incident Timeline");
+        doc.linked_table(
+            &["Date", "Title", "Priority"],
+            vec![
+                vec![
+                    Cell::from("2026-03-10".to_string()),
+                    Cell::linked("Disk full | db-1", incident_anchor("inc-1")),
+                    Cell::from("P1".to_string()),
+                ],
+                vec![
+                    Cell::from("2026-04-02".to_string()),
+                    Cell::from("Slow logins".to_string()),
+                    Cell::from("P3".to_string()),
+                ],
+            ],
+        );
+
+        doc.heading1("Critical Incident Breakdowns");
+        doc.anchored_heading2("[P1] Disk full | db-1 - Slack", &incident_anchor("inc-1"));
+        doc.markdown("1. Free space\n2. Restart");
+
+        let png = resvg::tiny_skia::Pixmap::new(1, 1).unwrap().encode_png().unwrap();
+        doc.blocks.push(Block::Image {
+            caption: "Chart: Incidents by Severity".to_string(),
+            png: png.clone(),
+            svg: Some(r#"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20"><rect width="40" height="20" fill="white"/></svg>"#.to_string()),
+        });
+        doc.blocks.push(Block::Image {
+            caption: "Chart: Supplied".to_string(),
+            png,
+            svg: None,
+        });
+        doc
+    }
+
+    /// Compare rendered output with `src/reports/snapshots/<name>`.
+    /// Run with `UPDATE_SNAPSHOTS=1` to rewrite the file after an intended change.
+    pub(crate) fn assert_snapshot(name: &str, actual: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/reports/snapshots")
+            .join(name);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("missing snapshot {}: {}", path.display(), e));
+        assert_eq!(actual, expected, "snapshot {} changed", name);
+    }
+
+    /// Test: slugs are lower-case and dash-separated
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Quarter-over-Quarter Comparison"), "quarter-over-quarter-comparison");
+        assert_eq!(slug("  Cost & Impact "), "cost-impact");
+    }
+}
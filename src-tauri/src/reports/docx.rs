@@ -0,0 +1,288 @@
+//! DOCX renderer for report documents using docx-rs.
+
+use std::io::Cursor;
+
+use docx_rs::*;
+
+use crate::error::{AppError, AppResult};
+use crate::models::report_theme::{hex, CoverLayout, ReportTheme};
+use crate::reports::charts::{self, add_chart_image};
+use crate::reports::document::{Block, ReportDocument, Span};
+
+const EMU_PER_INCH: u32 = 914400;
+
+/// Render a report document and return the DOCX bytes.
+pub fn render(doc: &ReportDocument) -> AppResult<Vec<u8>> {
+    let mut buf: Vec<u8> = Vec::new();
+    let cursor = Cursor::new(&mut buf);
+    build(doc)?
+        .build()
+        .pack(cursor)
+        .map_err(|e| AppError::Report(format!("Failed to build DOCX: {}", e)))?;
+    Ok(buf)
+}
+
+/// Font size in half-points for a size given at the default 11pt body.
+fn size(theme: &ReportTheme, points: u8) -> usize {
+    theme.scaled(points) as usize * 2
+}
+
+fn build(doc: &ReportDocument) -> AppResult<Docx> {
+    let theme = &doc.theme;
+    let family = theme.font.family_name();
+    let mut docx = Docx::new()
+        .default_fonts(RunFonts::new().ascii(family).hi_ansi(family).east_asia(family).cs(family))
+        .default_size(size(theme, 11));
+    docx = page_furniture(docx, theme);
+
+    if !doc.title.is_empty() {
+        docx = cover(docx, doc)?;
+    }
+
+    for block in &doc.blocks {
+        docx = match block {
+            Block::Heading { level: 1, text, .. } => docx.add_paragraph(heading1(theme, text)),
+            Block::Heading { level: 2, text, .. } => docx.add_paragraph(heading2(theme, text)),
+            Block::Heading { level, text, .. } => {
+                let points = if *level == 3 { 14 } else { 12 };
+                docx.add_paragraph(Paragraph::new().add_run(
+                    Run::new().add_text(text).bold().size(size(theme, points)).color(hex(theme.primary_rgb())),
+                ))
+            }
+            Block::Paragraph(spans) => docx.add_paragraph(spans_paragraph(theme, Paragraph::new(), spans)),
+            Block::ListItem { number, spans } => {
+                let prefix = match number {
+                    Some(n) => format!("{}. ", n),
+                    None => "  \u{2022}  ".to_string(),
+                };
+                let para = Paragraph::new().add_run(Run::new().add_text(prefix).size(size(theme, 11)));
+                docx.add_paragraph(spans_paragraph(theme, para, spans))
+            }
+            Block::Code(lines) => lines.iter().fold(docx, |docx, line| {
+                docx.add_paragraph(Paragraph::new().add_run(code_run(theme, line)))
+            }),
+            Block::Table { headers, rows } => {
+                let mut table_rows = vec![TableRow::new(headers.iter().map(|h| header_cell(theme, h)).collect())];
+                for row in rows {
+                    table_rows.push(TableRow::new(row.iter().map(|c| text_cell(theme, &c.text)).collect()));
+                }
+                docx.add_table(Table::new(table_rows))
+            }
+            Block::Image { caption, png, .. } => {
+                let docx = docx.add_paragraph(body_text(theme, caption));
+                // 5486400 EMU ~= 6 inches wide, 3200400 ~= 3.5 inches tall
+                add_chart_image(docx, png, 5486400, 3200400)
+            }
+            Block::Spacer => docx.add_paragraph(spacer()),
+        };
+    }
+
+    Ok(docx)
+}
+
+/// Page header (banner and header text) and footer (footer text and page
+/// number). A full-page cover gets an empty header and footer of its own.
+fn page_furniture(mut docx: Docx, theme: &ReportTheme) -> Docx {
+    let small = |text: &str| Run::new().add_text(text).size(size(theme, 8)).color("6B7280");
+
+    let mut header = Header::new();
+    if !theme.confidentiality_banner.trim().is_empty() {
+        header = header.add_paragraph(
+            Paragraph::new().align(AlignmentType::Center).add_run(
+                Run::new()
+                    .add_text(theme.confidentiality_banner.trim())
+                    .bold()
+                    .size(size(theme, 9))
+                    .color(hex(theme.primary_rgb())),
+            ),
+        );
+    }
+    if !theme.header_text.trim().is_empty() {
+        header = header.add_paragraph(Paragraph::new().align(AlignmentType::Right).add_run(small(theme.header_text.trim())));
+    }
+
+    let mut footer_line = Paragraph::new().align(AlignmentType::Center);
+    if !theme.footer_text.trim().is_empty() {
+        let separator = if theme.page_numbers { "  \u{00B7}  " } else { "" };
+        footer_line = footer_line.add_run(small(&format!("{}{}", theme.footer_text.trim(), separator)));
+    }
+    if theme.page_numbers {
+        footer_line = footer_line.add_run(small("Page ")).add_page_num(PageNum::new());
+    }
+
+    if !theme.confidentiality_banner.trim().is_empty() || !theme.header_text.trim().is_empty() {
+        docx = docx.header(header);
+    }
+    if !theme.footer_text.trim().is_empty() || theme.page_numbers {
+        docx = docx.footer(Footer::new().add_paragraph(footer_line));
+    }
+    if theme.cover_layout == CoverLayout::FullPage {
+        docx = docx.first_header(Header::new()).first_footer(Footer::new());
+    }
+    docx
+}
+
+/// Title, period lines, logo and banner in the theme's cover layout.
+fn cover(mut docx: Docx, doc: &ReportDocument) -> AppResult<Docx> {
+    let theme = &doc.theme;
+    let full_page = theme.cover_layout == CoverLayout::FullPage;
+    let align = if full_page { AlignmentType::Center } else { AlignmentType::Left };
+
+    if full_page {
+        for _ in 0..6 {
+            docx = docx.add_paragraph(spacer());
+        }
+    }
+    let logo = theme.logo()?;
+    if !logo.is_empty() {
+        // Fit the logo in a 2.5 x 1.5 inch box on a full page, 2 x 0.6 inches otherwise
+        let (max_w, max_h) = if full_page { (2.5, 1.5) } else { (2.0, 0.6) };
+        let (w, h, _) = charts::png_to_rgb(&logo)?;
+        let scale = f64::min(max_w / w as f64, max_h / h as f64) * EMU_PER_INCH as f64;
+        let pic = Pic::new_with_dimensions(logo, w, h).size((w as f64 * scale) as u32, (h as f64 * scale) as u32);
+        docx = docx.add_paragraph(Paragraph::new().align(align).add_run(Run::new().add_image(pic)));
+    }
+
+    let title_size = if full_page { 40 } else { 36 };
+    docx = docx.add_paragraph(
+        Paragraph::new()
+            .align(align)
+            .add_run(
+                Run::new()
+                    .add_text(&doc.title)
+                    .bold()
+                    .size(size(theme, title_size))
+                    .color(hex(theme.primary_rgb())),
+            )
+            .style("Heading1"),
+    );
+    for (i, line) in doc.subtitle.iter().enumerate() {
+        let points = if i == 0 { 16 } else { 12 };
+        docx = docx.add_paragraph(Paragraph::new().align(align).add_run(Run::new().add_text(line).size(size(theme, points))));
+    }
+    if full_page && !theme.confidentiality_banner.trim().is_empty() {
+        docx = docx.add_paragraph(spacer()).add_paragraph(
+            Paragraph::new().align(align).add_run(
+                Run::new()
+                    .add_text(theme.confidentiality_banner.trim())
+                    .bold()
+                    .size(size(theme, 12))
+                    .color(hex(theme.primary_rgb())),
+            ),
+        );
+    }
+    docx = if full_page {
+        docx.add_paragraph(Paragraph::new().add_run(Run::new().add_break(BreakType::Page)))
+    } else {
+        docx.add_paragraph(spacer())
+    };
+    Ok(docx)
+}
+
+fn code_run(theme: &ReportTheme, text: &str) -> Run {
+    Run::new()
+        .add_text(text)
+        .size(size(theme, 10))
+        .fonts(RunFonts::new().ascii("Courier New"))
+}
+
+fn spans_paragraph(theme: &ReportTheme, mut para: Paragraph, spans: &[Span]) -> Paragraph {
+    for span in spans {
+        let mut run = if span.code {
+            code_run(theme, &span.text)
+        } else {
+            Run::new().add_text(&span.text).size(size(theme, 11))
+        };
+        if span.bold {
+            run = run.bold();
+        }
+        if span.italic {
+            run = run.italic();
+        }
+        para = para.add_run(run);
+    }
+    para
+}
+
+/// Helper: create a Heading 1 paragraph.
+fn heading1(theme: &ReportTheme, text: &str) -> Paragraph {
+    Paragraph::new()
+        .add_run(Run::new().add_text(text).bold().size(size(theme, 28)).color(hex(theme.primary_rgb())))
+        .style("Heading1")
+}
+
+/// Helper: create a Heading 2 paragraph.
+fn heading2(theme: &ReportTheme, text: &str) -> Paragraph {
+    Paragraph::new()
+        .add_run(Run::new().add_text(text).bold().size(size(theme, 24)).color(hex(theme.primary_rgb())))
+        .style("Heading2")
+}
+
+/// Helper: create a body paragraph.
+fn body_text(theme: &ReportTheme, text: &str) -> Paragraph {
+    Paragraph::new()
+        .add_run(Run::new().add_text(text).size(size(theme, 11)))
+}
+
+/// Helper: create a table header cell (bold text on the accent colour).
+fn header_cell(theme: &ReportTheme, text: &str) -> TableCell {
+    TableCell::new()
+        .add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text(text).bold().size(size(theme, 10)).color(hex(theme.on_accent_rgb())))
+        )
+        .shading(Shading::new().fill(hex(theme.accent_rgb())))
+}
+
+/// Helper: create a regular table cell.
+fn text_cell(theme: &ReportTheme, text: &str) -> TableCell {
+    TableCell::new()
+        .add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text(text).size(size(theme, 10)))
+        )
+}
+
+/// Helper: add a blank spacer paragraph.
+fn spacer() -> Paragraph {
+    Paragraph::new()
+        .add_run(Run::new().add_text(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+
+    use super::*;
+    use crate::reports::document::tests::sample_document;
+
+    /// Test: the theme sets fonts, colours, header, footer and a bare full-page cover
+    #[test]
+    fn test_themed_docx() {
+        let logo = resvg::tiny_skia::Pixmap::new(40, 20).unwrap().encode_png().unwrap();
+        let mut doc = sample_document();
+        doc.theme = ReportTheme {
+            logo_png: Some(base64::engine::general_purpose::STANDARD.encode(logo)),
+            primary_color: "#0A4D8C".into(),
+            accent_color: "#1F4E79".into(),
+            font: crate::models::report_theme::ReportFont::Serif,
+            header_text: "Acme Reliability".into(),
+            footer_text: "Acme Corp".into(),
+            confidentiality_banner: "CONFIDENTIAL".into(),
+            cover_layout: CoverLayout::FullPage,
+            ..Default::default()
+        };
+        let json = build(&doc).unwrap().json();
+        assert!(json.contains("DejaVu Serif"));
+        assert!(json.contains("0A4D8C"));
+        assert!(json.contains("1F4E79"));
+        assert!(json.contains("FFFFFF"));
+        assert!(json.contains("CONFIDENTIAL"));
+        assert!(json.contains("Acme Reliability"));
+        assert!(json.contains("Acme Corp"));
+        assert!(json.contains("\"titlePg\": true"));
+
+        let bytes = render(&doc).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+    }
+}
@@ -0,0 +1,51 @@
+//! Fonts bundled with the app, so PDF reports and chart text render the same
+//! on every machine without relying on installed system fonts.
+
+use genpdf::fonts::{FontData, FontFamily};
+
+use crate::error::{AppError, AppResult};
+use crate::models::report_theme::ReportFont;
+
+/// Regular, bold, italic and bold italic faces of one family.
+type Faces = [&'static [u8]; 4];
+
+const SANS: Faces = [
+    include_bytes!("../../fonts/DejaVuSans.ttf"),
+    include_bytes!("../../fonts/DejaVuSans-Bold.ttf"),
+    include_bytes!("../../fonts/DejaVuSans-Oblique.ttf"),
+    include_bytes!("../../fonts/DejaVuSans-BoldOblique.ttf"),
+];
+
+const SERIF: Faces = [
+    include_bytes!("../../fonts/DejaVuSerif.ttf"),
+    include_bytes!("../../fonts/DejaVuSerif-Bold.ttf"),
+    include_bytes!("../../fonts/DejaVuSerif-Italic.ttf"),
+    include_bytes!("../../fonts/DejaVuSerif-BoldItalic.ttf"),
+];
+
+fn faces(font: ReportFont) -> &'static Faces {
+    match font {
+        ReportFont::Sans => &SANS,
+        ReportFont::Serif => &SERIF,
+    }
+}
+
+/// The bundled family for genpdf.
+pub fn pdf_family(font: ReportFont) -> AppResult<FontFamily<FontData>> {
+    let load = |data: &[u8]| {
+        FontData::new(data.to_vec(), None)
+            .map_err(|e| AppError::Report(format!("Failed to load bundled font: {}", e)))
+    };
+    let [regular, bold, italic, bold_italic] = faces(font);
+    Ok(FontFamily {
+        regular: load(regular)?,
+        bold: load(bold)?,
+        italic: load(italic)?,
+        bold_italic: load(bold_italic)?,
+    })
+}
+
+/// Every bundled face, for the chart rasterizer's font database.
+pub fn all_faces() -> impl Iterator<Item = &'static [u8]> {
+    SANS.into_iter().chain(SERIF)
+}
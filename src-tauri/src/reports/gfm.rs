@@ -0,0 +1,183 @@
+//! GitHub-flavoured Markdown renderer for report documents.
+//!
+//! Sections get explicit `<a id>` anchors so the table of contents and the
+//! incident timeline links work on any Markdown host. Charts are embedded as
+//! base64 PNG data URIs to keep the file self-contained.
+
+use std::fmt::Write;
+
+use base64::Engine;
+
+use crate::error::AppResult;
+use crate::reports::document::{Block, Cell, ReportDocument, Span};
+
+/// Render a report document as a Markdown file.
+pub fn render(doc: &ReportDocument) -> AppResult<Vec<u8>> {
+    Ok(render_string(doc).into_bytes())
+}
+
+fn render_string(doc: &ReportDocument) -> String {
+    // Each entry is one Markdown block; they are joined by blank lines
+    let mut parts: Vec<String> = Vec::new();
+
+    // Title page
+    if !doc.title.is_empty() {
+        parts.push(format!("# {}", escape(&doc.title)));
+        for (i, line) in doc.subtitle.iter().enumerate() {
+            if i == 0 {
+                parts.push(format!("**{}**", escape(line)));
+            } else {
+                parts.push(escape(line));
+            }
+        }
+    }
+
+    // Table of contents from the anchored section headings
+    let contents: Vec<String> = doc
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading { level: 1, text, anchor: Some(anchor) } => {
+                Some(format!("1. [{}](#{})", escape(text), anchor))
+            }
+            _ => None,
+        })
+        .collect();
+    if !contents.is_empty() {
+        parts.push("## Contents".to_string());
+        parts.push(contents.join("\n"));
+    }
+
+    let mut previous_was_item = false;
+    for block in &doc.blocks {
+        let is_item = matches!(block, Block::ListItem { .. });
+        let Some(text) = block_markdown(block) else {
+            continue;
+        };
+        // Consecutive list items form one list
+        match parts.last_mut() {
+            Some(last) if is_item && previous_was_item => {
+                last.push('\n');
+                last.push_str(&text);
+            }
+            _ => parts.push(text),
+        }
+        previous_was_item = is_item;
+    }
+
+    let mut out = parts.join("\n\n");
+    out.push('\n');
+    out
+}
+
+fn block_markdown(block: &Block) -> Option<String> {
+    let text = match block {
+        Block::Heading { level, text, anchor } => {
+            let hashes = "#".repeat((*level as usize + 1).min(6));
+            match anchor {
+                Some(anchor) => format!("<a id=\"{}\"></a>\n\n{} {}", anchor, hashes, escape(text)),
+                None => format!("{} {}", hashes, escape(text)),
+            }
+        }
+        Block::Paragraph(spans) => inline(spans),
+        Block::ListItem { number: Some(n), spans } => format!("{}. {}", n, inline(spans)),
+        Block::ListItem { number: None, spans } => format!("- {}", inline(spans)),
+        Block::Code(lines) => {
+            let fence = if lines.iter().any(|l| l.contains("```")) { "~~~~" } else { "```" };
+            format!("{}\n{}\n{}", fence, lines.join("\n"), fence)
+        }
+        Block::Table { headers, rows } => {
+            let mut table = String::new();
+            let header_cells: Vec<String> = headers.iter().map(|h| table_text(h)).collect();
+            let _ = writeln!(table, "| {} |", header_cells.join(" | "));
+            let _ = write!(table, "|{}", " --- |".repeat(headers.len()));
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(cell_markdown).collect();
+                let _ = write!(table, "\n| {} |", cells.join(" | "));
+            }
+            table
+        }
+        Block::Image { caption, png, .. } => {
+            let data = base64::engine::general_purpose::STANDARD.encode(png);
+            format!("**{}**\n\n![{}](data:image/png;base64,{})", escape(caption), escape(caption), data)
+        }
+        // Blocks are already separated by blank lines
+        Block::Spacer => return None,
+    };
+    Some(text)
+}
+
+/// Markdown for a run of spans. Emphasis markers are kept tight around the
+/// text, as `**Label: **` would not render as bold.
+fn inline(spans: &[Span]) -> String {
+    let mut out = String::new();
+    for span in spans {
+        let content = span.text.trim();
+        if content.is_empty() {
+            out.push_str(&span.text);
+            continue;
+        }
+        let leading = &span.text[..span.text.len() - span.text.trim_start().len()];
+        let trailing = &span.text[span.text.trim_end().len()..];
+
+        let mut text = if span.code {
+            let ticks = if content.contains('`') { "``" } else { "`" };
+            format!("{ticks}{content}{ticks}")
+        } else {
+            escape(content)
+        };
+        if span.italic {
+            text = format!("*{}*", text);
+        }
+        if span.bold {
+            text = format!("**{}**", text);
+        }
+        out.push_str(leading);
+        out.push_str(&text);
+        out.push_str(trailing);
+    }
+    out
+}
+
+fn cell_markdown(cell: &Cell) -> String {
+    match cell.link {
+        Some(ref anchor) => format!("[{}](#{})", table_text(&cell.text), anchor),
+        None => table_text(&cell.text),
+    }
+}
+
+/// Escape characters that Markdown would treat as formatting.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Table cells must stay on one line.
+fn table_text(text: &str) -> String {
+    escape(&text.replace(['\r', '\n'], " "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::document::tests::{assert_snapshot, sample_document};
+
+    /// Test: the Markdown rendering of the sample report matches the snapshot
+    #[test]
+    fn test_markdown_snapshot() {
+        assert_snapshot("report.md", &render_string(&sample_document()));
+    }
+
+    /// Test: emphasis stays tight around text and special characters are escaped
+    #[test]
+    fn test_inline_formatting() {
+        let spans = vec![Span::bold("Started: "), Span::plain("a_b | c*")];
+        assert_eq!(inline(&spans), "**Started:** a\\_b \\| c\\*");
+    }
+}
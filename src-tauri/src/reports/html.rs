@@ -0,0 +1,214 @@
+//! Self-contained HTML renderer for report documents.
+//!
+//! The output is a single file with inline CSS, charts embedded as inline SVG
+//! (or base64 PNG when the caller supplied the image), a table of contents
+//! and anchor links from the incident timeline to each incident breakdown.
+
+use std::fmt::Write;
+
+use base64::Engine;
+
+use crate::error::AppResult;
+use crate::reports::charts::escape;
+use crate::reports::document::{Block, Cell, ReportDocument, Span};
+
+const STYLE: &str = r#"body { margin: 0; background: #f3f4f6; color: #1f2937; font: 15px/1.55 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; }
+main { max-width: 960px; margin: 0 auto; padding: 32px 40px; background: #ffffff; }
+header h1 { margin: 0 0 8px; font-size: 32px; }
+header p { margin: 2px 0; color: #4b5563; }
+header p:first-of-type { font-size: 18px; }
+nav { margin: 24px 0; padding: 12px 20px; border: 1px solid #e5e7eb; border-radius: 6px; background: #f9fafb; }
+nav h2 { margin: 0 0 8px; font-size: 15px; text-transform: uppercase; letter-spacing: 0.05em; color: #6b7280; }
+h2 { margin: 32px 0 12px; padding-bottom: 4px; border-bottom: 2px solid #e5e7eb; font-size: 24px; }
+h3 { margin: 24px 0 8px; font-size: 19px; }
+h4, h5 { margin: 16px 0 6px; font-size: 16px; }
+a { color: #2563eb; }
+table { width: 100%; margin: 12px 0; border-collapse: collapse; font-size: 13px; }
+th, td { padding: 6px 8px; border: 1px solid #d1d5db; text-align: left; vertical-align: top; }
+th { background: #e0e0e0; }
+pre { padding: 12px; overflow-x: auto; border-radius: 4px; background: #f3f4f6; font-size: 13px; }
+code { font-family: "SFMono-Regular", Consolas, "Courier New", monospace; }
+figure { margin: 16px 0; }
+figcaption { margin-bottom: 6px; font-weight: bold; }
+figure svg, figure img { max-width: 100%; height: auto; }"#;
+
+/// Render a report document as a standalone HTML page.
+pub fn render(doc: &ReportDocument) -> AppResult<Vec<u8>> {
+    Ok(render_string(doc).into_bytes())
+}
+
+fn render_string(doc: &ReportDocument) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    let _ = writeln!(out, "<title>{}</title>", escape(&doc.title));
+    let _ = writeln!(out, "<style>\n{}\n</style>", STYLE);
+    out.push_str("</head>\n<body>\n<main>\n");
+
+    // Title page
+    if !doc.title.is_empty() {
+        out.push_str("<header>\n");
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&doc.title));
+        for line in &doc.subtitle {
+            let _ = writeln!(out, "<p>{}</p>", escape(line));
+        }
+        out.push_str("</header>\n");
+    }
+
+    // Table of contents from the anchored section headings
+    let sections: Vec<(&String, &String)> = doc
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Heading { level: 1, text, anchor: Some(anchor) } => Some((text, anchor)),
+            _ => None,
+        })
+        .collect();
+    if !sections.is_empty() {
+        out.push_str("<nav>\n<h2>Contents</h2>\n<ol>\n");
+        for (text, anchor) in sections {
+            let _ = writeln!(out, "<li><a href=\"#{}\">{}</a></li>", escape(anchor), escape(text));
+        }
+        out.push_str("</ol>\n</nav>\n");
+    }
+
+    // Consecutive list items share one list element
+    let mut open_list: Option<&str> = None;
+    for block in &doc.blocks {
+        let list = match block {
+            Block::ListItem { number: Some(_), .. } => Some("ol"),
+            Block::ListItem { number: None, .. } => Some("ul"),
+            _ => None,
+        };
+        if let Some(tag) = open_list.filter(|tag| list != Some(*tag)) {
+            let _ = writeln!(out, "</{}>", tag);
+            open_list = None;
+        }
+        if let (Some(tag), None) = (list, open_list) {
+            match block {
+                Block::ListItem { number: Some(n), .. } if *n != 1 => {
+                    let _ = writeln!(out, "<ol start=\"{}\">", n);
+                }
+                _ => {
+                    let _ = writeln!(out, "<{}>", tag);
+                }
+            }
+            open_list = Some(tag);
+        }
+        push_block(&mut out, block);
+    }
+    if let Some(tag) = open_list {
+        let _ = writeln!(out, "</{}>", tag);
+    }
+
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+fn push_block(out: &mut String, block: &Block) {
+    match block {
+        Block::Heading { level, text, anchor } => {
+            // The report title is the only <h1>
+            let tag = format!("h{}", (level + 1).min(5));
+            let id = anchor
+                .as_ref()
+                .map(|a| format!(" id=\"{}\"", escape(a)))
+                .unwrap_or_default();
+            let _ = writeln!(out, "<{tag}{id}>{}</{tag}>", escape(text));
+        }
+        Block::Paragraph(spans) => {
+            let _ = writeln!(out, "<p>{}</p>", inline(spans));
+        }
+        Block::ListItem { spans, .. } => {
+            let _ = writeln!(out, "<li>{}</li>", inline(spans));
+        }
+        Block::Code(lines) => {
+            let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&lines.join("\n")));
+        }
+        Block::Table { headers, rows } => {
+            out.push_str("<table>\n<thead>\n<tr>");
+            for header in headers {
+                let _ = write!(out, "<th>{}</th>", escape(header));
+            }
+            out.push_str("</tr>\n</thead>\n<tbody>\n");
+            for row in rows {
+                out.push_str("<tr>");
+                for cell in row {
+                    let _ = write!(out, "<td>{}</td>", cell_html(cell));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        Block::Image { caption, png, svg } => {
+            out.push_str("<figure>\n");
+            let _ = writeln!(out, "<figcaption>{}</figcaption>", escape(caption));
+            match svg {
+                Some(svg) => {
+                    out.push_str(svg);
+                    out.push('\n');
+                }
+                None => {
+                    let data = base64::engine::general_purpose::STANDARD.encode(png);
+                    let _ = writeln!(
+                        out,
+                        "<img src=\"data:image/png;base64,{}\" alt=\"{}\">",
+                        data,
+                        escape(caption)
+                    );
+                }
+            }
+            out.push_str("</figure>\n");
+        }
+        // Spacing comes from the stylesheet
+        Block::Spacer => {}
+    }
+}
+
+fn inline(spans: &[Span]) -> String {
+    let mut html = String::new();
+    for span in spans {
+        let mut text = escape(&span.text);
+        if span.code {
+            text = format!("<code>{}</code>", text);
+        }
+        if span.italic {
+            text = format!("<em>{}</em>", text);
+        }
+        if span.bold {
+            text = format!("<strong>{}</strong>", text);
+        }
+        html.push_str(&text);
+    }
+    html
+}
+
+fn cell_html(cell: &Cell) -> String {
+    match cell.link {
+        Some(ref anchor) => format!("<a href=\"#{}\">{}</a>", escape(anchor), escape(&cell.text)),
+        None => escape(&cell.text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::document::tests::{assert_snapshot, sample_document};
+
+    /// Test: the HTML rendering of the sample report matches the snapshot
+    #[test]
+    fn test_html_snapshot() {
+        assert_snapshot("report.html", &render_string(&sample_document()));
+    }
+
+    /// Test: text is escaped and ordered lists keep their start number
+    #[test]
+    fn test_escaping_and_list_numbering() {
+        let mut doc = ReportDocument::default();
+        doc.markdown("3. one\n4. two");
+        doc.text("<b>&</b>");
+        let html = render_string(&doc);
+        assert!(html.contains("<ol start=\"3\">\n<li>one</li>\n<li>two</li>\n</ol>\n<p>&lt;b&gt;&amp;&lt;/b&gt;</p>"));
+        assert!(!html.contains("<header>"));
+    }
+}
@@ -1,19 +1,16 @@
-//! Markdown → DOCX converter using pulldown-cmark.
+//! Markdown → report blocks using pulldown-cmark.
 //!
-//! Converts a markdown string into a Vec<Paragraph> that can be appended to a Docx document.
-//! Supports: bold, italic, code spans, headings, bullet lists, numbered lists, code blocks.
+//! Converts a markdown string into format-neutral blocks that every report
+//! renderer can draw. Supports: bold, italic, code spans, headings, bullet
+//! lists, numbered lists, code blocks.
 
-use docx_rs::*;
 use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 
-const BODY_SIZE: usize = 11 * 2; // 11pt in half-points
-const CODE_SIZE: usize = 10 * 2;
-const H3_SIZE: usize = 14 * 2;
-const H4_SIZE: usize = 12 * 2;
+use crate::reports::document::{Block, Span};
 
-/// Convert markdown text into DOCX paragraphs.
+/// Convert markdown text into report blocks.
 /// Falls back to plain text if markdown is trivial (no special syntax).
-pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
+pub fn markdown_to_blocks(md: &str) -> Vec<Block> {
     let trimmed = md.trim();
     if trimmed.is_empty() {
         return vec![];
@@ -23,8 +20,8 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
     let parser = Parser::new_ext(trimmed, options);
     let events: Vec<Event> = parser.collect();
 
-    let mut paragraphs: Vec<Paragraph> = Vec::new();
-    let mut current_runs: Vec<Run> = Vec::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut current_spans: Vec<Span> = Vec::new();
     let mut bold = false;
     let mut italic = false;
     let mut in_list = false;
@@ -40,7 +37,7 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
             Event::Start(tag) => {
                 match tag {
                     Tag::Heading { level, .. } => {
-                        flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+                        flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
                         in_heading = true;
                         heading_level = level as u8;
                     }
@@ -50,7 +47,7 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                     Tag::Strong => bold = true,
                     Tag::Emphasis => italic = true,
                     Tag::List(start) => {
-                        flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+                        flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
                         in_list = true;
                         if let Some(s) = start {
                             ordered_list = true;
@@ -61,10 +58,10 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                         }
                     }
                     Tag::Item => {
-                        flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+                        flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
                     }
                     Tag::CodeBlock(_kind) => {
-                        flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+                        flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
                         in_code_block = true;
                         code_block_text.clear();
                     }
@@ -74,17 +71,10 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
             Event::End(tag_end) => {
                 match tag_end {
                     TagEnd::Heading(_level) => {
-                        // Build heading paragraph
-                        let size = match heading_level {
-                            1 | 2 => H3_SIZE, // In sub-context, map h1/h2 down
-                            3 => H3_SIZE,
-                            _ => H4_SIZE,
-                        };
-                        let mut para = Paragraph::new();
-                        for run in current_runs.drain(..) {
-                            para = para.add_run(run.bold().size(size));
-                        }
-                        paragraphs.push(para);
+                        // In sub-context, map h1/h2 down below the report's own headings
+                        let level = if heading_level <= 3 { 3 } else { 4 };
+                        let text: String = current_spans.drain(..).map(|s| s.text).collect();
+                        blocks.push(Block::Heading { level, text, anchor: None });
                         in_heading = false;
                         heading_level = 0;
                     }
@@ -92,7 +82,7 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                         if in_heading {
                             continue;
                         }
-                        flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+                        flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
                     }
                     TagEnd::Strong => bold = false,
                     TagEnd::Emphasis => italic = false,
@@ -103,8 +93,8 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                     }
                     TagEnd::Item => {
                         flush_paragraph(
-                            &mut paragraphs,
-                            &mut current_runs,
+                            &mut blocks,
+                            &mut current_spans,
                             in_list,
                             ordered_list,
                             list_index,
@@ -115,16 +105,9 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                     }
                     TagEnd::CodeBlock => {
                         in_code_block = false;
-                        // Render code block as a shaded paragraph with monospace-style text
-                        for line in code_block_text.lines() {
-                            let run = Run::new()
-                                .add_text(line)
-                                .size(CODE_SIZE)
-                                .fonts(RunFonts::new().ascii("Courier New"));
-                            paragraphs.push(
-                                Paragraph::new().add_run(run)
-                            );
-                        }
+                        blocks.push(Block::Code(
+                            code_block_text.lines().map(str::to_string).collect(),
+                        ));
                         code_block_text.clear();
                     }
                     _ => {}
@@ -134,74 +117,56 @@ pub fn markdown_to_paragraphs(md: &str) -> Vec<Paragraph> {
                 if in_code_block {
                     code_block_text.push_str(&text);
                 } else {
-                    let mut run = Run::new().add_text(text.as_ref()).size(BODY_SIZE);
-                    if bold {
-                        run = run.bold();
-                    }
-                    if italic {
-                        run = run.italic();
-                    }
-                    current_runs.push(run);
+                    current_spans.push(Span {
+                        text: text.to_string(),
+                        bold,
+                        italic,
+                        code: false,
+                    });
                 }
             }
             Event::Code(code) => {
                 // Inline code
-                let run = Run::new()
-                    .add_text(code.as_ref())
-                    .size(CODE_SIZE)
-                    .fonts(RunFonts::new().ascii("Courier New"));
-                current_runs.push(run);
+                current_spans.push(Span {
+                    text: code.to_string(),
+                    code: true,
+                    ..Default::default()
+                });
             }
             Event::SoftBreak | Event::HardBreak => {
                 // Treat as paragraph break
-                flush_paragraph(&mut paragraphs, &mut current_runs, in_list, ordered_list, list_index);
+                flush_paragraph(&mut blocks, &mut current_spans, in_list, ordered_list, list_index);
             }
             _ => {}
         }
     }
 
     // Flush remaining
-    flush_paragraph(&mut paragraphs, &mut current_runs, false, false, 0);
+    flush_paragraph(&mut blocks, &mut current_spans, false, false, 0);
 
-    paragraphs
+    blocks
 }
 
 fn flush_paragraph(
-    paragraphs: &mut Vec<Paragraph>,
-    runs: &mut Vec<Run>,
+    blocks: &mut Vec<Block>,
+    spans: &mut Vec<Span>,
     is_list_item: bool,
     is_ordered: bool,
     list_index: u64,
 ) {
-    if runs.is_empty() {
+    if spans.is_empty() {
         return;
     }
 
-    let mut para = Paragraph::new();
-
+    let spans: Vec<Span> = std::mem::take(spans);
     if is_list_item {
-        let prefix = if is_ordered {
-            format!("{}. ", list_index)
-        } else {
-            "\u{2022}  ".to_string()
-        };
-        para = para.add_run(Run::new().add_text(prefix).size(BODY_SIZE));
-    }
-
-    for run in runs.drain(..) {
-        para = para.add_run(run);
-    }
-
-    paragraphs.push(para);
-}
-
-/// Convert markdown to DOCX paragraphs and append to a Docx.
-/// If the input is empty, returns the docx unchanged.
-pub fn append_markdown(mut docx: Docx, md: &str) -> Docx {
-    for para in markdown_to_paragraphs(md) {
-        docx = docx.add_paragraph(para);
+        blocks.push(Block::ListItem {
+            number: is_ordered.then_some(list_index),
+            spans,
+        });
+    } else {
+        blocks.push(Block::Paragraph(spans));
     }
-    docx
 }
 
 #[cfg(test)]
@@ -210,27 +175,41 @@ mod tests {
 
     #[test]
     fn empty_input_returns_empty() {
-        let result = markdown_to_paragraphs("");
+        let result = markdown_to_blocks("");
         assert!(result.is_empty());
     }
 
     #[test]
     fn plain_text_produces_one_paragraph() {
-        let result = markdown_to_paragraphs("Hello world");
+        let result = markdown_to_blocks("Hello world");
         assert_eq!(result.len(), 1);
     }
 
     #[test]
     fn bullet_list_produces_multiple_paragraphs() {
         let md = "- Item one\n- Item two\n- Item three";
-        let result = markdown_to_paragraphs(md);
+        let result = markdown_to_blocks(md);
         assert_eq!(result.len(), 3);
     }
 
     #[test]
     fn code_block_renders() {
         let md = "```\nlet x = 1;\nlet y = 2;\n```";
-        let result = markdown_to_paragraphs(md);
-        assert!(result.len() >= 2); // At least 2 lines
+        let result = markdown_to_blocks(md);
+        assert_eq!(result, vec![Block::Code(vec!["let x = 1;".into(), "let y = 2;".into()])]);
+    }
+
+    #[test]
+    fn inline_formatting_and_numbering_are_kept() {
+        let result = markdown_to_blocks("## Fix\n\n2. **Roll back** the `deploy`\n3. *Verify*");
+        assert_eq!(result[0], Block::Heading { level: 3, text: "Fix".into(), anchor: None });
+        match &result[1] {
+            Block::ListItem { number: Some(2), spans } => {
+                assert!(spans[0].bold);
+                assert!(spans[2].code);
+            }
+            other => panic!("unexpected block {:?}", other),
+        }
+        assert!(matches!(&result[2], Block::ListItem { number: Some(3), spans } if spans[0].italic));
     }
 }
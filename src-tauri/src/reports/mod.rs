@@ -1,25 +1,50 @@
 pub mod charts;
+pub mod document;
+pub mod docx;
+pub mod fonts;
+pub mod gfm;
+pub mod html;
 pub mod markdown;
 pub mod pdf;
+pub mod period;
+pub mod redaction;
 pub mod sections;
+pub mod snapshot;
 
 use std::collections::HashMap;
-use std::io::Cursor;
 
-use docx_rs::*;
+use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
-use crate::db::queries::{incidents, settings, metrics};
-use crate::error::{AppError, AppResult};
+use crate::db::queries::{
+    checklists, costs, discussion_rules, fatigue, forecasts, incidents, metrics, report_theme,
+    service_dependencies, services,
+};
+use crate::error::AppResult;
+use crate::models::checklist::ChecklistCompliance;
+use crate::models::cost::{format_currency, summarize_costs, CostModel, IncidentCost};
+use crate::models::discussion_rule::{evaluate_rules, DiscussionRule, RuleEvaluation, RuleInput};
+use crate::models::fatigue::ResponderLoadReport;
+use crate::models::forecast::ForecastRequest;
 use crate::models::incident::{ActionItem, Incident, IncidentFilters};
-use crate::models::metrics::{MetricFilters, QuarterlyTrends};
-use crate::models::quarter::QuarterConfig;
+use crate::models::metrics::{format_decimal, format_minutes, format_percentage, MetricFilters, QuarterlyTrends};
+use crate::models::redaction::RedactionProfile;
+use crate::models::report_template::{ReportSectionKind, TemplateSection};
+use crate::models::report_theme::ReportTheme;
+use crate::reports::charts::ChartImage;
+use crate::reports::document::ReportDocument;
+use crate::reports::period::{ComparisonMode, PeriodSpan, ReportPeriod, ResolvedPeriod};
+use crate::reports::redaction::Redactor;
 use crate::reports::sections::discussion_points::DiscussionPoint;
+use crate::reports::sections::outlook::Outlook;
+use crate::reports::sections::service_context::ServiceContext;
+use crate::reports::snapshot::ReportSnapshot;
 
-/// Report section configuration.
+/// Section toggles for reports generated without a template.
 #[derive(Debug, Clone)]
 pub struct ReportSections {
     pub executive_summary: bool,
+    pub service_context: bool,
     pub metrics_overview: bool,
     pub incident_timeline: bool,
     pub incident_breakdowns: bool,
@@ -27,36 +52,108 @@ pub struct ReportSections {
     pub qoq_comparison: bool,
     pub discussion_points: bool,
     pub action_items: bool,
+    pub cost_impact: bool,
+    pub outlook: bool,
+    pub responder_load: bool,
+    pub checklist_compliance: bool,
+}
+
+impl ReportSections {
+    /// The enabled sections in the classic report order.
+    pub fn layout(&self) -> Vec<TemplateSection> {
+        ReportSectionKind::BUILT_IN
+            .into_iter()
+            .filter(|kind| match kind {
+                ReportSectionKind::ExecutiveSummary => self.executive_summary,
+                ReportSectionKind::ServiceContext => self.service_context,
+                ReportSectionKind::MetricsOverview => self.metrics_overview,
+                ReportSectionKind::IncidentTimeline => self.incident_timeline,
+                ReportSectionKind::IncidentBreakdowns => self.incident_breakdowns,
+                ReportSectionKind::ServiceReliability => self.service_reliability,
+                ReportSectionKind::CostImpact => self.cost_impact,
+                ReportSectionKind::ResponderLoad => self.responder_load,
+                ReportSectionKind::ChecklistCompliance => self.checklist_compliance,
+                ReportSectionKind::QoqComparison => self.qoq_comparison,
+                ReportSectionKind::Outlook => self.outlook,
+                ReportSectionKind::DiscussionPoints => self.discussion_points,
+                ReportSectionKind::ActionItems => self.action_items,
+                ReportSectionKind::Custom => false,
+            })
+            .map(TemplateSection::built_in)
+            .collect()
+    }
 }
 
 /// Full report config used by the generation pipeline.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportConfig {
-    pub quarter_id: Option<String>,
-    #[allow(dead_code)]
-    pub fiscal_year: Option<i32>,
+    pub period: ReportPeriod,
+    pub comparison: ComparisonMode,
+    /// Services the report is limited to; empty covers every service.
+    pub service_ids: Vec<String>,
     pub title: String,
     pub introduction: String,
-    pub sections: ReportSections,
+    /// Sections in output order, from a template or `ReportSections::layout`.
+    pub sections: Vec<TemplateSection>,
+    /// Supplied chart overrides; snapshots keep the resolved charts in `ReportData` instead.
+    #[serde(skip)]
     pub chart_images: HashMap<String, Vec<u8>>, // decoded PNG bytes
     pub format: ReportFormat,
+    /// Redaction for reports shared outside the team; `None` leaves the report as is.
+    #[serde(default)]
+    pub redaction: Option<RedactionProfile>,
+}
+
+impl ReportConfig {
+    fn includes(&self, kind: ReportSectionKind) -> bool {
+        self.sections.iter().any(|s| s.kind == kind)
+    }
 }
 
 /// Supported output formats for reports.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ReportFormat {
     Docx,
     Pdf,
+    Html,
+    #[serde(rename = "md")]
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Parse a format name, defaulting to DOCX for unknown values.
+    pub fn parse(format: &str) -> Self {
+        match format.to_lowercase().as_str() {
+            "pdf" => ReportFormat::Pdf,
+            "html" => ReportFormat::Html,
+            "md" | "markdown" => ReportFormat::Markdown,
+            _ => ReportFormat::Docx,
+        }
+    }
+
+    /// File extension, also stored as the format in report history.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Docx => "docx",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Html => "html",
+            ReportFormat::Markdown => "md",
+        }
+    }
 }
 
-/// Collected data for report generation.
+/// Collected data for report generation; stored in report snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ReportData {
     incidents: Vec<Incident>,
     prev_incidents: Vec<Incident>,
     action_items_all: Vec<ActionItem>,
-    quarter: Option<QuarterConfig>,
-    #[allow(dead_code)]
-    prev_quarter: Option<QuarterConfig>,
+    period: ResolvedPeriod,
+    /// The services a service-scoped report covers, in the order requested.
+    services: Vec<ServiceContext>,
+    /// Enabled discussion rules, when the section is requested.
+    discussion_rules: Vec<DiscussionRule>,
     mttr: f64,
     mtta: f64,
     total_incidents: i64,
@@ -68,141 +165,154 @@ struct ReportData {
     prev_recurrence: Option<f64>,
     prev_tickets: Option<f64>,
     trends: QuarterlyTrends,
+    incident_costs: Vec<IncidentCost>,
+    cost_model: CostModel,
+    outlook: Option<Outlook>,
+    responder_load: Option<ResponderLoadReport>,
+    checklist_compliance: Option<ChecklistCompliance>,
+    /// Supplied chart PNGs, with rendered charts filling the gaps.
+    chart_images: HashMap<String, ChartImage>,
+    /// Applied to the data already; kept to redact the built document.
+    #[serde(default)]
+    redaction: Option<Redactor>,
+    /// Theme at generation time, so regenerated reports keep their branding.
+    #[serde(default)]
+    theme: ReportTheme,
 }
 
-/// Main entry point: generate a quarterly report and return the bytes.
-/// Supports both DOCX and PDF formats based on config.format.
+/// Main entry point: generate a quarterly report and return the bytes
+/// in the format given by config.format, with the snapshot they were rendered from.
 pub async fn generate_quarterly_report(
     db: &SqlitePool,
     config: &ReportConfig,
-) -> AppResult<Vec<u8>> {
+) -> AppResult<(Vec<u8>, ReportSnapshot)> {
     let data = fetch_report_data(db, config).await?;
+    let snapshot = ReportSnapshot::new(config.clone(), data);
+    Ok((render_snapshot(&snapshot)?, snapshot))
+}
 
-    match config.format {
-        ReportFormat::Pdf => {
-            pdf::build_pdf(
-                config,
-                &data.incidents,
-                &data.prev_incidents,
-                data.quarter.as_ref(),
-                data.mttr,
-                data.mtta,
-                data.total_incidents,
-                data.recurrence_rate,
-                data.avg_tickets,
-                data.prev_mttr,
-                data.prev_mtta,
-                data.prev_total,
-                data.prev_recurrence,
-                data.prev_tickets,
-                &data.action_items_all,
-                &data.trends,
-            )
-        }
-        ReportFormat::Docx => {
-            let docx = build_document(config, &data);
-
-            let mut buf: Vec<u8> = Vec::new();
-            let cursor = Cursor::new(&mut buf);
-            docx.build()
-                .pack(cursor)
-                .map_err(|e| AppError::Report(format!("Failed to build DOCX: {}", e)))?;
+/// Render a report from a snapshot alone, without reading current data.
+pub fn render_snapshot(snapshot: &ReportSnapshot) -> AppResult<Vec<u8>> {
+    let (config, data) = (&snapshot.config, &snapshot.data);
+    let document = build_document(config, data);
 
-            Ok(buf)
-        }
+    match config.format {
+        ReportFormat::Pdf => pdf::render(&document),
+        ReportFormat::Docx => docx::render(&document),
+        ReportFormat::Html => html::render(&document),
+        ReportFormat::Markdown => gfm::render(&document),
     }
 }
 
+/// Hash of the document a snapshot renders to, whatever its file format.
+pub fn snapshot_content_hash(snapshot: &ReportSnapshot) -> String {
+    snapshot::content_hash(&build_document(&snapshot.config, &snapshot.data))
+}
+
 /// Generate discussion points for preview (no DOCX build).
 pub async fn generate_discussion_points_only(
     db: &SqlitePool,
     quarter_id: &str,
 ) -> AppResult<Vec<DiscussionPoint>> {
-    let quarter = settings::get_quarter_by_id(db, quarter_id).await?;
-    let prev_quarter = settings::get_previous_quarter(
+    let rules = discussion_rules::list_enabled_discussion_rules(db).await?;
+    let (current, previous, action_items) = discussion_inputs(db, quarter_id).await?;
+    let input = RuleInput {
+        incidents: &current,
+        prev_incidents: &previous,
+        action_items: &action_items,
+    };
+    Ok(sections::discussion_points::generate(&rules, &input))
+}
+
+/// Dry run: every stored rule, enabled or not, evaluated for a quarter with
+/// the reason each one did or did not fire.
+pub async fn evaluate_discussion_rules(
+    db: &SqlitePool,
+    quarter_id: &str,
+) -> AppResult<Vec<RuleEvaluation>> {
+    let rules = discussion_rules::list_discussion_rules(db).await?;
+    let (current, previous, action_items) = discussion_inputs(db, quarter_id).await?;
+    let input = RuleInput {
+        incidents: &current,
+        prev_incidents: &previous,
+        action_items: &action_items,
+    };
+    Ok(evaluate_rules(&rules, &input))
+}
+
+/// Quarter and previous-period incidents plus all action items, as a
+/// quarterly report sees them.
+async fn discussion_inputs(
+    db: &SqlitePool,
+    quarter_id: &str,
+) -> AppResult<(Vec<Incident>, Vec<Incident>, Vec<ActionItem>)> {
+    let period = period::resolve(
         db,
-        quarter.fiscal_year,
-        quarter.quarter_number,
+        &ReportPeriod::Quarter(quarter_id.to_string()),
+        ComparisonMode::PreviousPeriod,
     )
     .await?;
+    let current = list_period_incidents(db, &period.current).await?;
+    let previous = match period.comparison {
+        Some(ref c) => list_period_incidents(db, c).await?,
+        None => vec![],
+    };
+    let action_items = incidents::list_action_items(db, None).await?;
+    Ok((current, previous, action_items))
+}
 
+/// Incidents started within a period's local days, oldest first.
+async fn list_period_incidents(db: &SqlitePool, span: &PeriodSpan) -> AppResult<Vec<Incident>> {
+    let range = metrics::resolve_date_range(db, &span.start, &span.end).await?;
     let filters = IncidentFilters {
-        quarter_id: Some(quarter_id.to_string()),
         sort_order: Some("asc".to_string()),
         ..Default::default()
     };
-    let quarter_dates = Some((quarter.start_date.clone(), quarter.end_date.clone()));
-    let current_incidents = incidents::list_incidents(db, &filters, quarter_dates).await?;
-    let total_incidents = current_incidents.len() as i64;
-
-    // Calc MTTR for current quarter
-    let mttr = calc_avg_duration(&current_incidents);
-
-    // Previous quarter data
-    let (prev_incidents, prev_mttr, prev_total) = if let Some(ref pq) = prev_quarter {
-        let pf = IncidentFilters {
-            sort_order: Some("asc".to_string()),
-            ..Default::default()
-        };
-        let pd = Some((pq.start_date.clone(), pq.end_date.clone()));
-        let pi = incidents::list_incidents(db, &pf, pd).await?;
-        let pm = calc_avg_duration(&pi);
-        let pt = pi.len() as i64;
-        (pi, Some(pm), Some(pt))
-    } else {
-        (vec![], None, None)
-    };
-
-    let all_action_items = incidents::list_action_items(db, None).await?;
-
-    Ok(sections::discussion_points::generate(
-        &current_incidents,
-        &prev_incidents,
-        mttr,
-        prev_mttr,
-        total_incidents,
-        prev_total,
-        &all_action_items,
-    ))
+    incidents::list_incidents(db, &filters, Some((range.start, range.end))).await
 }
 
 /// Fetch all data needed for the report.
 async fn fetch_report_data(db: &SqlitePool, config: &ReportConfig) -> AppResult<ReportData> {
-    // Get quarter info
-    let quarter = if let Some(ref qid) = config.quarter_id {
-        Some(settings::get_quarter_by_id(db, qid).await?)
-    } else {
-        None
+    let redactor = match config.redaction {
+        Some(ref profile) => Some(Redactor::load(db, profile).await?),
+        None => None,
+    };
+    let period = period::resolve(db, &config.period, config.comparison).await?;
+    let current_range =
+        metrics::resolve_date_range(db, &period.current.start, &period.current.end).await?;
+    let comparison_range = match period.comparison {
+        Some(ref c) => Some(metrics::resolve_date_range(db, &c.start, &c.end).await?),
+        None => None,
     };
 
-    let prev_quarter = if let Some(ref q) = quarter {
-        settings::get_previous_quarter(db, q.fiscal_year, q.quarter_number).await?
-    } else {
-        None
+    let mut contexts = Vec::new();
+    for id in &config.service_ids {
+        contexts.push(ServiceContext {
+            service: services::get_service_by_id(db, id).await?,
+            dependencies: service_dependencies::list_dependencies_for_service(db, id).await?,
+            dependents: service_dependencies::list_dependents_of_service(db, id).await?,
+        });
+    }
+    let in_scope = |service_id: &str| {
+        config.service_ids.is_empty() || config.service_ids.iter().any(|id| id == service_id)
     };
 
-    // Fetch current quarter incidents
-    let quarter_dates = quarter
-        .as_ref()
-        .map(|q| (q.start_date.clone(), q.end_date.clone()));
+    // Incidents in the report and comparison periods
+    let mut current_incidents = list_period_incidents(db, &period.current).await?;
+    current_incidents.retain(|i| in_scope(&i.service_id));
+    let total_incidents = current_incidents.len() as i64;
 
-    let filters = IncidentFilters {
-        sort_order: Some("asc".to_string()),
-        ..Default::default()
+    let prev_incidents = match period.comparison {
+        Some(ref c) => {
+            let mut prev = list_period_incidents(db, c).await?;
+            prev.retain(|i| in_scope(&i.service_id));
+            prev
+        }
+        None => vec![],
     };
-    let current_incidents = incidents::list_incidents(db, &filters, quarter_dates).await?;
-    let total_incidents = current_incidents.len() as i64;
 
-    // Previous quarter incidents
-    let prev_dates = prev_quarter
-        .as_ref()
-        .map(|q| (q.start_date.clone(), q.end_date.clone()));
-    let prev_incidents = if prev_dates.is_some() {
-        let pf = IncidentFilters {
-            sort_order: Some("asc".to_string()),
-            ..Default::default()
-        };
-        incidents::list_incidents(db, &pf, prev_dates).await?
+    let discussion_rules = if config.includes(ReportSectionKind::DiscussionPoints) {
+        discussion_rules::list_enabled_discussion_rules(db).await?
     } else {
         vec![]
     };
@@ -239,24 +349,92 @@ async fn fetch_report_data(db: &SqlitePool, config: &ReportConfig) -> AppResult<
         None
     };
 
-    // Get all action items
-    let action_items_all = incidents::list_action_items(db, None).await?;
+    // Action items, limited to incidents on the report's services
+    let mut action_items_all = incidents::list_action_items(db, None).await?;
+    if !config.service_ids.is_empty() {
+        let all = IncidentFilters::default();
+        let scoped: std::collections::HashSet<String> = incidents::list_incidents(db, &all, None)
+            .await?
+            .into_iter()
+            .filter(|i| in_scope(&i.service_id))
+            .map(|i| i.id)
+            .collect();
+        action_items_all.retain(|a| scoped.contains(&a.incident_id));
+    }
 
-    // Get quarterly trends via dashboard metrics
-    let metric_filters = MetricFilters::default();
-    let dashboard = metrics::get_dashboard_data_for_quarter(
+    let metric_filters = MetricFilters {
+        service_ids: (!config.service_ids.is_empty()).then(|| config.service_ids.clone()),
+        ..Default::default()
+    };
+    let mut dashboard = metrics::get_dashboard_data(
         db,
-        config.quarter_id.as_deref(),
+        &current_range,
+        comparison_range.as_ref(),
         &metric_filters,
+        &period.current.label,
     )
     .await?;
+    // Quarter-by-quarter trend for the period's quarters
+    let trends = metrics::get_quarterly_trends(db, &period.trend_quarters, &metric_filters).await?;
+
+    // Redacted reports only use charts rendered here, from redacted data;
+    // supplied captures could show anything
+    let no_charts = HashMap::new();
+    let supplied_charts = match redactor {
+        Some(ref r) => {
+            r.dashboard(&mut dashboard);
+            &no_charts
+        }
+        None => &config.chart_images,
+    };
+    let chart_images = if config.includes(ReportSectionKind::MetricsOverview) || config.includes(ReportSectionKind::QoqComparison) {
+        charts::resolve_chart_images(supplied_charts, &dashboard, &trends)?
+    } else {
+        HashMap::new()
+    };
+
+    // Estimated incident costs for the same period
+    let cost_model = costs::get_cost_model(db).await?;
+    let incident_costs =
+        costs::list_incident_costs(db, &current_range, &metric_filters, &cost_model).await?;
+
+    let responder_load = if config.includes(ReportSectionKind::ResponderLoad) {
+        Some(fatigue::get_responder_load(db, &current_range, &metric_filters).await?)
+    } else {
+        None
+    };
+
+    let checklist_compliance = if config.includes(ReportSectionKind::ChecklistCompliance) {
+        Some(checklists::get_checklist_compliance(db, &current_range, &metric_filters).await?)
+    } else {
+        None
+    };
 
-    Ok(ReportData {
+    // Next-quarter forecasts from the end of the period, only computed when the Outlook section is requested
+    let outlook = if config.includes(ReportSectionKind::Outlook) {
+        let as_of = Some(period.current.end.clone());
+        let request = |metric: &str| ForecastRequest {
+            metric: metric.to_string(),
+            horizon: 1,
+            history_quarters: 12,
+            as_of: as_of.clone(),
+            filters: metric_filters.clone(),
+        };
+        Some(Outlook {
+            volume: forecasts::get_incident_forecast(db, &request("count")).await?,
+            mttr: forecasts::get_incident_forecast(db, &request("mttr")).await?,
+        })
+    } else {
+        None
+    };
+
+    let mut data = ReportData {
         incidents: current_incidents,
         prev_incidents,
         action_items_all,
-        quarter,
-        prev_quarter,
+        period,
+        services: contexts,
+        discussion_rules,
         mttr,
         mtta,
         total_incidents,
@@ -267,101 +445,168 @@ async fn fetch_report_data(db: &SqlitePool, config: &ReportConfig) -> AppResult<
         prev_total,
         prev_recurrence,
         prev_tickets,
-        trends: dashboard.trends,
-    })
+        trends,
+        incident_costs,
+        cost_model,
+        outlook,
+        responder_load,
+        checklist_compliance,
+        chart_images,
+        redaction: None,
+        theme: report_theme::get_report_theme(db).await?,
+    };
+    if let Some(redactor) = redactor {
+        redactor.report_data(&mut data);
+        data.redaction = Some(redactor);
+    }
+    Ok(data)
 }
 
-/// Build the DOCX document from collected data.
-fn build_document(config: &ReportConfig, data: &ReportData) -> Docx {
-    let mut docx = Docx::new();
+/// Build the format-neutral document from collected data, one section per
+/// layout entry, in layout order.
+fn build_document(config: &ReportConfig, data: &ReportData) -> ReportDocument {
+    let mut doc = ReportDocument::new(&config.title);
+    doc.theme = data.theme.clone();
 
     // Title page
-    docx = docx.add_paragraph(
-        Paragraph::new()
-            .add_run(Run::new().add_text(&config.title).bold().size(36 * 2))
-            .style("Heading1")
-    );
-
-    if let Some(ref q) = data.quarter {
-        docx = docx.add_paragraph(
-            Paragraph::new()
-                .add_run(Run::new().add_text(&q.label).size(16 * 2))
-        );
-        docx = docx.add_paragraph(
-            Paragraph::new()
-                .add_run(Run::new().add_text(format!(
-                    "Period: {} to {}",
-                    q.start_date, q.end_date
-                )).size(12 * 2))
-        );
-    }
-
-    docx = docx.add_paragraph(sections::spacer());
-
-    // Add enabled sections
-    if config.sections.executive_summary {
-        docx = sections::executive_summary::build(
-            docx,
-            &data.incidents,
-            data.mttr,
-            data.mtta,
-            data.recurrence_rate,
-            data.total_incidents,
-            &config.introduction,
-        );
-    }
-
-    if config.sections.metrics_overview {
-        docx = sections::metrics_overview::build(
-            docx,
-            data.mttr,
-            data.mtta,
-            data.total_incidents,
-            data.recurrence_rate,
-            data.avg_tickets,
-            data.prev_mttr,
-            data.prev_mtta,
-            data.prev_total,
-            data.prev_recurrence,
-            data.prev_tickets,
-            &config.chart_images,
-        );
-    }
-
-    if config.sections.incident_timeline {
-        docx = sections::incident_timeline::build(docx, &data.incidents);
+    let period = &data.period;
+    doc.subtitle.push(period.current.label.clone());
+    doc.subtitle.push(format!("Period: {} to {}", period.current.start, period.current.end));
+    if let Some(ref c) = period.comparison {
+        let dates = format!("{} to {}", c.start, c.end);
+        if c.label == dates {
+            doc.subtitle.push(format!("Compared with: {}", dates));
+        } else {
+            doc.subtitle.push(format!("Compared with: {} ({})", c.label, dates));
+        }
     }
-
-    if config.sections.incident_breakdowns {
-        docx = sections::incident_breakdowns::build(docx, &data.incidents);
+    if !data.services.is_empty() {
+        let names: Vec<&str> = data.services.iter().map(|c| c.service.name.as_str()).collect();
+        doc.subtitle.push(format!("Services: {}", names.join(", ")));
     }
 
-    if config.sections.service_reliability {
-        docx = sections::service_reliability::build(docx, &data.incidents);
-    }
-
-    if config.sections.qoq_comparison {
-        docx = sections::qoq_comparison::build(docx, &data.trends);
-    }
+    let values = placeholder_values(config, data);
 
-    if config.sections.discussion_points {
-        let points = sections::discussion_points::generate(
-            &data.incidents,
-            &data.prev_incidents,
-            data.mttr,
-            data.prev_mttr,
-            data.total_incidents,
-            data.prev_total,
-            &data.action_items_all,
-        );
-        docx = sections::discussion_points::build(docx, &points);
+    for section in &config.sections {
+        let options = &section.options;
+        match section.kind {
+            ReportSectionKind::ExecutiveSummary => sections::executive_summary::build(
+                &mut doc,
+                &data.incidents,
+                data.mttr,
+                data.mtta,
+                data.recurrence_rate,
+                data.total_incidents,
+                &config.introduction,
+            ),
+            ReportSectionKind::ServiceContext => {
+                // Organization-wide reports have no service context to show
+                if !data.services.is_empty() {
+                    sections::service_context::build(&mut doc, &data.services);
+                }
+            }
+            ReportSectionKind::MetricsOverview => sections::metrics_overview::build(
+                &mut doc,
+                data.mttr,
+                data.mtta,
+                data.total_incidents,
+                data.recurrence_rate,
+                data.avg_tickets,
+                data.prev_mttr,
+                data.prev_mtta,
+                data.prev_total,
+                data.prev_recurrence,
+                data.prev_tickets,
+                &data.chart_images,
+                options,
+            ),
+            ReportSectionKind::IncidentTimeline => sections::incident_timeline::build(
+                &mut doc,
+                &data.incidents,
+                config.includes(ReportSectionKind::IncidentBreakdowns),
+            ),
+            ReportSectionKind::IncidentBreakdowns => {
+                sections::incident_breakdowns::build(&mut doc, &data.incidents, options.top_n)
+            }
+            ReportSectionKind::ServiceReliability => {
+                sections::service_reliability::build(&mut doc, &data.incidents, options.top_n)
+            }
+            ReportSectionKind::CostImpact => sections::cost_impact::build(
+                &mut doc,
+                &data.incident_costs,
+                &data.cost_model,
+                options.top_n_or(sections::cost_impact::TOP_INCIDENTS),
+            ),
+            ReportSectionKind::ResponderLoad => {
+                if let Some(ref load) = data.responder_load {
+                    let top_n = options.top_n_or(sections::responder_load::TOP_RESPONDERS);
+                    sections::responder_load::build(&mut doc, load, top_n);
+                }
+            }
+            ReportSectionKind::ChecklistCompliance => {
+                if let Some(ref compliance) = data.checklist_compliance {
+                    sections::checklist_compliance::build(&mut doc, compliance);
+                }
+            }
+            ReportSectionKind::QoqComparison => {
+                sections::qoq_comparison::build(&mut doc, &data.trends, &data.chart_images, options)
+            }
+            ReportSectionKind::Outlook => {
+                if let Some(ref outlook) = data.outlook {
+                    sections::outlook::build(&mut doc, outlook);
+                }
+            }
+            ReportSectionKind::DiscussionPoints => {
+                let input = RuleInput {
+                    incidents: &data.incidents,
+                    prev_incidents: &data.prev_incidents,
+                    action_items: &data.action_items_all,
+                };
+                let mut points = sections::discussion_points::generate(&data.discussion_rules, &input);
+                if let Some(n) = options.top_n {
+                    points.truncate(n);
+                }
+                sections::discussion_points::build(&mut doc, &points);
+            }
+            ReportSectionKind::ActionItems => {
+                sections::action_items::build(&mut doc, &data.action_items_all, options.top_n)
+            }
+            ReportSectionKind::Custom => {
+                sections::custom::build(&mut doc, &section.title, &section.body, &values)
+            }
+        }
     }
 
-    if config.sections.action_items {
-        docx = sections::action_items::build(docx, &data.action_items_all);
+    if let Some(ref redactor) = data.redaction {
+        redactor.document(&mut doc);
     }
+    doc
+}
 
-    docx
+/// Values for `{{placeholder}}` markers in custom sections.
+fn placeholder_values<'a>(config: &ReportConfig, data: &ReportData) -> HashMap<&'a str, String> {
+    let na = |available: bool, value: String| if available { value } else { "N/A".to_string() };
+    let has_incidents = data.total_incidents > 0;
+    let summary = summarize_costs(&data.incident_costs, &data.cost_model.currency);
+
+    let mut values = HashMap::new();
+    values.insert("title", config.title.clone());
+    values.insert("quarter", data.period.current.label.clone());
+    values.insert("period_start", data.period.current.start.clone());
+    values.insert("period_end", data.period.current.end.clone());
+    values.insert("total_incidents", data.total_incidents.to_string());
+    values.insert(
+        "critical_incidents",
+        data.incidents.iter().filter(|i| i.priority == "P0" || i.priority == "P1").count().to_string(),
+    );
+    values.insert("mttr", na(has_incidents, format_minutes(data.mttr)));
+    values.insert("mtta", na(has_incidents, format_minutes(data.mtta)));
+    values.insert("recurrence_rate", na(has_incidents, format_percentage(data.recurrence_rate)));
+    values.insert("avg_tickets", na(has_incidents, format_decimal(data.avg_tickets)));
+    values.insert("prev_total_incidents", data.prev_total.map(|t| t.to_string()).unwrap_or_else(|| "N/A".into()));
+    values.insert("prev_mttr", data.prev_mttr.map(format_minutes).unwrap_or_else(|| "N/A".into()));
+    values.insert("total_cost", format_currency(summary.total_cost, &data.cost_model.currency));
+    values
 }
 
 // -- In-memory metric helpers (avoid extra DB queries) --
@@ -417,3 +662,252 @@ fn calc_avg_tickets(incidents: &[Incident]) -> f64 {
     let total: f64 = incidents.iter().map(|i| i.tickets_submitted as f64).sum();
     total / incidents.len() as f64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::document::{Block, Span};
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("report-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    fn all_sections() -> ReportSections {
+        ReportSections {
+            executive_summary: true,
+            service_context: false,
+            metrics_overview: true,
+            incident_timeline: true,
+            incident_breakdowns: true,
+            service_reliability: true,
+            qoq_comparison: true,
+            discussion_points: true,
+            action_items: true,
+            cost_impact: true,
+            outlook: true,
+            responder_load: true,
+            checklist_compliance: true,
+        }
+    }
+
+    /// Test: one document carries every enabled section, charts and markdown for every format
+    #[tokio::test]
+    async fn test_document_covers_all_sections() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at, root_cause) \
+             VALUES ('inc-1', 'Checkout down', 'svc-slack', 'Critical', 'Critical', 'Resolved', \
+             '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', '2026-03-10T12:00:00Z', '**Disk** filled up\n\n- rotate logs')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let config = ReportConfig {
+            period: ReportPeriod::Quarter("fy27-q1".into()),
+            comparison: ComparisonMode::PreviousPeriod,
+            service_ids: vec![],
+            title: "Quarterly Review".into(),
+            introduction: String::new(),
+            sections: all_sections().layout(),
+            chart_images: HashMap::new(),
+            format: ReportFormat::Docx,
+            redaction: None,
+        };
+        let data = fetch_report_data(&db, &config).await.unwrap();
+        let doc = build_document(&config, &data);
+        assert_eq!(doc.subtitle[0], "FY27 Q1");
+
+        let titles: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Heading { level: 1, text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Executive Summary",
+                "Metrics Overview",
+                "Incident Timeline",
+                "Critical Incident Breakdowns",
+                "Service Reliability Summary",
+                "Cost & Business Impact",
+                "Responder Load",
+                "Checklist Compliance",
+                "Quarter-over-Quarter Comparison",
+                "Outlook",
+                "Discussion Points",
+                "Action Items",
+            ]
+        );
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Image { caption, .. } if caption.contains("severity"))));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Paragraph(spans) if spans.first() == Some(&Span { text: "Disk".into(), bold: true, ..Default::default() }))));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::ListItem { spans, .. } if spans[0].text == "rotate logs")));
+
+        let bytes = docx::render(&doc).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+
+        // The timeline links the P0/P1 incident to its breakdown heading
+        let anchor = document::incident_anchor("inc-1");
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Table { rows, .. } if rows.iter().flatten().any(|c| c.link.as_ref() == Some(&anchor)))));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Heading { level: 2, anchor: Some(a), .. } if *a == anchor)));
+        let html = String::from_utf8(html::render(&doc).unwrap()).unwrap();
+        assert!(html.contains(&format!("<a href=\"#{}\">Checkout down</a>", anchor)));
+        assert!(html.contains("<svg"));
+        let md = String::from_utf8(gfm::render(&doc).unwrap()).unwrap();
+        assert!(md.contains(&format!("[Checkout down](#{})", anchor)));
+    }
+
+    /// Test: a template controls section order, options and custom placeholder text
+    #[tokio::test]
+    async fn test_template_layout() {
+        use crate::models::report_template::SectionOptions;
+
+        let (_dir, db) = setup_db().await;
+        for (id, svc, minutes) in [("inc-1", "svc-slack", 120), ("inc-2", "svc-zoom", 30)] {
+            sqlx::query(
+                "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) \
+                 VALUES (?, 'Outage', ?, 'High', 'High', 'Resolved', '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', \
+                 strftime('%Y-%m-%dT%H:%M:%SZ', '2026-03-10T10:00:00Z', '+' || ? || ' minutes'))",
+            )
+            .bind(id)
+            .bind(svc)
+            .bind(minutes)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let mut overview = TemplateSection::built_in(ReportSectionKind::MetricsOverview);
+        overview.options = SectionOptions {
+            top_n: None,
+            metrics: Some(vec!["total_incidents".into(), "mttr".into()]),
+            charts: Some(vec![]),
+        };
+        let mut services = TemplateSection::built_in(ReportSectionKind::ServiceReliability);
+        services.options.top_n = Some(1);
+        let config = ReportConfig {
+            period: ReportPeriod::Quarter("fy27-q1".into()),
+            comparison: ComparisonMode::PreviousPeriod,
+            service_ids: vec![],
+            title: "Brief".into(),
+            introduction: String::new(),
+            sections: vec![
+                TemplateSection {
+                    kind: ReportSectionKind::Custom,
+                    title: "{{quarter}} at a glance".into(),
+                    body: "**{{total_incidents}}** incidents, MTTR {{mttr}}.".into(),
+                    options: SectionOptions::default(),
+                },
+                services,
+                overview,
+            ],
+            chart_images: HashMap::new(),
+            format: ReportFormat::Docx,
+            redaction: None,
+        };
+        let data = fetch_report_data(&db, &config).await.unwrap();
+        let doc = build_document(&config, &data);
+
+        let headings: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Heading { level: 1, text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(headings, vec!["FY27 Q1 at a glance", "Service Reliability Summary", "Metrics Overview"]);
+        assert!(matches!(&doc.blocks[1], Block::Paragraph(spans) if spans[0] == Span::bold("2") && spans[1].text == " incidents, MTTR 1h 15m."));
+
+        let tables: Vec<&Vec<Vec<document::Cell>>> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                Block::Table { rows, .. } => Some(rows),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tables[0].len(), 1);
+        let metrics: Vec<&str> = tables[1].iter().map(|row| row[0].text.as_str()).collect();
+        assert_eq!(metrics, vec!["Total Incidents", "MTTR"]);
+        assert!(!doc.blocks.iter().any(|b| matches!(b, Block::Image { .. })));
+    }
+
+    /// Test: a service-scoped date-range report keeps only that service and shows its context
+    #[tokio::test]
+    async fn test_service_scoped_date_range() {
+        let (_dir, db) = setup_db().await;
+        for (id, svc, started) in [
+            ("inc-1", "svc-slack", "2026-03-10T10:00:00Z"),
+            ("inc-2", "svc-zoom", "2026-03-11T10:00:00Z"),
+            ("inc-3", "svc-slack", "2026-02-20T10:00:00Z"),
+        ] {
+            sqlx::query(
+                "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at) \
+                 VALUES (?, 'Outage', ?, 'High', 'High', 'Active', ?, ?)",
+            )
+            .bind(id)
+            .bind(svc)
+            .bind(started)
+            .bind(started)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        sqlx::query("UPDATE services SET runbook = '1. Restart the gateway' WHERE id = 'svc-slack'")
+            .execute(&db)
+            .await
+            .unwrap();
+        service_dependencies::insert_dependency(&db, "dep-1", "svc-slack", "svc-zoom", "runtime")
+            .await
+            .unwrap();
+
+        let mut sections = all_sections();
+        sections.service_context = true;
+        let config = ReportConfig {
+            period: ReportPeriod::DateRange { start: "2026-03-01".into(), end: "2026-03-31".into() },
+            comparison: ComparisonMode::PreviousPeriod,
+            service_ids: vec!["svc-slack".into()],
+            title: "Slack Review".into(),
+            introduction: String::new(),
+            sections: sections.layout(),
+            chart_images: HashMap::new(),
+            format: ReportFormat::Markdown,
+            redaction: None,
+        };
+        let data = fetch_report_data(&db, &config).await.unwrap();
+        let ids: Vec<&str> = data.incidents.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["inc-1"]);
+        assert_eq!(data.prev_total, Some(1));
+
+        let doc = build_document(&config, &data);
+        assert_eq!(doc.subtitle[0], "2026-03-01 to 2026-03-31");
+        assert_eq!(doc.subtitle[2], "Compared with: 2026-01-29 to 2026-02-28");
+        assert_eq!(doc.subtitle[3], "Services: Slack");
+        assert!(matches!(&doc.blocks[0], Block::Heading { text, .. } if text == "Executive Summary"));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Heading { level: 1, text, .. } if text == "Service Context")));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::Table { rows, .. } if rows.iter().any(|r| r[0].text == "Depends on" && r[1].text == "Zoom"))));
+        assert!(doc.blocks.iter().any(|b| matches!(b, Block::ListItem { spans, .. } if spans[0].text == "Restart the gateway")));
+    }
+}
@@ -1,296 +1,303 @@
 //! PDF report generation using genpdf.
 //!
-//! Parallel PDF builder that mirrors the DOCX report structure.
+//! Renders the same report document as the DOCX builder, block for block.
 
-use genpdf::elements::{Break, Paragraph};
-use genpdf::fonts;
-use genpdf::style::Style;
-use genpdf::{Document, Element, SimplePageDecorator};
+use genpdf::elements::{Break, FrameCellDecorator, Image, PageBreak, Paragraph, TableLayout};
+use genpdf::render::Area;
+use genpdf::style::{Color, Style};
+use genpdf::{error::Error, Alignment, Context, Document, Element, Mm, PageDecorator, Position};
 
 use crate::error::{AppError, AppResult};
-use crate::models::incident::{ActionItem, Incident};
-use crate::models::metrics::{format_minutes, format_percentage, QuarterlyTrends};
-use crate::models::quarter::QuarterConfig;
-use crate::reports::ReportConfig;
-
-/// Build a PDF document and return the bytes.
-pub fn build_pdf(
-    config: &ReportConfig,
-    incidents: &[Incident],
-    _prev_incidents: &[Incident],
-    quarter: Option<&QuarterConfig>,
-    mttr: f64,
-    mtta: f64,
-    total_incidents: i64,
-    recurrence_rate: f64,
-    avg_tickets: f64,
-    prev_mttr: Option<f64>,
-    prev_mtta: Option<f64>,
-    prev_total: Option<i64>,
-    prev_recurrence: Option<f64>,
-    prev_tickets: Option<f64>,
-    action_items: &[ActionItem],
-    _trends: &QuarterlyTrends,
-) -> AppResult<Vec<u8>> {
-    // Use built-in Liberation Sans font family (bundled with genpdf)
-    let font_family = fonts::from_files("", "LiberationSans", None)
-        .or_else(|_| {
-            // Fallback: try system fonts on macOS
-            fonts::from_files("/Library/Fonts", "Arial", None)
-        })
-        .or_else(|_| {
-            fonts::from_files("/System/Library/Fonts/Supplemental", "Arial", None)
-        })
-        .map_err(|e| AppError::Report(format!("Failed to load PDF fonts: {}. Install Liberation Sans or Arial.", e)))?;
-
-    let mut doc = Document::new(font_family);
-    doc.set_title(&config.title);
-
-    let mut decorator = SimplePageDecorator::new();
-    decorator.set_margins(20);
-    doc.set_page_decorator(decorator);
-
-    // Title
-    doc.push(
-        Paragraph::new(&config.title)
-            .styled(Style::new().bold().with_font_size(24)),
-    );
+use crate::models::report_theme::{CoverLayout, ReportTheme};
+use crate::reports::charts;
+use crate::reports::document::{Block, Cell, ReportDocument, Span};
+use crate::reports::fonts;
+
+/// Page margin on every side, in millimetres.
+const MARGIN: f64 = 20.0;
+
+/// Render a report document and return the PDF bytes.
+pub fn render(report: &ReportDocument) -> AppResult<Vec<u8>> {
+    let theme = &report.theme;
+    let mut doc = Document::new(fonts::pdf_family(theme.font)?);
+    doc.set_title(&report.title);
+    doc.set_font_size(theme.scaled(12));
+    doc.set_page_decorator(ThemedPages::new(theme));
+
+    if !report.title.is_empty() {
+        push_cover(&mut doc, report)?;
+    }
 
-    if let Some(q) = quarter {
-        doc.push(
-            Paragraph::new(&q.label)
-                .styled(Style::new().with_font_size(14)),
-        );
-        doc.push(
-            Paragraph::new(format!("Period: {} to {}", q.start_date, q.end_date))
-                .styled(Style::new().with_font_size(10)),
-        );
+    for block in &report.blocks {
+        push_block(&mut doc, theme, block)?;
     }
 
-    doc.push(Break::new(1));
+    // Render to bytes
+    let mut buf: Vec<u8> = Vec::new();
+    doc.render(&mut buf)
+        .map_err(|e| AppError::Report(format!("Failed to render PDF: {}", e)))?;
 
-    // Executive Summary
-    if config.sections.executive_summary {
-        push_heading(&mut doc, "Executive Summary");
+    Ok(buf)
+}
 
-        if !config.introduction.is_empty() {
-            doc.push(Paragraph::new(&config.introduction));
-            doc.push(Break::new(0.5));
-        }
+/// Title, period lines, logo and banner in the theme's cover layout.
+fn push_cover(doc: &mut Document, report: &ReportDocument) -> AppResult<()> {
+    let theme = &report.theme;
+    let full_page = theme.cover_layout == CoverLayout::FullPage;
+    let align = if full_page { Alignment::Center } else { Alignment::Left };
+    let primary = primary(theme);
 
-        let summary = format!(
-            "This quarter saw {} total incident(s) with a Mean Time to Resolve (MTTR) of {} and a Mean Time to Acknowledge (MTTA) of {}. The recurrence rate was {}.",
-            total_incidents,
-            if total_incidents == 0 { "N/A".into() } else { format_minutes(mttr) },
-            if total_incidents == 0 { "N/A".into() } else { format_minutes(mtta) },
-            if total_incidents == 0 { "N/A".into() } else { format_percentage(recurrence_rate) },
-        );
-        doc.push(Paragraph::new(summary));
+    if full_page {
+        doc.push(Break::new(6));
+    }
+    let logo = theme.logo()?;
+    if !logo.is_empty() {
+        let height_mm = if full_page { 35.0 } else { 15.0 };
+        doc.push(image(&logo, height_mm)?.with_alignment(align));
         doc.push(Break::new(0.5));
+    }
 
-        let critical_count = incidents.iter().filter(|i| i.severity == "Critical").count();
-        let high_count = incidents.iter().filter(|i| i.severity == "High").count();
-        let p0_count = incidents.iter().filter(|i| i.priority == "P0").count();
-        let p1_count = incidents.iter().filter(|i| i.priority == "P1").count();
-        let resolved_count = incidents.iter().filter(|i| i.status == "Resolved" || i.status == "Post-Mortem").count();
-
-        doc.push(bullet(&format!("{} Critical and {} High severity incidents", critical_count, high_count)));
-        doc.push(bullet(&format!("{} P0 and {} P1 priority incidents", p0_count, p1_count)));
-        doc.push(bullet(&format!("{} of {} incidents resolved", resolved_count, total_incidents)));
-
-        let recurring_count = incidents.iter().filter(|i| i.is_recurring).count();
-        if recurring_count > 0 {
-            doc.push(bullet(&format!("{} recurring incident(s) detected", recurring_count)));
-        }
-
+    let title_size = if full_page { 28 } else { 24 };
+    doc.push(
+        Paragraph::new(&report.title)
+            .aligned(align)
+            .styled(Style::new().bold().with_font_size(theme.scaled(title_size)).with_color(primary)),
+    );
+    for (i, line) in report.subtitle.iter().enumerate() {
+        let size = if i == 0 { 14 } else { 10 };
+        doc.push(Paragraph::new(line.as_str()).aligned(align).styled(Style::new().with_font_size(theme.scaled(size))));
+    }
+    if full_page && !theme.confidentiality_banner.trim().is_empty() {
         doc.push(Break::new(1));
+        doc.push(
+            Paragraph::new(theme.confidentiality_banner.trim())
+                .aligned(align)
+                .styled(Style::new().bold().with_color(primary)),
+        );
     }
-
-    // Metrics Overview
-    if config.sections.metrics_overview {
-        push_heading(&mut doc, "Metrics Overview");
-
-        push_metric_row(&mut doc, "Total Incidents", &total_incidents.to_string(), prev_total.map(|v| v.to_string()).as_deref());
-        push_metric_row(&mut doc, "MTTR", &format_minutes(mttr), prev_mttr.map(|v| format_minutes(v)).as_deref());
-        push_metric_row(&mut doc, "MTTA", &format_minutes(mtta), prev_mtta.map(|v| format_minutes(v)).as_deref());
-        push_metric_row(&mut doc, "Recurrence Rate", &format_percentage(recurrence_rate), prev_recurrence.map(|v| format_percentage(v)).as_deref());
-        push_metric_row(&mut doc, "Avg Tickets/Incident", &format!("{:.1}", avg_tickets), prev_tickets.map(|v| format!("{:.1}", v)).as_deref());
-
+    if full_page {
+        doc.push(PageBreak::new());
+    } else {
         doc.push(Break::new(1));
     }
+    Ok(())
+}
 
-    // Incident Timeline
-    if config.sections.incident_timeline {
-        push_heading(&mut doc, "Incident Timeline");
-
-        if incidents.is_empty() {
-            doc.push(Paragraph::new("No incidents recorded this quarter."));
-        } else {
-            for incident in incidents {
-                let duration = incident.duration_minutes
-                    .map(|d| format_minutes(d as f64))
-                    .unwrap_or_else(|| "Ongoing".to_string());
+fn primary(theme: &ReportTheme) -> Color {
+    let (r, g, b) = theme.primary_rgb();
+    Color::Rgb(r, g, b)
+}
 
-                doc.push(
-                    Paragraph::new(format!(
-                        "[{}] {} - {} ({})",
-                        incident.priority, incident.title, incident.service_name, duration
-                    )).styled(Style::new().bold().with_font_size(10)),
-                );
+fn push_block(doc: &mut Document, theme: &ReportTheme, block: &Block) -> AppResult<()> {
+    match block {
+        Block::Heading { level, text, .. } => {
+            let size = match level {
+                1 => 16,
+                2 => 13,
+                3 => 12,
+                _ => 11,
+            };
+            doc.push(
+                Paragraph::new(text.as_str())
+                    .styled(Style::new().bold().with_font_size(theme.scaled(size)).with_color(primary(theme))),
+            );
+            if *level == 1 {
+                doc.push(Break::new(0.3));
             }
         }
-
-        doc.push(Break::new(1));
-    }
-
-    // Critical Incident Breakdowns
-    if config.sections.incident_breakdowns {
-        push_heading(&mut doc, "Critical Incident Breakdowns");
-
-        let critical: Vec<&Incident> = incidents.iter()
-            .filter(|i| i.priority == "P0" || i.priority == "P1")
-            .collect();
-
-        if critical.is_empty() {
-            doc.push(Paragraph::new("No P0 or P1 incidents this quarter."));
-        } else {
-            for incident in &critical {
-                doc.push(
-                    Paragraph::new(format!(
-                        "[{}] {} - {}",
-                        incident.priority, incident.title, incident.service_name
-                    )).styled(Style::new().bold().with_font_size(12)),
-                );
-
-                let duration = incident.duration_minutes
-                    .map(|d| format_minutes(d as f64))
-                    .unwrap_or_else(|| "Ongoing".to_string());
-
-                doc.push(Paragraph::new(format!("Severity: {} | Impact: {} | Duration: {}", incident.severity, incident.impact, duration)));
-
-                if !incident.root_cause.is_empty() {
-                    doc.push(Paragraph::new("Root Cause:").styled(Style::new().bold()));
-                    push_markdown_text(&mut doc, &incident.root_cause);
-                }
-                if !incident.resolution.is_empty() {
-                    doc.push(Paragraph::new("Resolution:").styled(Style::new().bold()));
-                    push_markdown_text(&mut doc, &incident.resolution);
-                }
-                if !incident.lessons_learned.is_empty() {
-                    doc.push(Paragraph::new("Lessons Learned:").styled(Style::new().bold()));
-                    push_markdown_text(&mut doc, &incident.lessons_learned);
-                }
-
-                doc.push(Break::new(0.5));
+        Block::Paragraph(spans) => doc.push(spans_paragraph(theme, Paragraph::default(), spans)),
+        Block::ListItem { number, spans } => {
+            let prefix = match number {
+                Some(n) => format!("{}. ", n),
+                None => "\u{2022}  ".to_string(),
+            };
+            doc.push(spans_paragraph(theme, Paragraph::new(prefix), spans));
+        }
+        Block::Code(lines) => {
+            for line in lines {
+                doc.push(Paragraph::new(line.as_str()).styled(code_style(theme)));
             }
         }
-
-        doc.push(Break::new(1));
+        Block::Table { headers, rows } => doc.push(table(theme, headers, rows)?),
+        Block::Image { caption, png, .. } => {
+            doc.push(Paragraph::new(caption.as_str()).styled(Style::new().bold()));
+            doc.push(chart(png)?);
+        }
+        Block::Spacer => doc.push(Break::new(0.5)),
     }
+    Ok(())
+}
 
-    // Service Reliability
-    if config.sections.service_reliability {
-        push_heading(&mut doc, "Service Reliability");
+fn code_style(theme: &ReportTheme) -> Style {
+    Style::new().with_font_size(theme.scaled(9)).with_color(Color::Greyscale(60))
+}
 
-        let mut service_counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
-        for incident in incidents {
-            *service_counts.entry(&incident.service_name).or_default() += 1;
+fn spans_paragraph(theme: &ReportTheme, mut para: Paragraph, spans: &[Span]) -> Paragraph {
+    for span in spans {
+        let mut style = if span.code { code_style(theme) } else { Style::new() };
+        if span.bold {
+            style = style.bold();
         }
-
-        let mut sorted: Vec<_> = service_counts.into_iter().collect();
-        sorted.sort_by(|a, b| b.1.cmp(&a.1));
-
-        for (service, count) in &sorted {
-            doc.push(Paragraph::new(format!("{}: {} incident(s)", service, count)));
+        if span.italic {
+            style = style.italic();
         }
-
-        doc.push(Break::new(1));
+        para.push_styled(span.text.as_str(), style);
     }
+    para
+}
 
-    // Action Items
-    if config.sections.action_items {
-        push_heading(&mut doc, "Action Items");
-
-        if action_items.is_empty() {
-            doc.push(Paragraph::new("No action items."));
-        } else {
-            for item in action_items {
-                let status_icon = if item.status == "done" { "[x]" } else { "[ ]" };
-                doc.push(Paragraph::new(format!(
-                    "{} {} (Owner: {}, Due: {})",
-                    status_icon,
-                    item.title,
-                    if item.owner.is_empty() { "Unassigned" } else { &item.owner },
-                    item.due_date.as_deref().unwrap_or("N/A"),
-                )));
-            }
+/// A table framed in the accent colour. Cells cannot be shaded in genpdf,
+/// so the header row is set apart with bold text in the primary colour.
+fn table(theme: &ReportTheme, headers: &[String], rows: &[Vec<Cell>]) -> AppResult<impl Element> {
+    let mut table = TableLayout::new(vec![1; headers.len().max(1)]);
+    table.set_cell_decorator(FrameCellDecorator::new(true, true, false));
+
+    let size = theme.scaled(9);
+    let cell = |text: &str, style: Style| Paragraph::new(text).styled(style.with_font_size(size)).padded(1);
+    let mut header = table.row();
+    for h in headers {
+        header.push_element(cell(h, Style::new().bold().with_color(primary(theme))));
+    }
+    header
+        .push()
+        .map_err(|e| AppError::Report(format!("Failed to lay out PDF table: {}", e)))?;
+
+    for row in rows {
+        let mut table_row = table.row();
+        for value in row {
+            table_row.push_element(cell(&value.text, Style::new().with_color(Color::Greyscale(0))));
         }
-
-        doc.push(Break::new(1));
+        table_row
+            .push()
+            .map_err(|e| AppError::Report(format!("Failed to lay out PDF table: {}", e)))?;
     }
+    let (r, g, b) = theme.accent_rgb();
+    Ok(table.styled(Style::new().with_color(Color::Rgb(r, g, b))))
+}
 
-    // Render to bytes
-    let mut buf: Vec<u8> = Vec::new();
-    doc.render(&mut buf)
-        .map_err(|e| AppError::Report(format!("Failed to render PDF: {}", e)))?;
+/// Embed a PNG chart; 960px at 150 dpi is ~16cm, inside the A4 text width.
+fn chart(png: &[u8]) -> AppResult<Image> {
+    Ok(decode(png)?.0.with_dpi(150.0))
+}
 
-    Ok(buf)
+/// Embed a PNG scaled to the given height.
+fn image(png: &[u8], height_mm: f64) -> AppResult<Image> {
+    let (image, height_px) = decode(png)?;
+    Ok(image.with_dpi(height_px as f64 * 25.4 / height_mm))
 }
 
-fn push_heading(doc: &mut Document, text: &str) {
-    doc.push(
-        Paragraph::new(text)
-            .styled(Style::new().bold().with_font_size(16)),
-    );
-    doc.push(Break::new(0.3));
+/// An image element and its height in pixels.
+fn decode(png: &[u8]) -> AppResult<(Image, u32)> {
+    let (width, height, rgb) = charts::png_to_rgb(png)?;
+    let buffer = image::RgbImage::from_raw(width, height, rgb)
+        .ok_or_else(|| AppError::Report("Invalid image".to_string()))?;
+    Image::from_dynamic_image(image::DynamicImage::ImageRgb8(buffer))
+        .map(|img| (img, height))
+        .map_err(|e| AppError::Report(format!("Failed to embed image: {}", e)))
 }
 
-fn bullet(text: &str) -> Paragraph {
-    Paragraph::new(format!("\u{2022}  {}", text))
+/// Applies the page margins, then prints the banner and header text at the
+/// top of each page and the footer text and page number at the bottom.
+/// A full-page cover is left bare.
+struct ThemedPages {
+    page: usize,
+    banner: String,
+    header: String,
+    footer: String,
+    page_numbers: bool,
+    bare_cover: bool,
+    primary: Color,
+    small_size: u8,
 }
 
-fn push_metric_row(doc: &mut Document, label: &str, current: &str, previous: Option<&str>) {
-    let line = if let Some(prev) = previous {
-        format!("{}: {} (prev: {})", label, current, prev)
-    } else {
-        format!("{}: {}", label, current)
-    };
-    doc.push(Paragraph::new(line));
+impl ThemedPages {
+    fn new(theme: &ReportTheme) -> Self {
+        Self {
+            page: 0,
+            banner: theme.confidentiality_banner.trim().to_string(),
+            header: theme.header_text.trim().to_string(),
+            footer: theme.footer_text.trim().to_string(),
+            page_numbers: theme.page_numbers,
+            bare_cover: theme.cover_layout == CoverLayout::FullPage,
+            primary: primary(theme),
+            small_size: theme.scaled(8),
+        }
+    }
 }
 
-/// Simple markdown text renderer for PDF — strips markdown syntax and renders as plain text.
-/// Full markdown rendering in PDF is limited by genpdf capabilities.
-fn push_markdown_text(doc: &mut Document, md: &str) {
-    use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+impl PageDecorator for ThemedPages {
+    fn decorate_page<'a>(&mut self, context: &Context, mut area: Area<'a>, style: Style) -> Result<Area<'a>, Error> {
+        self.page += 1;
+        area.add_margins(MARGIN);
+        if self.bare_cover && self.page == 1 {
+            return Ok(area);
+        }
 
-    let parser = Parser::new_ext(md.trim(), Options::empty());
-    let mut text_buf = String::new();
-    for event in parser {
-        match event {
-            Event::Text(t) => text_buf.push_str(&t),
-            Event::Code(c) => {
-                text_buf.push('`');
-                text_buf.push_str(&c);
-                text_buf.push('`');
-            }
-            Event::SoftBreak | Event::HardBreak => text_buf.push(' '),
-            Event::Start(Tag::Item) => {
-                if !text_buf.trim().is_empty() {
-                    doc.push(Paragraph::new(text_buf.trim()));
-                    text_buf.clear();
-                }
-                text_buf.push_str("\u{2022}  ");
-            }
-            Event::End(TagEnd::Paragraph) | Event::End(TagEnd::Item) => {
-                if !text_buf.trim().is_empty() {
-                    doc.push(Paragraph::new(text_buf.trim()));
-                }
-                text_buf.clear();
+        let fonts = &context.font_cache;
+        let width = area.size().width;
+        let small = style.with_font_size(self.small_size).with_color(Color::Greyscale(110));
+        let mut top = Mm::from(0.0);
+        if !self.banner.is_empty() {
+            let banner = style.bold().with_font_size(self.small_size + 1).with_color(self.primary);
+            let x = (width - banner.str_width(fonts, &self.banner)) / 2.0;
+            area.print_str(fonts, Position::new(x.max(Mm::from(0.0)), top), banner, &self.banner)?;
+            top += banner.line_height(fonts);
+        }
+        if !self.header.is_empty() {
+            let x = width - small.str_width(fonts, &self.header);
+            area.print_str(fonts, Position::new(x.max(Mm::from(0.0)), top), small, &self.header)?;
+            top += small.line_height(fonts);
+        }
+        if top > Mm::from(0.0) {
+            area.add_offset(Position::new(0.0, top + Mm::from(4.0)));
+        }
+
+        let mut footer = self.footer.clone();
+        if self.page_numbers {
+            if !footer.is_empty() {
+                footer.push_str("  \u{00B7}  ");
             }
-            _ => {}
+            footer.push_str(&format!("Page {}", self.page));
+        }
+        if !footer.is_empty() {
+            // Leave a millimetre below the line so it always fits the page area
+            let y = area.size().height - small.line_height(fonts) - Mm::from(1.0);
+            let x = (width - small.str_width(fonts, &footer)) / 2.0;
+            area.print_str(fonts, Position::new(x.max(Mm::from(0.0)), y), small, &footer)?;
+            area.set_height(y - Mm::from(4.0));
         }
+        Ok(area)
     }
-    if !text_buf.trim().is_empty() {
-        doc.push(Paragraph::new(text_buf.trim()));
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+
+    use super::*;
+    use crate::reports::document::tests::sample_document;
+
+    /// Test: reports render with the bundled fonts in both cover layouts
+    #[test]
+    fn test_render_with_theme() {
+        let mut doc = sample_document();
+        let plain = render(&doc).unwrap();
+        assert!(plain.starts_with(b"%PDF"));
+
+        let logo = resvg::tiny_skia::Pixmap::new(40, 20).unwrap().encode_png().unwrap();
+        doc.theme = ReportTheme {
+            logo_png: Some(base64::engine::general_purpose::STANDARD.encode(logo)),
+            primary_color: "#0A4D8C".into(),
+            font: crate::models::report_theme::ReportFont::Serif,
+            font_size: 13,
+            header_text: "Acme Reliability".into(),
+            footer_text: "Acme Corp".into(),
+            confidentiality_banner: "CONFIDENTIAL".into(),
+            cover_layout: CoverLayout::FullPage,
+            ..Default::default()
+        };
+        let themed = render(&doc).unwrap();
+        assert!(themed.starts_with(b"%PDF"));
+        assert_ne!(plain.len(), themed.len());
     }
 }
@@ -0,0 +1,260 @@
+//! Report periods: a configured quarter, a whole fiscal year or any date
+//! range, plus the comparison period derived from it.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::db::queries::settings;
+use crate::error::{AppError, AppResult};
+use crate::models::quarter::QuarterConfig;
+
+/// Number of quarters in the trend table for reports that are not a fiscal year.
+const TREND_QUARTERS: usize = 4;
+
+/// The span of time a report covers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportPeriod {
+    Quarter(String),
+    FiscalYear(i64),
+    /// Local calendar days `start..=end`, as YYYY-MM-DD.
+    DateRange { start: String, end: String },
+}
+
+/// How the comparison period is derived from the report period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonMode {
+    /// The period of equal length just before (the previous quarter or fiscal year when configured).
+    PreviousPeriod,
+    /// The same dates one year earlier.
+    SamePeriodLastYear,
+    None,
+}
+
+impl ComparisonMode {
+    pub fn parse(mode: &str) -> AppResult<Self> {
+        match mode {
+            "" | "previous_period" => Ok(ComparisonMode::PreviousPeriod),
+            "same_period_last_year" => Ok(ComparisonMode::SamePeriodLastYear),
+            "none" => Ok(ComparisonMode::None),
+            other => Err(AppError::Validation(format!("Unknown comparison mode '{}'", other))),
+        }
+    }
+}
+
+/// A labelled span of local calendar days, inclusive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodSpan {
+    pub label: String,
+    pub start: String,
+    pub end: String,
+}
+
+impl PeriodSpan {
+    fn from_dates(start: NaiveDate, end: NaiveDate) -> Self {
+        let (start, end) = (start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string());
+        Self { label: format!("{} to {}", start, end), start, end }
+    }
+
+    fn from_quarter(quarter: &QuarterConfig) -> Self {
+        Self {
+            label: quarter.label.clone(),
+            start: quarter.start_date.clone(),
+            end: quarter.end_date.clone(),
+        }
+    }
+}
+
+/// A report period resolved against the configured quarters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedPeriod {
+    pub current: PeriodSpan,
+    pub comparison: Option<PeriodSpan>,
+    /// Quarters for the trend table, oldest first: the fiscal year's own
+    /// quarters, or the last few that start by the end of the period.
+    pub trend_quarters: Vec<QuarterConfig>,
+}
+
+pub async fn resolve(
+    db: &SqlitePool,
+    period: &ReportPeriod,
+    mode: ComparisonMode,
+) -> AppResult<ResolvedPeriod> {
+    // Newest first
+    let quarters = settings::get_quarter_configs(db).await?;
+
+    let (current, configured_previous, configured_last_year) = match period {
+        ReportPeriod::Quarter(id) => {
+            let q = quarters
+                .iter()
+                .find(|q| q.id == *id)
+                .ok_or_else(|| AppError::NotFound(format!("Quarter '{}' not found", id)))?;
+            let previous = settings::get_previous_quarter(db, q.fiscal_year, q.quarter_number)
+                .await?
+                .map(|p| PeriodSpan::from_quarter(&p));
+            let last_year = quarters
+                .iter()
+                .find(|p| p.fiscal_year == q.fiscal_year - 1 && p.quarter_number == q.quarter_number)
+                .map(PeriodSpan::from_quarter);
+            (PeriodSpan::from_quarter(q), previous, last_year)
+        }
+        ReportPeriod::FiscalYear(fy) => {
+            let current = fiscal_year_span(&quarters, *fy)
+                .ok_or_else(|| AppError::NotFound(format!("No quarters configured for fiscal year {}", fy)))?;
+            let previous = fiscal_year_span(&quarters, fy - 1);
+            (current, previous.clone(), previous)
+        }
+        ReportPeriod::DateRange { start, end } => {
+            let (start, end) = (parse_date(start)?, parse_date(end)?);
+            if end < start {
+                return Err(AppError::Validation("Report end date must not be before the start date".into()));
+            }
+            (PeriodSpan::from_dates(start, end), None, None)
+        }
+    };
+
+    let (start, end) = (parse_date(&current.start)?, parse_date(&current.end)?);
+    let comparison = match mode {
+        ComparisonMode::PreviousPeriod => Some(configured_previous.unwrap_or_else(|| {
+            let (s, e) = preceding_range(start, end);
+            PeriodSpan::from_dates(s, e)
+        })),
+        ComparisonMode::SamePeriodLastYear => Some(configured_last_year.unwrap_or_else(|| {
+            PeriodSpan::from_dates(one_year_earlier(start), one_year_earlier(end))
+        })),
+        ComparisonMode::None => None,
+    };
+
+    let mut trend_quarters: Vec<QuarterConfig> = match period {
+        ReportPeriod::FiscalYear(fy) => quarters.into_iter().filter(|q| q.fiscal_year == *fy).collect(),
+        _ => quarters
+            .into_iter()
+            .filter(|q| q.start_date.as_str() <= current.end.as_str())
+            .take(TREND_QUARTERS)
+            .collect(),
+    };
+    trend_quarters.reverse();
+
+    Ok(ResolvedPeriod { current, comparison, trend_quarters })
+}
+
+/// The span from the first to the last configured quarter of a fiscal year.
+fn fiscal_year_span(quarters: &[QuarterConfig], fiscal_year: i64) -> Option<PeriodSpan> {
+    let in_year: Vec<&QuarterConfig> = quarters.iter().filter(|q| q.fiscal_year == fiscal_year).collect();
+    let start = in_year.iter().map(|q| q.start_date.as_str()).min()?;
+    let end = in_year.iter().map(|q| q.end_date.as_str()).max()?;
+    // Quarter labels read "FY27 Q1"; the year label is the part before the quarter
+    let label = in_year
+        .iter()
+        .find_map(|q| q.label.split_whitespace().find(|w| w.starts_with("FY")))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("FY{}", fiscal_year));
+    Some(PeriodSpan { label, start: start.to_string(), end: end.to_string() })
+}
+
+fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10).unwrap_or(value), "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("Invalid date: {}", value)))
+}
+
+/// The range of equal length ending the day before `start`.
+fn preceding_range(start: NaiveDate, end: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let days = (end - start).num_days();
+    let prev_end = start - Duration::days(1);
+    (prev_end - Duration::days(days), prev_end)
+}
+
+/// The same calendar date a year earlier; 29 February becomes the 28th.
+fn one_year_earlier(date: NaiveDate) -> NaiveDate {
+    date.with_year(date.year() - 1)
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(date.year() - 1, 2, 28).expect("valid date"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("report-period-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    /// Test: the preceding range has the same length and leap days fall back to 28 February
+    #[test]
+    fn test_derived_ranges() {
+        assert_eq!(
+            preceding_range(date("2026-03-01"), date("2026-03-31")),
+            (date("2026-01-29"), date("2026-02-28"))
+        );
+        assert_eq!(one_year_earlier(date("2028-02-29")), date("2027-02-28"));
+        assert_eq!(one_year_earlier(date("2026-10-18")), date("2025-10-18"));
+    }
+
+    /// Test: quarters compare with the configured previous quarter, falling back to derived dates
+    #[tokio::test]
+    async fn test_quarter_comparison() {
+        let (_dir, db) = setup_db().await;
+        let period = ReportPeriod::Quarter("fy27-q2".into());
+        let resolved = resolve(&db, &period, ComparisonMode::PreviousPeriod).await.unwrap();
+        assert_eq!(resolved.current.label, "FY27 Q2");
+        assert_eq!(resolved.comparison.unwrap().label, "FY27 Q1");
+        let labels: Vec<&str> = resolved.trend_quarters.iter().map(|q| q.label.as_str()).collect();
+        assert_eq!(labels, vec!["FY27 Q1", "FY27 Q2"]);
+
+        // No FY26 quarters are configured, so last year is derived from the dates
+        let resolved = resolve(&db, &period, ComparisonMode::SamePeriodLastYear).await.unwrap();
+        let comparison = resolved.comparison.unwrap();
+        assert_eq!((comparison.start.as_str(), comparison.end.as_str()), ("2025-05-01", "2025-07-31"));
+
+        let resolved = resolve(&db, &period, ComparisonMode::None).await.unwrap();
+        assert!(resolved.comparison.is_none());
+    }
+
+    /// Test: fiscal years span their quarters and date ranges compare with the preceding range
+    #[tokio::test]
+    async fn test_fiscal_year_and_date_range() {
+        let (_dir, db) = setup_db().await;
+        let resolved = resolve(&db, &ReportPeriod::FiscalYear(2027), ComparisonMode::PreviousPeriod)
+            .await
+            .unwrap();
+        assert_eq!(resolved.current, PeriodSpan {
+            label: "FY27".into(),
+            start: "2026-02-02".into(),
+            end: "2027-01-31".into(),
+        });
+        assert_eq!(resolved.comparison.unwrap().end, "2026-02-01");
+        assert_eq!(resolved.trend_quarters.len(), 4);
+
+        let range = ReportPeriod::DateRange { start: "2026-03-01".into(), end: "2026-03-10".into() };
+        let resolved = resolve(&db, &range, ComparisonMode::PreviousPeriod).await.unwrap();
+        assert_eq!(resolved.current.label, "2026-03-01 to 2026-03-10");
+        assert_eq!(resolved.comparison.unwrap().label, "2026-02-19 to 2026-02-28");
+        assert_eq!(resolved.trend_quarters.len(), 1);
+
+        assert!(resolve(&db, &ReportPeriod::FiscalYear(2030), ComparisonMode::None).await.is_err());
+        let backwards = ReportPeriod::DateRange { start: "2026-03-10".into(), end: "2026-03-01".into() };
+        assert!(resolve(&db, &backwards, ComparisonMode::None).await.is_err());
+    }
+}
@@ -0,0 +1,513 @@
+//! Redaction for reports shared outside the team. Collected data is redacted
+//! before charts and sections are built, then every string in the finished
+//! document is passed through the same rules, so free text that mentions a
+//! person, service or masked pattern is caught as well.
+
+use std::sync::OnceLock;
+
+use regex::{NoExpand, Regex};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::commands::attachments::Attachment;
+use crate::db::queries::{redaction, services};
+use crate::error::AppResult;
+use crate::models::incident::{ActionItem, Incident};
+use crate::models::metrics::{CategoryCount, DashboardData};
+use crate::models::redaction::{PeopleRedaction, RedactionProfile};
+use crate::reports::document::{Block, ReportDocument};
+use crate::reports::ReportData;
+
+/// Replacement for masked text and for names without an alias.
+pub const MASK: &str = "[redacted]";
+
+/// Category shown for services that have none.
+const UNCATEGORIZED: &str = "Service";
+
+/// A redaction profile with the names it replaces. Stored in report
+/// snapshots so a regenerated report is redacted the same way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Redactor {
+    pub profile: RedactionProfile,
+    /// Known people and service owners, each with its alias.
+    people: Vec<(String, String)>,
+    /// Service names with their categories.
+    services: Vec<(String, String)>,
+    #[serde(skip)]
+    rules: OnceLock<Vec<(Regex, String)>>,
+}
+
+impl Redactor {
+    /// Validate the profile and load the people and services it applies to.
+    pub async fn load(db: &SqlitePool, profile: &RedactionProfile) -> AppResult<Self> {
+        profile.validate()?;
+
+        let mut people: Vec<(String, String)> = redaction::list_people(db)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name, format!("Person {}", i + 1)))
+            .collect();
+
+        let all_services = services::list_all_services(db).await?;
+        let mut teams: Vec<&str> = all_services
+            .iter()
+            .map(|s| s.owner.trim())
+            .filter(|o| !o.is_empty())
+            .collect();
+        teams.sort_by_key(|t| t.to_lowercase());
+        teams.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        for (i, team) in teams.into_iter().enumerate() {
+            if !people.iter().any(|(n, _)| n.eq_ignore_ascii_case(team)) {
+                people.push((team.to_string(), format!("Team {}", i + 1)));
+            }
+        }
+
+        let services = all_services
+            .iter()
+            .map(|s| {
+                let category = match s.category.trim() {
+                    "" => UNCATEGORIZED.to_string(),
+                    c => c.to_string(),
+                };
+                (s.name.clone(), category)
+            })
+            .collect();
+
+        Ok(Self {
+            profile: profile.clone(),
+            people,
+            services,
+            rules: OnceLock::new(),
+        })
+    }
+
+    /// A person's or owning team's name as it should appear.
+    pub fn person(&self, name: &str) -> String {
+        let name = name.trim();
+        if name.is_empty() {
+            return String::new();
+        }
+        match self.profile.people {
+            PeopleRedaction::Keep => self.text(name),
+            PeopleRedaction::Drop => MASK.to_string(),
+            PeopleRedaction::Pseudonymize => self
+                .people
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, alias)| alias.clone())
+                .unwrap_or_else(|| MASK.to_string()),
+        }
+    }
+
+    /// A service's name, or its category when services are generalized.
+    pub fn service(&self, name: &str) -> String {
+        if !self.profile.generalize_services {
+            return self.text(name);
+        }
+        self.services
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name.trim()))
+            .map(|(_, category)| category.clone())
+            .unwrap_or_else(|| UNCATEGORIZED.to_string())
+    }
+
+    /// Free text with masked patterns, service names and people's names replaced.
+    pub fn text(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for (pattern, replacement) in self.rules() {
+            if pattern.is_match(&out) {
+                out = pattern.replace_all(&out, NoExpand(replacement)).into_owned();
+            }
+        }
+        out
+    }
+
+    /// Tags that are not hidden, with their text redacted.
+    pub fn tags(&self, tags: &[String]) -> Vec<String> {
+        tags.iter()
+            .filter(|t| !self.profile.hides_tag(t))
+            .map(|t| self.text(t))
+            .collect()
+    }
+
+    pub fn incident(&self, incident: &mut Incident) {
+        incident.service_name = self.service(&incident.service_name);
+        if self.profile.strip_notes {
+            incident.notes.clear();
+            incident.external_ref.clear();
+        }
+        for field in [
+            &mut incident.title,
+            &mut incident.root_cause,
+            &mut incident.resolution,
+            &mut incident.lessons_learned,
+            &mut incident.action_items,
+            &mut incident.notes,
+            &mut incident.external_ref,
+        ] {
+            *field = self.text(field);
+        }
+    }
+
+    pub fn action_item(&self, item: &mut ActionItem) {
+        item.owner = self.person(&item.owner);
+        if self.profile.strip_notes {
+            item.outcome_notes.clear();
+        }
+        for field in [&mut item.title, &mut item.description, &mut item.outcome_notes] {
+            *field = self.text(field);
+        }
+        if let Some(title) = item.incident_title.as_mut() {
+            *title = self.text(title);
+        }
+    }
+
+    /// Whether attachment listings are left out entirely.
+    pub fn withholds_attachments(&self) -> bool {
+        self.profile.strip_notes
+    }
+
+    /// Attachments an output may list: none when notes are stripped,
+    /// otherwise with their filenames redacted.
+    pub fn attachments(&self, attachments: &mut Vec<Attachment>) {
+        if self.withholds_attachments() {
+            attachments.clear();
+        }
+        for attachment in attachments.iter_mut() {
+            attachment.filename = self.text(&attachment.filename);
+            attachment.file_path = self.text(&attachment.file_path);
+        }
+    }
+
+    /// Redact the service breakdown the report charts are drawn from,
+    /// merging services that share a category.
+    pub(super) fn dashboard(&self, dashboard: &mut DashboardData) {
+        let mut merged: Vec<CategoryCount> = Vec::new();
+        for count in dashboard.by_service.drain(..) {
+            let name = self.service(&count.category);
+            match merged.iter_mut().find(|m| m.category == name) {
+                Some(m) => {
+                    m.count += count.count;
+                    m.previous_count = match (m.previous_count, count.previous_count) {
+                        (None, None) => None,
+                        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+                    };
+                }
+                None => merged.push(CategoryCount { category: name, ..count }),
+            }
+        }
+        merged.sort_by_key(|m| std::cmp::Reverse(m.count));
+        dashboard.by_service = merged;
+    }
+
+    pub(super) fn report_data(&self, data: &mut ReportData) {
+        for incident in data.incidents.iter_mut().chain(data.prev_incidents.iter_mut()) {
+            self.incident(incident);
+        }
+        for item in &mut data.action_items_all {
+            self.action_item(item);
+        }
+        for ctx in &mut data.services {
+            let service = &mut ctx.service;
+            service.name = self.service(&service.name);
+            service.owner = self.person(&service.owner);
+            if self.profile.strip_notes {
+                service.runbook.clear();
+            }
+            service.description = self.text(&service.description);
+            service.runbook = self.text(&service.runbook);
+            for dep in ctx.dependencies.iter_mut().chain(ctx.dependents.iter_mut()) {
+                if let Some(name) = dep.depends_on_service_name.as_mut() {
+                    *name = self.service(name);
+                }
+            }
+        }
+        for cost in &mut data.incident_costs {
+            cost.service_name = self.service(&cost.service_name);
+            cost.title = self.text(&cost.title);
+            cost.override_note = self.text(&cost.override_note);
+        }
+        if let Some(load) = data.responder_load.as_mut() {
+            for responder in &mut load.responders {
+                responder.label = self.person(&responder.label);
+                responder.key = responder.label.clone();
+            }
+            for team in &mut load.teams {
+                team.label = self.text(&team.label);
+                team.key = team.label.clone();
+            }
+            for service in &mut load.services {
+                service.label = self.service(&service.label);
+            }
+        }
+        if let Some(outlook) = data.outlook.as_mut() {
+            for series in outlook.volume.by_service.iter_mut().chain(outlook.mttr.by_service.iter_mut()) {
+                series.label = self.service(&series.label);
+            }
+        }
+    }
+
+    /// Pass every string in the document through `text`.
+    pub fn document(&self, doc: &mut ReportDocument) {
+        doc.title = self.text(&doc.title);
+        for line in &mut doc.subtitle {
+            *line = self.text(line);
+        }
+        for block in &mut doc.blocks {
+            match block {
+                Block::Heading { text, .. } => *text = self.text(text),
+                Block::Paragraph(spans) | Block::ListItem { spans, .. } => {
+                    for span in spans {
+                        span.text = self.text(&span.text);
+                    }
+                }
+                Block::Code(lines) => {
+                    for line in lines {
+                        *line = self.text(line);
+                    }
+                }
+                Block::Table { headers, rows } => {
+                    for header in headers {
+                        *header = self.text(header);
+                    }
+                    for cell in rows.iter_mut().flatten() {
+                        cell.text = self.text(&cell.text);
+                    }
+                }
+                Block::Image { caption, .. } => *caption = self.text(caption),
+                Block::Spacer => {}
+            }
+        }
+    }
+
+    /// Replacement rules in the order they apply: masks first so emails and
+    /// hostnames go whole, then services and people, longest names first.
+    fn rules(&self) -> &[(Regex, String)] {
+        self.rules.get_or_init(|| {
+            // Patterns were validated by `load`; a tampered snapshot's bad pattern is skipped
+            let mut rules: Vec<(Regex, String)> = self
+                .profile
+                .mask_patterns
+                .iter()
+                .filter_map(|p| Regex::new(p).ok())
+                .map(|re| (re, MASK.to_string()))
+                .collect();
+
+            let mut names: Vec<(&str, String)> = Vec::new();
+            if self.profile.generalize_services {
+                names.extend(self.services.iter().map(|(n, c)| (n.as_str(), c.clone())));
+            }
+            if self.profile.people != PeopleRedaction::Keep {
+                names.extend(self.people.iter().map(|(n, alias)| {
+                    let replacement = match self.profile.people {
+                        PeopleRedaction::Drop => MASK.to_string(),
+                        _ => alias.clone(),
+                    };
+                    (n.as_str(), replacement)
+                }));
+            }
+            names.sort_by_key(|(n, _)| std::cmp::Reverse(n.len()));
+            rules.extend(names.into_iter().filter_map(|(name, replacement)| {
+                name_pattern(name).map(|re| (re, replacement))
+            }));
+            rules
+        })
+    }
+}
+
+/// Case-insensitive match of a whole name, bounded by non-word characters.
+fn name_pattern(name: &str) -> Option<Regex> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if word(name.chars().next()) { r"\b" } else { "" };
+    let end = if word(name.chars().last()) { r"\b" } else { "" };
+    Regex::new(&format!("(?i){}{}{}", start, regex::escape(name), end)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::reports::period::{ComparisonMode, ReportPeriod};
+    use crate::reports::{build_document, fetch_report_data, gfm, html, ReportConfig, ReportFormat, ReportSections};
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("redaction-report-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    fn external_profile() -> RedactionProfile {
+        RedactionProfile {
+            people: PeopleRedaction::Pseudonymize,
+            hidden_custom_fields: vec![],
+            hidden_tags: vec![],
+            generalize_services: true,
+            strip_notes: true,
+            mask_patterns: vec![
+                r"[\w.+-]+@[\w-]+(\.[\w-]+)+".into(),
+                r"\b\d{1,3}(\.\d{1,3}){3}\b".into(),
+                r"\b[a-z0-9-]+(\.[a-z0-9-]+)*\.internal\b".into(),
+            ],
+        }
+    }
+
+    /// Test: names are aliased consistently, services generalize and patterns mask in free text
+    #[tokio::test]
+    async fn test_text_rules() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query("UPDATE services SET owner = 'Platform Team' WHERE id = 'svc-slack'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let redactor = Redactor::load(&db, &external_profile()).await.unwrap();
+
+        assert_eq!(redactor.person("platform team"), "Team 1");
+        assert_eq!(redactor.person("Mallory"), MASK);
+        assert_eq!(redactor.service("Slack"), "Communication");
+        assert_eq!(
+            redactor.text("Slack on db1.internal (10.0.0.7) paged Platform Team, not Slacker"),
+            "Communication on [redacted] ([redacted]) paged Team 1, not Slacker"
+        );
+
+        let dropped = RedactionProfile { people: PeopleRedaction::Drop, ..Default::default() };
+        let redactor = Redactor::load(&db, &dropped).await.unwrap();
+        assert_eq!(redactor.person("Platform Team"), MASK);
+        assert_eq!(redactor.service("Slack"), "Slack");
+    }
+
+    /// Test: stripping notes withholds attachments, otherwise their filenames are masked
+    #[tokio::test]
+    async fn test_attachment_rules() {
+        let (_dir, db) = setup_db().await;
+        let attachment = Attachment {
+            id: "att-1".into(),
+            incident_id: "inc-1".into(),
+            filename: "db01.internal-disk.png".into(),
+            file_path: "/data/att-1/db01.internal-disk.png".into(),
+            mime_type: "image/png".into(),
+            size_bytes: 2048,
+            created_at: "2026-03-10T10:00:00Z".into(),
+        };
+
+        let redactor = Redactor::load(&db, &external_profile()).await.unwrap();
+        assert!(redactor.withholds_attachments());
+        let mut attachments = vec![attachment.clone()];
+        redactor.attachments(&mut attachments);
+        assert!(attachments.is_empty());
+
+        let masked = RedactionProfile { strip_notes: false, ..external_profile() };
+        let redactor = Redactor::load(&db, &masked).await.unwrap();
+        assert!(!redactor.withholds_attachments());
+        let mut attachments = vec![attachment];
+        redactor.attachments(&mut attachments);
+        assert_eq!(attachments[0].filename, "[redacted]-disk.png");
+        assert!(!attachments[0].file_path.contains("db01"));
+    }
+
+    /// Test: a redacted report leaks no names, runbooks or masked patterns in any output
+    #[tokio::test]
+    async fn test_report_leaks_no_secrets() {
+        let (_dir, db) = setup_db().await;
+        sqlx::query(
+            "UPDATE services SET owner = 'Platform Team', runbook = 'SECRET-RUNBOOK', \
+             description = 'Chat run by Alice Smith' WHERE id = 'svc-slack'",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, \
+             resolved_at, root_cause, resolution, lessons_learned, notes, external_ref) \
+             VALUES ('inc-1', 'Login failures for alice@example.com', 'svc-slack', 'Critical', 'Critical', 'Resolved', \
+             '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', '2026-03-10T12:00:00Z', \
+             'db01.internal at 10.1.2.3 ran out of disk', 'Alice Smith paged Platform Team', \
+             'Slack alerts were noisy', 'SECRET-NOTE', 'JIRA-123')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO incident_roles (id, incident_id, role, assignee) \
+             VALUES ('r1', 'inc-1', 'Incident Commander', 'Alice Smith')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO action_items (id, incident_id, title, status, owner, outcome_notes) \
+             VALUES ('a1', 'inc-1', 'Rotate logs on db01.internal', 'Open', 'Alice Smith', 'SECRET-OUTCOME')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let sections = ReportSections {
+            executive_summary: true,
+            service_context: true,
+            metrics_overview: true,
+            incident_timeline: true,
+            incident_breakdowns: true,
+            service_reliability: true,
+            qoq_comparison: true,
+            discussion_points: true,
+            action_items: true,
+            cost_impact: true,
+            outlook: true,
+            responder_load: true,
+            checklist_compliance: true,
+        };
+        let config = ReportConfig {
+            period: ReportPeriod::Quarter("fy27-q1".into()),
+            comparison: ComparisonMode::PreviousPeriod,
+            service_ids: vec!["svc-slack".into()],
+            title: "Review for Platform Team".into(),
+            introduction: "Prepared from Slack data".into(),
+            sections: sections.layout(),
+            chart_images: HashMap::new(),
+            format: ReportFormat::Html,
+            redaction: Some(external_profile()),
+        };
+        let data = fetch_report_data(&db, &config).await.unwrap();
+        let doc = build_document(&config, &data);
+        let md = String::from_utf8(gfm::render(&doc).unwrap()).unwrap();
+        let html = String::from_utf8(html::render(&doc).unwrap()).unwrap();
+
+        for secret in [
+            "Alice",
+            "alice@example.com",
+            "db01",
+            "10.1.2.3",
+            "Platform Team",
+            "SECRET-RUNBOOK",
+            "Slack",
+        ] {
+            assert!(!md.contains(secret), "markdown leaks {}", secret);
+            assert!(!html.contains(secret), "html leaks {}", secret);
+        }
+        assert!(md.contains("Person 1"));
+        assert!(md.contains("Team 1"));
+        assert!(md.contains("Communication"));
+        assert!(md.contains("redacted"));
+    }
+}
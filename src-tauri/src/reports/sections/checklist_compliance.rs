@@ -0,0 +1,85 @@
+use crate::models::checklist::{ChecklistCompliance, ComplianceGroup, ComplianceStats};
+use crate::models::metrics::format_minutes;
+use crate::reports::document::ReportDocument;
+
+pub fn build(doc: &mut ReportDocument, report: &ChecklistCompliance) {
+    doc.heading1("Checklist Compliance");
+
+    if report.overall.item_count == 0 {
+        doc.text("No template checklists were used this period.");
+        doc.spacer();
+        return;
+    }
+
+    doc.text(&overview(report));
+    let skipped = skipped_items(report);
+    if !skipped.is_empty() {
+        doc.text("Items most often left unchecked at resolution:");
+        for item in &skipped {
+            doc.bullet(item);
+        }
+    }
+    doc.spacer();
+
+    for (title, label, groups) in [
+        ("By Template", "Template", &report.templates),
+        ("By Service", "Service", &report.by_service),
+        ("By Severity", "Severity", &report.by_severity),
+    ] {
+        doc.heading2(title);
+        doc.table(
+            &[label, "Checklists", "Completion", "Unchecked at Resolution", "Median Time to Check"],
+            group_rows(groups),
+        );
+        doc.spacer();
+    }
+}
+
+fn median_text(stats: &ComplianceStats) -> String {
+    stats
+        .median_minutes_to_check
+        .map(format_minutes)
+        .unwrap_or_else(|| "\u{2014}".to_string())
+}
+
+pub fn overview(report: &ChecklistCompliance) -> String {
+    let overall = &report.overall;
+    format!(
+        "{} checklist(s) from {} template(s): {:.0}% of {} item(s) were checked, with a median of {} from incident start. {} item(s) on resolved incidents ({:.0}%) were still unchecked at resolution.",
+        overall.checklist_count,
+        report.templates.len(),
+        overall.completion_rate,
+        overall.item_count,
+        median_text(overall),
+        overall.unchecked_at_resolution,
+        overall.skip_rate
+    )
+}
+
+pub fn skipped_items(report: &ChecklistCompliance) -> Vec<String> {
+    report
+        .most_skipped
+        .iter()
+        .map(|item| {
+            format!(
+                "{} ({}): unchecked at resolution {} time(s), {:.0}% of resolved incidents",
+                item.label, item.template_name, item.stats.unchecked_at_resolution, item.stats.skip_rate
+            )
+        })
+        .collect()
+}
+
+pub fn group_rows(groups: &[ComplianceGroup]) -> Vec<Vec<String>> {
+    groups
+        .iter()
+        .map(|g| {
+            vec![
+                g.label.clone(),
+                g.stats.checklist_count.to_string(),
+                format!("{:.0}%", g.stats.completion_rate),
+                format!("{} ({:.0}%)", g.stats.unchecked_at_resolution, g.stats.skip_rate),
+                median_text(&g.stats),
+            ]
+        })
+        .collect()
+}
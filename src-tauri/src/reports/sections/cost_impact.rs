@@ -0,0 +1,97 @@
+use crate::models::cost::{format_currency, summarize_costs, CostModel, IncidentCost};
+use crate::reports::document::ReportDocument;
+
+/// Costly incidents listed when the template sets no limit.
+pub const TOP_INCIDENTS: usize = 5;
+
+pub fn build(doc: &mut ReportDocument, costs: &[IncidentCost], model: &CostModel, top_n: usize) {
+    doc.heading1("Cost & Business Impact");
+
+    if costs.is_empty() {
+        doc.text("No incidents recorded for this period.");
+        doc.spacer();
+        return;
+    }
+
+    let summary = summarize_costs(costs, &model.currency);
+    let currency = model.currency.as_str();
+
+    let mut overview = format!(
+        "Estimated total cost: {} across {} incident(s).",
+        format_currency(summary.total_cost, currency),
+        summary.incident_count
+    );
+    if summary.overridden_count > 0 {
+        overview.push_str(&format!(
+            " {} incident(s) use a manually entered cost.",
+            summary.overridden_count
+        ));
+    }
+    doc.text(&overview);
+    doc.text(&methodology(model));
+    doc.spacer();
+
+    // Cost by service
+    doc.heading2("Cost by Service");
+    let mut rows = vec![];
+    for svc in &summary.by_service {
+        let share = if summary.total_cost > 0.0 {
+            format!("{:.1}%", svc.total_cost / summary.total_cost * 100.0)
+        } else {
+            "\u{2014}".to_string()
+        };
+        rows.push(vec![
+            svc.service_name.clone(),
+            svc.incident_count.to_string(),
+            format_currency(svc.total_cost, currency),
+            share,
+        ]);
+    }
+    doc.table(&["Service", "Incidents", "Estimated Cost", "Share"], rows);
+    doc.spacer();
+
+    // Most expensive incidents
+    doc.heading2("Most Costly Incidents");
+    let mut sorted: Vec<&IncidentCost> = costs.iter().collect();
+    sorted.sort_by(|a, b| {
+        b.total_cost
+            .partial_cmp(&a.total_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut rows = vec![];
+    for cost in sorted.iter().take(top_n) {
+        let total = if cost.override_amount.is_some() {
+            format!("{} (manual)", format_currency(cost.total_cost, currency))
+        } else {
+            format_currency(cost.total_cost, currency)
+        };
+        rows.push(vec![
+            cost.title.clone(),
+            cost.service_name.clone(),
+            format_currency(cost.breakdown.user_impact, currency),
+            format_currency(cost.breakdown.responder_effort, currency),
+            format_currency(cost.breakdown.tickets, currency),
+            format_currency(cost.breakdown.lost_revenue, currency),
+            total,
+        ]);
+    }
+    doc.table(
+        &["Incident", "Service", "User Impact", "Responders", "Tickets", "Revenue", "Total"],
+        rows,
+    );
+    doc.spacer();
+}
+
+/// One-line description of the rates behind the estimates.
+pub fn methodology(model: &CostModel) -> String {
+    let mut text = format!(
+        "Estimates use {:.2} {} per affected-user-minute, {:.2} per ticket and {:.2} per responder-hour",
+        model.per_affected_user_minute, model.currency, model.per_ticket, model.per_engineer_hour
+    );
+    if !model.service_revenue_per_hour.is_empty() {
+        text.push_str(", plus lost revenue for services with a configured revenue rate");
+    }
+    text.push('.');
+    text
+}
@@ -0,0 +1,12 @@
+use std::collections::HashMap;
+
+use crate::models::report_template::expand_placeholders;
+use crate::reports::document::ReportDocument;
+
+/// A template-defined section: a heading and markdown body, both with
+/// `{{placeholder}}` values filled in.
+pub fn build(doc: &mut ReportDocument, title: &str, body: &str, values: &HashMap<&str, String>) {
+    doc.heading1(&expand_placeholders(title, values));
+    doc.markdown(&expand_placeholders(body, values));
+    doc.spacer();
+}
@@ -1,32 +1,46 @@
-use docx_rs::*;
-
 use crate::models::incident::Incident;
 use crate::models::metrics::format_minutes;
+use crate::reports::document::{incident_anchor, ReportDocument};
 
-use crate::reports::markdown;
-use super::{heading1, heading2, body_text, label_value, header_cell, text_cell, spacer};
+/// Only P0 and P1 incidents get a breakdown.
+pub fn has_breakdown(incident: &Incident) -> bool {
+    incident.priority == "P0" || incident.priority == "P1"
+}
 
-pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
-    let mut docx = docx.add_paragraph(heading1("Critical Incident Breakdowns"));
+/// `top_n` keeps the most severe incidents: P0 before P1, then longest first.
+pub fn build(doc: &mut ReportDocument, incidents: &[Incident], top_n: Option<usize>) {
+    doc.heading1("Critical Incident Breakdowns");
 
-    // Filter to P0 and P1 incidents
-    let critical_incidents: Vec<&Incident> = incidents
-        .iter()
-        .filter(|i| i.priority == "P0" || i.priority == "P1")
-        .collect();
+    let mut critical_incidents: Vec<&Incident> = incidents.iter().filter(|i| has_breakdown(i)).collect();
 
     if critical_incidents.is_empty() {
-        docx = docx.add_paragraph(body_text("No P0 or P1 incidents this quarter."));
-        docx = docx.add_paragraph(spacer());
-        return docx;
+        doc.text("No P0 or P1 incidents this period.");
+        doc.spacer();
+        return;
+    }
+
+    if let Some(n) = top_n.filter(|n| *n < critical_incidents.len()) {
+        doc.text(&format!(
+            "Showing the {} most severe of {} P0/P1 incidents.",
+            n,
+            critical_incidents.len()
+        ));
+        doc.spacer();
+        critical_incidents.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then(b.duration_minutes.unwrap_or(0).cmp(&a.duration_minutes.unwrap_or(0)))
+        });
+        critical_incidents.truncate(n);
+        critical_incidents.sort_by(|a, b| a.started_at.cmp(&b.started_at));
     }
 
     for incident in &critical_incidents {
         // Incident heading
-        docx = docx.add_paragraph(heading2(&format!(
-            "[{}] {} - {}",
-            incident.priority, incident.title, incident.service_name
-        )));
+        doc.anchored_heading2(
+            &format!("[{}] {} - {}", incident.priority, incident.title, incident.service_name),
+            &incident_anchor(&incident.id),
+        );
 
         // Details table
         let duration = incident
@@ -34,68 +48,49 @@ pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
             .map(|d| format_minutes(d as f64))
             .unwrap_or_else(|| "Ongoing".to_string());
 
-        let details_table = Table::new(vec![
-            TableRow::new(vec![header_cell("Field"), header_cell("Value")]),
-            TableRow::new(vec![text_cell("Severity"), text_cell(&incident.severity)]),
-            TableRow::new(vec![text_cell("Impact"), text_cell(&incident.impact)]),
-            TableRow::new(vec![text_cell("Priority"), text_cell(&incident.priority)]),
-            TableRow::new(vec![text_cell("Status"), text_cell(&incident.status)]),
-            TableRow::new(vec![text_cell("Duration"), text_cell(&duration)]),
-            TableRow::new(vec![text_cell("Tickets"), text_cell(&incident.tickets_submitted.to_string())]),
-            TableRow::new(vec![text_cell("Affected Users"), text_cell(&incident.affected_users.to_string())]),
-        ]);
-        docx = docx.add_table(details_table);
-        docx = docx.add_paragraph(spacer());
+        let field = |name: &str, value: &str| vec![name.to_string(), value.to_string()];
+        doc.table(
+            &["Field", "Value"],
+            vec![
+                field("Severity", &incident.severity),
+                field("Impact", &incident.impact),
+                field("Priority", &incident.priority),
+                field("Status", &incident.status),
+                field("Duration", &duration),
+                field("Tickets", &incident.tickets_submitted.to_string()),
+                field("Affected Users", &incident.affected_users.to_string()),
+            ],
+        );
+        doc.spacer();
 
         // Timeline
-        docx = docx.add_paragraph(label_value("Started: ", &incident.started_at));
-        docx = docx.add_paragraph(label_value("Detected: ", &incident.detected_at));
+        doc.label_value("Started: ", &incident.started_at);
+        doc.label_value("Detected: ", &incident.detected_at);
         if let Some(ref responded) = incident.responded_at {
-            docx = docx.add_paragraph(label_value("Responded: ", responded));
+            doc.label_value("Responded: ", responded);
         }
         if let Some(ref resolved) = incident.resolved_at {
-            docx = docx.add_paragraph(label_value("Resolved: ", resolved));
+            doc.label_value("Resolved: ", resolved);
         }
-        docx = docx.add_paragraph(spacer());
+        doc.spacer();
 
-        // Root cause (markdown-rendered)
-        if !incident.root_cause.is_empty() {
-            docx = docx.add_paragraph(
-                Paragraph::new()
-                    .add_run(Run::new().add_text("Root Cause:").bold().size(11 * 2))
-            );
-            docx = markdown::append_markdown(docx, &incident.root_cause);
-            docx = docx.add_paragraph(spacer());
-        }
-
-        // Resolution (markdown-rendered)
-        if !incident.resolution.is_empty() {
-            docx = docx.add_paragraph(
-                Paragraph::new()
-                    .add_run(Run::new().add_text("Resolution:").bold().size(11 * 2))
-            );
-            docx = markdown::append_markdown(docx, &incident.resolution);
-            docx = docx.add_paragraph(spacer());
-        }
-
-        // Lessons learned (markdown-rendered)
-        if !incident.lessons_learned.is_empty() {
-            docx = docx.add_paragraph(
-                Paragraph::new()
-                    .add_run(Run::new().add_text("Lessons Learned:").bold().size(11 * 2))
-            );
-            docx = markdown::append_markdown(docx, &incident.lessons_learned);
-            docx = docx.add_paragraph(spacer());
+        // Narrative fields (markdown-rendered)
+        for (label, text) in [
+            ("Root Cause:", &incident.root_cause),
+            ("Resolution:", &incident.resolution),
+            ("Lessons Learned:", &incident.lessons_learned),
+        ] {
+            if !text.is_empty() {
+                doc.label(label);
+                doc.markdown(text);
+                doc.spacer();
+            }
         }
 
         // Recurring flag
         if incident.is_recurring {
-            docx = docx.add_paragraph(body_text(
-                "This is a recurring incident. Review prior remediation actions."
-            ));
-            docx = docx.add_paragraph(spacer());
+            doc.text("This is a recurring incident. Review prior remediation actions.");
+            doc.spacer();
         }
     }
-
-    docx
 }
@@ -1,61 +1,52 @@
-use docx_rs::*;
-
 use crate::models::incident::Incident;
 use crate::models::metrics::format_minutes;
+use crate::reports::document::{incident_anchor, Cell, ReportDocument};
+use crate::reports::sections::incident_breakdowns::has_breakdown;
 
-use super::{heading1, header_cell, text_cell, spacer};
-
-pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
-    let mut docx = docx.add_paragraph(heading1("Incident Timeline"));
+/// `link_breakdowns` links the titles of incidents that have a breakdown
+/// section in the same report.
+pub fn build(doc: &mut ReportDocument, incidents: &[Incident], link_breakdowns: bool) {
+    doc.heading1("Incident Timeline");
 
     if incidents.is_empty() {
-        docx = docx.add_paragraph(
-            Paragraph::new()
-                .add_run(Run::new().add_text("No incidents recorded for this quarter.").size(11 * 2))
-        );
-        docx = docx.add_paragraph(spacer());
-        return docx;
+        doc.text("No incidents recorded for this period.");
+        doc.spacer();
+        return;
     }
 
     // Sort chronologically (incidents come pre-sorted but we ensure it)
     let mut sorted: Vec<&Incident> = incidents.iter().collect();
     sorted.sort_by(|a, b| a.started_at.cmp(&b.started_at));
 
-    let header_row = TableRow::new(vec![
-        header_cell("Date"),
-        header_cell("Title"),
-        header_cell("Service"),
-        header_cell("Severity"),
-        header_cell("Impact"),
-        header_cell("Priority"),
-        header_cell("Duration"),
-        header_cell("Status"),
-    ]);
-
-    let mut rows = vec![header_row];
-
-    for incident in &sorted {
-        let date = incident.started_at.get(..10).unwrap_or(&incident.started_at);
-        let duration = incident
-            .duration_minutes
-            .map(|d| format_minutes(d as f64))
-            .unwrap_or_else(|| "Ongoing".to_string());
-
-        rows.push(TableRow::new(vec![
-            text_cell(date),
-            text_cell(&incident.title),
-            text_cell(&incident.service_name),
-            text_cell(&incident.severity),
-            text_cell(&incident.impact),
-            text_cell(&incident.priority),
-            text_cell(&duration),
-            text_cell(&incident.status),
-        ]));
-    }
-
-    let table = Table::new(rows);
-    docx = docx.add_table(table);
-    docx = docx.add_paragraph(spacer());
-
-    docx
+    let rows = sorted
+        .iter()
+        .map(|incident| {
+            let date = incident.started_at.get(..10).unwrap_or(&incident.started_at);
+            let duration = incident
+                .duration_minutes
+                .map(|d| format_minutes(d as f64))
+                .unwrap_or_else(|| "Ongoing".to_string());
+            let title = if link_breakdowns && has_breakdown(incident) {
+                Cell::linked(incident.title.clone(), incident_anchor(&incident.id))
+            } else {
+                Cell::from(incident.title.clone())
+            };
+            vec![
+                Cell::from(date.to_string()),
+                title,
+                Cell::from(incident.service_name.clone()),
+                Cell::from(incident.severity.clone()),
+                Cell::from(incident.impact.clone()),
+                Cell::from(incident.priority.clone()),
+                Cell::from(duration),
+                Cell::from(incident.status.clone()),
+            ]
+        })
+        .collect();
+
+    doc.linked_table(
+        &["Date", "Title", "Service", "Severity", "Impact", "Priority", "Duration", "Status"],
+        rows,
+    );
+    doc.spacer();
 }
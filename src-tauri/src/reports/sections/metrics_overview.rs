@@ -1,13 +1,12 @@
 use std::collections::HashMap;
-use docx_rs::*;
 
 use crate::models::metrics::{format_minutes, format_percentage, format_decimal};
-use crate::reports::charts::add_chart_image;
-
-use super::{heading1, body_text, header_cell, text_cell, spacer};
+use crate::models::report_template::SectionOptions;
+use crate::reports::charts::{ChartImage, OVERVIEW_CHARTS};
+use crate::reports::document::ReportDocument;
 
 pub fn build(
-    docx: Docx,
+    doc: &mut ReportDocument,
     mttr: f64,
     mtta: f64,
     total_incidents: i64,
@@ -18,47 +17,31 @@ pub fn build(
     prev_total: Option<i64>,
     prev_recurrence: Option<f64>,
     prev_tickets: Option<f64>,
-    chart_images: &HashMap<String, Vec<u8>>,
-) -> Docx {
-    let mut docx = docx.add_paragraph(heading1("Metrics Overview"));
+    chart_images: &HashMap<String, ChartImage>,
+    options: &SectionOptions,
+) {
+    doc.heading1("Metrics Overview");
 
     // Build metrics table
-    let header_row = TableRow::new(vec![
-        header_cell("Metric"),
-        header_cell("Current Quarter"),
-        header_cell("Previous Quarter"),
-        header_cell("Change %"),
-    ]);
-
-    let rows = vec![
-        build_metric_row("MTTR", &format_minutes(mttr), prev_mttr.map(|v| format_minutes(v)), mttr, prev_mttr),
-        build_metric_row("MTTA", &format_minutes(mtta), prev_mtta.map(|v| format_minutes(v)), mtta, prev_mtta),
-        build_metric_row("Total Incidents", &total_incidents.to_string(), prev_total.map(|v| v.to_string()), total_incidents as f64, prev_total.map(|v| v as f64)),
-        build_metric_row("Recurrence Rate", &format_percentage(recurrence_rate), prev_recurrence.map(|v| format_percentage(v)), recurrence_rate, prev_recurrence),
-        build_metric_row("Avg Tickets", &format_decimal(avg_tickets), prev_tickets.map(|v| format_decimal(v)), avg_tickets, prev_tickets),
-    ];
-
-    let mut all_rows = vec![header_row];
-    all_rows.extend(rows);
+    let rows = options
+        .metric_keys()
+        .into_iter()
+        .filter_map(|key| match key {
+            "mttr" => Some(build_metric_row("MTTR", &format_minutes(mttr), prev_mttr.map(|v| format_minutes(v)), mttr, prev_mttr)),
+            "mtta" => Some(build_metric_row("MTTA", &format_minutes(mtta), prev_mtta.map(|v| format_minutes(v)), mtta, prev_mtta)),
+            "total_incidents" => Some(build_metric_row("Total Incidents", &total_incidents.to_string(), prev_total.map(|v| v.to_string()), total_incidents as f64, prev_total.map(|v| v as f64))),
+            "recurrence_rate" => Some(build_metric_row("Recurrence Rate", &format_percentage(recurrence_rate), prev_recurrence.map(|v| format_percentage(v)), recurrence_rate, prev_recurrence)),
+            "avg_tickets" => Some(build_metric_row("Avg Tickets", &format_decimal(avg_tickets), prev_tickets.map(|v| format_decimal(v)), avg_tickets, prev_tickets)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
 
-    let table = Table::new(all_rows);
-    docx = docx.add_table(table);
-    docx = docx.add_paragraph(spacer());
-
-    // Add chart images if provided (validate PNG magic bytes first)
-    const PNG_MAGIC: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
-    for key in &["severity_chart", "service_chart", "trend_chart"] {
-        if let Some(bytes) = chart_images.get(*key) {
-            if bytes.len() >= 8 && bytes[..8] == *PNG_MAGIC {
-                docx = docx.add_paragraph(body_text(&format!("Chart: {}", key.replace('_', " "))));
-                // 5486400 EMU ~= 6 inches wide, 3200400 ~= 3.5 inches tall
-                docx = add_chart_image(docx, bytes, 5486400, 3200400);
-                docx = docx.add_paragraph(spacer());
-            }
-        }
+    if !rows.is_empty() {
+        doc.table(&["Metric", "Current Period", "Comparison Period", "Change %"], rows);
+        doc.spacer();
     }
 
-    docx
+    doc.charts(chart_images, &options.chart_keys(OVERVIEW_CHARTS));
 }
 
 fn build_metric_row(
@@ -67,7 +50,7 @@ fn build_metric_row(
     prev_formatted: Option<String>,
     current: f64,
     previous: Option<f64>,
-) -> TableRow {
+) -> Vec<String> {
     let change = match previous {
         Some(prev) if prev != 0.0 => {
             let pct = ((current - prev) / prev) * 100.0;
@@ -78,10 +61,10 @@ fn build_metric_row(
         None => "\u{2014}".to_string(),
     };
 
-    TableRow::new(vec![
-        text_cell(name),
-        text_cell(current_formatted),
-        text_cell(&prev_formatted.unwrap_or_else(|| "\u{2014}".to_string())),
-        text_cell(&change),
-    ])
+    vec![
+        name.to_string(),
+        current_formatted.to_string(),
+        prev_formatted.unwrap_or_else(|| "\u{2014}".to_string()),
+        change,
+    ]
 }
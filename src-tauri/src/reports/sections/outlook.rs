@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::forecast::{ForecastSeries, IncidentForecast, METHOD_SEASONAL_NAIVE};
+use crate::models::metrics::format_minutes;
+use crate::reports::document::ReportDocument;
+
+const TOP_SERVICES: usize = 8;
+
+/// Next-quarter forecasts shown in the Outlook section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Outlook {
+    pub volume: IncidentForecast,
+    pub mttr: IncidentForecast,
+}
+
+pub fn build(doc: &mut ReportDocument, outlook: &Outlook) {
+    doc.heading1("Outlook");
+
+    let lines = summary_lines(outlook);
+    if lines.is_empty() {
+        doc.text("Not enough incident history to forecast the next quarter.");
+        doc.spacer();
+        return;
+    }
+    for line in &lines {
+        doc.text(line);
+    }
+    doc.spacer();
+
+    let services = service_rows(outlook);
+    if !services.is_empty() {
+        doc.heading2("Expected Volume by Service");
+        doc.table(
+            &["Service", "Expected Incidents", "80% Range", "Expected MTTR", "Method"],
+            services,
+        );
+        doc.spacer();
+    }
+
+    if let Some(text) = accuracy_note(outlook) {
+        doc.text(&text);
+        doc.spacer();
+    }
+}
+
+pub fn method_label(method: &str) -> &'static str {
+    if method == METHOD_SEASONAL_NAIVE {
+        "Same quarter last year"
+    } else {
+        "Smoothed recent trend"
+    }
+}
+
+/// Headline sentences for overall volume and MTTR. Empty when neither can be forecast.
+pub fn summary_lines(outlook: &Outlook) -> Vec<String> {
+    let mut lines = Vec::new();
+    let volume = outlook.volume.overall.as_ref().and_then(|s| s.points.first());
+    let Some(quarter) = outlook.volume.forecast_buckets.first() else {
+        return lines;
+    };
+
+    if let (Some(point), Some(last)) = (volume, outlook.volume.history_buckets.last()) {
+        lines.push(format!(
+            "Forecast for {} based on {} quarter(s) of history through {}.",
+            quarter.label,
+            outlook.volume.history_buckets.len(),
+            last.label
+        ));
+        lines.push(format!(
+            "Expected incidents: about {:.0} (80% range {:.0}\u{2013}{:.0}, 95% range {:.0}\u{2013}{:.0}).",
+            point.expected, point.lower_80, point.upper_80, point.lower_95, point.upper_95
+        ));
+    }
+
+    if let Some(point) = outlook.mttr.overall.as_ref().and_then(|s| s.points.first()) {
+        lines.push(format!(
+            "Expected MTTR: {} (80% range {}\u{2013}{}).",
+            format_minutes(point.expected),
+            format_minutes(point.lower_80),
+            format_minutes(point.upper_80)
+        ));
+    }
+
+    lines
+}
+
+/// Table rows for the services with the highest expected volume.
+pub fn service_rows(outlook: &Outlook) -> Vec<Vec<String>> {
+    outlook
+        .volume
+        .by_service
+        .iter()
+        .filter(|s| s.points.first().is_some_and(|p| p.expected > 0.0))
+        .take(TOP_SERVICES)
+        .map(|svc| {
+            let point = &svc.points[0];
+            let mttr = outlook
+                .mttr
+                .by_service
+                .iter()
+                .find(|m| m.key == svc.key)
+                .and_then(|m| m.points.first())
+                .map(|p| format_minutes(p.expected))
+                .unwrap_or_else(|| "\u{2014}".to_string());
+            vec![
+                svc.label.clone(),
+                format!("{:.0}", point.expected),
+                format!("{:.0}\u{2013}{:.0}", point.lower_80, point.upper_80),
+                mttr,
+                method_label(&svc.method).to_string(),
+            ]
+        })
+        .collect()
+}
+
+/// How well the volume model did on recent held-out quarters.
+pub fn accuracy_note(outlook: &Outlook) -> Option<String> {
+    let series: &ForecastSeries = outlook.volume.overall.as_ref()?;
+    let backtest = series.backtest.as_ref()?;
+    let mut text = format!(
+        "Accuracy: forecasting each of the last {} quarter(s) one quarter ahead, volume was off by {:.1} incident(s) on average",
+        backtest.folds, backtest.mae
+    );
+    if let Some(mape) = backtest.mape {
+        text.push_str(&format!(" ({:.0}%)", mape));
+    }
+    text.push_str(&format!(
+        ", and {:.0}% of actuals fell inside the 80% range.",
+        backtest.coverage_80 * 100.0
+    ));
+    Some(text)
+}
+
@@ -1,53 +1,53 @@
-use docx_rs::*;
+use std::collections::HashMap;
 
 use crate::models::metrics::{format_minutes, format_percentage, format_decimal, QuarterlyTrends};
-
-use super::{heading1, body_text, header_cell, text_cell, spacer};
-
-pub fn build(docx: Docx, trends: &QuarterlyTrends) -> Docx {
-    let mut docx = docx.add_paragraph(heading1("Quarter-over-Quarter Comparison"));
+use crate::models::report_template::SectionOptions;
+use crate::reports::charts::{ChartImage, TREND_CHARTS};
+use crate::reports::document::ReportDocument;
+
+pub fn build(
+    doc: &mut ReportDocument,
+    trends: &QuarterlyTrends,
+    chart_images: &HashMap<String, ChartImage>,
+    options: &SectionOptions,
+) {
+    doc.heading1("Quarter-over-Quarter Comparison");
 
     if trends.quarters.is_empty() {
-        docx = docx.add_paragraph(body_text(
-            "No historical quarter data available for comparison."
-        ));
-        docx = docx.add_paragraph(spacer());
-        return docx;
+        doc.text("No historical quarter data available for comparison.");
+        doc.spacer();
+        return;
     }
 
     // Build header row dynamically from quarters
-    let mut header_cells = vec![header_cell("Metric")];
-    for quarter in &trends.quarters {
-        header_cells.push(header_cell(quarter));
+    let mut headers = vec!["Metric"];
+    headers.extend(trends.quarters.iter().map(String::as_str));
+
+    let rows = options
+        .metric_keys()
+        .into_iter()
+        .filter_map(|key| match key {
+            "mttr" => Some(build_row("MTTR", &trends.mttr, |v| format_minutes(v))),
+            "mtta" => Some(build_row("MTTA", &trends.mtta, |v| format_minutes(v))),
+            "total_incidents" => Some(build_row("Total Incidents", &trends.incident_count.iter().map(|v| *v as f64).collect::<Vec<_>>(), |v| format!("{}", v as i64))),
+            "recurrence_rate" => Some(build_row("Recurrence Rate", &trends.recurrence_rate, |v| format_percentage(v))),
+            "avg_tickets" => Some(build_row("Avg Tickets", &trends.avg_tickets, |v| format_decimal(v))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if !rows.is_empty() {
+        doc.table(&headers, rows);
+        doc.spacer();
     }
-    let header_row = TableRow::new(header_cells);
-
-    // MTTR row
-    let mttr_row = build_row("MTTR", &trends.mttr, |v| format_minutes(v));
-    let mtta_row = build_row("MTTA", &trends.mtta, |v| format_minutes(v));
-    let count_row = build_row("Total Incidents", &trends.incident_count.iter().map(|v| *v as f64).collect::<Vec<_>>(), |v| format!("{}", v as i64));
-    let recurrence_row = build_row("Recurrence Rate", &trends.recurrence_rate, |v| format_percentage(v));
-    let tickets_row = build_row("Avg Tickets", &trends.avg_tickets, |v| format_decimal(v));
-
-    let table = Table::new(vec![
-        header_row,
-        mttr_row,
-        mtta_row,
-        count_row,
-        recurrence_row,
-        tickets_row,
-    ]);
-
-    docx = docx.add_table(table);
-    docx = docx.add_paragraph(spacer());
-
-    docx
+
+    doc.charts(chart_images, &options.chart_keys(TREND_CHARTS));
 }
 
-fn build_row(metric_name: &str, values: &[f64], formatter: impl Fn(f64) -> String) -> TableRow {
-    let mut cells = vec![text_cell(metric_name)];
+fn build_row(metric_name: &str, values: &[f64], formatter: impl Fn(f64) -> String) -> Vec<String> {
+    let mut cells = vec![metric_name.to_string()];
     for val in values {
-        cells.push(text_cell(&formatter(*val)));
+        cells.push(formatter(*val));
     }
-    TableRow::new(cells)
+    cells
 }
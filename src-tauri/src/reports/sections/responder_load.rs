@@ -0,0 +1,129 @@
+use crate::models::fatigue::{LoadSummary, ResponderLoadReport, WorkingHoursConfig};
+use crate::reports::document::ReportDocument;
+
+/// Responders listed when the template sets no limit.
+pub const TOP_RESPONDERS: usize = 10;
+/// After-hours engagements before a responder is called out.
+const CONCERN_MIN_AFTER_HOURS: i64 = 3;
+/// Share of a responder's engagements that must start after hours to be called out.
+const CONCERN_MIN_RATE: f64 = 50.0;
+
+pub fn build(doc: &mut ReportDocument, report: &ResponderLoadReport, top_n: usize) {
+    doc.heading1("Responder Load");
+
+    if report.responders.is_empty() {
+        doc.text("No incident roles were assigned this period.");
+        doc.spacer();
+        return;
+    }
+
+    doc.text(&overview(report));
+    for concern in concerns(report) {
+        doc.bullet(&concern);
+    }
+    doc.spacer();
+
+    doc.heading2("Most Engaged Responders");
+    doc.table(
+        &["Responder", "Incidents", "After Hours", "Weekend", "Hours Engaged", "Longest Streak"],
+        responder_rows(report, top_n),
+    );
+    doc.spacer();
+
+    doc.heading2("Load by Team");
+    doc.table(
+        &["Team", "Incidents", "After-Hours Engagements", "Responder-Hours", "After-Hours Share"],
+        group_rows(&report.teams),
+    );
+    doc.spacer();
+}
+
+fn day_name(day: u32) -> &'static str {
+    match day {
+        1 => "Mon",
+        2 => "Tue",
+        3 => "Wed",
+        4 => "Thu",
+        5 => "Fri",
+        6 => "Sat",
+        _ => "Sun",
+    }
+}
+
+/// Human-readable working hours, e.g. "09:00–17:00 Mon, Tue, Wed, Thu, Fri (UTC)".
+pub fn working_hours_text(config: &WorkingHoursConfig, time_zone: &str) -> String {
+    let mut days = config.working_days.clone();
+    days.sort_unstable();
+    let days: Vec<&str> = days.into_iter().map(day_name).collect();
+    format!(
+        "{}\u{2013}{} {} ({})",
+        config.workday_start,
+        config.workday_end,
+        days.join(", "),
+        time_zone
+    )
+}
+
+pub fn overview(report: &ResponderLoadReport) -> String {
+    let engagements: i64 = report.responders.iter().map(|r| r.engagement_count).sum();
+    let after_hours: i64 = report.responders.iter().map(|r| r.after_hours_engagements).sum();
+    let weekend: i64 = report.responders.iter().map(|r| r.weekend_engagements).sum();
+    format!(
+        "{} responder(s) took part in {} incident engagement(s); {} started outside working hours, {} of them on weekends or holidays. Working hours: {}.",
+        report.responders.len(),
+        engagements,
+        after_hours,
+        weekend,
+        working_hours_text(&report.working_hours, &report.time_zone)
+    )
+}
+
+/// Responders carrying a disproportionate share of after-hours work.
+pub fn concerns(report: &ResponderLoadReport) -> Vec<String> {
+    report
+        .responders
+        .iter()
+        .filter(|r| {
+            r.after_hours_engagements >= CONCERN_MIN_AFTER_HOURS && r.after_hours_rate >= CONCERN_MIN_RATE
+        })
+        .map(|r| {
+            format!(
+                "{} was engaged after hours {} time(s) ({:.0}% of their incidents), {:.1} hour(s) outside working hours.",
+                r.label, r.after_hours_engagements, r.after_hours_rate, r.after_hours_hours
+            )
+        })
+        .collect()
+}
+
+pub fn responder_rows(report: &ResponderLoadReport, top_n: usize) -> Vec<Vec<String>> {
+    report
+        .responders
+        .iter()
+        .take(top_n)
+        .map(|r| {
+            vec![
+                r.label.clone(),
+                r.incident_count.to_string(),
+                r.after_hours_engagements.to_string(),
+                r.weekend_engagements.to_string(),
+                format!("{:.1}", r.engaged_hours),
+                format!("{} day(s)", r.longest_streak_days),
+            ]
+        })
+        .collect()
+}
+
+pub fn group_rows(groups: &[LoadSummary]) -> Vec<Vec<String>> {
+    groups
+        .iter()
+        .map(|g| {
+            vec![
+                g.label.clone(),
+                g.incident_count.to_string(),
+                g.after_hours_engagements.to_string(),
+                format!("{:.1}", g.engaged_hours),
+                format!("{:.0}%", g.after_hours_rate),
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::service::{Service, ServiceDependency};
+use crate::reports::document::ReportDocument;
+
+/// A service covered by a service-scoped report, with its dependency context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceContext {
+    pub service: Service,
+    /// Services this one depends on.
+    pub dependencies: Vec<ServiceDependency>,
+    /// Services that depend on this one; `depends_on_service_name` holds the dependent's name.
+    pub dependents: Vec<ServiceDependency>,
+}
+
+pub fn build(doc: &mut ReportDocument, services: &[ServiceContext]) {
+    doc.heading1("Service Context");
+
+    for ctx in services {
+        let service = &ctx.service;
+        doc.heading2(&service.name);
+        doc.label_value("Category: ", &service.category);
+        doc.label_value("Tier: ", &service.tier);
+        doc.label_value("Owner: ", or_not_set(&service.owner));
+        if !service.description.trim().is_empty() {
+            doc.text(&service.description);
+        }
+
+        if ctx.dependencies.is_empty() && ctx.dependents.is_empty() {
+            doc.text("No dependencies recorded.");
+        } else {
+            let rows = ctx
+                .dependencies
+                .iter()
+                .map(|d| dependency_row("Depends on", d, &d.depends_on_service_id))
+                .chain(
+                    ctx.dependents
+                        .iter()
+                        .map(|d| dependency_row("Depended on by", d, &d.service_id)),
+                )
+                .collect();
+            doc.table(&["Direction", "Service", "Type"], rows);
+        }
+
+        if !service.runbook.trim().is_empty() {
+            doc.label("Runbook:");
+            doc.markdown(&service.runbook);
+        }
+        doc.spacer();
+    }
+}
+
+fn dependency_row(direction: &str, dependency: &ServiceDependency, other_id: &str) -> Vec<String> {
+    vec![
+        direction.to_string(),
+        dependency
+            .depends_on_service_name
+            .clone()
+            .unwrap_or_else(|| other_id.to_string()),
+        dependency.dependency_type.clone(),
+    ]
+}
+
+fn or_not_set(value: &str) -> &str {
+    if value.trim().is_empty() {
+        "Not set"
+    } else {
+        value
+    }
+}
@@ -1,18 +1,17 @@
 use std::collections::HashMap;
-use docx_rs::*;
 
 use crate::models::incident::Incident;
 use crate::models::metrics::format_minutes;
+use crate::reports::document::ReportDocument;
 
-use super::{heading1, body_text, header_cell, text_cell, spacer};
-
-pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
-    let mut docx = docx.add_paragraph(heading1("Service Reliability Summary"));
+/// `top_n` limits the table to the services with the most incidents.
+pub fn build(doc: &mut ReportDocument, incidents: &[Incident], top_n: Option<usize>) {
+    doc.heading1("Service Reliability Summary");
 
     if incidents.is_empty() {
-        docx = docx.add_paragraph(body_text("No incidents recorded for this quarter."));
-        docx = docx.add_paragraph(spacer());
-        return docx;
+        doc.text("No incidents recorded for this period.");
+        doc.spacer();
+        return;
     }
 
     // Aggregate by service
@@ -36,19 +35,16 @@ pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
         }
     }
 
-    let header_row = TableRow::new(vec![
-        header_cell("Service"),
-        header_cell("Incident Count"),
-        header_cell("Total Downtime"),
-        header_cell("Avg MTTR"),
-    ]);
-
-    let mut rows = vec![header_row];
+    let mut rows = vec![];
 
     // Sort by incident count descending
     let mut service_list: Vec<_> = service_data.into_iter().collect();
     service_list.sort_by(|a, b| b.1.incident_count.cmp(&a.1.incident_count));
 
+    if let Some(n) = top_n {
+        service_list.truncate(n);
+    }
+
     for (name, stats) in &service_list {
         let avg_mttr = if stats.resolved_count > 0 {
             format_minutes(stats.total_mttr_minutes / stats.resolved_count as f64)
@@ -56,19 +52,16 @@ pub fn build(docx: Docx, incidents: &[Incident]) -> Docx {
             "\u{2014}".to_string()
         };
 
-        rows.push(TableRow::new(vec![
-            text_cell(name),
-            text_cell(&stats.incident_count.to_string()),
-            text_cell(&format_minutes(stats.total_downtime_minutes as f64)),
-            text_cell(&avg_mttr),
-        ]));
+        rows.push(vec![
+            name.clone(),
+            stats.incident_count.to_string(),
+            format_minutes(stats.total_downtime_minutes as f64),
+            avg_mttr,
+        ]);
     }
 
-    let table = Table::new(rows);
-    docx = docx.add_table(table);
-    docx = docx.add_paragraph(spacer());
-
-    docx
+    doc.table(&["Service", "Incident Count", "Total Downtime", "Avg MTTR"], rows);
+    doc.spacer();
 }
 
 struct ServiceStats {
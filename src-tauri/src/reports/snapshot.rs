@@ -0,0 +1,367 @@
+//! Report snapshots: the config and collected data a report was rendered
+//! from, stored with its history entry so the same report can be rendered
+//! again after incidents are edited, and two generations compared.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AppError, AppResult};
+use crate::models::incident::Incident;
+use crate::reports::document::ReportDocument;
+use crate::reports::{ReportConfig, ReportData, ReportFormat};
+
+/// Bump when `ReportData` or `ReportConfig` change shape; older snapshots
+/// are then refused rather than rendered from partial data.
+pub const SNAPSHOT_VERSION: i64 = 1;
+
+/// Incident fields left out of snapshot diffs; they change on every edit.
+const IGNORED_INCIDENT_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSnapshot {
+    pub version: i64,
+    pub(super) config: ReportConfig,
+    pub(super) data: ReportData,
+}
+
+impl ReportSnapshot {
+    pub(super) fn new(config: ReportConfig, data: ReportData) -> Self {
+        Self { version: SNAPSHOT_VERSION, config, data }
+    }
+
+    /// The format the report was rendered in.
+    pub fn format(&self) -> &ReportFormat {
+        &self.config.format
+    }
+
+    pub fn to_json(&self) -> AppResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| AppError::Report(format!("Failed to serialize report snapshot: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> AppResult<Self> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| AppError::Report(format!("Invalid report snapshot: {}", e)))?;
+        let version = value.get("version").and_then(Value::as_i64).unwrap_or(0);
+        if version != SNAPSHOT_VERSION {
+            return Err(AppError::Validation(format!(
+                "Report snapshot version {} is not supported (expected {})",
+                version, SNAPSHOT_VERSION
+            )));
+        }
+        serde_json::from_value(value)
+            .map_err(|e| AppError::Report(format!("Invalid report snapshot: {}", e)))
+    }
+}
+
+/// SHA-256 of a generated report file, as lowercase hex.
+pub fn artifact_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// SHA-256 of a rendered document's content, as lowercase hex. DOCX and PDF
+/// files carry generated element ids and timestamps, so two renders of the
+/// same document only compare equal by content.
+pub fn content_hash(doc: &ReportDocument) -> String {
+    artifact_hash(format!("{:?}", doc).as_bytes())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentRef {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentChange {
+    pub id: String,
+    pub title: String,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricChange {
+    pub metric: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+/// What changed between two generations of a report, from `from` to `to`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Period label of each snapshot; they differ when the two reports cover different periods.
+    pub from_period: String,
+    pub to_period: String,
+    pub incidents_added: Vec<IncidentRef>,
+    pub incidents_removed: Vec<IncidentRef>,
+    pub incidents_changed: Vec<IncidentChange>,
+    /// Only the metrics whose value differs.
+    pub metrics: Vec<MetricChange>,
+}
+
+/// Compare the incidents (report and comparison periods) and headline
+/// metrics of two snapshots.
+pub fn diff(from: &ReportSnapshot, to: &ReportSnapshot) -> SnapshotDiff {
+    let before = report_incidents(&from.data);
+    let after = report_incidents(&to.data);
+
+    let mut incidents_added = Vec::new();
+    let mut incidents_changed = Vec::new();
+    for incident in &after {
+        match before.iter().find(|i| i.id == incident.id) {
+            None => incidents_added.push(incident_ref(incident)),
+            Some(old) => {
+                let changes = field_changes(old, incident);
+                if !changes.is_empty() {
+                    incidents_changed.push(IncidentChange {
+                        id: incident.id.clone(),
+                        title: incident.title.clone(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    let incidents_removed = before
+        .iter()
+        .filter(|i| !after.iter().any(|a| a.id == i.id))
+        .map(|i| incident_ref(i))
+        .collect();
+
+    let metrics = metric_values(&from.data)
+        .into_iter()
+        .zip(metric_values(&to.data))
+        .filter(|((_, a), (_, b))| match (a, b) {
+            (Some(a), Some(b)) => (a - b).abs() > 1e-9,
+            _ => a.is_some() != b.is_some(),
+        })
+        .map(|((metric, before), (_, after))| MetricChange {
+            metric: metric.to_string(),
+            before,
+            after,
+        })
+        .collect();
+
+    SnapshotDiff {
+        from_period: from.data.period.current.label.clone(),
+        to_period: to.data.period.current.label.clone(),
+        incidents_added,
+        incidents_removed,
+        incidents_changed,
+        metrics,
+    }
+}
+
+/// Report-period incidents followed by comparison-period ones, each once.
+fn report_incidents(data: &ReportData) -> Vec<&Incident> {
+    let mut incidents: Vec<&Incident> = Vec::new();
+    for incident in data.incidents.iter().chain(&data.prev_incidents) {
+        if !incidents.iter().any(|i| i.id == incident.id) {
+            incidents.push(incident);
+        }
+    }
+    incidents
+}
+
+fn incident_ref(incident: &Incident) -> IncidentRef {
+    IncidentRef { id: incident.id.clone(), title: incident.title.clone() }
+}
+
+fn field_changes(before: &Incident, after: &Incident) -> Vec<FieldChange> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return vec![];
+    };
+    new.iter()
+        .filter(|(field, _)| !IGNORED_INCIDENT_FIELDS.contains(&field.as_str()))
+        .filter(|(field, value)| old.get(*field) != Some(*value))
+        .map(|(field, value)| FieldChange {
+            field: field.clone(),
+            before: old.get(field).map(display_value).unwrap_or_default(),
+            after: display_value(value),
+        })
+        .collect()
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn metric_values(data: &ReportData) -> Vec<(&'static str, Option<f64>)> {
+    let open_actions = data
+        .action_items_all
+        .iter()
+        .filter(|a| a.status != "Done")
+        .count();
+    vec![
+        ("Total incidents", Some(data.total_incidents as f64)),
+        ("MTTR (minutes)", Some(data.mttr)),
+        ("MTTA (minutes)", Some(data.mtta)),
+        ("Recurrence rate (%)", Some(data.recurrence_rate)),
+        ("Average tickets", Some(data.avg_tickets)),
+        ("Comparison incidents", data.prev_total.map(|t| t as f64)),
+        ("Comparison MTTR (minutes)", data.prev_mttr),
+        ("Comparison MTTA (minutes)", data.prev_mtta),
+        ("Estimated cost", Some(data.incident_costs.iter().map(|c| c.total_cost).sum())),
+        ("Open action items", Some(open_actions as f64)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use sqlx::SqlitePool;
+
+    use crate::reports::period::{ComparisonMode, ReportPeriod};
+    use crate::reports::document::Block;
+    use crate::reports::{build_document, generate_quarterly_report, render_snapshot, snapshot_content_hash, ReportSections};
+
+    async fn setup_db() -> (tempfile::TempDir, SqlitePool) {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("snapshot-tests.db");
+        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        let options = SqliteConnectOptions::from_str(&db_url)
+            .expect("sqlite url")
+            .journal_mode(SqliteJournalMode::Wal)
+            .pragma("foreign_keys", "ON")
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("connect");
+        crate::db::migrations::run_migrations(&pool).await.expect("migrations");
+        (dir, pool)
+    }
+
+    async fn insert_incident(db: &SqlitePool, id: &str, title: &str, minutes: i64) {
+        sqlx::query(
+            "INSERT INTO incidents (id, title, service_id, severity, impact, status, started_at, detected_at, resolved_at) \
+             VALUES (?, ?, 'svc-slack', 'High', 'High', 'Resolved', '2026-03-10T10:00:00Z', '2026-03-10T10:05:00Z', \
+             strftime('%Y-%m-%dT%H:%M:%SZ', '2026-03-10T10:00:00Z', '+' || ? || ' minutes'))",
+        )
+        .bind(id)
+        .bind(title)
+        .bind(minutes)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    fn config() -> ReportConfig {
+        ReportConfig {
+            period: ReportPeriod::Quarter("fy27-q1".into()),
+            comparison: ComparisonMode::PreviousPeriod,
+            service_ids: vec![],
+            title: "Quarterly Review".into(),
+            introduction: String::new(),
+            sections: ReportSections {
+                executive_summary: true,
+                service_context: false,
+                metrics_overview: true,
+                incident_timeline: true,
+                incident_breakdowns: false,
+                service_reliability: true,
+                qoq_comparison: false,
+                discussion_points: true,
+                action_items: true,
+                cost_impact: false,
+                outlook: false,
+                responder_load: false,
+                checklist_compliance: false,
+            }
+            .layout(),
+            chart_images: HashMap::new(),
+            format: ReportFormat::Html,
+            redaction: None,
+        }
+    }
+
+    /// Test: a stored snapshot renders the original report after incidents are edited
+    #[tokio::test]
+    async fn test_snapshot_roundtrip() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "inc-1", "Checkout down", 90).await;
+
+        let (bytes, snapshot) = generate_quarterly_report(&db, &config()).await.unwrap();
+        let json = snapshot.to_json().unwrap();
+
+        sqlx::query("UPDATE incidents SET title = 'Renamed' WHERE id = 'inc-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        let restored = ReportSnapshot::from_json(&json).unwrap();
+        let regenerated = render_snapshot(&restored).unwrap();
+        assert_eq!(artifact_hash(&regenerated), artifact_hash(&bytes));
+        assert!(String::from_utf8(regenerated).unwrap().contains("Checkout down"));
+
+        let future = json.replacen("\"version\":1", "\"version\":99", 1);
+        assert!(matches!(ReportSnapshot::from_json(&future), Err(AppError::Validation(_))));
+    }
+
+    /// Test: a DOCX with charts regenerates from its snapshot with the original content
+    #[tokio::test]
+    async fn test_snapshot_roundtrip_docx_with_charts() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "inc-1", "Checkout down", 90).await;
+
+        let config = ReportConfig { format: ReportFormat::Docx, ..config() };
+        let (bytes, snapshot) = generate_quarterly_report(&db, &config).await.unwrap();
+        let restored = ReportSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+        let document = build_document(&restored.config, &restored.data);
+        assert!(document.blocks.iter().any(|b| matches!(b, Block::Image { .. })));
+
+        let regenerated = render_snapshot(&restored).unwrap();
+        assert_eq!(&bytes[..2], b"PK");
+        assert_eq!(&regenerated[..2], b"PK");
+        assert_eq!(snapshot_content_hash(&restored), snapshot_content_hash(&snapshot));
+    }
+
+    /// Test: the diff lists added and edited incidents and the metrics they moved
+    #[tokio::test]
+    async fn test_snapshot_diff() {
+        let (_dir, db) = setup_db().await;
+        insert_incident(&db, "inc-1", "Checkout down", 60).await;
+        let (_, first) = generate_quarterly_report(&db, &config()).await.unwrap();
+
+        sqlx::query("UPDATE incidents SET severity = 'Critical' WHERE id = 'inc-1'")
+            .execute(&db)
+            .await
+            .unwrap();
+        insert_incident(&db, "inc-2", "Login errors", 120).await;
+        let (_, second) = generate_quarterly_report(&db, &config()).await.unwrap();
+
+        let diff = diff(&first, &second);
+        assert_eq!(diff.from_period, "FY27 Q1");
+        assert_eq!(diff.incidents_added.len(), 1);
+        assert_eq!(diff.incidents_added[0].id, "inc-2");
+        assert!(diff.incidents_removed.is_empty());
+        assert_eq!(diff.incidents_changed.len(), 1);
+        let change = &diff.incidents_changed[0].changes[0];
+        assert_eq!((change.field.as_str(), change.before.as_str(), change.after.as_str()), ("severity", "High", "Critical"));
+
+        let total = diff.metrics.iter().find(|m| m.metric == "Total incidents").unwrap();
+        assert_eq!((total.before, total.after), (Some(1.0), Some(2.0)));
+        let mttr = diff.metrics.iter().find(|m| m.metric == "MTTR (minutes)").unwrap();
+        assert_eq!((mttr.before, mttr.after), (Some(60.0), Some(90.0)));
+        assert!(!diff.metrics.iter().any(|m| m.metric == "Average tickets"));
+    }
+}
@@ -1411,6 +1411,7 @@ mod csv_injection_prevention {
         let mapping = ColumnMapping {
             mappings,
             default_values: HashMap::new(),
+            preset: None,
         };
 
         let results = apply_mapping(&[row], &mapping);
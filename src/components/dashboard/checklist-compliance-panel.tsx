@@ -0,0 +1,107 @@
+import { useChecklistCompliance } from "@/hooks/use-checklists";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+import type { ComplianceGroup } from "@/types/checklist";
+
+interface ChecklistCompliancePanelProps {
+  startDate: string;
+  endDate: string;
+}
+
+function formatMinutes(mins: number | null): string {
+  if (mins == null) return "—";
+  if (mins < 60) return `${mins.toFixed(0)} min`;
+  const h = Math.floor(mins / 60);
+  const m = Math.round(mins % 60);
+  return m === 0 ? `${h}h` : `${h}h ${m}m`;
+}
+
+function GroupTable({ title, groups }: { title: string; groups: ComplianceGroup[] }) {
+  return (
+    <Table>
+      <TableHeader>
+        <TableRow>
+          <TableHead>{title}</TableHead>
+          <TableHead className="text-right">Completion</TableHead>
+          <TableHead className="text-right">Skipped</TableHead>
+          <TableHead className="text-right">Median to Check</TableHead>
+        </TableRow>
+      </TableHeader>
+      <TableBody>
+        {groups.map((g) => (
+          <TableRow key={g.key}>
+            <TableCell className="font-medium">{g.label}</TableCell>
+            <TableCell className="text-right">
+              {g.stats.completion_rate.toFixed(0)}%
+            </TableCell>
+            <TableCell className="text-right">
+              {g.stats.unchecked_at_resolution}
+            </TableCell>
+            <TableCell className="text-right text-sm">
+              {formatMinutes(g.stats.median_minutes_to_check)}
+            </TableCell>
+          </TableRow>
+        ))}
+      </TableBody>
+    </Table>
+  );
+}
+
+export function ChecklistCompliancePanel({
+  startDate,
+  endDate,
+}: ChecklistCompliancePanelProps) {
+  const { data: compliance, isLoading } = useChecklistCompliance(startDate, endDate);
+
+  if (isLoading || !compliance) return null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-base">Checklist Compliance</CardTitle>
+      </CardHeader>
+      <CardContent>
+        {compliance.overall.item_count === 0 ? (
+          <p className="flex h-32 items-center justify-center text-sm text-muted-foreground">
+            No template checklists used in this period
+          </p>
+        ) : (
+          <div className="grid grid-cols-1 gap-6 lg:grid-cols-2">
+            <div className="space-y-2">
+              <p className="text-sm font-medium">Most often unchecked at resolution</p>
+              {compliance.most_skipped.length === 0 ? (
+                <p className="text-sm text-muted-foreground">
+                  Every item was checked before resolution
+                </p>
+              ) : (
+                <ul className="space-y-1 text-sm">
+                  {compliance.most_skipped.map((item) => (
+                    <li key={item.key} className="flex justify-between gap-4">
+                      <span>
+                        {item.label}
+                        <span className="text-muted-foreground"> · {item.template_name}</span>
+                      </span>
+                      <span className="text-muted-foreground">
+                        {item.stats.unchecked_at_resolution}× ({item.stats.skip_rate.toFixed(0)}%)
+                      </span>
+                    </li>
+                  ))}
+                </ul>
+              )}
+            </div>
+            <GroupTable title="Template" groups={compliance.templates} />
+            <GroupTable title="Service" groups={compliance.by_service} />
+            <GroupTable title="Severity" groups={compliance.by_severity} />
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,57 @@
+import { useCriticalPathServices } from "@/hooks/use-services";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+
+interface CriticalPathServicesProps {
+  startDate: string;
+  endDate: string;
+}
+
+export function CriticalPathServices({ startDate, endDate }: CriticalPathServicesProps) {
+  const { data: services, isLoading } = useCriticalPathServices(startDate, endDate);
+
+  if (isLoading || !services) return null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-base">Critical Path Services</CardTitle>
+      </CardHeader>
+      <CardContent>
+        {services.length === 0 ? (
+          <p className="flex h-32 items-center justify-center text-sm text-muted-foreground">
+            No incidents propagated through service dependencies in this period
+          </p>
+        ) : (
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>Service</TableHead>
+                <TableHead className="text-right">Propagated Incidents</TableHead>
+                <TableHead className="text-right">Source Incidents</TableHead>
+                <TableHead className="text-right">Dependents</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {services.map((s) => (
+                <TableRow key={s.service_id}>
+                  <TableCell className="font-medium">{s.service_name}</TableCell>
+                  <TableCell className="text-right">{s.propagated_incidents}</TableCell>
+                  <TableCell className="text-right">{s.source_incidents}</TableCell>
+                  <TableCell className="text-right">{s.dependent_count}</TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
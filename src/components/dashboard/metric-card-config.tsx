@@ -13,6 +13,8 @@ const CARD_LABELS: Record<keyof DashboardCardConfig, string> = {
   mtta: "MTTA",
   recurrence_rate: "Recurrence Rate",
   avg_tickets: "Avg Tickets",
+  estimated_cost: "Estimated Cost",
+  checklist_completion: "Checklist Completion",
   by_severity: "By Severity Chart",
   by_service: "By Service Chart",
   heatmap: "Incident Heatmap",
@@ -1,6 +1,7 @@
 import { useServiceReliability } from "@/hooks/use-analytics";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Badge } from "@/components/ui/badge";
+import type { ServiceReliabilityScore } from "@/types/analytics";
 import {
   Table,
   TableBody,
@@ -37,6 +38,20 @@ function complianceBadge(pct: number) {
   );
 }
 
+function scoreTitle(s: ServiceReliabilityScore): string {
+  const b = s.breakdown;
+  const lines = [
+    `Tier ${b.tier} (x${b.tier_multiplier})`,
+    ...b.by_severity.map(
+      (p) => `${p.severity}: ${p.count} incident(s), -${p.penalty.toFixed(1)}`
+    ),
+    `Downtime: -${b.downtime_penalty.toFixed(1)}`,
+    `Recurrence: -${b.recurrence_penalty.toFixed(1)}`,
+    `SLA breaches: -${b.sla_breach_penalty.toFixed(1)}`,
+  ];
+  return lines.join("\n");
+}
+
 export function ServiceReliabilityScorecard({
   startDate,
   endDate,
@@ -60,6 +75,7 @@ export function ServiceReliabilityScorecard({
             <TableHeader>
               <TableRow>
                 <TableHead>Service</TableHead>
+                <TableHead className="text-right">Score</TableHead>
                 <TableHead className="text-right">Incidents</TableHead>
                 <TableHead className="text-right">Avg MTTR</TableHead>
                 <TableHead className="text-right">SLA Compliance</TableHead>
@@ -68,7 +84,13 @@ export function ServiceReliabilityScorecard({
             <TableBody>
               {scores.map((s) => (
                 <TableRow key={s.service_id}>
-                  <TableCell className="font-medium">{s.service_name}</TableCell>
+                  <TableCell className="font-medium">
+                    {s.service_name}
+                    <span className="ml-1 text-xs text-muted-foreground">{s.tier}</span>
+                  </TableCell>
+                  <TableCell className="text-right font-medium" title={scoreTitle(s)}>
+                    {s.score.toFixed(0)}
+                  </TableCell>
                   <TableCell className="text-right">{s.incident_count}</TableCell>
                   <TableCell className="text-right text-sm">
                     {s.mttr_formatted}
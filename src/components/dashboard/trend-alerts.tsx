@@ -55,7 +55,7 @@ export function TrendAlerts() {
               <div className="text-right text-sm">
                 <div className="font-medium">{trend.incident_count_current}</div>
                 <div className="text-xs text-muted-foreground">
-                  vs {trend.incident_count_previous}
+                  exp. {trend.expected_count.toFixed(1)}
                 </div>
               </div>
             </div>
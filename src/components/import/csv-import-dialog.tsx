@@ -4,6 +4,7 @@ import { Upload, FileSpreadsheet, ArrowRight, ArrowLeft, Check } from "lucide-re
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { Select } from "@/components/ui/select";
 import {
   Dialog,
   DialogContent,
@@ -17,11 +18,15 @@ import {
   useParseCSVHeaders,
   usePreviewImport,
   useExecuteImport,
+  usePreviewJsonImport,
+  useExecuteJsonImport,
   useImportTemplates,
   useSaveTemplate,
 } from "@/hooks/use-import";
 import type {
   ColumnMapping,
+  ImportOptions,
+  ImportPresetMapping,
   ImportPreview,
   ImportResult,
 } from "@/hooks/use-import";
@@ -29,6 +34,8 @@ import { toast } from "@/components/ui/use-toast";
 
 type Step = "file" | "template" | "mapping" | "preview" | "result";
 
+const DEFAULT_OPTIONS: ImportOptions = { mode: "create", match_field: "external_ref" };
+
 interface CSVImportDialogProps {
   open: boolean;
   onOpenChange: (open: boolean) => void;
@@ -49,10 +56,16 @@ export function CSVImportDialog({
   const [preview, setPreview] = useState<ImportPreview | null>(null);
   const [result, setResult] = useState<ImportResult | null>(null);
   const [templateName, setTemplateName] = useState("");
+  const [isJson, setIsJson] = useState(false);
+  const [presets, setPresets] = useState<ImportPresetMapping[]>([]);
+  const [presetId, setPresetId] = useState<string | null>(null);
+  const [options, setOptions] = useState<ImportOptions>(DEFAULT_OPTIONS);
 
   const parseHeaders = useParseCSVHeaders();
   const previewImport = usePreviewImport();
   const executeImport = useExecuteImport();
+  const previewJsonImport = usePreviewJsonImport();
+  const executeJsonImport = useExecuteJsonImport();
   const { data: templates } = useImportTemplates();
   const saveTemplate = useSaveTemplate();
 
@@ -66,6 +79,10 @@ export function CSVImportDialog({
     setPreview(null);
     setResult(null);
     setTemplateName("");
+    setIsJson(false);
+    setPresets([]);
+    setPresetId(null);
+    setOptions(DEFAULT_OPTIONS);
   }, []);
 
   const handleOpenChange = useCallback(
@@ -78,30 +95,56 @@ export function CSVImportDialog({
     [onOpenChange, reset]
   );
 
-  // Step 1: Select CSV file
+  // Step 1: Select CSV or JSON file
   const handleSelectFile = async () => {
     const selected = await open({
       multiple: false,
-      filters: [{ name: "CSV Files", extensions: ["csv"] }],
+      filters: [
+        { name: "CSV Files", extensions: ["csv"] },
+        { name: "JSON Files", extensions: ["json", "ndjson", "jsonl"] },
+      ],
     });
     if (selected) {
       const path = selected;
       setFilePath(path);
       setFileName(path.split("/").pop() ?? path.split("\\").pop() ?? path);
 
+      // JSON files carry field names, so they skip mapping and go straight to preview
+      if (/\.(json|ndjson|jsonl)$/i.test(path)) {
+        setIsJson(true);
+        try {
+          const p = await previewJsonImport.mutateAsync({ filePath: path, options });
+          setPreview(p);
+          setStep("preview");
+        } catch (err) {
+          toast({
+            title: "Failed to parse JSON",
+            description: String(err),
+            variant: "destructive",
+          });
+        }
+        return;
+      }
+      setIsJson(false);
+
       try {
-        const headers = await parseHeaders.mutateAsync(path);
-        setCsvColumns(headers);
-        // Auto-detect mappings
-        const detected = autoDetectMappings(headers);
-        setMappings(detected);
-
-        // If templates exist, go to template step; otherwise skip to mapping
-        if (templates && templates.length > 0) {
-          setStep("template");
-        } else {
+        const parsed = await parseHeaders.mutateAsync(path);
+        setCsvColumns(parsed.headers);
+        setPresets(parsed.presets);
+
+        // A recognized tool export gets its preset; otherwise guess from header names
+        const detectedPreset = parsed.presets.find(
+          (p) => p.id === parsed.detected_preset
+        );
+        if (detectedPreset) {
+          setMappings(detectedPreset.mapping.mappings);
+          setPresetId(detectedPreset.id);
           setStep("mapping");
+          return;
         }
+        setMappings(autoDetectMappings(parsed.headers));
+        setPresetId(null);
+        setStep("template");
       } catch (err) {
         toast({
           title: "Failed to parse CSV",
@@ -124,6 +167,7 @@ export function CSVImportDialog({
         const validated = parsed as ColumnMapping;
         setMappings(validated.mappings);
         setDefaultValues(validated.default_values);
+        setPresetId(validated.preset ?? null);
       } catch {
         toast({
           title: "Invalid template",
@@ -135,6 +179,13 @@ export function CSVImportDialog({
     setStep("mapping");
   };
 
+  // Step 2: Apply a built-in preset
+  const handleApplyPreset = (preset: ImportPresetMapping) => {
+    setMappings(preset.mapping.mappings);
+    setPresetId(preset.id);
+    setStep("mapping");
+  };
+
   // Step 3: Handle mapping change
   const handleMappingChange = (csvColumn: string, incidentField: string) => {
     setMappings((prev) => {
@@ -153,11 +204,13 @@ export function CSVImportDialog({
     const mapping: ColumnMapping = {
       mappings,
       default_values: defaultValues,
+      preset: presetId,
     };
     try {
       const p = await previewImport.mutateAsync({
         filePath,
         mapping,
+        options,
       });
       setPreview(p);
       setStep("preview");
@@ -175,12 +228,16 @@ export function CSVImportDialog({
     const mapping: ColumnMapping = {
       mappings,
       default_values: defaultValues,
+      preset: presetId,
     };
     try {
-      const r = await executeImport.mutateAsync({
-        filePath,
-        mapping,
-      });
+      const r = isJson
+        ? await executeJsonImport.mutateAsync({ filePath, options })
+        : await executeImport.mutateAsync({
+            filePath,
+            mapping,
+            options,
+          });
       setResult(r);
       setStep("result");
     } catch (err) {
@@ -198,6 +255,7 @@ export function CSVImportDialog({
     const mapping: ColumnMapping = {
       mappings,
       default_values: defaultValues,
+      preset: presetId,
     };
     try {
       await saveTemplate.mutateAsync({
@@ -223,11 +281,11 @@ export function CSVImportDialog({
         <DialogHeader>
           <DialogTitle className="flex items-center gap-2">
             <FileSpreadsheet className="h-5 w-5" />
-            Import Incidents from CSV
+            Import Incidents
           </DialogTitle>
           <DialogDescription>
-            {step === "file" && "Select a CSV file to import incidents from."}
-            {step === "template" && "Use a saved template or map columns manually."}
+            {step === "file" && "Select a CSV, JSON or NDJSON file to import incidents from."}
+            {step === "template" && "Use a built-in preset, a saved template, or map columns manually."}
             {step === "mapping" && "Map CSV columns to incident fields."}
             {step === "preview" && "Review the import before confirming."}
             {step === "result" && "Import complete."}
@@ -239,10 +297,48 @@ export function CSVImportDialog({
           <div className="flex flex-col items-center gap-4 py-8">
             <Upload className="h-12 w-12 text-muted-foreground" />
             <p className="text-sm text-muted-foreground">
-              Choose a .csv file to import
+              Choose a .csv, .json or .ndjson file to import
             </p>
-            <Button onClick={handleSelectFile} disabled={parseHeaders.isPending}>
-              {parseHeaders.isPending ? "Parsing..." : "Select CSV File"}
+            <p className="text-xs text-muted-foreground text-center max-w-sm">
+              JSON files use the field names of the JSON export and may include
+              tags, custom fields, action items and contributing factors.
+            </p>
+            <div className="w-full max-w-sm space-y-2 rounded border p-3">
+              <label className="flex items-center gap-2 text-sm">
+                <input
+                  type="checkbox"
+                  checked={options.mode === "upsert"}
+                  onChange={(e) =>
+                    setOptions({ ...options, mode: e.target.checked ? "upsert" : "create" })
+                  }
+                />
+                Update existing incidents instead of duplicating them
+              </label>
+              {options.mode === "upsert" && (
+                <div className="flex items-center gap-2 text-sm">
+                  <span className="text-muted-foreground shrink-0">Match on</span>
+                  <Select
+                    value={options.match_field}
+                    onChange={(e) =>
+                      setOptions({
+                        ...options,
+                        match_field: e.target.value as ImportOptions["match_field"],
+                      })
+                    }
+                  >
+                    <option value="external_ref">External Ref</option>
+                    <option value="title">Title</option>
+                  </Select>
+                </div>
+              )}
+            </div>
+            <Button
+              onClick={handleSelectFile}
+              disabled={parseHeaders.isPending || previewJsonImport.isPending}
+            >
+              {parseHeaders.isPending || previewJsonImport.isPending
+                ? "Parsing..."
+                : "Select File"}
             </Button>
           </div>
         )}
@@ -258,6 +354,23 @@ export function CSVImportDialog({
               </span>
             </div>
 
+            {presets.length > 0 && (
+              <div className="space-y-2">
+                <Label>Use a built-in preset:</Label>
+                <div className="grid grid-cols-2 gap-1">
+                  {presets.map((preset) => (
+                    <button
+                      key={preset.id}
+                      onClick={() => handleApplyPreset(preset)}
+                      className="text-left rounded border px-3 py-2 text-sm hover:bg-accent transition-colors"
+                    >
+                      {preset.name} export
+                    </button>
+                  ))}
+                </div>
+              </div>
+            )}
+
             {templates && templates.length > 0 && (
               <div className="space-y-2">
                 <Label>Use saved template:</Label>
@@ -296,6 +409,25 @@ export function CSVImportDialog({
               </span>
             </div>
 
+            {presetId && (
+              <div className="rounded border border-blue-500/30 bg-blue-500/5 p-3 flex items-center justify-between gap-2 text-sm">
+                <span>
+                  {presets.find((p) => p.id === presetId)?.name ?? presetId} preset:
+                  severities, statuses and dates are translated on import.
+                </span>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => {
+                    setPresetId(null);
+                    setMappings(autoDetectMappings(csvColumns));
+                  }}
+                >
+                  Map manually
+                </Button>
+              </div>
+            )}
+
             <div className="max-h-[350px] overflow-y-auto pr-1">
               <ColumnMapper
                 csvColumns={csvColumns}
@@ -325,9 +457,7 @@ export function CSVImportDialog({
             <div className="flex justify-between">
               <Button
                 variant="outline"
-                onClick={() =>
-                  setStep(templates && templates.length > 0 ? "template" : "file")
-                }
+                onClick={() => setStep("template")}
               >
                 <ArrowLeft className="h-4 w-4 mr-1" />
                 Back
@@ -349,20 +479,25 @@ export function CSVImportDialog({
             <ImportPreviewTable preview={preview} />
 
             <div className="flex justify-between">
-              <Button variant="outline" onClick={() => setStep("mapping")}>
+              <Button
+                variant="outline"
+                onClick={() => (isJson ? reset() : setStep("mapping"))}
+              >
                 <ArrowLeft className="h-4 w-4 mr-1" />
-                Back to Mapping
+                {isJson ? "Choose Another File" : "Back to Mapping"}
               </Button>
               <Button
                 onClick={handleExecute}
                 disabled={
-                  preview.ready_count === 0 || executeImport.isPending
+                  preview.create_count + preview.update_count === 0 ||
+                  executeImport.isPending ||
+                  executeJsonImport.isPending
                 }
               >
-                {executeImport.isPending
+                {executeImport.isPending || executeJsonImport.isPending
                   ? "Importing..."
-                  : `Import ${preview.ready_count + preview.warning_count} Incident${
-                      preview.ready_count + preview.warning_count !== 1 ? "s" : ""
+                  : `Import ${preview.create_count + preview.update_count} Incident${
+                      preview.create_count + preview.update_count !== 1 ? "s" : ""
                     }`}
               </Button>
             </div>
@@ -386,6 +521,20 @@ export function CSVImportDialog({
                   {result.created}
                 </span>
               </div>
+              {result.updated > 0 && (
+                <div className="flex justify-between">
+                  <span className="text-muted-foreground">Updated:</span>
+                  <span className="font-medium text-blue-600">
+                    {result.updated}
+                  </span>
+                </div>
+              )}
+              {result.unchanged > 0 && (
+                <div className="flex justify-between">
+                  <span className="text-muted-foreground">Unchanged:</span>
+                  <span className="font-medium">{result.unchanged}</span>
+                </div>
+              )}
               {result.skipped > 0 && (
                 <div className="flex justify-between">
                   <span className="text-muted-foreground">Skipped:</span>
@@ -19,6 +19,13 @@ const STATUS_STYLES: Record<string, string> = {
   error: "bg-red-500/10 text-red-500 border-red-500/20",
 };
 
+const ACTION_STYLES: Record<string, string> = {
+  create: "bg-green-500/10 text-green-600 border-green-500/20",
+  update: "bg-blue-500/10 text-blue-600 border-blue-500/20",
+  unchanged: "bg-muted text-muted-foreground",
+  conflict: "bg-red-500/10 text-red-500 border-red-500/20",
+};
+
 export function ImportPreviewTable({ preview }: ImportPreviewProps) {
   return (
     <div className="space-y-4">
@@ -37,6 +44,12 @@ export function ImportPreviewTable({ preview }: ImportPreviewProps) {
             {preview.error_count} errors
           </span>
         )}
+        {preview.update_count + preview.unchanged_count > 0 && (
+          <span className="text-muted-foreground">
+            {preview.create_count} new, {preview.update_count} to update,{" "}
+            {preview.unchanged_count} unchanged
+          </span>
+        )}
       </div>
 
       {/* Preview table */}
@@ -45,6 +58,7 @@ export function ImportPreviewTable({ preview }: ImportPreviewProps) {
           <TableHeader>
             <TableRow>
               <TableHead className="w-16">Status</TableHead>
+              <TableHead className="w-20">Action</TableHead>
               <TableHead>Title</TableHead>
               <TableHead>Service</TableHead>
               <TableHead>Severity</TableHead>
@@ -59,8 +73,24 @@ export function ImportPreviewTable({ preview }: ImportPreviewProps) {
                     {row.row_status}
                   </Badge>
                 </TableCell>
-                <TableCell className="font-medium max-w-[200px] truncate">
-                  {row.title || "(empty)"}
+                <TableCell>
+                  <Badge className={ACTION_STYLES[row.action] ?? ""}>
+                    {row.action}
+                  </Badge>
+                </TableCell>
+                <TableCell className="max-w-[240px]">
+                  <div className="font-medium truncate">{row.title || "(empty)"}</div>
+                  {row.changes.length > 0 && (
+                    <ul className="mt-1 space-y-0.5 text-xs text-muted-foreground">
+                      {row.changes.map((change) => (
+                        <li key={change.field} className="truncate">
+                          {change.field}:{" "}
+                          <span className="line-through">{change.old_value || "(empty)"}</span>{" "}
+                          -&gt; <span className="text-foreground">{change.new_value}</span>
+                        </li>
+                      ))}
+                    </ul>
+                  )}
                 </TableCell>
                 <TableCell>{row.service_name || "(empty)"}</TableCell>
                 <TableCell>{row.severity || "(empty)"}</TableCell>
@@ -0,0 +1,45 @@
+import { useNavigate } from "react-router-dom";
+import { Network } from "lucide-react";
+import { useAffectedServiceSuggestions } from "@/hooks/use-services";
+import { Badge } from "@/components/ui/badge";
+
+interface AffectedServicesHintProps {
+  serviceId: string;
+}
+
+export function AffectedServicesHint({ serviceId }: AffectedServicesHintProps) {
+  const navigate = useNavigate();
+  const { data: suggestions } = useAffectedServiceSuggestions(serviceId || undefined);
+
+  if (!suggestions || suggestions.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="rounded-md border border-blue-500/40 bg-blue-50 p-3 dark:bg-blue-950/20">
+      <div className="mb-2 flex items-center gap-2 text-sm font-medium text-blue-800 dark:text-blue-200">
+        <Network className="h-4 w-4" />
+        Dependent services that may also be affected
+      </div>
+      <ul className="space-y-1.5">
+        {suggestions.map((s) => (
+          <li key={s.service_id} className="flex items-center gap-2 text-sm">
+            <button
+              type="button"
+              className="truncate text-left text-blue-600 underline-offset-2 hover:underline dark:text-blue-400"
+              onClick={() => navigate(`/services/${s.service_id}`)}
+            >
+              {s.service_name}
+            </button>
+            <Badge variant="outline" className="shrink-0 text-[10px]">
+              {s.dependency_type}
+            </Badge>
+            <span className="truncate text-xs text-muted-foreground">
+              {s.path.join(" → ")}
+            </span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
@@ -1,9 +1,13 @@
-import { FileSpreadsheet, FileJson } from "lucide-react";
+import { useState } from "react";
+import { FileSpreadsheet, FileJson, Settings2, Sheet } from "lucide-react";
 import { save } from "@tauri-apps/plugin-dialog";
 import { copyFile } from "@tauri-apps/plugin-fs";
-import { useExportCsv, useExportJson } from "@/hooks/use-export";
+import { useExportCsv, useExportJson, useExportXlsx } from "@/hooks/use-export";
 import { Button } from "@/components/ui/button";
+import { ExportOptionsDialog } from "@/components/incidents/export-options-dialog";
 import { toast } from "@/components/ui/use-toast";
+import { DEFAULT_EXPORT_OPTIONS } from "@/types/export";
+import type { ExportOptions } from "@/types/export";
 import type { IncidentFilters } from "@/types/incident";
 
 interface ExportMenuProps {
@@ -13,12 +17,15 @@ interface ExportMenuProps {
 export function ExportMenu({ filters }: ExportMenuProps) {
   const exportCsv = useExportCsv();
   const exportJson = useExportJson();
+  const exportXlsx = useExportXlsx();
+  const [options, setOptions] = useState<ExportOptions>(DEFAULT_EXPORT_OPTIONS);
+  const [optionsOpen, setOptionsOpen] = useState(false);
 
-  const handleExport = async (format: "csv" | "json") => {
+  const handleExport = async (format: "csv" | "json" | "xlsx") => {
     try {
-      const mutation = format === "csv" ? exportCsv : exportJson;
+      const mutation = { csv: exportCsv, json: exportJson, xlsx: exportXlsx }[format];
       const ext = format;
-      const tempPath = await mutation.mutateAsync(filters);
+      const tempPath = await mutation.mutateAsync({ filters, options });
 
       const savePath = await save({
         defaultPath: `incidents_export.${ext}`,
@@ -68,6 +75,25 @@ export function ExportMenu({ filters }: ExportMenuProps) {
         <FileJson className="h-3.5 w-3.5" />
         <span className="hidden sm:inline">JSON</span>
       </Button>
+      <Button
+        size="sm"
+        variant="outline"
+        onClick={() => handleExport("xlsx")}
+        disabled={exportXlsx.isPending}
+        title="Export as Excel workbook"
+      >
+        <Sheet className="h-3.5 w-3.5" />
+        <span className="hidden sm:inline">XLSX</span>
+      </Button>
+      <Button size="sm" variant="ghost" onClick={() => setOptionsOpen(true)} title="CSV and JSON export options">
+        <Settings2 className="h-3.5 w-3.5" />
+      </Button>
+      <ExportOptionsDialog
+        open={optionsOpen}
+        onOpenChange={setOptionsOpen}
+        options={options}
+        onChange={setOptions}
+      />
     </div>
   );
 }
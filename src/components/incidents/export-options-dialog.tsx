@@ -0,0 +1,137 @@
+import { useExportColumns } from "@/hooks/use-export";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Select } from "@/components/ui/select";
+import {
+  Dialog,
+  DialogContent,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import {
+  DATE_FORMAT_PRESETS,
+  DEFAULT_EXPORT_OPTIONS,
+  DELIMITER_LABELS,
+  ENCODING_LABELS,
+  JSON_MODE_LABELS,
+} from "@/types/export";
+import type { ExportEncoding, ExportOptions, JsonExportMode } from "@/types/export";
+
+/** Number of standard columns exported when no columns are picked. */
+const STANDARD_COLUMN_COUNT = 31;
+
+interface ExportOptionsDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  options: ExportOptions;
+  onChange: (options: ExportOptions) => void;
+}
+
+export function ExportOptionsDialog({ open, onOpenChange, options, onChange }: ExportOptionsDialogProps) {
+  const { data: columns } = useExportColumns();
+  const standardKeys = (columns ?? []).slice(0, STANDARD_COLUMN_COUNT).map((c) => c.key);
+  const selected = options.columns.length > 0 ? options.columns : standardKeys;
+
+  const toggleColumn = (key: string) => {
+    const next = selected.includes(key) ? selected.filter((k) => k !== key) : [...selected, key];
+    // Keep the backend's column order so exports read consistently
+    const ordered = (columns ?? []).map((c) => c.key).filter((k) => next.includes(k));
+    onChange({ ...options, columns: ordered });
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>CSV / JSON Export Options</DialogTitle>
+        </DialogHeader>
+
+        <div className="grid gap-3 md:grid-cols-2">
+          <div>
+            <Label>Delimiter</Label>
+            <Select value={options.delimiter} onChange={(e) => onChange({ ...options, delimiter: e.target.value })}>
+              {Object.entries(DELIMITER_LABELS).map(([value, label]) => (
+                <option key={label} value={value}>
+                  {label}
+                </option>
+              ))}
+            </Select>
+          </div>
+          <div>
+            <Label>Encoding</Label>
+            <Select
+              value={options.encoding}
+              onChange={(e) => onChange({ ...options, encoding: e.target.value as ExportEncoding })}
+            >
+              {(Object.keys(ENCODING_LABELS) as ExportEncoding[]).map((enc) => (
+                <option key={enc} value={enc}>
+                  {ENCODING_LABELS[enc]}
+                </option>
+              ))}
+            </Select>
+          </div>
+          <div>
+            <Label>Date format</Label>
+            <Select value={options.date_format} onChange={(e) => onChange({ ...options, date_format: e.target.value })}>
+              {DATE_FORMAT_PRESETS.map((preset) => (
+                <option key={preset.label} value={preset.format}>
+                  {preset.label}
+                </option>
+              ))}
+            </Select>
+          </div>
+          <div>
+            <Label>List separator (tags, roles, factors)</Label>
+            <Input
+              value={options.list_delimiter}
+              onChange={(e) => onChange({ ...options, list_delimiter: e.target.value })}
+            />
+          </div>
+          <div className="md:col-span-2">
+            <Label>JSON layout</Label>
+            <Select
+              value={options.json_mode}
+              onChange={(e) => onChange({ ...options, json_mode: e.target.value as JsonExportMode })}
+            >
+              {(Object.keys(JSON_MODE_LABELS) as JsonExportMode[]).map((mode) => (
+                <option key={mode} value={mode}>
+                  {JSON_MODE_LABELS[mode]}
+                </option>
+              ))}
+            </Select>
+          </div>
+        </div>
+
+        <div>
+          <div className="flex items-center justify-between">
+            <Label>Columns</Label>
+            <Button size="sm" variant="ghost" onClick={() => onChange({ ...options, columns: [] })}>
+              Standard columns
+            </Button>
+          </div>
+          <div className="mt-1 grid max-h-64 grid-cols-2 gap-1 overflow-y-auto rounded border p-2 md:grid-cols-3">
+            {(columns ?? []).map((column) => (
+              <label key={column.key} className="flex items-center gap-1.5 text-sm">
+                <input
+                  type="checkbox"
+                  checked={selected.includes(column.key)}
+                  onChange={() => toggleColumn(column.key)}
+                />
+                {column.key.startsWith("custom:") ? `${column.label} (custom)` : column.label}
+              </label>
+            ))}
+          </div>
+        </div>
+
+        <DialogFooter>
+          <Button variant="ghost" onClick={() => onChange(DEFAULT_EXPORT_OPTIONS)}>
+            Reset
+          </Button>
+          <Button onClick={() => onOpenChange(false)}>Done</Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
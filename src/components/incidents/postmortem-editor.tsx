@@ -8,7 +8,7 @@ import {
   usePostmortemReadiness,
 } from "@/hooks/use-postmortems";
 import { usePirBriefMarkdown, useGeneratePirBriefFile } from "@/hooks/use-pir-review";
-import { useSaveReport } from "@/hooks/use-reports";
+import { useSaveReport, useRedactionProfile } from "@/hooks/use-reports";
 import { useAiPostmortemDraft, useAiStatus } from "@/hooks/use-ai";
 import { useContributingFactors } from "@/hooks/use-postmortems";
 import { Button } from "@/components/ui/button";
@@ -58,6 +58,7 @@ export function PostmortemEditor({
   const updatePm = useUpdatePostmortem();
   const aiDraft = useAiPostmortemDraft();
   const pirBrief = usePirBriefMarkdown();
+  const { data: redactionProfile } = useRedactionProfile();
   const generatePirFile = useGeneratePirBriefFile();
   const saveReport = useSaveReport();
 
@@ -66,6 +67,8 @@ export function PostmortemEditor({
     "draft"
   );
   const [noActionItemsJustified, setNoActionItemsJustified] = useState(false);
+  const [redactBrief, setRedactBrief] = useState(false);
+  const [includeUpdates, setIncludeUpdates] = useState(false);
   const [noActionItemsJustification, setNoActionItemsJustification] =
     useState("");
   const contentRef = useRef(content);
@@ -220,7 +223,10 @@ export function PostmortemEditor({
 
   const handleCopyPirBrief = useCallback(async () => {
     try {
-      const brief = await pirBrief.mutateAsync(incidentId);
+      const brief = await pirBrief.mutateAsync({
+        incidentId,
+        redaction: redactBrief ? redactionProfile : null,
+      });
       await navigator.clipboard.writeText(brief.markdown);
       toast({ title: "PIR brief copied" });
     } catch (err) {
@@ -230,12 +236,17 @@ export function PostmortemEditor({
         variant: "destructive",
       });
     }
-  }, [incidentId, pirBrief]);
+  }, [incidentId, pirBrief, redactBrief, redactionProfile]);
 
   const handleExportPirBrief = useCallback(
     async (format: "docx" | "pdf") => {
       try {
-        const tempPath = await generatePirFile.mutateAsync({ incidentId, format });
+        const tempPath = await generatePirFile.mutateAsync({
+          incidentId,
+          format,
+          redaction: redactBrief ? redactionProfile : null,
+          includeStakeholderUpdates: includeUpdates,
+        });
         const ext = format;
         const filterName = format === "pdf" ? "PDF Document" : "Word Document";
         const safeTitle = title.replace(/[^a-zA-Z0-9]/g, "_").slice(0, 80);
@@ -252,7 +263,13 @@ export function PostmortemEditor({
           savePath,
           title: `PIR Brief - ${title}`,
           quarterId: null,
-          configJson: JSON.stringify({ kind: "pir_brief", incident_id: incidentId, format }),
+          configJson: JSON.stringify({
+            kind: "pir_brief",
+            incident_id: incidentId,
+            format,
+            redacted: redactBrief,
+            stakeholder_updates: includeUpdates,
+          }),
         });
         toast({ title: "PIR brief exported", description: `Saved to ${savePath}` });
       } catch (err) {
@@ -263,7 +280,7 @@ export function PostmortemEditor({
         });
       }
     },
-    [generatePirFile, incidentId, saveReport, title]
+    [generatePirFile, incidentId, includeUpdates, redactBrief, redactionProfile, saveReport, title]
   );
 
   // No PM yet — show create button
@@ -425,7 +442,23 @@ export function PostmortemEditor({
           )}
         </div>
 
-        <div className="flex gap-2">
+        <div className="flex items-center gap-2">
+          <label className="flex items-center gap-1.5 text-sm">
+            <input
+              type="checkbox"
+              checked={redactBrief}
+              onChange={(e) => setRedactBrief(e.target.checked)}
+            />
+            External-safe
+          </label>
+          <label className="flex items-center gap-1.5 text-sm">
+            <input
+              type="checkbox"
+              checked={includeUpdates}
+              onChange={(e) => setIncludeUpdates(e.target.checked)}
+            />
+            Append stakeholder updates
+          </label>
           <Button
             size="sm"
             variant="outline"
@@ -0,0 +1,74 @@
+import { useBlastRadius } from "@/hooks/use-services";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { Badge } from "@/components/ui/badge";
+import type { ImpactedService } from "@/types/incident";
+
+interface BlastRadiusCardProps {
+  serviceId: string;
+}
+
+function ImpactList({ services, empty }: { services: ImpactedService[]; empty: string }) {
+  if (services.length === 0) {
+    return <p className="text-sm text-muted-foreground">{empty}</p>;
+  }
+  return (
+    <div className="space-y-1">
+      {services.map((s) => (
+        <div
+          key={s.service_id}
+          className="flex items-center justify-between gap-2 rounded border px-3 py-2"
+        >
+          <div className="min-w-0">
+            <p className="text-sm font-medium">{s.service_name}</p>
+            <p className="truncate text-xs text-muted-foreground">
+              {s.path.join(" → ")}
+            </p>
+          </div>
+          <div className="flex shrink-0 items-center gap-2">
+            <Badge variant="outline" className="text-[10px]">
+              depth {s.depth}
+            </Badge>
+            <Badge variant="outline" className="text-[10px]">
+              {Math.round(s.weight * 100)}%
+            </Badge>
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+export function BlastRadiusCard({ serviceId }: BlastRadiusCardProps) {
+  const { data: radius } = useBlastRadius(serviceId);
+
+  if (!radius) return null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="text-base">Blast Radius</CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-sm text-muted-foreground">
+          Impact score {radius.impact_score.toFixed(1)} across{" "}
+          {radius.upstream_dependents.length} dependent service(s). Percentages are
+          how strongly a failure carries along the path, by dependency type.
+        </p>
+        <div className="space-y-2">
+          <p className="text-sm font-medium">Affected if this service fails</p>
+          <ImpactList
+            services={radius.upstream_dependents}
+            empty="No services depend on this one, directly or indirectly."
+          />
+        </div>
+        <div className="space-y-2">
+          <p className="text-sm font-medium">Could be affected by</p>
+          <ImpactList
+            services={radius.downstream_dependencies}
+            empty="This service has no dependencies."
+          />
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
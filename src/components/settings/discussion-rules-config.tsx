@@ -0,0 +1,349 @@
+import { useState } from "react";
+import { Plus, Pencil, Trash2, Check, X, FlaskConical } from "lucide-react";
+import {
+  useDiscussionRules,
+  useCreateDiscussionRule,
+  useUpdateDiscussionRule,
+  useDeleteDiscussionRule,
+  useDryRunDiscussionRules,
+} from "@/hooks/use-reports";
+import { useQuarters } from "@/hooks/use-quarters";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Select } from "@/components/ui/select";
+import { Badge } from "@/components/ui/badge";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+import { toast } from "@/components/ui/use-toast";
+import {
+  RULE_METRIC_LABELS,
+  RULE_SEVERITIES,
+  RULE_MESSAGE_PLACEHOLDERS,
+} from "@/types/reports";
+import type {
+  DiscussionRule,
+  CreateDiscussionRuleRequest,
+  RuleComparator,
+  RuleMetric,
+  RuleScope,
+} from "@/types/reports";
+
+const COMPARATORS: RuleComparator[] = [">", ">=", "<", "<=", "="];
+const SCOPES: RuleScope[] = ["period", "service", "incident"];
+
+const EMPTY_RULE: CreateDiscussionRuleRequest = {
+  name: "",
+  metric: "incident_count",
+  comparator: ">=",
+  threshold: 1,
+  scope: "service",
+  message: "",
+  severity: "medium",
+  enabled: true,
+};
+
+function RuleFields({
+  value,
+  onChange,
+}: {
+  value: CreateDiscussionRuleRequest;
+  onChange: (value: CreateDiscussionRuleRequest) => void;
+}) {
+  return (
+    <div className="grid gap-3 md:grid-cols-2">
+      <div>
+        <Label>Name</Label>
+        <Input value={value.name} onChange={(e) => onChange({ ...value, name: e.target.value })} />
+      </div>
+      <div>
+        <Label>Severity</Label>
+        <Select value={value.severity} onChange={(e) => onChange({ ...value, severity: e.target.value })}>
+          {RULE_SEVERITIES.map((s) => (
+            <option key={s} value={s}>
+              {s}
+            </option>
+          ))}
+        </Select>
+      </div>
+      <div>
+        <Label>Scope</Label>
+        <Select
+          value={value.scope}
+          onChange={(e) => onChange({ ...value, scope: e.target.value as RuleScope })}
+        >
+          {SCOPES.map((s) => (
+            <option key={s} value={s}>
+              {s}
+            </option>
+          ))}
+        </Select>
+      </div>
+      <div className="flex gap-2">
+        <div className="flex-1">
+          <Label>Metric</Label>
+          <Select
+            value={value.metric}
+            onChange={(e) => onChange({ ...value, metric: e.target.value as RuleMetric })}
+          >
+            {(Object.keys(RULE_METRIC_LABELS) as RuleMetric[]).map((m) => (
+              <option key={m} value={m}>
+                {RULE_METRIC_LABELS[m]}
+              </option>
+            ))}
+          </Select>
+        </div>
+        <div className="w-20">
+          <Label>When</Label>
+          <Select
+            value={value.comparator}
+            onChange={(e) => onChange({ ...value, comparator: e.target.value as RuleComparator })}
+          >
+            {COMPARATORS.map((c) => (
+              <option key={c} value={c}>
+                {c}
+              </option>
+            ))}
+          </Select>
+        </div>
+        <div className="w-24">
+          <Label>Threshold</Label>
+          <Input
+            type="number"
+            step="any"
+            value={value.threshold}
+            onChange={(e) => onChange({ ...value, threshold: Number(e.target.value) })}
+          />
+        </div>
+      </div>
+      <div className="md:col-span-2">
+        <Label>Message</Label>
+        <Input value={value.message} onChange={(e) => onChange({ ...value, message: e.target.value })} />
+        <p className="mt-1 text-xs text-muted-foreground">
+          Placeholders: {RULE_MESSAGE_PLACEHOLDERS.map((p) => `{{${p}}}`).join(", ")}
+        </p>
+      </div>
+    </div>
+  );
+}
+
+function DryRunPanel() {
+  const { data: quarters } = useQuarters();
+  const [quarterId, setQuarterId] = useState<string | null>(null);
+  const { data: evaluations, isLoading } = useDryRunDiscussionRules(quarterId);
+
+  return (
+    <Card>
+      <CardHeader className="pb-3">
+        <CardTitle className="flex items-center gap-2 text-base">
+          <FlaskConical className="h-4 w-4" />
+          Dry Run
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="max-w-xs">
+          <Label>Quarter</Label>
+          <Select value={quarterId ?? ""} onChange={(e) => setQuarterId(e.target.value || null)}>
+            <option value="">Select a quarter...</option>
+            {quarters?.map((q) => (
+              <option key={q.id} value={q.id}>
+                {q.label}
+              </option>
+            ))}
+          </Select>
+        </div>
+        {isLoading && quarterId && <p className="text-sm text-muted-foreground">Evaluating rules...</p>}
+        {evaluations?.map((ev) => (
+          <div key={ev.rule_id} className="rounded border px-3 py-2">
+            <div className="flex items-center gap-2">
+              <span className="text-sm font-medium">{ev.rule_name}</span>
+              {ev.fired ? <Badge variant="destructive">Fired</Badge> : <Badge variant="secondary">Quiet</Badge>}
+              {!ev.enabled && <Badge variant="outline">Disabled</Badge>}
+            </div>
+            <ul className="mt-1 space-y-0.5">
+              {ev.outcomes.map((o, i) => (
+                <li key={i} className={`text-xs ${o.fired ? "" : "text-muted-foreground"}`}>
+                  {o.explanation}
+                  {o.message && <span className="ml-1 italic">— {o.message}</span>}
+                </li>
+              ))}
+            </ul>
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
+
+export function DiscussionRulesConfig() {
+  const { data: rules, isLoading } = useDiscussionRules();
+  const createRule = useCreateDiscussionRule();
+  const updateRule = useUpdateDiscussionRule();
+  const deleteRule = useDeleteDiscussionRule();
+
+  const [draft, setDraft] = useState<CreateDiscussionRuleRequest | null>(null);
+  const [editingId, setEditingId] = useState<string | null>(null);
+  const [edit, setEdit] = useState<CreateDiscussionRuleRequest>(EMPTY_RULE);
+
+  const handleCreate = async () => {
+    if (!draft) return;
+    try {
+      await createRule.mutateAsync(draft);
+      setDraft(null);
+    } catch (err) {
+      toast({ title: "Failed to create rule", description: String(err), variant: "destructive" });
+    }
+  };
+
+  const startEdit = (rule: DiscussionRule) => {
+    setEditingId(rule.id);
+    setEdit({
+      name: rule.name,
+      metric: rule.metric,
+      comparator: rule.comparator,
+      threshold: rule.threshold,
+      scope: rule.scope,
+      message: rule.message,
+      severity: rule.severity,
+      enabled: rule.enabled,
+    });
+  };
+
+  const handleUpdate = async () => {
+    if (!editingId) return;
+    try {
+      await updateRule.mutateAsync({ id: editingId, req: edit });
+      setEditingId(null);
+    } catch (err) {
+      toast({ title: "Failed to update rule", description: String(err), variant: "destructive" });
+    }
+  };
+
+  const handleToggle = async (rule: DiscussionRule) => {
+    try {
+      await updateRule.mutateAsync({ id: rule.id, req: { enabled: !rule.enabled } });
+    } catch (err) {
+      toast({ title: "Failed to update rule", description: String(err), variant: "destructive" });
+    }
+  };
+
+  const handleDelete = async (rule: DiscussionRule) => {
+    if (!confirm(`Delete discussion rule "${rule.name}"?`)) return;
+    try {
+      await deleteRule.mutateAsync(rule.id);
+    } catch (err) {
+      toast({ title: "Failed to delete rule", description: String(err), variant: "destructive" });
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div>
+          <h2 className="text-lg font-semibold">Discussion Rules</h2>
+          <p className="text-sm text-muted-foreground">
+            Rules that generate the discussion points in quarterly reports.
+          </p>
+        </div>
+        <Button size="sm" onClick={() => setDraft(EMPTY_RULE)} disabled={draft !== null}>
+          <Plus className="mr-1 h-4 w-4" />
+          Add Rule
+        </Button>
+      </div>
+
+      {draft && (
+        <Card>
+          <CardContent className="space-y-3 pt-4">
+            <RuleFields value={draft} onChange={setDraft} />
+            <div className="flex gap-2">
+              <Button size="sm" onClick={handleCreate} disabled={createRule.isPending}>
+                <Check className="mr-1 h-4 w-4" />
+                Save
+              </Button>
+              <Button size="sm" variant="ghost" onClick={() => setDraft(null)}>
+                <X className="mr-1 h-4 w-4" />
+                Cancel
+              </Button>
+            </div>
+          </CardContent>
+        </Card>
+      )}
+
+      {isLoading ? (
+        <p className="text-sm text-muted-foreground">Loading...</p>
+      ) : (
+        <Table>
+          <TableHeader>
+            <TableRow>
+              <TableHead className="w-16">On</TableHead>
+              <TableHead>Rule</TableHead>
+              <TableHead>Condition</TableHead>
+              <TableHead>Scope</TableHead>
+              <TableHead>Severity</TableHead>
+              <TableHead className="w-24" />
+            </TableRow>
+          </TableHeader>
+          <TableBody>
+            {rules?.map((rule) =>
+              editingId === rule.id ? (
+                <TableRow key={rule.id}>
+                  <TableCell colSpan={6} className="space-y-3">
+                    <RuleFields value={edit} onChange={setEdit} />
+                    <div className="flex gap-2">
+                      <Button size="sm" onClick={handleUpdate} disabled={updateRule.isPending}>
+                        <Check className="mr-1 h-4 w-4" />
+                        Save
+                      </Button>
+                      <Button size="sm" variant="ghost" onClick={() => setEditingId(null)}>
+                        <X className="mr-1 h-4 w-4" />
+                        Cancel
+                      </Button>
+                    </div>
+                  </TableCell>
+                </TableRow>
+              ) : (
+                <TableRow key={rule.id} className={rule.enabled ? "" : "opacity-60"}>
+                  <TableCell>
+                    <input type="checkbox" checked={rule.enabled} onChange={() => handleToggle(rule)} />
+                  </TableCell>
+                  <TableCell>
+                    <div className="font-medium">{rule.name}</div>
+                    <div className="text-xs text-muted-foreground">{rule.message}</div>
+                  </TableCell>
+                  <TableCell className="whitespace-nowrap text-sm">
+                    {RULE_METRIC_LABELS[rule.metric]} {rule.comparator} {rule.threshold}
+                  </TableCell>
+                  <TableCell>{rule.scope}</TableCell>
+                  <TableCell>
+                    <Badge variant="outline">{rule.severity}</Badge>
+                  </TableCell>
+                  <TableCell>
+                    <div className="flex gap-1">
+                      <Button size="icon" variant="ghost" onClick={() => startEdit(rule)}>
+                        <Pencil className="h-4 w-4" />
+                      </Button>
+                      {!rule.is_builtin && (
+                        <Button size="icon" variant="ghost" onClick={() => handleDelete(rule)}>
+                          <Trash2 className="h-4 w-4" />
+                        </Button>
+                      )}
+                    </div>
+                  </TableCell>
+                </TableRow>
+              ),
+            )}
+          </TableBody>
+        </Table>
+      )}
+
+      <DryRunPanel />
+    </div>
+  );
+}
@@ -0,0 +1,192 @@
+import { useEffect, useState } from "react";
+import { Plus, Trash2, Save } from "lucide-react";
+import { useRedactionProfile, useUpdateRedactionProfile } from "@/hooks/use-reports";
+import { useCustomFields } from "@/hooks/use-custom-fields";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Select } from "@/components/ui/select";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { toast } from "@/components/ui/use-toast";
+import { MASK_PATTERN_PRESETS, PEOPLE_REDACTION_LABELS } from "@/types/reports";
+import type { PeopleRedaction, RedactionProfile } from "@/types/reports";
+
+const EMPTY_PROFILE: RedactionProfile = {
+  people: "pseudonymize",
+  hidden_custom_fields: [],
+  hidden_tags: [],
+  generalize_services: false,
+  strip_notes: false,
+  mask_patterns: [],
+};
+
+export function RedactionConfig() {
+  const { data: saved, isLoading } = useRedactionProfile();
+  const { data: customFields } = useCustomFields();
+  const updateProfile = useUpdateRedactionProfile();
+
+  const [profile, setProfile] = useState<RedactionProfile>(EMPTY_PROFILE);
+  const [tagsText, setTagsText] = useState("");
+  const [newPattern, setNewPattern] = useState("");
+
+  useEffect(() => {
+    if (saved) {
+      setProfile(saved);
+      setTagsText(saved.hidden_tags.join(", "));
+    }
+  }, [saved]);
+
+  const addPattern = (pattern: string) => {
+    const trimmed = pattern.trim();
+    if (!trimmed || profile.mask_patterns.includes(trimmed)) return;
+    setProfile({ ...profile, mask_patterns: [...profile.mask_patterns, trimmed] });
+    setNewPattern("");
+  };
+
+  const toggleField = (name: string) => {
+    const hidden = profile.hidden_custom_fields.includes(name)
+      ? profile.hidden_custom_fields.filter((f) => f !== name)
+      : [...profile.hidden_custom_fields, name];
+    setProfile({ ...profile, hidden_custom_fields: hidden });
+  };
+
+  const handleSave = async () => {
+    const hidden_tags = tagsText
+      .split(",")
+      .map((t) => t.trim())
+      .filter(Boolean);
+    try {
+      await updateProfile.mutateAsync({ ...profile, hidden_tags });
+      toast({ title: "Redaction profile saved" });
+    } catch (err) {
+      toast({ title: "Failed to save redaction profile", description: String(err), variant: "destructive" });
+    }
+  };
+
+  if (isLoading) {
+    return <p className="text-sm text-muted-foreground">Loading...</p>;
+  }
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div>
+          <h2 className="text-lg font-semibold">Redaction</h2>
+          <p className="text-sm text-muted-foreground">
+            What to remove from external-safe reports and PIR briefs shared with customers or auditors.
+          </p>
+        </div>
+        <Button size="sm" onClick={handleSave} disabled={updateProfile.isPending}>
+          <Save className="mr-1 h-4 w-4" />
+          Save
+        </Button>
+      </div>
+
+      <Card>
+        <CardHeader className="pb-3">
+          <CardTitle className="text-base">Names and Content</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="max-w-sm">
+            <Label>People</Label>
+            <Select
+              value={profile.people}
+              onChange={(e) => setProfile({ ...profile, people: e.target.value as PeopleRedaction })}
+            >
+              {(Object.keys(PEOPLE_REDACTION_LABELS) as PeopleRedaction[]).map((p) => (
+                <option key={p} value={p}>
+                  {PEOPLE_REDACTION_LABELS[p]}
+                </option>
+              ))}
+            </Select>
+          </div>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={profile.generalize_services}
+              onChange={(e) => setProfile({ ...profile, generalize_services: e.target.checked })}
+            />
+            Replace service names with their category
+          </label>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={profile.strip_notes}
+              onChange={(e) => setProfile({ ...profile, strip_notes: e.target.checked })}
+            />
+            Remove notes, external refs, outcome notes and runbooks
+          </label>
+          <div>
+            <Label>Hidden tags</Label>
+            <Input
+              value={tagsText}
+              onChange={(e) => setTagsText(e.target.value)}
+              placeholder="Comma-separated, e.g. customer-acme, security"
+            />
+          </div>
+          {customFields && customFields.length > 0 && (
+            <div>
+              <Label>Hidden custom fields</Label>
+              <div className="mt-1 flex flex-wrap gap-3">
+                {customFields.map((field) => (
+                  <label key={field.id} className="flex items-center gap-1.5 text-sm">
+                    <input
+                      type="checkbox"
+                      checked={profile.hidden_custom_fields.includes(field.name)}
+                      onChange={() => toggleField(field.name)}
+                    />
+                    {field.name}
+                  </label>
+                ))}
+              </div>
+            </div>
+          )}
+        </CardContent>
+      </Card>
+
+      <Card>
+        <CardHeader className="pb-3">
+          <CardTitle className="text-base">Mask Patterns</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <p className="text-sm text-muted-foreground">
+            Regular expressions whose matches are replaced with [redacted] in all text.
+          </p>
+          {profile.mask_patterns.map((pattern) => (
+            <div key={pattern} className="flex items-center gap-2">
+              <code className="flex-1 rounded bg-muted px-2 py-1 text-xs">{pattern}</code>
+              <Button
+                size="icon"
+                variant="ghost"
+                onClick={() =>
+                  setProfile({ ...profile, mask_patterns: profile.mask_patterns.filter((p) => p !== pattern) })
+                }
+              >
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+          <div className="flex gap-2">
+            <Input
+              value={newPattern}
+              onChange={(e) => setNewPattern(e.target.value)}
+              placeholder="e.g. \bACME-\d+\b"
+            />
+            <Button size="sm" variant="outline" onClick={() => addPattern(newPattern)}>
+              <Plus className="mr-1 h-4 w-4" />
+              Add
+            </Button>
+          </div>
+          <div className="flex flex-wrap gap-2">
+            {MASK_PATTERN_PRESETS.filter((p) => !profile.mask_patterns.includes(p.pattern)).map((preset) => (
+              <Button key={preset.label} size="sm" variant="ghost" onClick={() => addPattern(preset.pattern)}>
+                <Plus className="mr-1 h-4 w-4" />
+                {preset.label}
+              </Button>
+            ))}
+          </div>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
@@ -0,0 +1,221 @@
+import { useEffect, useState } from "react";
+import { Save, Trash2, Upload } from "lucide-react";
+import { useReportTheme, useUpdateReportTheme } from "@/hooks/use-reports";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Select } from "@/components/ui/select";
+import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { toast } from "@/components/ui/use-toast";
+import { COVER_LAYOUT_LABELS, REPORT_FONT_LABELS } from "@/types/reports";
+import type { CoverLayout, ReportFont, ReportTheme } from "@/types/reports";
+
+const MAX_LOGO_BYTES = 1024 * 1024;
+
+const DEFAULT_THEME: ReportTheme = {
+  logo_png: null,
+  primary_color: "#1F2937",
+  accent_color: "#E0E0E0",
+  font: "sans",
+  font_size: 11,
+  header_text: "",
+  footer_text: "",
+  page_numbers: true,
+  confidentiality_banner: "",
+  cover_layout: "compact",
+};
+
+export function ReportThemeConfig() {
+  const { data: saved, isLoading } = useReportTheme();
+  const updateTheme = useUpdateReportTheme();
+  const [theme, setTheme] = useState<ReportTheme>(DEFAULT_THEME);
+
+  useEffect(() => {
+    if (saved) setTheme(saved);
+  }, [saved]);
+
+  const handleLogo = (file: File | undefined) => {
+    if (!file) return;
+    if (file.type !== "image/png") {
+      toast({ title: "Logo must be a PNG image", variant: "destructive" });
+      return;
+    }
+    if (file.size > MAX_LOGO_BYTES) {
+      toast({ title: "Logo must be 1 MB or smaller", variant: "destructive" });
+      return;
+    }
+    const reader = new FileReader();
+    reader.onload = () => {
+      const dataUrl = String(reader.result);
+      setTheme((t) => ({ ...t, logo_png: dataUrl.slice(dataUrl.indexOf(",") + 1) }));
+    };
+    reader.readAsDataURL(file);
+  };
+
+  const handleSave = async () => {
+    try {
+      await updateTheme.mutateAsync(theme);
+      toast({ title: "Report theme saved" });
+    } catch (err) {
+      toast({ title: "Failed to save report theme", description: String(err), variant: "destructive" });
+    }
+  };
+
+  if (isLoading) {
+    return <p className="text-sm text-muted-foreground">Loading...</p>;
+  }
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div>
+          <h2 className="text-lg font-semibold">Report Theme</h2>
+          <p className="text-sm text-muted-foreground">
+            Branding and styling for DOCX and PDF reports.
+          </p>
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" variant="ghost" onClick={() => setTheme(DEFAULT_THEME)}>
+            Reset
+          </Button>
+          <Button size="sm" onClick={handleSave} disabled={updateTheme.isPending}>
+            <Save className="mr-1 h-4 w-4" />
+            Save
+          </Button>
+        </div>
+      </div>
+
+      <Card>
+        <CardHeader className="pb-3">
+          <CardTitle className="text-base">Branding</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div>
+            <Label>Logo</Label>
+            <div className="mt-1 flex items-center gap-3">
+              {theme.logo_png ? (
+                <img
+                  src={`data:image/png;base64,${theme.logo_png}`}
+                  alt="Report logo"
+                  className="h-12 max-w-[12rem] rounded border object-contain"
+                />
+              ) : (
+                <span className="text-sm text-muted-foreground">No logo</span>
+              )}
+              <Button size="sm" variant="outline" asChild>
+                <label className="cursor-pointer">
+                  <Upload className="mr-1 h-4 w-4" />
+                  Upload PNG
+                  <input
+                    type="file"
+                    accept="image/png"
+                    className="hidden"
+                    onChange={(e) => handleLogo(e.target.files?.[0])}
+                  />
+                </label>
+              </Button>
+              {theme.logo_png && (
+                <Button size="icon" variant="ghost" onClick={() => setTheme({ ...theme, logo_png: null })}>
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              )}
+            </div>
+          </div>
+          <div className="grid gap-3 md:grid-cols-2">
+            <div>
+              <Label>Primary colour (title and headings)</Label>
+              <div className="flex gap-2">
+                <input
+                  type="color"
+                  value={theme.primary_color}
+                  onChange={(e) => setTheme({ ...theme, primary_color: e.target.value.toUpperCase() })}
+                  className="h-9 w-12 rounded border"
+                />
+                <Input value={theme.primary_color} onChange={(e) => setTheme({ ...theme, primary_color: e.target.value })} />
+              </div>
+            </div>
+            <div>
+              <Label>Accent colour (table headers)</Label>
+              <div className="flex gap-2">
+                <input
+                  type="color"
+                  value={theme.accent_color}
+                  onChange={(e) => setTheme({ ...theme, accent_color: e.target.value.toUpperCase() })}
+                  className="h-9 w-12 rounded border"
+                />
+                <Input value={theme.accent_color} onChange={(e) => setTheme({ ...theme, accent_color: e.target.value })} />
+              </div>
+            </div>
+            <div>
+              <Label>Font</Label>
+              <Select value={theme.font} onChange={(e) => setTheme({ ...theme, font: e.target.value as ReportFont })}>
+                {(Object.keys(REPORT_FONT_LABELS) as ReportFont[]).map((f) => (
+                  <option key={f} value={f}>
+                    {REPORT_FONT_LABELS[f]}
+                  </option>
+                ))}
+              </Select>
+            </div>
+            <div>
+              <Label>Body text size (pt)</Label>
+              <Input
+                type="number"
+                min={8}
+                max={16}
+                value={theme.font_size}
+                onChange={(e) => setTheme({ ...theme, font_size: Number(e.target.value) })}
+              />
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
+      <Card>
+        <CardHeader className="pb-3">
+          <CardTitle className="text-base">Layout</CardTitle>
+        </CardHeader>
+        <CardContent className="space-y-3">
+          <div className="max-w-sm">
+            <Label>Cover</Label>
+            <Select
+              value={theme.cover_layout}
+              onChange={(e) => setTheme({ ...theme, cover_layout: e.target.value as CoverLayout })}
+            >
+              {(Object.keys(COVER_LAYOUT_LABELS) as CoverLayout[]).map((c) => (
+                <option key={c} value={c}>
+                  {COVER_LAYOUT_LABELS[c]}
+                </option>
+              ))}
+            </Select>
+          </div>
+          <div>
+            <Label>Confidentiality banner</Label>
+            <Input
+              value={theme.confidentiality_banner}
+              onChange={(e) => setTheme({ ...theme, confidentiality_banner: e.target.value })}
+              placeholder="e.g. Confidential - internal use only"
+            />
+          </div>
+          <div className="grid gap-3 md:grid-cols-2">
+            <div>
+              <Label>Header text</Label>
+              <Input value={theme.header_text} onChange={(e) => setTheme({ ...theme, header_text: e.target.value })} />
+            </div>
+            <div>
+              <Label>Footer text</Label>
+              <Input value={theme.footer_text} onChange={(e) => setTheme({ ...theme, footer_text: e.target.value })} />
+            </div>
+          </div>
+          <label className="flex items-center gap-2 text-sm">
+            <input
+              type="checkbox"
+              checked={theme.page_numbers}
+              onChange={(e) => setTheme({ ...theme, page_numbers: e.target.checked })}
+            />
+            Page numbers
+          </label>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
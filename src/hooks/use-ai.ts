@@ -109,6 +109,9 @@ export interface ServiceTrend {
   message: string;
   incident_count_current: number;
   incident_count_previous: number;
+  expected_count: number;
+  confidence: number;
+  significance: number;
 }
 
 export function useServiceTrends() {
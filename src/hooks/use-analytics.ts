@@ -1,9 +1,13 @@
-import { useQuery } from "@tanstack/react-query";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
 import type {
   BacklogAgingBucket,
   ServiceReliabilityScore,
+  ReliabilityHistoryEntry,
+  ReliabilityScoringConfig,
   EscalationFunnelEntry,
+  ResponderLoadReport,
+  WorkingHoursConfig,
 } from "@/types/analytics";
 
 export function useBacklogAging() {
@@ -39,3 +43,76 @@ export function useEscalationFunnel(startDate: string, endDate: string) {
     staleTime: 30000,
   });
 }
+
+export function useResponderLoad(startDate: string, endDate: string) {
+  return useQuery({
+    queryKey: ["responder-load", startDate, endDate],
+    queryFn: () =>
+      tauriInvoke<ResponderLoadReport>("get_responder_load", {
+        startDate,
+        endDate,
+      }),
+    enabled: !!startDate && !!endDate,
+    staleTime: 30000,
+  });
+}
+
+export function useWorkingHoursConfig() {
+  return useQuery({
+    queryKey: ["working-hours-config"],
+    queryFn: () => tauriInvoke<WorkingHoursConfig>("get_working_hours_config"),
+  });
+}
+
+export function useUpdateWorkingHoursConfig() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (config: WorkingHoursConfig) =>
+      tauriInvoke<WorkingHoursConfig>("update_working_hours_config", { config }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["working-hours-config"] });
+      queryClient.invalidateQueries({ queryKey: ["responder-load"] });
+    },
+  });
+}
+
+export function useReliabilityHistory(serviceId?: string) {
+  return useQuery({
+    queryKey: ["reliability-history", serviceId ?? null],
+    queryFn: () =>
+      tauriInvoke<ReliabilityHistoryEntry[]>("get_reliability_history", {
+        serviceId: serviceId ?? null,
+      }),
+    staleTime: 30000,
+  });
+}
+
+export function useReliabilityScoringConfig() {
+  return useQuery({
+    queryKey: ["reliability-scoring-config"],
+    queryFn: () => tauriInvoke<ReliabilityScoringConfig>("get_reliability_scoring_config"),
+  });
+}
+
+export function useUpdateReliabilityScoringConfig() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (config: ReliabilityScoringConfig) =>
+      tauriInvoke<ReliabilityScoringConfig>("update_reliability_scoring_config", { config }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["reliability-scoring-config"] });
+      queryClient.invalidateQueries({ queryKey: ["service-reliability"] });
+      queryClient.invalidateQueries({ queryKey: ["reliability-history"] });
+    },
+  });
+}
+
+export function useRebuildReliabilityHistory() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => tauriInvoke<number>("rebuild_reliability_history"),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["reliability-history"] });
+    },
+  });
+}
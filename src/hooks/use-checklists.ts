@@ -8,6 +8,7 @@ import type {
   CreateIncidentChecklistRequest,
   ChecklistItem,
   ToggleChecklistItemRequest,
+  ChecklistCompliance,
 } from "@/types/checklist";
 
 // ── Template Hooks ────────────────────────────────────────────────
@@ -108,6 +109,22 @@ export function useToggleChecklistItem() {
     }) => tauriInvoke<ChecklistItem>("toggle_checklist_item", { itemId, req }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["incident-checklists"] });
+      queryClient.invalidateQueries({ queryKey: ["checklist-compliance"] });
     },
   });
 }
+
+// ── Compliance ────────────────────────────────────────────────────
+
+export function useChecklistCompliance(startDate: string, endDate: string) {
+  return useQuery({
+    queryKey: ["checklist-compliance", startDate, endDate],
+    queryFn: () =>
+      tauriInvoke<ChecklistCompliance>("get_checklist_compliance", {
+        startDate,
+        endDate,
+      }),
+    enabled: !!startDate && !!endDate,
+    staleTime: 30000,
+  });
+}
@@ -0,0 +1,77 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { tauriInvoke } from "@/lib/tauri";
+import type { MetricFilters } from "@/types/metrics";
+import type {
+  CostModel,
+  CostSummary,
+  IncidentCost,
+  SetCostOverrideRequest,
+} from "@/types/cost";
+
+export function useCostModel() {
+  return useQuery({
+    queryKey: ["cost-model"],
+    queryFn: () => tauriInvoke<CostModel>("get_cost_model"),
+  });
+}
+
+export function useUpdateCostModel() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (model: CostModel) =>
+      tauriInvoke<CostModel>("update_cost_model", { model }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["cost-model"] });
+      queryClient.invalidateQueries({ queryKey: ["incident-cost"] });
+      queryClient.invalidateQueries({ queryKey: ["cost-summary"] });
+      queryClient.invalidateQueries({ queryKey: ["dashboard"] });
+    },
+  });
+}
+
+export function useIncidentCost(incidentId: string | undefined) {
+  return useQuery({
+    queryKey: ["incident-cost", incidentId],
+    queryFn: () => tauriInvoke<IncidentCost>("get_incident_cost", { incidentId }),
+    enabled: !!incidentId,
+  });
+}
+
+export function useSetIncidentCostOverride() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (req: SetCostOverrideRequest) =>
+      tauriInvoke<IncidentCost>("set_incident_cost_override", { req }),
+    onSuccess: (_data, req) => {
+      queryClient.invalidateQueries({ queryKey: ["incident-cost", req.incident_id] });
+      queryClient.invalidateQueries({ queryKey: ["cost-summary"] });
+      queryClient.invalidateQueries({ queryKey: ["dashboard"] });
+    },
+  });
+}
+
+export function useClearIncidentCostOverride() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (incidentId: string) =>
+      tauriInvoke<IncidentCost>("clear_incident_cost_override", { incidentId }),
+    onSuccess: (_data, incidentId) => {
+      queryClient.invalidateQueries({ queryKey: ["incident-cost", incidentId] });
+      queryClient.invalidateQueries({ queryKey: ["cost-summary"] });
+      queryClient.invalidateQueries({ queryKey: ["dashboard"] });
+    },
+  });
+}
+
+export function useCostSummary(
+  startDate: string,
+  endDate: string,
+  filters?: MetricFilters
+) {
+  return useQuery({
+    queryKey: ["cost-summary", startDate, endDate, filters],
+    queryFn: () =>
+      tauriInvoke<CostSummary>("get_cost_summary", { startDate, endDate, filters }),
+    enabled: !!startDate && !!endDate,
+  });
+}
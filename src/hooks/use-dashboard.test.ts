@@ -140,6 +140,8 @@ describe("useDashboardConfig", () => {
     mtta: true,
     recurrence_rate: true,
     avg_tickets: true,
+    estimated_cost: true,
+    checklist_completion: true,
     by_severity: true,
     by_service: true,
     heatmap: true,
@@ -40,7 +40,8 @@ export function useDashboardConfig() {
       });
       if (!raw) return defaultConfig();
       try {
-        return JSON.parse(raw) as DashboardCardConfig;
+        // Merge over defaults so cards added later start out visible
+        return { ...defaultConfig(), ...JSON.parse(raw) } as DashboardCardConfig;
       } catch {
         return defaultConfig();
       }
@@ -71,6 +72,8 @@ export interface DashboardCardConfig {
   mtta: boolean;
   recurrence_rate: boolean;
   avg_tickets: boolean;
+  estimated_cost: boolean;
+  checklist_completion: boolean;
   by_severity: boolean;
   by_service: boolean;
   heatmap: boolean;
@@ -85,6 +88,8 @@ function defaultConfig(): DashboardCardConfig {
     mtta: true,
     recurrence_rate: true,
     avg_tickets: true,
+    estimated_cost: true,
+    checklist_completion: true,
     by_severity: true,
     by_service: true,
     heatmap: true,
@@ -1,21 +1,45 @@
-import { useMutation } from "@tanstack/react-query";
+import { useMutation, useQuery } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
 import type { IncidentFilters } from "@/types/incident";
+import type { ExportColumn, ExportOptions } from "@/types/export";
+
+interface ExportRequest {
+  filters: IncidentFilters;
+  options?: ExportOptions;
+}
 
 export function useExportCsv() {
   return useMutation({
-    mutationFn: (filters: IncidentFilters) =>
+    mutationFn: ({ filters, options }: ExportRequest) =>
       tauriInvoke<string>("export_incidents_csv", {
         filtersJson: JSON.stringify(filters),
+        options: options ?? null,
       }),
   });
 }
 
 export function useExportJson() {
   return useMutation({
-    mutationFn: (filters: IncidentFilters) =>
+    mutationFn: ({ filters, options }: ExportRequest) =>
       tauriInvoke<string>("export_incidents_json", {
         filtersJson: JSON.stringify(filters),
+        options: options ?? null,
       }),
   });
 }
+
+export function useExportXlsx() {
+  return useMutation({
+    mutationFn: ({ filters }: ExportRequest) =>
+      tauriInvoke<string>("export_incidents_xlsx", {
+        filtersJson: JSON.stringify(filters),
+      }),
+  });
+}
+
+export function useExportColumns() {
+  return useQuery({
+    queryKey: ["export-columns"],
+    queryFn: () => tauriInvoke<ExportColumn[]>("list_export_columns"),
+  });
+}
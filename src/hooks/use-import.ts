@@ -6,6 +6,34 @@ import { tauriInvoke } from "@/lib/tauri";
 export interface ColumnMapping {
   mappings: Record<string, string>;
   default_values: Record<string, string>;
+  /** Built-in preset whose value translations apply, e.g. "jira". */
+  preset?: string | null;
+}
+
+export interface ImportPresetMapping {
+  id: string;
+  name: string;
+  mapping: ColumnMapping;
+}
+
+export interface CsvHeaders {
+  headers: string[];
+  presets: ImportPresetMapping[];
+  detected_preset: string | null;
+}
+
+export type ImportMode = "create" | "upsert";
+
+export interface ImportOptions {
+  mode: ImportMode;
+  /** Incident field matched against existing incidents in upsert mode. */
+  match_field: "external_ref" | "title";
+}
+
+export interface FieldChange {
+  field: string;
+  old_value: string;
+  new_value: string;
 }
 
 export interface PreviewRow {
@@ -18,6 +46,9 @@ export interface PreviewRow {
   detected_at: string;
   row_status: "ready" | "warning" | "error";
   messages: string[];
+  action: "create" | "update" | "unchanged" | "conflict";
+  matched_incident_id: string | null;
+  changes: FieldChange[];
 }
 
 export interface ImportPreview {
@@ -26,10 +57,15 @@ export interface ImportPreview {
   error_count: number;
   ready_count: number;
   warning_count: number;
+  create_count: number;
+  update_count: number;
+  unchanged_count: number;
 }
 
 export interface ImportResult {
   created: number;
+  updated: number;
+  unchanged: number;
   skipped: number;
   errors: string[];
 }
@@ -58,7 +94,7 @@ export interface BackupImportResult {
 export function useParseCSVHeaders() {
   return useMutation({
     mutationFn: (filePath: string) =>
-      tauriInvoke<string[]>("parse_csv_headers", { filePath }),
+      tauriInvoke<CsvHeaders>("parse_csv_headers", { filePath }),
   });
 }
 
@@ -67,11 +103,17 @@ export function usePreviewImport() {
     mutationFn: ({
       filePath,
       mapping,
+      options,
     }: {
       filePath: string;
       mapping: ColumnMapping;
+      options: ImportOptions;
     }) =>
-      tauriInvoke<ImportPreview>("preview_csv_import", { filePath, mapping }),
+      tauriInvoke<ImportPreview>("preview_csv_import", {
+        filePath,
+        mapping,
+        options,
+      }),
   });
 }
 
@@ -81,13 +123,49 @@ export function useExecuteImport() {
     mutationFn: ({
       filePath,
       mapping,
+      options,
     }: {
       filePath: string;
       mapping: ColumnMapping;
+      options: ImportOptions;
     }) =>
-      tauriInvoke<ImportResult>("execute_csv_import", { filePath, mapping }),
+      tauriInvoke<ImportResult>("execute_csv_import", {
+        filePath,
+        mapping,
+        options,
+      }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["incidents"] });
+    },
+  });
+}
+
+export function usePreviewJsonImport() {
+  return useMutation({
+    mutationFn: ({
+      filePath,
+      options,
+    }: {
+      filePath: string;
+      options: ImportOptions;
+    }) => tauriInvoke<ImportPreview>("preview_json_import", { filePath, options }),
+  });
+}
+
+export function useExecuteJsonImport() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({
+      filePath,
+      options,
+    }: {
+      filePath: string;
+      options: ImportOptions;
+    }) => tauriInvoke<ImportResult>("execute_json_import", { filePath, options }),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["incidents"] });
+      queryClient.invalidateQueries({ queryKey: ["action-items"] });
+      queryClient.invalidateQueries({ queryKey: ["all-tags"] });
     },
   });
 }
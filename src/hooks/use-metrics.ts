@@ -1,6 +1,14 @@
-import { useQuery } from "@tanstack/react-query";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
-import type { DashboardData, MetricFilters } from "@/types/metrics";
+import type {
+  DashboardData,
+  MetricFilters,
+  MetricTimeseries,
+  RollupConsistencyReport,
+  RollupRebuildResult,
+  TimeseriesRequest,
+} from "@/types/metrics";
+import type { ForecastRequest, IncidentForecast } from "@/types/forecast";
 
 const EMPTY_FILTERS: MetricFilters = {};
 
@@ -18,3 +26,41 @@ export function useDashboardData(
     staleTime: 30000,
   });
 }
+
+export function useMetricTimeseries(request: TimeseriesRequest | null) {
+  return useQuery({
+    queryKey: ["metric-timeseries", request],
+    queryFn: () =>
+      tauriInvoke<MetricTimeseries>("get_metric_timeseries", { request }),
+    enabled: !!request && !!request.start_date && !!request.end_date,
+    staleTime: 30000,
+  });
+}
+
+export function useIncidentForecast(request: ForecastRequest | null) {
+  return useQuery({
+    queryKey: ["incident-forecast", request],
+    queryFn: () =>
+      tauriInvoke<IncidentForecast>("get_incident_forecast", { request }),
+    enabled: !!request,
+    staleTime: 30000,
+  });
+}
+
+export function useCheckMetricRollups() {
+  return useMutation({
+    mutationFn: () =>
+      tauriInvoke<RollupConsistencyReport>("check_metric_rollups"),
+  });
+}
+
+export function useRebuildMetricRollups() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => tauriInvoke<RollupRebuildResult>("rebuild_metric_rollups"),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["dashboard"] });
+      queryClient.invalidateQueries({ queryKey: ["incident-forecast"] });
+    },
+  });
+}
@@ -1,5 +1,6 @@
 import { useMutation, useQuery } from "@tanstack/react-query";
 import { tauriInvoke } from "../lib/tauri";
+import type { RedactionProfile } from "../types/reports";
 
 type PirBrief = {
   markdown: string;
@@ -7,15 +8,30 @@ type PirBrief = {
 
 export function usePirBriefMarkdown() {
   return useMutation({
-    mutationFn: (incidentId: string) =>
-      tauriInvoke<PirBrief>("generate_pir_brief_markdown", { incidentId })
+    mutationFn: ({ incidentId, redaction }: { incidentId: string; redaction?: RedactionProfile | null }) =>
+      tauriInvoke<PirBrief>("generate_pir_brief_markdown", { incidentId, redaction: redaction ?? null })
   });
 }
 
 export function useGeneratePirBriefFile() {
   return useMutation({
-    mutationFn: ({ incidentId, format }: { incidentId: string; format: "docx" | "pdf" }) =>
-      tauriInvoke<string>("generate_pir_brief_file", { incidentId, format })
+    mutationFn: ({
+      incidentId,
+      format,
+      redaction,
+      includeStakeholderUpdates,
+    }: {
+      incidentId: string;
+      format: "docx" | "pdf";
+      redaction?: RedactionProfile | null;
+      includeStakeholderUpdates?: boolean;
+    }) =>
+      tauriInvoke<string>("generate_pir_brief_file", {
+        incidentId,
+        format,
+        redaction: redaction ?? null,
+        includeStakeholderUpdates: includeStakeholderUpdates ?? false,
+      })
   });
 }
 
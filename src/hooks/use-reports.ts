@@ -1,6 +1,21 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { tauriInvoke } from "@/lib/tauri";
-import type { ReportConfig, DiscussionPoint, ReportHistoryEntry } from "@/types/reports";
+import type {
+  ReportConfig,
+  DiscussionPoint,
+  ReportHistoryEntry,
+  ReportTemplate,
+  CreateReportTemplateRequest,
+  UpdateReportTemplateRequest,
+  DiscussionRule,
+  CreateDiscussionRuleRequest,
+  UpdateDiscussionRuleRequest,
+  RuleEvaluation,
+  RegeneratedReport,
+  SnapshotDiff,
+  RedactionProfile,
+  ReportTheme,
+} from "@/types/reports";
 
 export function useGenerateReport() {
   return useMutation({
@@ -67,9 +82,159 @@ export function useDeleteReportHistory() {
   });
 }
 
+export function useRegenerateFromSnapshot() {
+  return useMutation({
+    mutationFn: (historyId: string) =>
+      tauriInvoke<RegeneratedReport>("regenerate_from_snapshot", { historyId }),
+  });
+}
+
+export function useReportSnapshotDiff(fromId: string | null, toId: string | null) {
+  return useQuery({
+    queryKey: ["report-snapshot-diff", fromId, toId],
+    queryFn: () =>
+      tauriInvoke<SnapshotDiff>("diff_report_snapshots", {
+        fromId: fromId as string,
+        toId: toId as string,
+      }),
+    enabled: fromId !== null && toId !== null,
+  });
+}
+
+export function useReportTemplates() {
+  return useQuery({
+    queryKey: ["report-templates"],
+    queryFn: () => tauriInvoke<ReportTemplate[]>("list_report_templates"),
+  });
+}
+
+export function useCreateReportTemplate() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (req: CreateReportTemplateRequest) =>
+      tauriInvoke<ReportTemplate>("create_report_template", { req }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["report-templates"] });
+    },
+  });
+}
+
+export function useUpdateReportTemplate() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, req }: { id: string; req: UpdateReportTemplateRequest }) =>
+      tauriInvoke<ReportTemplate>("update_report_template", { id, req }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["report-templates"] });
+    },
+  });
+}
+
+export function useDeleteReportTemplate() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => tauriInvoke<void>("delete_report_template", { id }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["report-templates"] });
+    },
+  });
+}
+
+export function useDiscussionRules() {
+  return useQuery({
+    queryKey: ["discussion-rules"],
+    queryFn: () => tauriInvoke<DiscussionRule[]>("list_discussion_rules"),
+  });
+}
+
+export function useCreateDiscussionRule() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (req: CreateDiscussionRuleRequest) =>
+      tauriInvoke<DiscussionRule>("create_discussion_rule", { req }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["discussion-rules"] });
+      qc.invalidateQueries({ queryKey: ["discussion-points"] });
+      qc.invalidateQueries({ queryKey: ["discussion-rule-dry-run"] });
+    },
+  });
+}
+
+export function useUpdateDiscussionRule() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, req }: { id: string; req: UpdateDiscussionRuleRequest }) =>
+      tauriInvoke<DiscussionRule>("update_discussion_rule", { id, req }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["discussion-rules"] });
+      qc.invalidateQueries({ queryKey: ["discussion-points"] });
+      qc.invalidateQueries({ queryKey: ["discussion-rule-dry-run"] });
+    },
+  });
+}
+
+export function useDeleteDiscussionRule() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => tauriInvoke<void>("delete_discussion_rule", { id }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["discussion-rules"] });
+      qc.invalidateQueries({ queryKey: ["discussion-points"] });
+      qc.invalidateQueries({ queryKey: ["discussion-rule-dry-run"] });
+    },
+  });
+}
+
+export function useDryRunDiscussionRules(quarterId: string | null) {
+  return useQuery({
+    queryKey: ["discussion-rule-dry-run", quarterId],
+    queryFn: () =>
+      tauriInvoke<RuleEvaluation[]>("dry_run_discussion_rules", {
+        quarterId: quarterId as string,
+      }),
+    enabled: quarterId !== null && quarterId.length > 0,
+  });
+}
+
 export function useGenerateNarrative() {
   return useMutation({
     mutationFn: (quarterId: string) =>
       tauriInvoke<string>("generate_narrative", { quarterId }),
   });
 }
+
+export function useRedactionProfile() {
+  return useQuery({
+    queryKey: ["redaction-profile"],
+    queryFn: () => tauriInvoke<RedactionProfile>("get_redaction_profile"),
+  });
+}
+
+export function useUpdateRedactionProfile() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (profile: RedactionProfile) =>
+      tauriInvoke<RedactionProfile>("update_redaction_profile", { profile }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["redaction-profile"] });
+    },
+  });
+}
+
+export function useReportTheme() {
+  return useQuery({
+    queryKey: ["report-theme"],
+    queryFn: () => tauriInvoke<ReportTheme>("get_report_theme"),
+  });
+}
+
+export function useUpdateReportTheme() {
+  const qc = useQueryClient();
+  return useMutation({
+    mutationFn: (theme: ReportTheme) =>
+      tauriInvoke<ReportTheme>("update_report_theme", { theme }),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: ["report-theme"] });
+    },
+  });
+}
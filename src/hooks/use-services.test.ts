@@ -127,6 +127,7 @@ describe("service dependency mutations", () => {
     expect(invalidateSpy).toHaveBeenCalledWith({
       queryKey: ["service-dependents", "svc-2"],
     });
+    expect(invalidateSpy).toHaveBeenCalledWith({ queryKey: ["blast-radius"] });
   });
 
   it("invalidates service-specific dependency caches after remove", async () => {
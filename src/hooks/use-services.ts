@@ -6,6 +6,9 @@ import type {
   UpdateServiceRequest,
   ServiceDependency,
   CreateServiceDependencyRequest,
+  BlastRadius,
+  ImpactedService,
+  CriticalPathService,
 } from "@/types/incident";
 
 export function useServices() {
@@ -100,6 +103,9 @@ export function useAddServiceDependency() {
       queryClient.invalidateQueries({
         queryKey: ["service-dependents", variables.depends_on_service_id],
       });
+      queryClient.invalidateQueries({ queryKey: ["blast-radius"] });
+      queryClient.invalidateQueries({ queryKey: ["affected-service-suggestions"] });
+      queryClient.invalidateQueries({ queryKey: ["critical-path-services"] });
     },
   });
 }
@@ -122,6 +128,41 @@ export function useRemoveServiceDependency() {
       queryClient.invalidateQueries({
         queryKey: ["service-dependents", variables.dependsOnServiceId],
       });
+      queryClient.invalidateQueries({ queryKey: ["blast-radius"] });
+      queryClient.invalidateQueries({ queryKey: ["affected-service-suggestions"] });
+      queryClient.invalidateQueries({ queryKey: ["critical-path-services"] });
     },
   });
 }
+
+// Dependency graph analysis hooks
+
+export function useBlastRadius(serviceId: string | undefined) {
+  return useQuery({
+    queryKey: ["blast-radius", serviceId],
+    queryFn: () => tauriInvoke<BlastRadius>("get_blast_radius", { serviceId }),
+    enabled: !!serviceId,
+  });
+}
+
+export function useAffectedServiceSuggestions(serviceId: string | undefined) {
+  return useQuery({
+    queryKey: ["affected-service-suggestions", serviceId],
+    queryFn: () =>
+      tauriInvoke<ImpactedService[]>("suggest_affected_services", { serviceId }),
+    enabled: !!serviceId,
+  });
+}
+
+export function useCriticalPathServices(startDate: string, endDate: string) {
+  return useQuery({
+    queryKey: ["critical-path-services", startDate, endDate],
+    queryFn: () =>
+      tauriInvoke<CriticalPathService[]>("get_critical_path_services", {
+        startDate,
+        endDate,
+      }),
+    enabled: !!startDate && !!endDate,
+    staleTime: 30000,
+  });
+}
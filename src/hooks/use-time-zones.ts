@@ -0,0 +1,41 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { tauriInvoke } from "@/lib/tauri";
+import type { TimeZoneConfig, TimeZoneOption } from "@/types/timezone";
+
+export function useTimeZoneConfig() {
+  return useQuery({
+    queryKey: ["time-zone-config"],
+    queryFn: () => tauriInvoke<TimeZoneConfig>("get_time_zone_config"),
+  });
+}
+
+export function useUpdateTimeZoneConfig() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (config: TimeZoneConfig) =>
+      tauriInvoke<TimeZoneConfig>("update_time_zone_config", { config }),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["time-zone-config"] });
+      queryClient.invalidateQueries({ queryKey: ["dashboard"] });
+      queryClient.invalidateQueries({ queryKey: ["heatmap"] });
+      queryClient.invalidateQueries({ queryKey: ["by-hour"] });
+      queryClient.invalidateQueries({ queryKey: ["metric-timeseries"] });
+      queryClient.invalidateQueries({ queryKey: ["service-reliability"] });
+      queryClient.invalidateQueries({ queryKey: ["escalation-funnel"] });
+      queryClient.invalidateQueries({ queryKey: ["cost-summary"] });
+      queryClient.invalidateQueries({ queryKey: ["incident-forecast"] });
+      queryClient.invalidateQueries({ queryKey: ["responder-load"] });
+      queryClient.invalidateQueries({ queryKey: ["checklist-compliance"] });
+      queryClient.invalidateQueries({ queryKey: ["critical-path-services"] });
+      queryClient.invalidateQueries({ queryKey: ["reliability-history"] });
+    },
+  });
+}
+
+export function useTimeZones() {
+  return useQuery({
+    queryKey: ["time-zones"],
+    queryFn: () => tauriInvoke<TimeZoneOption[]>("list_time_zones"),
+    staleTime: Infinity,
+  });
+}
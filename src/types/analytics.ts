@@ -31,6 +31,53 @@ export interface ServiceReliabilityScore {
   mttr_minutes: number;
   mttr_formatted: string;
   sla_compliance_pct: number;
+  tier: string;
+  sla_breaches: number;
+  downtime_minutes: number;
+  recurring_count: number;
+  /** 0-100, higher is more reliable. */
+  score: number;
+  breakdown: ScoreBreakdown;
+}
+
+export interface SeverityPenalty {
+  severity: string;
+  count: number;
+  penalty: number;
+}
+
+/** Penalties already include the tier multiplier. */
+export interface ScoreBreakdown {
+  tier: string;
+  tier_multiplier: number;
+  incident_penalty: number;
+  by_severity: SeverityPenalty[];
+  downtime_penalty: number;
+  recurrence_penalty: number;
+  sla_breach_penalty: number;
+  total_penalty: number;
+}
+
+export interface ReliabilityScoringConfig {
+  severity_weights: Record<string, number>;
+  impact_multipliers: Record<string, number>;
+  tier_multipliers: Record<string, number>;
+  downtime_penalty_per_hour: number;
+  recurrence_penalty: number;
+  sla_breach_penalty: number;
+}
+
+export interface ReliabilityHistoryEntry {
+  quarter_id: string;
+  quarter_label: string;
+  service_id: string;
+  service_name: string;
+  score: number;
+  incident_count: number;
+  breakdown: ScoreBreakdown;
+  /** False for the quarter in progress. */
+  is_final: boolean;
+  computed_at: string;
 }
 
 export interface EscalationFunnelEntry {
@@ -38,3 +85,38 @@ export interface EscalationFunnelEntry {
   count: number;
   percentage: number;
 }
+
+export interface WorkingHoursConfig {
+  /** "HH:MM" local time. */
+  workday_start: string;
+  workday_end: string;
+  /** ISO weekdays, 1 = Monday .. 7 = Sunday. */
+  working_days: number[];
+  holidays: string[];
+  /** Defaults to the organization time zone when null. */
+  time_zone: string | null;
+}
+
+export interface LoadSummary {
+  key: string;
+  label: string;
+  incident_count: number;
+  engagement_count: number;
+  after_hours_engagements: number;
+  weekend_engagements: number;
+  engaged_hours: number;
+  after_hours_hours: number;
+  engaged_days: number;
+  longest_streak_days: number;
+  after_hours_rate: number;
+}
+
+export interface ResponderLoadReport {
+  start_date: string;
+  end_date: string;
+  time_zone: string;
+  working_hours: WorkingHoursConfig;
+  responders: LoadSummary[];
+  teams: LoadSummary[];
+  services: LoadSummary[];
+}
@@ -59,3 +59,39 @@ export interface CreateIncidentChecklistRequest {
 export interface ToggleChecklistItemRequest {
   checked_by?: string;
 }
+
+export interface ComplianceStats {
+  checklist_count: number;
+  item_count: number;
+  checked_count: number;
+  completion_rate: number;
+  resolved_item_count: number;
+  unchecked_at_resolution: number;
+  skip_rate: number;
+  median_minutes_to_check: number | null;
+}
+
+export interface ComplianceGroup {
+  key: string;
+  label: string;
+  stats: ComplianceStats;
+}
+
+export interface ItemCompliance {
+  key: string;
+  template_id: string;
+  template_name: string;
+  label: string;
+  stats: ComplianceStats;
+}
+
+export interface ChecklistCompliance {
+  start_date: string;
+  end_date: string;
+  overall: ComplianceStats;
+  templates: ComplianceGroup[];
+  items: ItemCompliance[];
+  most_skipped: ItemCompliance[];
+  by_service: ComplianceGroup[];
+  by_severity: ComplianceGroup[];
+}
@@ -0,0 +1,48 @@
+export interface CostModel {
+  currency: string;
+  per_affected_user_minute: number;
+  per_ticket: number;
+  per_engineer_hour: number;
+  service_revenue_per_hour: Record<string, number>;
+}
+
+export interface CostBreakdown {
+  user_impact: number;
+  tickets: number;
+  responder_effort: number;
+  lost_revenue: number;
+  estimated_total: number;
+}
+
+export interface IncidentCost {
+  incident_id: string;
+  title: string;
+  service_id: string;
+  service_name: string;
+  started_at: string;
+  breakdown: CostBreakdown;
+  override_amount: number | null;
+  override_note: string;
+  total_cost: number;
+}
+
+export interface ServiceCost {
+  service_id: string;
+  service_name: string;
+  incident_count: number;
+  total_cost: number;
+}
+
+export interface CostSummary {
+  currency: string;
+  total_cost: number;
+  incident_count: number;
+  overridden_count: number;
+  by_service: ServiceCost[];
+}
+
+export interface SetCostOverrideRequest {
+  incident_id: string;
+  amount: number;
+  note?: string;
+}
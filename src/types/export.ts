@@ -0,0 +1,53 @@
+export type ExportEncoding = "utf8" | "utf8_bom" | "utf16_le";
+export type JsonExportMode = "flat" | "nested";
+
+export interface ExportOptions {
+  /** Column keys in output order; empty for the standard columns. */
+  columns: string[];
+  delimiter: string;
+  encoding: ExportEncoding;
+  /** strftime pattern for timestamps; empty keeps ISO 8601. */
+  date_format: string;
+  list_delimiter: string;
+  json_mode: JsonExportMode;
+}
+
+export interface ExportColumn {
+  key: string;
+  label: string;
+}
+
+export const DEFAULT_EXPORT_OPTIONS: ExportOptions = {
+  columns: [],
+  delimiter: ",",
+  encoding: "utf8",
+  date_format: "",
+  list_delimiter: "; ",
+  json_mode: "flat",
+};
+
+export const DELIMITER_LABELS: Record<string, string> = {
+  ",": "Comma",
+  ";": "Semicolon",
+  "\t": "Tab",
+  "|": "Pipe",
+};
+
+export const ENCODING_LABELS: Record<ExportEncoding, string> = {
+  utf8: "UTF-8",
+  utf8_bom: "UTF-8 with BOM (Excel)",
+  utf16_le: "UTF-16 LE",
+};
+
+export const DATE_FORMAT_PRESETS: { label: string; format: string }[] = [
+  { label: "ISO 8601 (as stored)", format: "" },
+  { label: "2026-03-10 14:30", format: "%Y-%m-%d %H:%M" },
+  { label: "10/03/2026 14:30", format: "%d/%m/%Y %H:%M" },
+  { label: "03/10/2026 14:30", format: "%m/%d/%Y %H:%M" },
+  { label: "2026-03-10", format: "%Y-%m-%d" },
+];
+
+export const JSON_MODE_LABELS: Record<JsonExportMode, string> = {
+  flat: "Flat (one object per incident)",
+  nested: "Nested (embed related records)",
+};
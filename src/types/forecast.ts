@@ -0,0 +1,51 @@
+import type { MetricFilters, TimeseriesBucket } from "@/types/metrics";
+
+export type ForecastMetric = "count" | "mttr";
+
+export type ForecastMethod = "seasonal_naive" | "exponential_smoothing";
+
+export interface ForecastRequest {
+  metric: ForecastMetric;
+  /** Quarters to forecast past the last complete quarter (1-4). */
+  horizon?: number;
+  history_quarters?: number;
+  /** YYYY-MM-DD; defaults to today in the organization time zone. */
+  as_of?: string | null;
+  filters?: MetricFilters;
+}
+
+export interface ForecastPoint {
+  label: string;
+  start: string;
+  end: string;
+  expected: number;
+  lower_80: number;
+  upper_80: number;
+  lower_95: number;
+  upper_95: number;
+}
+
+export interface BacktestSummary {
+  folds: number;
+  mae: number;
+  mape: number | null;
+  coverage_80: number;
+  coverage_95: number;
+}
+
+export interface ForecastSeries {
+  key: string;
+  label: string;
+  method: ForecastMethod;
+  history: number[];
+  points: ForecastPoint[];
+  backtest: BacktestSummary | null;
+}
+
+export interface IncidentForecast {
+  metric: ForecastMetric;
+  history_buckets: TimeseriesBucket[];
+  forecast_buckets: TimeseriesBucket[];
+  overall: ForecastSeries | null;
+  by_service: ForecastSeries[];
+}
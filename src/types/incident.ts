@@ -182,6 +182,32 @@ export interface CreateServiceDependencyRequest {
   dependency_type?: string;
 }
 
+export interface ImpactedService {
+  service_id: string;
+  service_name: string;
+  depth: number;
+  weight: number;
+  dependency_type: string;
+  path: string[];
+}
+
+export interface BlastRadius {
+  service_id: string;
+  service_name: string;
+  upstream_dependents: ImpactedService[];
+  downstream_dependencies: ImpactedService[];
+  impact_score: number;
+}
+
+export interface CriticalPathService {
+  service_id: string;
+  service_name: string;
+  propagated_incidents: number;
+  source_incidents: number;
+  dependent_count: number;
+  impact_score: number;
+}
+
 export interface QuarterConfig {
   id: string;
   fiscal_year: number;
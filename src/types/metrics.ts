@@ -1,3 +1,5 @@
+import type { ServiceCost } from "@/types/cost";
+
 export interface MetricResult {
   value: number;
   previous_value: number | null;
@@ -25,6 +27,13 @@ export interface QuarterlyTrends {
   incident_count: number[];
   recurrence_rate: number[];
   avg_tickets: number[];
+  /** Incident counts per severity, aligned with `quarters`. */
+  severity_counts: SeverityTrend[];
+}
+
+export interface SeverityTrend {
+  severity: string;
+  counts: number[];
 }
 
 export interface DashboardData {
@@ -39,6 +48,9 @@ export interface DashboardData {
   trends: QuarterlyTrends;
   total_incidents: number;
   period_label: string;
+  estimated_cost: MetricResult;
+  cost_by_service: ServiceCost[];
+  checklist_completion: MetricResult;
 }
 
 export interface MetricFilters {
@@ -56,3 +68,67 @@ export interface HourCount {
   hour: number;
   count: number;
 }
+
+export type TimeseriesMetric =
+  | "count"
+  | "mttr"
+  | "mtta"
+  | "downtime"
+  | "affected_users"
+  | "tickets";
+
+export type TimeseriesBucketSize = "day" | "week" | "month" | "quarter";
+
+export type TimeseriesGroupBy = "service" | "severity" | "tag" | "tier";
+
+export interface TimeseriesRequest {
+  metric: TimeseriesMetric;
+  bucket: TimeseriesBucketSize;
+  group_by?: TimeseriesGroupBy | null;
+  start_date: string;
+  end_date: string;
+  filters?: MetricFilters;
+}
+
+export interface TimeseriesBucket {
+  start: string;
+  end: string;
+  label: string;
+}
+
+export interface TimeseriesSeries {
+  key: string;
+  label: string;
+  values: number[];
+  sample_counts: number[];
+}
+
+export interface MetricTimeseries {
+  metric: TimeseriesMetric;
+  bucket: TimeseriesBucketSize;
+  group_by: TimeseriesGroupBy | null;
+  buckets: TimeseriesBucket[];
+  series: TimeseriesSeries[];
+}
+
+export interface RollupMismatch {
+  day: string;
+  service_id: string;
+  severity: string;
+  impact: string;
+  detail: string;
+}
+
+export interface RollupConsistencyReport {
+  consistent: boolean;
+  rollup_rows: number;
+  raw_rows: number;
+  mismatch_count: number;
+  mismatches: RollupMismatch[];
+  checked_at: string;
+}
+
+export interface RollupRebuildResult {
+  days: number;
+  rows: number;
+}
@@ -1,5 +1,6 @@
 export interface ReportSections {
   executive_summary: boolean;
+  service_context: boolean;
   metrics_overview: boolean;
   incident_timeline: boolean;
   incident_breakdowns: boolean;
@@ -7,26 +8,265 @@ export interface ReportSections {
   qoq_comparison: boolean;
   discussion_points: boolean;
   action_items: boolean;
+  cost_impact: boolean;
+  outlook: boolean;
+  responder_load: boolean;
+  checklist_compliance: boolean;
 }
 
-export type ReportFormat = "docx" | "pdf";
+export type ReportFormat = "docx" | "pdf" | "html" | "md";
+
+/** Save-dialog filter name for each report format; the format is also the file extension. */
+export const REPORT_FORMAT_LABELS: Record<ReportFormat, string> = {
+  docx: "Word Document",
+  pdf: "PDF Document",
+  html: "HTML Page",
+  md: "Markdown",
+};
+
+/** How the comparison period is derived from the report period. */
+export type ReportComparison = "previous_period" | "same_period_last_year" | "none";
 
 export interface ReportConfig {
   quarter_id: string | null;
+  /** Whole fiscal year, used when no quarter is given. */
   fiscal_year: number | null;
+  /** Custom date range (YYYY-MM-DD, inclusive); takes precedence over quarter and fiscal year. */
+  date_from?: string | null;
+  date_to?: string | null;
+  /** Limit the report to these services; empty covers every service. */
+  service_ids?: string[];
+  comparison?: ReportComparison;
   title: string;
   introduction: string;
   sections: ReportSections;
   chart_images: Record<string, string>;
   format: ReportFormat;
+  /** Stored template whose section layout replaces `sections`. */
+  template_id?: string | null;
+  /** Redact the report for sharing outside the team. */
+  redaction?: RedactionProfile | null;
+}
+
+export type ReportFont = "sans" | "serif";
+
+export const REPORT_FONT_LABELS: Record<ReportFont, string> = {
+  sans: "DejaVu Sans",
+  serif: "DejaVu Serif",
+};
+
+export type CoverLayout = "compact" | "full_page";
+
+export const COVER_LAYOUT_LABELS: Record<CoverLayout, string> = {
+  compact: "Compact (title at the top of the first page)",
+  full_page: "Full cover page",
+};
+
+/** Branding applied to DOCX and PDF reports. */
+export interface ReportTheme {
+  /** Base64-encoded PNG, without a data: prefix. */
+  logo_png: string | null;
+  primary_color: string;
+  accent_color: string;
+  font: ReportFont;
+  font_size: number;
+  header_text: string;
+  footer_text: string;
+  page_numbers: boolean;
+  confidentiality_banner: string;
+  cover_layout: CoverLayout;
+}
+
+export type PeopleRedaction = "keep" | "drop" | "pseudonymize";
+
+export const PEOPLE_REDACTION_LABELS: Record<PeopleRedaction, string> = {
+  keep: "Keep names",
+  drop: "Remove names",
+  pseudonymize: "Replace with aliases (Person 1, Team 1)",
+};
+
+export interface RedactionProfile {
+  people: PeopleRedaction;
+  hidden_custom_fields: string[];
+  hidden_tags: string[];
+  generalize_services: boolean;
+  strip_notes: boolean;
+  mask_patterns: string[];
+}
+
+/** Common mask patterns offered when editing a redaction profile. */
+export const MASK_PATTERN_PRESETS: { label: string; pattern: string }[] = [
+  { label: "Email addresses", pattern: "[\\w.+-]+@[\\w-]+(\\.[\\w-]+)+" },
+  { label: "IPv4 addresses", pattern: "\\b\\d{1,3}(\\.\\d{1,3}){3}\\b" },
+  { label: "Hostnames", pattern: "(?i)\\b[a-z0-9-]+(\\.[a-z0-9-]+)*\\.(com|net|org|io|local|internal)\\b" },
+];
+
+export type ReportSectionKind = keyof ReportSections | "custom";
+
+export interface SectionOptions {
+  top_n?: number | null;
+  metrics?: string[] | null;
+  charts?: string[] | null;
 }
 
+export interface TemplateSection {
+  kind: ReportSectionKind;
+  /** Heading for custom sections; supports {{placeholders}}. */
+  title?: string;
+  /** Markdown body for custom sections; supports {{placeholders}}. */
+  body?: string;
+  options?: SectionOptions;
+}
+
+export interface ReportTemplate {
+  id: string;
+  name: string;
+  description: string;
+  sections: TemplateSection[];
+  is_default: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreateReportTemplateRequest {
+  name: string;
+  description?: string;
+  sections: TemplateSection[];
+  is_default?: boolean;
+}
+
+export interface UpdateReportTemplateRequest {
+  name?: string;
+  description?: string;
+  sections?: TemplateSection[];
+  is_default?: boolean;
+}
+
+/** Values available to custom sections as {{name}}. */
+export const REPORT_PLACEHOLDERS = [
+  "title",
+  "quarter",
+  "period_start",
+  "period_end",
+  "total_incidents",
+  "critical_incidents",
+  "mttr",
+  "mtta",
+  "recurrence_rate",
+  "avg_tickets",
+  "prev_total_incidents",
+  "prev_mttr",
+  "total_cost",
+] as const;
+
 export interface DiscussionPoint {
   text: string;
   trigger: string;
   severity: string;
 }
 
+export type RuleMetric =
+  | "incident_count"
+  | "previous_incident_count"
+  | "incident_count_change_pct"
+  | "previous_incidents_now_quiet"
+  | "mttr_minutes"
+  | "mttr_change_pct"
+  | "downtime_minutes"
+  | "p0_incidents"
+  | "recurring_incidents"
+  | "avg_tickets"
+  | "recurrence_rate"
+  | "open_action_items";
+
+export const RULE_METRIC_LABELS: Record<RuleMetric, string> = {
+  incident_count: "Incident count",
+  previous_incident_count: "Previous incident count",
+  incident_count_change_pct: "Incident count change (%)",
+  previous_incidents_now_quiet: "Previous incidents, now quiet",
+  mttr_minutes: "MTTR (minutes)",
+  mttr_change_pct: "MTTR change (%)",
+  downtime_minutes: "Downtime (minutes)",
+  p0_incidents: "P0 incidents",
+  recurring_incidents: "Recurring incidents",
+  avg_tickets: "Average tickets",
+  recurrence_rate: "Recurrence rate (%)",
+  open_action_items: "Open action items",
+};
+
+export type RuleComparator = ">" | ">=" | "<" | "<=" | "=";
+
+/** What a rule is evaluated over: the whole period once, each service, or each incident. */
+export type RuleScope = "period" | "service" | "incident";
+
+export const RULE_SEVERITIES = ["critical", "high", "medium", "low"] as const;
+
+/** Values available to rule messages as {{name}}. */
+export const RULE_MESSAGE_PLACEHOLDERS = [
+  "value",
+  "threshold",
+  "current",
+  "previous",
+  "service",
+  "incident",
+  "incidents",
+] as const;
+
+export interface DiscussionRule {
+  id: string;
+  name: string;
+  metric: RuleMetric;
+  comparator: RuleComparator;
+  threshold: number;
+  scope: RuleScope;
+  message: string;
+  severity: string;
+  enabled: boolean;
+  sort_order: number;
+  is_builtin: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreateDiscussionRuleRequest {
+  name: string;
+  metric: RuleMetric;
+  comparator: RuleComparator;
+  threshold: number;
+  scope: RuleScope;
+  message: string;
+  severity: string;
+  enabled?: boolean;
+}
+
+export interface UpdateDiscussionRuleRequest {
+  name?: string;
+  metric?: RuleMetric;
+  comparator?: RuleComparator;
+  threshold?: number;
+  scope?: RuleScope;
+  message?: string;
+  severity?: string;
+  enabled?: boolean;
+}
+
+export interface RuleOutcome {
+  subject: string;
+  value: number | null;
+  fired: boolean;
+  explanation: string;
+  message: string | null;
+}
+
+export interface RuleEvaluation {
+  rule_id: string;
+  rule_name: string;
+  enabled: boolean;
+  severity: string;
+  fired: boolean;
+  outcomes: RuleOutcome[];
+}
+
 export interface ReportHistoryEntry {
   id: string;
   title: string;
@@ -36,4 +276,30 @@ export interface ReportHistoryEntry {
   file_path: string;
   config_json: string;
   file_size_bytes: number | null;
+  /** Null for reports saved before snapshots were recorded. */
+  snapshot_version: number | null;
+  artifact_sha256: string | null;
+  content_sha256: string | null;
+}
+
+export interface RegeneratedReport {
+  temp_path: string;
+  artifact_sha256: string;
+  /** Same document content as the saved report; file bytes may still differ. */
+  matches_original: boolean;
+}
+
+export interface SnapshotFieldChange {
+  field: string;
+  before: string;
+  after: string;
+}
+
+export interface SnapshotDiff {
+  from_period: string;
+  to_period: string;
+  incidents_added: { id: string; title: string }[];
+  incidents_removed: { id: string; title: string }[];
+  incidents_changed: { id: string; title: string; changes: SnapshotFieldChange[] }[];
+  metrics: { metric: string; before: number | null; after: number | null }[];
 }
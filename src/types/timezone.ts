@@ -0,0 +1,11 @@
+export interface TimeZoneConfig {
+  org_time_zone: string;
+  /** Service ID -> IANA zone name; services not listed use the org zone. */
+  service_time_zones: Record<string, string>;
+}
+
+export interface TimeZoneOption {
+  name: string;
+  standard_offset: string;
+  observes_dst: boolean;
+}
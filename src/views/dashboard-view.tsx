@@ -28,6 +28,8 @@ import { EscalationFunnel } from "@/components/dashboard/escalation-funnel";
 import { ServiceReliabilityScorecard } from "@/components/dashboard/service-reliability-scorecard";
 import { PeriodComparisonCard } from "@/components/dashboard/period-comparison-card";
 import { TrendAlerts } from "@/components/dashboard/trend-alerts";
+import { CriticalPathServices } from "@/components/dashboard/critical-path-services";
+import { ChecklistCompliancePanel } from "@/components/dashboard/checklist-compliance-panel";
 import { CHART_COLORS } from "@/lib/constants";
 import type { CategoryCount } from "@/types/metrics";
 import type { DashboardCardConfig } from "@/hooks/use-dashboard";
@@ -37,6 +39,8 @@ const DEFAULT_DASHBOARD_CONFIG: DashboardCardConfig = {
   mtta: true,
   recurrence_rate: true,
   avg_tickets: true,
+  estimated_cost: true,
+  checklist_completion: true,
   by_severity: true,
   by_service: true,
   heatmap: true,
@@ -230,6 +234,21 @@ export function DashboardView() {
             invertGood
           />
         )}
+        {config.estimated_cost && (
+          <PeriodComparisonCard
+            label="Estimated Cost"
+            metric={dashboard.estimated_cost}
+            description="Lower is better"
+          />
+        )}
+        {config.checklist_completion && (
+          <PeriodComparisonCard
+            label="Checklist Completion"
+            metric={dashboard.checklist_completion}
+            description="Higher is better"
+            invertGood
+          />
+        )}
       </div>
 
       {/* Charts Row 1 */}
@@ -319,6 +338,16 @@ export function DashboardView() {
         <ServiceReliabilityScorecard startDate={startDate} endDate={endDate} />
       )}
 
+      {/* Critical Path Services */}
+      {startDate && endDate && (
+        <CriticalPathServices startDate={startDate} endDate={endDate} />
+      )}
+
+      {/* Checklist Compliance */}
+      {startDate && endDate && (
+        <ChecklistCompliancePanel startDate={startDate} endDate={endDate} />
+      )}
+
       {/* Trend Charts */}
       {config.trends && dashboard.trends.quarters.length > 0 && (
         <TrendCharts trends={dashboard.trends} />
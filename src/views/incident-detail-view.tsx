@@ -34,6 +34,7 @@ import { AiSummaryPanel } from "@/components/ai/ai-summary-panel";
 import { SimilarIncidentsPanel } from "@/components/ai/similar-incidents-panel";
 import { RootCauseSuggestions } from "@/components/ai/root-cause-suggestions";
 import { DedupWarning } from "@/components/ai/dedup-warning";
+import { AffectedServicesHint } from "@/components/incidents/affected-services-hint";
 import { StakeholderUpdatePanel } from "@/components/incidents/stakeholder-update-panel";
 import { toast } from "@/components/ui/use-toast";
 import {
@@ -436,6 +437,9 @@ export function IncidentDetailView() {
               />
             )}
 
+            {/* Blast radius suggestions (create mode only) */}
+            {!isEditMode && <AffectedServicesHint serviceId={watchedServiceId} />}
+
             {/* Classification */}
             <Card>
               <CardHeader>
@@ -1,8 +1,9 @@
 import { useState, useCallback } from "react";
 import { save } from "@tauri-apps/plugin-dialog";
 import { format } from "date-fns";
-import { FileText, Download, Loader2, Eye, CheckSquare, Square, History, Trash2, Sparkles } from "lucide-react";
+import { FileText, Download, Loader2, Eye, CheckSquare, Square, History, Trash2, Sparkles, RefreshCw, GitCompare } from "lucide-react";
 import { useQuarters } from "@/hooks/use-quarters";
+import { useActiveServices } from "@/hooks/use-services";
 import {
   useGenerateReport,
   useSaveReport,
@@ -10,6 +11,10 @@ import {
   useReportHistory,
   useDeleteReportHistory,
   useGenerateNarrative,
+  useReportTemplates,
+  useRegenerateFromSnapshot,
+  useReportSnapshotDiff,
+  useRedactionProfile,
 } from "@/hooks/use-reports";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
@@ -27,10 +32,20 @@ import {
   TableRow,
 } from "@/components/ui/table";
 import { toast } from "@/components/ui/use-toast";
-import type { ReportSections, ReportFormat, DiscussionPoint, ReportHistoryEntry } from "@/types/reports";
+import { REPORT_FORMAT_LABELS } from "@/types/reports";
+import type {
+  ReportSections,
+  ReportFormat,
+  ReportComparison,
+  DiscussionPoint,
+  ReportHistoryEntry,
+} from "@/types/reports";
+
+type PeriodType = "quarter" | "fiscal_year" | "range";
 
 const DEFAULT_SECTIONS: ReportSections = {
   executive_summary: true,
+  service_context: true,
   metrics_overview: true,
   incident_timeline: true,
   incident_breakdowns: true,
@@ -38,10 +53,15 @@ const DEFAULT_SECTIONS: ReportSections = {
   qoq_comparison: true,
   discussion_points: true,
   action_items: true,
+  cost_impact: true,
+  outlook: false,
+  responder_load: true,
+  checklist_compliance: true,
 };
 
 const SECTION_LABELS: Record<keyof ReportSections, string> = {
   executive_summary: "Executive Summary",
+  service_context: "Service Context (service reports)",
   metrics_overview: "Metrics Overview",
   incident_timeline: "Incident Timeline",
   incident_breakdowns: "Critical Incident Breakdowns",
@@ -49,6 +69,10 @@ const SECTION_LABELS: Record<keyof ReportSections, string> = {
   qoq_comparison: "Quarter-over-Quarter Comparison",
   discussion_points: "Discussion Points",
   action_items: "Action Items",
+  cost_impact: "Cost & Business Impact",
+  outlook: "Outlook (Next-Quarter Forecast)",
+  responder_load: "Responder Load",
+  checklist_compliance: "Checklist Compliance",
 };
 
 function severityColor(severity: string): string {
@@ -68,26 +92,61 @@ function severityColor(severity: string): string {
 
 export function ReportsView() {
   const { data: quarters, isLoading: quartersLoading } = useQuarters();
+  const { data: services } = useActiveServices();
   const generateReport = useGenerateReport();
   const saveReport = useSaveReport();
   const narrativeMutation = useGenerateNarrative();
   const { data: reportHistory } = useReportHistory();
   const deleteHistory = useDeleteReportHistory();
+  const regenerate = useRegenerateFromSnapshot();
+  const [compareIds, setCompareIds] = useState<string[]>([]);
 
+  const [periodType, setPeriodType] = useState<PeriodType>("quarter");
   const [selectedQuarterId, setSelectedQuarterId] = useState<string | null>(null);
+  const [selectedFiscalYear, setSelectedFiscalYear] = useState<number | null>(null);
+  const [dateFrom, setDateFrom] = useState("");
+  const [dateTo, setDateTo] = useState("");
+  const [serviceIds, setServiceIds] = useState<string[]>([]);
+  const [comparison, setComparison] = useState<ReportComparison>("previous_period");
   const [title, setTitle] = useState("");
   const [introduction, setIntroduction] = useState("");
   const [sections, setSections] = useState<ReportSections>(DEFAULT_SECTIONS);
   const [reportFormat, setReportFormat] = useState<ReportFormat>("docx");
+  const { data: templates } = useReportTemplates();
+  const [templateId, setTemplateId] = useState<string | null>(null);
+  const { data: redactionProfile } = useRedactionProfile();
+  const [redacted, setRedacted] = useState(false);
+  const selectedTemplate = templates?.find((t) => t.id === templateId);
   const [showDiscussionPreview, setShowDiscussionPreview] = useState(false);
   const [isGenerating, setIsGenerating] = useState(false);
 
   const { data: discussionPoints, isLoading: discussionLoading } =
     useDiscussionPoints(showDiscussionPreview ? selectedQuarterId : null);
 
-  // Build default title from quarter selection
+  // Build default title from the period selection
   const selectedQuarter = quarters?.find((q) => q.id === selectedQuarterId);
-  const effectiveTitle = title || (selectedQuarter ? `${selectedQuarter.label} Incident Review` : "Incident Review Report");
+  const fiscalYears = [...new Set(quarters?.map((q) => q.fiscal_year) ?? [])];
+  const periodLabel =
+    periodType === "quarter"
+      ? selectedQuarter?.label
+      : periodType === "fiscal_year"
+        ? selectedFiscalYear
+          ? `FY${selectedFiscalYear}`
+          : undefined
+        : dateFrom && dateTo
+          ? `${dateFrom} to ${dateTo}`
+          : undefined;
+  const periodReady =
+    periodType === "quarter"
+      ? !!selectedQuarterId
+      : periodType === "fiscal_year"
+        ? selectedFiscalYear !== null
+        : !!dateFrom && !!dateTo && dateFrom <= dateTo;
+  const effectiveTitle = title || (periodLabel ? `${periodLabel} Incident Review` : "Incident Review Report");
+
+  const toggleService = useCallback((id: string) => {
+    setServiceIds((prev) => (prev.includes(id) ? prev.filter((s) => s !== id) : [...prev, id]));
+  }, []);
 
   const toggleSection = useCallback((key: keyof ReportSections) => {
     setSections((prev) => ({ ...prev, [key]: !prev[key] }));
@@ -107,30 +166,37 @@ export function ReportsView() {
   const noneChecked = Object.values(sections).every((v) => !v);
 
   const handleGenerate = useCallback(async () => {
-    if (!selectedQuarterId) {
+    if (!periodReady) {
       toast({
-        title: "Select a quarter",
-        description: "Please select a quarter before generating a report.",
+        title: "Select a period",
+        description: "Please select a quarter, fiscal year or date range before generating a report.",
         variant: "destructive",
       });
       return;
     }
+    const quarterId = periodType === "quarter" ? selectedQuarterId : null;
 
     setIsGenerating(true);
 
     try {
-      const ext = reportFormat === "pdf" ? "pdf" : "docx";
-      const filterName = reportFormat === "pdf" ? "PDF Document" : "Word Document";
+      const ext = reportFormat;
+      const filterName = REPORT_FORMAT_LABELS[reportFormat];
 
       // Generate the report (returns temp file path)
       const tempPath = await generateReport.mutateAsync({
-        quarter_id: selectedQuarterId,
-        fiscal_year: selectedQuarter?.fiscal_year ?? null,
+        quarter_id: quarterId,
+        fiscal_year: periodType === "fiscal_year" ? selectedFiscalYear : null,
+        date_from: periodType === "range" ? dateFrom : null,
+        date_to: periodType === "range" ? dateTo : null,
+        service_ids: serviceIds,
+        comparison,
         title: effectiveTitle,
         introduction,
         sections,
         chart_images: {},
         format: reportFormat,
+        template_id: templateId,
+        redaction: redacted ? redactionProfile : null,
       });
 
       // Prompt user for save location
@@ -144,7 +210,7 @@ export function ReportsView() {
           tempPath,
           savePath,
           title: effectiveTitle,
-          quarterId: selectedQuarterId,
+          quarterId,
         });
         toast({
           title: "Report saved",
@@ -167,7 +233,50 @@ export function ReportsView() {
     } finally {
       setIsGenerating(false);
     }
-  }, [selectedQuarterId, selectedQuarter, effectiveTitle, introduction, sections, generateReport, saveReport]);
+  }, [periodReady, periodType, selectedQuarterId, selectedFiscalYear, dateFrom, dateTo, serviceIds, comparison, effectiveTitle, introduction, sections, reportFormat, templateId, redacted, redactionProfile, generateReport, saveReport]);
+
+  const handleRegenerate = useCallback(async (entry: ReportHistoryEntry) => {
+    try {
+      const result = await regenerate.mutateAsync(entry.id);
+      const ext = entry.format as ReportFormat;
+      const savePath = await save({
+        defaultPath: `${entry.title.replace(/[^a-zA-Z0-9]/g, "_")}.${ext}`,
+        filters: [{ name: REPORT_FORMAT_LABELS[ext] ?? ext, extensions: [ext] }],
+      });
+      if (savePath) {
+        await saveReport.mutateAsync({
+          tempPath: result.temp_path,
+          savePath,
+          title: entry.title,
+          quarterId: entry.quarter_id,
+          configJson: entry.config_json,
+        });
+        toast({
+          title: "Report regenerated",
+          description: result.matches_original
+            ? "Same content as the original report."
+            : "Rendered from the original snapshot; the content differs from the report first saved.",
+        });
+      }
+    } catch (err) {
+      toast({
+        title: "Regeneration failed",
+        description: err instanceof Error ? err.message : String(err),
+        variant: "destructive",
+      });
+    }
+  }, [regenerate, saveReport]);
+
+  const toggleCompare = useCallback((id: string) => {
+    setCompareIds((prev) =>
+      prev.includes(id) ? prev.filter((x) => x !== id) : [...prev, id].slice(-2)
+    );
+  }, []);
+
+  // Diff from the older of the two selected reports to the newer
+  const compared = (reportHistory ?? [])
+    .filter((e) => compareIds.includes(e.id))
+    .sort((a, b) => a.generated_at.localeCompare(b.generated_at));
 
   const handlePreviewDiscussion = useCallback(() => {
     if (!selectedQuarterId) {
@@ -190,7 +299,7 @@ export function ReportsView() {
             Report Generator
           </h1>
           <p className="text-sm text-muted-foreground mt-1">
-            Generate quarterly incident review reports as DOCX or PDF files.
+            Generate incident review reports for a quarter, fiscal year or any date range as DOCX, PDF, HTML or Markdown files.
           </p>
         </div>
       </div>
@@ -203,10 +312,25 @@ export function ReportsView() {
         <CardContent className="space-y-4">
           <div className="grid grid-cols-3 gap-4">
             <div className="space-y-2">
-              <Label>Quarter</Label>
+              <Label>Period</Label>
+              <Select
+                value={periodType}
+                onChange={(e) => {
+                  setPeriodType(e.target.value as PeriodType);
+                  setShowDiscussionPreview(false);
+                }}
+              >
+                <option value="quarter">Quarter</option>
+                <option value="fiscal_year">Fiscal year</option>
+                <option value="range">Custom date range</option>
+              </Select>
+            </div>
+
+            <div className="space-y-2">
+              <Label>{periodType === "quarter" ? "Quarter" : periodType === "fiscal_year" ? "Fiscal Year" : "Dates"}</Label>
               {quartersLoading ? (
                 <div className="h-10 bg-muted animate-pulse rounded" />
-              ) : (
+              ) : periodType === "quarter" ? (
                 <Select
                   value={selectedQuarterId ?? ""}
                   onChange={(e) => {
@@ -221,9 +345,38 @@ export function ReportsView() {
                     </option>
                   ))}
                 </Select>
+              ) : periodType === "fiscal_year" ? (
+                <Select
+                  value={selectedFiscalYear ?? ""}
+                  onChange={(e) => setSelectedFiscalYear(e.target.value ? Number(e.target.value) : null)}
+                >
+                  <option value="">Select a fiscal year...</option>
+                  {fiscalYears.map((fy) => (
+                    <option key={fy} value={fy}>
+                      FY{fy}
+                    </option>
+                  ))}
+                </Select>
+              ) : (
+                <div className="flex items-center gap-2">
+                  <Input type="date" value={dateFrom} onChange={(e) => setDateFrom(e.target.value)} aria-label="Start date" />
+                  <Input type="date" value={dateTo} onChange={(e) => setDateTo(e.target.value)} aria-label="End date" />
+                </div>
               )}
             </div>
 
+            <div className="space-y-2">
+              <Label>Compare With</Label>
+              <Select
+                value={comparison}
+                onChange={(e) => setComparison(e.target.value as ReportComparison)}
+              >
+                <option value="previous_period">Previous period</option>
+                <option value="same_period_last_year">Same period last year</option>
+                <option value="none">No comparison</option>
+              </Select>
+            </div>
+
             <div className="space-y-2">
               <Label>Report Title</Label>
               <Input
@@ -241,10 +394,47 @@ export function ReportsView() {
               >
                 <option value="docx">Word Document (.docx)</option>
                 <option value="pdf">PDF Document (.pdf)</option>
+                <option value="html">HTML Page (.html)</option>
+                <option value="md">Markdown (.md)</option>
               </Select>
+              <label className="flex items-center gap-2 text-sm">
+                <input
+                  type="checkbox"
+                  checked={redacted}
+                  onChange={(e) => setRedacted(e.target.checked)}
+                />
+                External-safe (redacted)
+              </label>
+              {redacted && (
+                <p className="text-xs text-muted-foreground">
+                  Names, notes and masked patterns are removed using the redaction profile in Settings.
+                </p>
+              )}
             </div>
           </div>
 
+          <div className="space-y-2">
+            <Label>Services (optional)</Label>
+            <div className="flex flex-wrap gap-2">
+              {services?.map((svc) => (
+                <Button
+                  key={svc.id}
+                  type="button"
+                  variant={serviceIds.includes(svc.id) ? "default" : "outline"}
+                  size="sm"
+                  onClick={() => toggleService(svc.id)}
+                >
+                  {svc.name}
+                </Button>
+              ))}
+            </div>
+            <p className="text-xs text-muted-foreground">
+              {serviceIds.length === 0
+                ? "The report covers every service."
+                : "The report covers only the selected services and includes their runbooks and dependencies."}
+            </p>
+          </div>
+
           <div className="space-y-2">
             <div className="flex items-center justify-between">
               <Label>Custom Introduction (optional)</Label>
@@ -290,35 +480,69 @@ export function ReportsView() {
         <CardHeader>
           <div className="flex items-center justify-between">
             <CardTitle className="text-lg">Report Sections</CardTitle>
-            <Button
-              variant="ghost"
-              size="sm"
-              onClick={() => toggleAll(!allChecked)}
-            >
-              {allChecked ? "Deselect All" : "Select All"}
-            </Button>
+            <div className="flex items-center gap-2">
+              <Select
+                value={templateId ?? ""}
+                onChange={(e) => setTemplateId(e.target.value || null)}
+                aria-label="Report template"
+              >
+                <option value="">Custom selection</option>
+                {templates?.map((t) => (
+                  <option key={t.id} value={t.id}>
+                    {t.name}
+                  </option>
+                ))}
+              </Select>
+              {!selectedTemplate && (
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => toggleAll(!allChecked)}
+                >
+                  {allChecked ? "Deselect All" : "Select All"}
+                </Button>
+              )}
+            </div>
           </div>
         </CardHeader>
         <CardContent>
-          <div className="grid grid-cols-2 gap-3">
-            {(Object.keys(SECTION_LABELS) as (keyof ReportSections)[]).map(
-              (key) => (
-                <button
-                  key={key}
-                  type="button"
-                  className="flex items-center gap-3 p-3 rounded-lg border hover:bg-muted/50 transition-colors text-left"
-                  onClick={() => toggleSection(key)}
-                >
-                  {sections[key] ? (
-                    <CheckSquare className="h-5 w-5 text-primary shrink-0" />
-                  ) : (
-                    <Square className="h-5 w-5 text-muted-foreground shrink-0" />
-                  )}
-                  <span className="text-sm">{SECTION_LABELS[key]}</span>
-                </button>
-              )
-            )}
-          </div>
+          {selectedTemplate ? (
+            <div className="space-y-2">
+              {selectedTemplate.description && (
+                <p className="text-sm text-muted-foreground">{selectedTemplate.description}</p>
+              )}
+              <ol className="list-decimal pl-5 space-y-1 text-sm">
+                {selectedTemplate.sections.map((section, i) => (
+                  <li key={i}>
+                    {section.kind === "custom" ? section.title : SECTION_LABELS[section.kind]}
+                    {section.options?.top_n ? (
+                      <span className="text-muted-foreground"> (top {section.options.top_n})</span>
+                    ) : null}
+                  </li>
+                ))}
+              </ol>
+            </div>
+          ) : (
+            <div className="grid grid-cols-2 gap-3">
+              {(Object.keys(SECTION_LABELS) as (keyof ReportSections)[]).map(
+                (key) => (
+                  <button
+                    key={key}
+                    type="button"
+                    className="flex items-center gap-3 p-3 rounded-lg border hover:bg-muted/50 transition-colors text-left"
+                    onClick={() => toggleSection(key)}
+                  >
+                    {sections[key] ? (
+                      <CheckSquare className="h-5 w-5 text-primary shrink-0" />
+                    ) : (
+                      <Square className="h-5 w-5 text-muted-foreground shrink-0" />
+                    )}
+                    <span className="text-sm">{SECTION_LABELS[key]}</span>
+                  </button>
+                )
+              )}
+            </div>
+          )}
         </CardContent>
       </Card>
 
@@ -328,7 +552,7 @@ export function ReportsView() {
           <div className="flex items-center gap-3">
             <Button
               onClick={handleGenerate}
-              disabled={isGenerating || !selectedQuarterId || noneChecked}
+              disabled={isGenerating || !periodReady || (!selectedTemplate && noneChecked)}
               className="gap-2"
             >
               {isGenerating ? (
@@ -347,7 +571,7 @@ export function ReportsView() {
             <Button
               variant="outline"
               onClick={handlePreviewDiscussion}
-              disabled={!selectedQuarterId || discussionLoading}
+              disabled={periodType !== "quarter" || !selectedQuarterId || discussionLoading}
               className="gap-2"
             >
               {discussionLoading ? (
@@ -359,9 +583,9 @@ export function ReportsView() {
             </Button>
           </div>
 
-          {!selectedQuarterId && (
+          {!periodReady && (
             <p className="text-sm text-muted-foreground mt-2">
-              Select a quarter above to enable report generation.
+              Select a report period above to enable report generation.
             </p>
           )}
         </CardContent>
@@ -434,7 +658,28 @@ export function ReportsView() {
                         ? formatBytes(entry.file_size_bytes)
                         : "--"}
                     </TableCell>
-                    <TableCell className="text-right">
+                    <TableCell className="text-right whitespace-nowrap">
+                      {entry.snapshot_version !== null && (
+                        <>
+                          <Button
+                            size="icon"
+                            variant="ghost"
+                            title="Regenerate from snapshot"
+                            onClick={() => handleRegenerate(entry)}
+                            disabled={regenerate.isPending}
+                          >
+                            <RefreshCw className="h-4 w-4" />
+                          </Button>
+                          <Button
+                            size="icon"
+                            variant={compareIds.includes(entry.id) ? "secondary" : "ghost"}
+                            title="Select to compare"
+                            onClick={() => toggleCompare(entry.id)}
+                          >
+                            <GitCompare className="h-4 w-4" />
+                          </Button>
+                        </>
+                      )}
                       <Button
                         size="icon"
                         variant="ghost"
@@ -458,12 +703,89 @@ export function ReportsView() {
               No reports generated yet. Generate your first report above.
             </p>
           )}
+          {compared.length === 2 && (
+            <SnapshotDiffPanel from={compared[0]} to={compared[1]} />
+          )}
         </CardContent>
       </Card>
     </div>
   );
 }
 
+function SnapshotDiffPanel({ from, to }: { from: ReportHistoryEntry; to: ReportHistoryEntry }) {
+  const { data: diff, isLoading, error } = useReportSnapshotDiff(from.id, to.id);
+
+  return (
+    <div className="mt-4 space-y-3 rounded border p-4">
+      <p className="text-sm font-medium">
+        Changes from {formatHistoryDate(from.generated_at)} to {formatHistoryDate(to.generated_at)}
+      </p>
+      {isLoading && <Loader2 className="h-4 w-4 animate-spin" />}
+      {error && <p className="text-sm text-destructive">{String(error)}</p>}
+      {diff && (
+        <>
+          {diff.from_period !== diff.to_period && (
+            <p className="text-sm text-muted-foreground">
+              Period changed: {diff.from_period} to {diff.to_period}
+            </p>
+          )}
+          {diff.metrics.length === 0 &&
+            diff.incidents_added.length === 0 &&
+            diff.incidents_removed.length === 0 &&
+            diff.incidents_changed.length === 0 && (
+              <p className="text-sm text-muted-foreground">No incidents or metrics changed.</p>
+            )}
+          {diff.metrics.length > 0 && (
+            <Table>
+              <TableHeader>
+                <TableRow>
+                  <TableHead>Metric</TableHead>
+                  <TableHead>Before</TableHead>
+                  <TableHead>After</TableHead>
+                </TableRow>
+              </TableHeader>
+              <TableBody>
+                {diff.metrics.map((m) => (
+                  <TableRow key={m.metric}>
+                    <TableCell>{m.metric}</TableCell>
+                    <TableCell>{m.before?.toFixed(1) ?? "--"}</TableCell>
+                    <TableCell>{m.after?.toFixed(1) ?? "--"}</TableCell>
+                  </TableRow>
+                ))}
+              </TableBody>
+            </Table>
+          )}
+          {diff.incidents_added.map((i) => (
+            <p key={i.id} className="text-sm">
+              <Badge variant="outline" className="mr-2">Added</Badge>
+              {i.title}
+            </p>
+          ))}
+          {diff.incidents_removed.map((i) => (
+            <p key={i.id} className="text-sm">
+              <Badge variant="outline" className="mr-2">Removed</Badge>
+              {i.title}
+            </p>
+          ))}
+          {diff.incidents_changed.map((i) => (
+            <div key={i.id} className="text-sm">
+              <Badge variant="outline" className="mr-2">Changed</Badge>
+              {i.title}
+              <ul className="ml-6 list-disc text-xs text-muted-foreground">
+                {i.changes.map((c) => (
+                  <li key={c.field}>
+                    {c.field}: {c.before || "(empty)"} → {c.after || "(empty)"}
+                  </li>
+                ))}
+              </ul>
+            </div>
+          ))}
+        </>
+      )}
+    </div>
+  );
+}
+
 function formatHistoryDate(dateStr: string): string {
   try {
     return format(new Date(dateStr), "MMM d, yyyy HH:mm");
@@ -23,6 +23,7 @@ import {
 } from "@/components/ui/card";
 import { Tabs, TabsList, TabsTrigger, TabsContent } from "@/components/ui/tabs";
 import { RunbookEditor } from "@/components/services/runbook-editor";
+import { BlastRadiusCard } from "@/components/services/blast-radius-card";
 import { toast } from "@/components/ui/use-toast";
 import {
   SEVERITY_LEVELS,
@@ -442,6 +443,8 @@ export function ServiceDetailView() {
                 )}
               </CardContent>
             </Card>
+
+            {id && <BlastRadiusCard serviceId={id} />}
           </div>
         </TabsContent>
 
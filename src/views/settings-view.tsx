@@ -70,6 +70,9 @@ import type {
 import type { SlaDefinition } from "@/types/sla";
 import { OllamaConfig } from "@/components/settings/ollama-config";
 import { BackupConfig } from "@/components/settings/backup-config";
+import { DiscussionRulesConfig } from "@/components/settings/discussion-rules-config";
+import { RedactionConfig } from "@/components/settings/redaction-config";
+import { ReportThemeConfig } from "@/components/settings/report-theme-config";
 
 // ===================== Services Tab =====================
 
@@ -768,15 +771,17 @@ function ImportDataTab() {
 
   return (
     <div className="space-y-6">
-      {/* CSV Import */}
+      {/* CSV / JSON Import */}
       <div className="space-y-3">
-        <h2 className="text-lg font-semibold">CSV Import</h2>
+        <h2 className="text-lg font-semibold">Incident Import</h2>
         <p className="text-sm text-muted-foreground">
-          Import incidents from a CSV file with column mapping.
+          Import incidents from a CSV file with column mapping, or from a JSON
+          or NDJSON file with their tags, custom fields, action items and
+          contributing factors.
         </p>
         <Button onClick={() => setCsvImportOpen(true)}>
           <FileSpreadsheet className="h-4 w-4" />
-          Import from CSV
+          Import from CSV or JSON
         </Button>
         <CSVImportDialog
           open={csvImportOpen}
@@ -1434,7 +1439,7 @@ function SlaTab() {
 
 // ===================== Settings View =====================
 
-type SettingsTab = "services" | "quarters" | "custom-fields" | "sla" | "import" | "backup" | "ai";
+type SettingsTab = "services" | "quarters" | "custom-fields" | "sla" | "discussion-rules" | "redaction" | "report-theme" | "import" | "backup" | "ai";
 
 export function SettingsView() {
   const [activeTab, setActiveTab] = useState<SettingsTab>("services");
@@ -1444,6 +1449,9 @@ export function SettingsView() {
     { key: "quarters", label: "Quarters" },
     { key: "custom-fields", label: "Custom Fields" },
     { key: "sla", label: "SLA Targets" },
+    { key: "discussion-rules", label: "Discussion Rules" },
+    { key: "redaction", label: "Redaction" },
+    { key: "report-theme", label: "Report Theme" },
     { key: "import", label: "Import & Data" },
     { key: "backup", label: "Backup" },
     { key: "ai", label: "AI (Ollama)" },
@@ -1475,6 +1483,9 @@ export function SettingsView() {
       {activeTab === "quarters" && <QuartersTab />}
       {activeTab === "custom-fields" && <CustomFieldsTab />}
       {activeTab === "sla" && <SlaTab />}
+      {activeTab === "discussion-rules" && <DiscussionRulesConfig />}
+      {activeTab === "redaction" && <RedactionConfig />}
+      {activeTab === "report-theme" && <ReportThemeConfig />}
       {activeTab === "import" && <ImportDataTab />}
       {activeTab === "backup" && <BackupConfig />}
       {activeTab === "ai" && <OllamaConfig />}